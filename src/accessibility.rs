@@ -0,0 +1,57 @@
+//! Screen reader support.
+//!
+//! Cursive draws on a grid of cells, which screen readers can't make sense
+//! of. Instead, once an [`Announcer`] is set with
+//! [`Cursive::set_announcer`], the focused element is described as text
+//! (see [`View::describe`]), and announced each time this description
+//! changes.
+//!
+//! [`Announcer`]: trait.Announcer.html
+//! [`Cursive::set_announcer`]: ../struct.Cursive.html#method.set_announcer
+//! [`View::describe`]: ../view/trait.View.html#method.describe
+
+use std::process::{Command, Stdio};
+
+/// Tells the user about changes, for instance with a speech synthesizer.
+///
+/// Any `FnMut(&str)` closure is an announcer.
+pub trait Announcer {
+    /// Announces `text`.
+    fn announce(&mut self, text: &str);
+}
+
+impl<F: FnMut(&str)> Announcer for F {
+    fn announce(&mut self, text: &str) {
+        self(text);
+    }
+}
+
+/// Speaks announcements with speech-dispatcher, using `spd-say`.
+///
+/// Nothing happens if `spd-say` isn't installed.
+pub struct SpeechDispatcher;
+
+new_default!(SpeechDispatcher);
+
+impl SpeechDispatcher {
+    /// Creates a new announcer using speech-dispatcher.
+    pub fn new() -> Self {
+        SpeechDispatcher
+    }
+}
+
+impl Announcer for SpeechDispatcher {
+    fn announce(&mut self, text: &str) {
+        // Only the last focused element matters: stop older messages.
+        let _ = Command::new("spd-say")
+            .arg("--cancel")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let _ = Command::new("spd-say")
+            .arg(text)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
@@ -24,14 +24,34 @@ impl Align {
         Align::new(HAlign::Right, VAlign::Top)
     }
 
+    /// Creates a top-center alignment.
+    pub fn top_center() -> Self {
+        Align::new(HAlign::Center, VAlign::Top)
+    }
+
+    /// Creates a center-left alignment.
+    pub fn center_left() -> Self {
+        Align::new(HAlign::Left, VAlign::Center)
+    }
+
+    /// Creates a center-right alignment.
+    pub fn center_right() -> Self {
+        Align::new(HAlign::Right, VAlign::Center)
+    }
+
     /// Creates a bottom-left alignment.
     pub fn bot_left() -> Self {
         Align::new(HAlign::Left, VAlign::Bottom)
     }
 
+    /// Creates a bottom-center alignment.
+    pub fn bot_center() -> Self {
+        Align::new(HAlign::Center, VAlign::Bottom)
+    }
+
     /// Creates a bottom-right alignment.
     pub fn bot_right() -> Self {
-        Align::new(HAlign::Right, VAlign::Top)
+        Align::new(HAlign::Right, VAlign::Bottom)
     }
 
     /// Creates an alignment centered both horizontally and vertically.
@@ -41,6 +61,7 @@ impl Align {
 }
 
 /// Horizontal alignment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HAlign {
     /// Place the element to the left of available space
     Left,
@@ -51,6 +72,7 @@ pub enum HAlign {
 }
 
 /// Vertical alignment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VAlign {
     /// Place the element at the top of available space
     Top,
@@ -77,6 +99,17 @@ impl HAlign {
             }
         }
     }
+
+    /// Returns the mirrored alignment.
+    ///
+    /// Used to align right-to-left text.
+    pub fn mirrored(&self) -> Self {
+        match *self {
+            HAlign::Left => HAlign::Right,
+            HAlign::Center => HAlign::Center,
+            HAlign::Right => HAlign::Left,
+        }
+    }
 }
 
 impl VAlign {
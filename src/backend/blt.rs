@@ -6,7 +6,7 @@ use self::bear_lib_terminal::terminal::{self, Event as BltEvent, KeyCode};
 use backend;
 use event::{Event, Key};
 use std::collections::BTreeMap;
-use theme::{BaseColor, Color, ColorStyle, Effect};
+use theme::{BaseColor, Color, ColorDepth, ColorStyle, Effect};
 
 pub struct Concrete {
     colours: BTreeMap<i16, (BltColor, BltColor)>,
@@ -27,18 +27,27 @@ impl backend::Backend for Concrete {
     fn init_color_style(&mut self, style: ColorStyle, foreground: &Color,
                         background: &Color) {
         self.colours.insert(style.id(),
-                            (colour_to_blt_colour(foreground),
-                             colour_to_blt_colour(background)));
+                            (colour_to_blt_colour(foreground, FRONT),
+                             colour_to_blt_colour(background, BACK)));
     }
 
     fn with_color<F: FnOnce()>(&self, color: ColorStyle, f: F) {
-        let (fg, bg) = self.colours[&color.id()];
+        let (fg, bg) = match color {
+            ColorStyle::Custom { ref front, ref back } => {
+                (colour_to_blt_colour(front, FRONT),
+                 colour_to_blt_colour(back, BACK))
+            }
+            _ => self.colours[&color.id()],
+        };
         terminal::with_colors(fg, bg, f);
     }
 
     fn with_effect<F: FnOnce()>(&self, effect: Effect, f: F) {
         match effect {
-            Effect::Simple => f(),
+            // BearLibTerminal fonts have no bold or underline variants,
+            // and it can't dim or hide text.
+            Effect::Simple | Effect::Bold | Effect::Underline |
+            Effect::Dim | Effect::Invisible => f(),
             // TODO: how to do this correctly?`
             //       BLT itself doesn't do this kind of thing,
             //       we'd need the colours in our position,
@@ -55,6 +64,10 @@ impl backend::Backend for Concrete {
         true
     }
 
+    fn color_depth(&self) -> ColorDepth {
+        ColorDepth::TrueColor
+    }
+
     fn screen_size(&self) -> (usize, usize) {
         let Size { width, height } = terminal::state::size();
         (width as usize, height as usize)
@@ -103,7 +116,11 @@ impl backend::Backend for Concrete {
     }
 }
 
-fn colour_to_blt_colour(clr: &Color) -> BltColor {
+// BearLibTerminal has no default colors: use white text on black.
+const FRONT: (u8, u8, u8) = (255, 255, 255);
+const BACK: (u8, u8, u8) = (0, 0, 0);
+
+fn colour_to_blt_colour(clr: &Color, default: (u8, u8, u8)) -> BltColor {
     let (r, g, b) = match *clr {
         // Colours taken from
         // https://en.wikipedia.org/wiki/ANSI_escape_code#Colors
@@ -131,6 +148,7 @@ fn colour_to_blt_colour(clr: &Color) -> BltColor {
              (g as f32 / 5.0 * 255.0) as u8,
              (b as f32 / 5.0 * 255.0) as u8)
         }
+        Color::TerminalDefault => default,
     };
     BltColor::from_rgb(r, g, b)
 }
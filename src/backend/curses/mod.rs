@@ -1,3 +1,6 @@
+use event::Event;
+use std::collections::HashMap;
+use std::io::{self, Write};
 use theme::{BaseColor, Color};
 
 #[cfg(feature = "ncurses")]
@@ -11,7 +14,72 @@ mod pan;
 pub use self::pan::*;
 
 
-fn find_closest(color: &Color) -> u8 {
+// Color pairs allocated for custom color styles.
+//
+// Curses only has a few pairs. Once they are all used, the pair used the
+// longest ago is given to the new colors, unless it was used in the
+// current frame: its cells would then change color too.
+struct PairCache {
+    // ID of the pair for each (front, back) colors, and the frame it was
+    // last used in.
+    pairs: HashMap<(i16, i16), (i16, usize)>,
+    frame: usize,
+}
+
+impl PairCache {
+    fn new() -> Self {
+        PairCache {
+            pairs: HashMap::new(),
+            frame: 0,
+        }
+    }
+
+    // Returns the pair ID for the given colors, and `true` if it must be
+    // initialized first.
+    //
+    // `limit` is the first ID not available. Returns `None` if no pair
+    // can be used.
+    fn get(&mut self, front: i16, back: i16, limit: i16)
+           -> Option<(i16, bool)> {
+        let frame = self.frame;
+        if let Some(pair) = self.pairs.get_mut(&(front, back)) {
+            pair.1 = frame;
+            return Some((pair.0, false));
+        }
+
+        // IDs up to 9 are used by the theme.
+        let id = 10 + self.pairs.len() as i16;
+        let id = if id < limit {
+            id
+        } else {
+            let (&colors, &(id, last_used)) =
+                self.pairs.iter().min_by_key(|&(_, &(_, last))| last)?;
+            if last_used == frame {
+                return None;
+            }
+            self.pairs.remove(&colors);
+            id
+        };
+        self.pairs.insert((front, back), (id, frame));
+        Some((id, true))
+    }
+
+    // Starts a new frame: pairs used before can be reassigned.
+    fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+}
+
+// Writes `sequence` straight to the terminal: curses would escape it.
+fn write_sequence(sequence: &str) -> bool {
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())
+        .and_then(|_| stdout.flush())
+        .is_ok()
+}
+
+// Returns the curses color number, or `-1` for the default color.
+fn find_closest(color: &Color) -> i16 {
     match *color {
         Color::Dark(BaseColor::Black) => 0,
         Color::Dark(BaseColor::Red) => 1,
@@ -33,8 +101,91 @@ fn find_closest(color: &Color) -> u8 {
             let r = 6 * r as u16 / 256;
             let g = 6 * g as u16 / 256;
             let b = 6 * b as u16 / 256;
-            (16 + 36 * r + 6 * g + b) as u8
+            (16 + 36 * r + 6 * g + b) as i16
         }
-        Color::RgbLowRes(r, g, b) => (16 + 36 * r + 6 * g + b) as u8,
+        Color::RgbLowRes(r, g, b) => i16::from(16 + 36 * r + 6 * g + b),
+        Color::TerminalDefault => -1,
+    }
+}
+
+// Reads the rest of a control sequence, after `ESC` and `intro`.
+//
+// `next` returns the following byte, or `None` if none is available yet.
+//
+// Returns the whole sequence, including `ESC` and `intro`.
+fn read_sequence<F>(intro: u8, mut next: F) -> Vec<u8>
+    where F: FnMut() -> Option<u8>
+{
+    let mut bytes = vec![0x1b, intro];
+    match intro {
+        // SS3: a single character follows.
+        b'O' => bytes.extend(next()),
+        // CSI: parameters, then a final byte.
+        b'[' => {
+            while let Some(byte) = next() {
+                bytes.push(byte);
+                if (0x40..0x7f).contains(&byte) {
+                    break;
+                }
+            }
+        }
+        _ => (),
+    }
+    bytes
+}
+
+// Returns the event for a sequence read with `read_sequence`.
+//
+// An introducer alone was typed with Alt.
+fn sequence_event(bytes: Vec<u8>) -> Event {
+    if bytes.len() == 2 {
+        Event::AltChar(bytes[1] as char)
+    } else {
+        Event::Unknown(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PairCache, read_sequence, sequence_event};
+    use event::Event;
+
+    #[test]
+    fn test_read_sequence() {
+        let mut input = b"1;9Zx".iter().cloned();
+        assert_eq!(read_sequence(b'[', || input.next()), b"\x1b[1;9Z");
+        // The rest is left for the next event.
+        assert_eq!(input.next(), Some(b'x'));
+
+        let mut input = b"Pq".iter().cloned();
+        assert_eq!(read_sequence(b'O', || input.next()), b"\x1bOP");
+
+        // Incomplete sequences stop when the input runs dry.
+        let mut input = b"12".iter().cloned();
+        assert_eq!(read_sequence(b'[', || input.next()), b"\x1b[12");
+
+        // With nothing after it, `ESC [` is Alt-[.
+        let mut input = None.into_iter();
+        let bytes = read_sequence(b'[', || input.next());
+        assert_eq!(sequence_event(bytes), Event::AltChar('['));
+        let bytes = b"\x1b[1;9Z".to_vec();
+        assert_eq!(sequence_event(bytes.clone()), Event::Unknown(bytes));
+    }
+
+    #[test]
+    fn test_pair_cache() {
+        // Room for two pairs.
+        let mut cache = PairCache::new();
+        assert_eq!(cache.get(1, 2, 12), Some((10, true)));
+        assert_eq!(cache.get(3, 4, 12), Some((11, true)));
+        assert_eq!(cache.get(1, 2, 12), Some((10, false)));
+        // Both were used in this frame.
+        assert_eq!(cache.get(5, 6, 12), None);
+
+        cache.next_frame();
+        assert_eq!(cache.get(3, 4, 12), Some((11, false)));
+        // (1, 2) is the oldest.
+        assert_eq!(cache.get(5, 6, 12), Some((10, true)));
+        assert_eq!(cache.get(5, 6, 12), Some((10, false)));
     }
 }
@@ -1,13 +1,77 @@
 extern crate ncurses;
 
 
-use self::super::find_closest;
+use self::super::{PairCache, find_closest, read_sequence,
+                  sequence_event, write_sequence};
 use backend;
 use event::{Event, Key};
-use theme::{Color, ColorStyle, Effect};
+use std::cell::RefCell;
+use std::cmp::min;
+use theme::{Color, ColorDepth, ColorStyle, Effect};
 use utf8;
 
-pub struct Concrete;
+pub struct Concrete {
+    // Pairs allocated for custom color styles.
+    pairs: RefCell<PairCache>,
+    // Input timeout, in milliseconds, or -1 to block.
+    timeout: i32,
+    // Whether frames are sent with synchronized output.
+    synchronized: bool,
+}
+
+impl Concrete {
+    // Returns the pair ID to use for the given style.
+    fn pair_id(&self, style: ColorStyle) -> i16 {
+        let (front, back) = match style {
+            ColorStyle::Custom { front, back } => {
+                (find_closest(&front), find_closest(&back))
+            }
+            _ => return style.id(),
+        };
+
+        let limit = min(ncurses::COLOR_PAIRS(), 0x7fff) as i16;
+        match self.pairs.borrow_mut().get(front, back, limit) {
+            Some((id, true)) => {
+                ncurses::init_pair(id, front, back);
+                id
+            }
+            Some((id, false)) => id,
+            // No more pairs available.
+            None => ColorStyle::Primary.id(),
+        }
+    }
+
+    // Called after reading an escape character.
+    fn parse_escape(&self) -> Event {
+        // ncurses already waited `ESCDELAY` for the rest of the sequence:
+        // whatever followed, like a key pressed with Alt, is waiting.
+        ncurses::timeout(0);
+        let next_byte = || match ncurses::getch() {
+            byte @ 0...255 => Some(byte as u8),
+            _ => None,
+        };
+        let event = match ncurses::getch() {
+            -1 => Event::Key(Key::Esc),
+            intro @ 79 | intro @ 91 => {
+                sequence_event(read_sequence(intro as u8, next_byte))
+            }
+            ch @ 32...255 if ch != 127 => {
+                match utf8::read_char(ch as u8, next_byte) {
+                    Ok(c) => Event::AltChar(c),
+                    // Cut short, or not utf-8.
+                    Err(_) => Event::Unknown(vec![0x1b, ch as u8]),
+                }
+            }
+            other => {
+                // Not a sequence: this key is the next event.
+                ncurses::ungetch(other);
+                Event::Key(Key::Esc)
+            }
+        };
+        ncurses::timeout(self.timeout);
+        event
+    }
+}
 
 impl backend::Backend for Concrete {
     fn init() -> Self {
@@ -21,11 +85,17 @@ impl backend::Backend for Concrete {
         ncurses::noecho();
         ncurses::cbreak();
         ncurses::start_color();
+        // Allows `Color::TerminalDefault`.
+        ncurses::use_default_colors();
         ncurses::curs_set(ncurses::CURSOR_VISIBILITY::CURSOR_INVISIBLE);
         ncurses::wbkgd(ncurses::stdscr(),
                        ncurses::COLOR_PAIR(ColorStyle::Background.id()));
 
-        Concrete
+        Concrete {
+            pairs: RefCell::new(PairCache::new()),
+            timeout: -1,
+            synchronized: true,
+        }
     }
 
     fn screen_size(&self) -> (usize, usize) {
@@ -39,6 +109,15 @@ impl backend::Backend for Concrete {
         ncurses::has_colors()
     }
 
+    fn color_depth(&self) -> ColorDepth {
+        // Curses can only use colors from the 256-color palette.
+        match ncurses::COLORS() {
+            n if n >= 256 => ColorDepth::Colors256,
+            n if n >= 16 => ColorDepth::Colors16,
+            _ => ColorDepth::Colors8,
+        }
+    }
+
     fn finish(&mut self) {
         ncurses::endwin();
     }
@@ -49,8 +128,8 @@ impl backend::Backend for Concrete {
         // TODO: build the color on the spot
 
         ncurses::init_pair(style.id(),
-                           find_closest(foreground) as i16,
-                           find_closest(background) as i16);
+                           find_closest(foreground),
+                           find_closest(background));
     }
 
     fn with_color<F: FnOnce()>(&self, color: ColorStyle, f: F) {
@@ -58,7 +137,7 @@ impl backend::Backend for Concrete {
         let mut current_color: i16 = 0;
         ncurses::attr_get(&mut current_style, &mut current_color);
 
-        let style = ncurses::COLOR_PAIR(color.id());
+        let style = ncurses::COLOR_PAIR(self.pair_id(color));
         ncurses::attron(style);
         f();
         // ncurses::attroff(style);
@@ -68,6 +147,10 @@ impl backend::Backend for Concrete {
     fn with_effect<F: FnOnce()>(&self, effect: Effect, f: F) {
         let style = match effect {
             Effect::Reverse => ncurses::A_REVERSE(),
+            Effect::Bold => ncurses::A_BOLD(),
+            Effect::Underline => ncurses::A_UNDERLINE(),
+            Effect::Dim => ncurses::A_DIM(),
+            Effect::Invisible => ncurses::A_INVIS(),
             Effect::Simple => ncurses::A_NORMAL(),
         };
         ncurses::attron(style);
@@ -80,13 +163,25 @@ impl backend::Backend for Concrete {
     }
 
     fn refresh(&mut self) {
+        // ncurses sends the whole frame at once, when refreshing.
+        if self.synchronized {
+            write_sequence(backend::BEGIN_SYNC);
+        }
         ncurses::refresh();
+        if self.synchronized {
+            write_sequence(backend::END_SYNC);
+        }
+        self.pairs.borrow_mut().next_frame();
     }
 
     fn print_at(&self, (x, y): (usize, usize), text: &str) {
         ncurses::mvaddstr(y as i32, x as i32, text);
     }
 
+    fn send_sequence(&self, sequence: &str) -> bool {
+        write_sequence(sequence)
+    }
+
     fn poll_event(&self) -> Event {
         let ch: i32 = ncurses::getch();
 
@@ -95,17 +190,24 @@ impl backend::Backend for Concrete {
             Event::Char(utf8::read_char(ch as u8,
                                         || Some(ncurses::getch() as u8))
                                 .unwrap())
+        } else if ch == 27 {
+            self.parse_escape()
         } else {
             parse_ncurses_char(ch)
         }
     }
 
     fn set_refresh_rate(&mut self, fps: u32) {
-        if fps == 0 {
-            ncurses::timeout(-1);
-        } else {
-            ncurses::timeout(1000 / fps as i32);
-        }
+        self.timeout = if fps == 0 { -1 } else { 1000 / fps as i32 };
+        ncurses::timeout(self.timeout);
+    }
+
+    fn set_esc_delay(&mut self, delay: u32) {
+        ncurses::set_escdelay(delay as i32);
+    }
+
+    fn set_synchronized_output(&mut self, enabled: bool) {
+        self.synchronized = enabled;
     }
 }
 
@@ -122,8 +224,7 @@ fn parse_ncurses_char(ch: i32) -> Event {
         // Treat '\n' and the numpad Enter the same
         10 |
         ncurses::KEY_ENTER => Event::Key(Key::Enter),
-        // This is the escape key when pressed by itself.
-        // When used for control sequences, it should have been caught earlier.
+        // Escape sequences are parsed in `Concrete::parse_escape`.
         27 => Event::Key(Key::Esc),
         // `Backspace` sends 127, but Ctrl-H sends `Backspace`
         127 |
@@ -2,14 +2,79 @@ extern crate pancurses;
 
 
 
-use self::super::find_closest;
+use self::super::{PairCache, find_closest, read_sequence,
+                  sequence_event, write_sequence};
 use backend;
 use event::{Event, Key};
-use theme::{Color, ColorStyle, Effect};
+use std::cell::RefCell;
+use theme::{Color, ColorDepth, ColorStyle, Effect};
 use utf8;
 
 pub struct Concrete {
     window: pancurses::Window,
+    // Pairs allocated for custom color styles.
+    pairs: RefCell<PairCache>,
+    // Input timeout, in milliseconds, or -1 to block.
+    timeout: i32,
+    // How long to wait after an escape character, in milliseconds.
+    esc_delay: i32,
+    // Whether frames are sent with synchronized output.
+    synchronized: bool,
+}
+
+impl Concrete {
+    // Returns the pair ID to use for the given style.
+    fn pair_id(&self, style: ColorStyle) -> i16 {
+        let (front, back) = match style {
+            ColorStyle::Custom { front, back } => {
+                (find_closest(&front), find_closest(&back))
+            }
+            _ => return style.id(),
+        };
+
+        // `ColorPair` only takes a `u8`, so we can't go past 255.
+        match self.pairs.borrow_mut().get(front, back, 256) {
+            Some((id, true)) => {
+                pancurses::init_pair(id, front, back);
+                id
+            }
+            Some((id, false)) => id,
+            // No more pairs available.
+            None => ColorStyle::Primary.id(),
+        }
+    }
+
+    // Called after reading an escape character.
+    fn parse_escape(&self) -> Event {
+        // Wait a little for the rest of a sequence pancurses didn't
+        // recognize, or for a key pressed with Alt.
+        self.window.timeout(self.esc_delay);
+        let event = match self.window.getch() {
+            None => Event::Key(Key::Esc),
+            Some(pancurses::Input::Character(intro)) if intro == 'O' ||
+                                                       intro == '[' => {
+                sequence_event(read_sequence(intro as u8, || {
+                    match self.window.getch() {
+                        Some(pancurses::Input::Character(c)) if (c as u32) <
+                                                               256 => {
+                            Some(c as u8)
+                        }
+                        _ => None,
+                    }
+                }))
+            }
+            Some(pancurses::Input::Character(c)) if !c.is_control() => {
+                Event::AltChar(c)
+            }
+            Some(other) => {
+                // Not a sequence: this key is the next event.
+                self.window.ungetch(&other);
+                Event::Key(Key::Esc)
+            }
+        };
+        self.window.timeout(self.timeout);
+        event
+    }
 }
 
 impl backend::Backend for Concrete {
@@ -20,10 +85,19 @@ impl backend::Backend for Concrete {
         pancurses::noecho();
         pancurses::cbreak();
         pancurses::start_color();
+        // Allows `Color::TerminalDefault`.
+        pancurses::use_default_colors();
         pancurses::curs_set(0);
         window.bkgd(pancurses::ColorPair(ColorStyle::Background.id() as u8));
 
-        Concrete { window: window }
+        Concrete {
+            window: window,
+            pairs: RefCell::new(PairCache::new()),
+            timeout: -1,
+            esc_delay: 25,
+            // The Windows console doesn't read escape sequences.
+            synchronized: cfg!(unix),
+        }
     }
 
     fn screen_size(&self) -> (usize, usize) {
@@ -35,6 +109,15 @@ impl backend::Backend for Concrete {
         pancurses::has_colors()
     }
 
+    fn color_depth(&self) -> ColorDepth {
+        // pancurses doesn't tell how many colors are available.
+        if pancurses::has_colors() {
+            ColorDepth::Colors256
+        } else {
+            ColorDepth::Colors8
+        }
+    }
+
     fn finish(&mut self) {
         pancurses::endwin();
     }
@@ -42,13 +125,13 @@ impl backend::Backend for Concrete {
     fn init_color_style(&mut self, style: ColorStyle, foreground: &Color,
                         background: &Color) {
         pancurses::init_pair(style.id(),
-                             find_closest(foreground) as i16,
-                             find_closest(background) as i16);
+                             find_closest(foreground),
+                             find_closest(background));
     }
 
     fn with_color<F: FnOnce()>(&self, color: ColorStyle, f: F) {
         let (_, current_color_pair) = self.window.attrget();
-        let color_attribute = pancurses::ColorPair(color.id() as u8);
+        let color_attribute = pancurses::ColorPair(self.pair_id(color) as u8);
 
         self.window.attron(color_attribute);
         f();
@@ -58,6 +141,10 @@ impl backend::Backend for Concrete {
     fn with_effect<F: FnOnce()>(&self, effect: Effect, f: F) {
         let style = match effect {
             Effect::Reverse => pancurses::Attribute::Reverse,
+            Effect::Bold => pancurses::Attribute::Bold,
+            Effect::Underline => pancurses::Attribute::Underline,
+            Effect::Dim => pancurses::Attribute::Dim,
+            Effect::Invisible => pancurses::Attribute::Invisible,
             Effect::Simple => pancurses::Attribute::Normal,
         };
         self.window.attron(style);
@@ -70,7 +157,14 @@ impl backend::Backend for Concrete {
     }
 
     fn refresh(&mut self) {
+        if self.synchronized {
+            write_sequence(backend::BEGIN_SYNC);
+        }
         self.window.refresh();
+        if self.synchronized {
+            write_sequence(backend::END_SYNC);
+        }
+        self.pairs.borrow_mut().next_frame();
     }
 
     fn print_at(&self, (x, y): (usize, usize), text: &str) {
@@ -90,7 +184,7 @@ impl backend::Backend for Concrete {
                     Event::Key(Key::Backspace)
                 }
                 pancurses::Input::Character('\u{9}') => Event::Key(Key::Tab),
-                pancurses::Input::Character('\u{1b}') => Event::Key(Key::Esc),
+                pancurses::Input::Character('\u{1b}') => self.parse_escape(),
                 pancurses::Input::Character(c) if 32 <= (c as u32) &&
                                                   (c as u32) <= 255 => {
                     Event::Char(utf8::read_char(c as u8, || {
@@ -238,10 +332,15 @@ impl backend::Backend for Concrete {
     }
 
     fn set_refresh_rate(&mut self, fps: u32) {
-        if fps == 0 {
-            self.window.timeout(-1);
-        } else {
-            self.window.timeout(1000 / fps as i32);
-        }
+        self.timeout = if fps == 0 { -1 } else { 1000 / fps as i32 };
+        self.window.timeout(self.timeout);
+    }
+
+    fn set_esc_delay(&mut self, delay: u32) {
+        self.esc_delay = delay as i32;
+    }
+
+    fn set_synchronized_output(&mut self, enabled: bool) {
+        self.synchronized = enabled && cfg!(unix);
     }
 }
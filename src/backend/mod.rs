@@ -15,6 +15,13 @@ pub use self::curses::*;
 #[cfg(feature = "termion")]
 pub use self::termion::*;
 
+// Markers around a frame for synchronized output (DEC mode 2026).
+//
+// Supporting terminals hold the screen until the end marker, so a frame
+// is never shown half-drawn. Other terminals ignore unknown modes.
+const BEGIN_SYNC: &str = "\x1b[?2026h";
+const END_SYNC: &str = "\x1b[?2026l";
+
 pub trait Backend {
     fn init() -> Self;
     // TODO: take `self` by value?
@@ -25,13 +32,61 @@ pub trait Backend {
 
     fn has_colors(&self) -> bool;
 
+    /// Returns the number of colors the terminal can show.
+    fn color_depth(&self) -> theme::ColorDepth;
+
     fn init_color_style(&mut self, style: theme::ColorStyle,
                         foreground: &theme::Color, background: &theme::Color);
 
     fn print_at(&self, (usize, usize), &str);
 
+    /// Writes raw bytes (like escape sequences) at the given position.
+    ///
+    /// Returns `false` if this backend can't write to the terminal directly.
+    fn print_raw(&self, pos: (usize, usize), data: &[u8]) -> bool {
+        let _ = (pos, data);
+        false
+    }
+
+    /// Prints some text as a hyperlink to `url`.
+    ///
+    /// Backends that can't show hyperlinks just print the text.
+    fn print_link(&self, pos: (usize, usize), text: &str, url: &str) {
+        let _ = url;
+        self.print_at(pos, text);
+    }
+
+    /// Sends a control sequence to the terminal, like one changing a mode.
+    ///
+    /// Returns `false` if this backend can't.
+    fn send_sequence(&self, sequence: &str) -> bool {
+        let _ = sequence;
+        false
+    }
+
+    /// Enables or disables synchronized output.
+    ///
+    /// When enabled, `refresh` sends each frame between the markers of
+    /// synchronized output.
+    ///
+    /// Backends not drawing with escape sequences may ignore it.
+    fn set_synchronized_output(&mut self, enabled: bool) {
+        let _ = enabled;
+    }
+
     fn poll_event(&self) -> event::Event;
     fn set_refresh_rate(&mut self, fps: u32);
+
+    /// Sets how long to wait after an escape character, in milliseconds.
+    ///
+    /// Bytes arriving within this delay belong to the same key, like an
+    /// arrow key or an Alt combination. Otherwise, the escape key was
+    /// pressed by itself.
+    ///
+    /// Backends parsing input with their own rules may ignore it.
+    fn set_esc_delay(&mut self, delay: u32) {
+        let _ = delay;
+    }
     fn screen_size(&self) -> (usize, usize);
 
     // TODO: unify those into a single method?
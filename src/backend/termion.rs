@@ -12,10 +12,10 @@ use self::termion::style as tstyle;
 use backend;
 use chan;
 use event::{Event, Key};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::BTreeMap;
 use std::fmt;
-use std::io::Write;
+use std::io::{self, Write};
 use std::thread;
 
 use theme;
@@ -24,6 +24,10 @@ pub struct Concrete {
     terminal: AlternateScreen<termion::raw::RawTerminal<::std::io::Stdout>>,
     current_style: Cell<theme::ColorStyle>,
     colors: BTreeMap<i16, (Box<tcolor::Color>, Box<tcolor::Color>)>,
+    // Everything drawn since the last refresh.
+    output: RefCell<Vec<u8>>,
+    // Whether frames are sent with synchronized output.
+    synchronized: bool,
 
     input: chan::Receiver<Event>,
     resize: chan::Receiver<chan_signal::Signal>,
@@ -31,8 +35,8 @@ pub struct Concrete {
 }
 
 trait Effectable {
-    fn on(&self);
-    fn off(&self);
+    fn on(&self, backend: &Concrete);
+    fn off(&self, backend: &Concrete);
 }
 
 struct ColorRef<'a>(&'a tcolor::Color);
@@ -50,29 +54,70 @@ impl<'a> tcolor::Color for ColorRef<'a> {
 }
 
 impl Effectable for theme::Effect {
-    fn on(&self) {
-        match *self {
-            theme::Effect::Simple => (),
-            theme::Effect::Reverse => print!("{}", tstyle::Invert),
-        }
+    fn on(&self, backend: &Concrete) {
+        let style: &fmt::Display = match *self {
+            theme::Effect::Simple => return,
+            theme::Effect::Reverse => &tstyle::Invert,
+            theme::Effect::Bold => &tstyle::Bold,
+            theme::Effect::Underline => &tstyle::Underline,
+            theme::Effect::Dim => &tstyle::Faint,
+            // Termion has no name for this one.
+            theme::Effect::Invisible => &"\x1b[8m",
+        };
+        backend.write(format_args!("{}", style));
     }
 
-    fn off(&self) {
-        match *self {
-            theme::Effect::Simple => (),
-            theme::Effect::Reverse => print!("{}", tstyle::NoInvert),
-        }
+    fn off(&self, backend: &Concrete) {
+        let style: &fmt::Display = match *self {
+            theme::Effect::Simple => return,
+            theme::Effect::Reverse => &tstyle::NoInvert,
+            // `NoBold` turns on double underlines on some terminals.
+            theme::Effect::Bold => &tstyle::NoFaint,
+            theme::Effect::Underline => &tstyle::NoUnderline,
+            theme::Effect::Dim => &tstyle::NoFaint,
+            theme::Effect::Invisible => &"\x1b[28m",
+        };
+        backend.write(format_args!("{}", style));
     }
 }
 
-fn apply_colors(fg: &tcolor::Color, bg: &tcolor::Color) {
-    print!("{}{}", tcolor::Fg(ColorRef(fg)), tcolor::Bg(ColorRef(bg)));
-}
-
 impl Concrete {
+    // Queues output until the next refresh.
+    fn write(&self, args: fmt::Arguments) {
+        // Writing to a `Vec` can't fail.
+        let _ = self.output.borrow_mut().write_fmt(args);
+    }
+
+    // Writes the queued output to the terminal.
+    fn flush_output(&self, synchronized: bool) -> io::Result<()> {
+        let mut output = self.output.borrow_mut();
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        if synchronized && !output.is_empty() {
+            stdout.write_all(backend::BEGIN_SYNC.as_bytes())?;
+            stdout.write_all(&output)?;
+            stdout.write_all(backend::END_SYNC.as_bytes())?;
+        } else {
+            stdout.write_all(&output)?;
+        }
+        output.clear();
+        stdout.flush()
+    }
+
+    fn apply_colors(&self, fg: &tcolor::Color, bg: &tcolor::Color) {
+        self.write(format_args!("{}{}",
+                                tcolor::Fg(ColorRef(fg)),
+                                tcolor::Bg(ColorRef(bg))));
+    }
+
     fn apply_colorstyle(&self, color_style: theme::ColorStyle) {
+        if let theme::ColorStyle::Custom { front, back } = color_style {
+            self.apply_colors(&*colour_to_termion_colour(&front),
+                              &*colour_to_termion_colour(&back));
+            return;
+        }
         let (ref fg, ref bg) = self.colors[&color_style.id()];
-        apply_colors(&**fg, &**bg);
+        self.apply_colors(&**fg, &**bg);
     }
 }
 
@@ -95,6 +140,8 @@ impl backend::Backend for Concrete {
             terminal: terminal,
             current_style: Cell::new(theme::ColorStyle::Background),
             colors: BTreeMap::new(),
+            output: RefCell::new(Vec::new()),
+            synchronized: true,
             input: receiver,
             resize: resize,
             timeout: None,
@@ -132,9 +179,9 @@ impl backend::Backend for Concrete {
     }
 
     fn with_effect<F: FnOnce()>(&self, effect: theme::Effect, f: F) {
-        effect.on();
+        effect.on(self);
         f();
-        effect.off();
+        effect.off(self);
     }
 
     fn has_colors(&self) -> bool {
@@ -142,6 +189,16 @@ impl backend::Backend for Concrete {
         true
     }
 
+    fn color_depth(&self) -> theme::ColorDepth {
+        // Terminals with 24-bit colors usually advertise it here.
+        match ::std::env::var("COLORTERM") {
+            Ok(ref value) if value == "truecolor" || value == "24bit" => {
+                theme::ColorDepth::TrueColor
+            }
+            _ => theme::ColorDepth::Colors256,
+        }
+    }
+
     fn screen_size(&self) -> (usize, usize) {
         let (x, y) = termion::terminal_size().unwrap_or((1, 1));
         (x as usize, y as usize)
@@ -149,17 +206,47 @@ impl backend::Backend for Concrete {
 
     fn clear(&self) {
         self.apply_colorstyle(theme::ColorStyle::Background);
-        print!("{}", termion::clear::All);
+        self.write(format_args!("{}", termion::clear::All));
     }
 
     fn refresh(&mut self) {
+        // The whole frame is sent at once.
+        self.flush_output(self.synchronized).unwrap();
         self.terminal.flush().unwrap();
     }
 
     fn print_at(&self, (x, y): (usize, usize), text: &str) {
-        print!("{}{}",
-               termion::cursor::Goto(1 + x as u16, 1 + y as u16),
-               text);
+        self.write(format_args!("{}{}",
+                                termion::cursor::Goto(1 + x as u16,
+                                                      1 + y as u16),
+                                text));
+    }
+
+    fn print_link(&self, (x, y): (usize, usize), text: &str, url: &str) {
+        // OSC 8 hyperlink, ignored by terminals not supporting it.
+        self.write(format_args!("{}\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\",
+                                termion::cursor::Goto(1 + x as u16,
+                                                      1 + y as u16),
+                                url,
+                                text));
+    }
+
+    fn print_raw(&self, (x, y): (usize, usize), data: &[u8]) -> bool {
+        self.write(format_args!("{}",
+                                termion::cursor::Goto(1 + x as u16,
+                                                      1 + y as u16)));
+        self.output.borrow_mut().extend_from_slice(data);
+        true
+    }
+
+    fn send_sequence(&self, sequence: &str) -> bool {
+        // Sent right away, with anything queued before.
+        self.write(format_args!("{}", sequence));
+        self.flush_output(false).is_ok()
+    }
+
+    fn set_synchronized_output(&mut self, enabled: bool) {
+        self.synchronized = enabled;
     }
 
     fn set_refresh_rate(&mut self, fps: u32) {
@@ -258,5 +345,6 @@ fn colour_to_termion_colour(clr: &theme::Color) -> Box<tcolor::Color> {
         theme::Color::RgbLowRes(r, g, b) => {
             Box::new(tcolor::AnsiValue::rgb(r, g, b))
         }
+        theme::Color::TerminalDefault => Box::new(tcolor::Reset),
     }
 }
@@ -0,0 +1,171 @@
+//! Reusable composite views, built by name.
+//!
+//! A [`Component`] packages a tree of views, for instance a label next to
+//! a validated field, so it can be shared between applications. Once
+//! registered with [`Cursive::register_component`], it can be built
+//! anywhere from its name with [`Cursive::build_component`].
+//!
+//! Components are given the `Cursive` root when built, so they can set up
+//! what they need besides the views. By convention:
+//!
+//! * Parts of the component that others may want to reach are given an
+//!   ID, prefixed with the name of the instance: `"<instance>/<part>"`.
+//! * The component reports what happens to it by publishing on topics
+//!   named the same way, using [`Cursive::publish`]. The application
+//!   subscribes to the topics it cares about, rather than passing
+//!   callbacks to the component.
+//!
+//! [`instance_id`] builds these names.
+//!
+//! [`Component`]: trait.Component.html
+//! [`instance_id`]: fn.instance_id.html
+//! [`Cursive::register_component`]: ../struct.Cursive.html#method.register_component
+//! [`Cursive::build_component`]: ../struct.Cursive.html#method.build_component
+//! [`Cursive::publish`]: ../struct.Cursive.html#method.publish
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use cursive::Cursive;
+//! # use cursive::component::{Component, instance_id};
+//! # use cursive::traits::*;
+//! # use cursive::view::View;
+//! # use cursive::views::{Dialog, EditView, LinearLayout, TextView};
+//! // A field only accepting numbers.
+//! struct NumberField {
+//!     instance: String,
+//! }
+//!
+//! impl Component for NumberField {
+//!     fn build(&self, _: &mut Cursive) -> Box<View> {
+//!         let topic = instance_id(&self.instance, "changed");
+//!         let field = EditView::new()
+//!             .on_edit(move |s, text, _| if let Ok(n) = text.parse::<i64>() {
+//!                          s.publish(&topic, n);
+//!                      })
+//!             .with_id(instance_id(&self.instance, "field"))
+//!             .fixed_width(10);
+//!         Box::new(LinearLayout::horizontal()
+//!                      .child(TextView::new("Age: "))
+//!                      .child(field))
+//!     }
+//! }
+//!
+//! let mut siv = Cursive::new();
+//! siv.register_component("age", NumberField { instance: "age".into() });
+//! siv.subscribe("age/changed", |_, age: &i64| println!("{}", age));
+//!
+//! let view = siv.build_component("age").unwrap();
+//! siv.add_layer(Dialog::around(view));
+//! ```
+
+use Cursive;
+use std::collections::HashMap;
+use std::rc::Rc;
+use view::View;
+
+/// Builds a composite view.
+///
+/// Closures taking the `Cursive` root and returning a view implement this
+/// trait.
+pub trait Component {
+    /// Builds a new instance of this component.
+    fn build(&self, siv: &mut Cursive) -> Box<View>;
+}
+
+impl<F> Component for F
+    where F: Fn(&mut Cursive) -> Box<View>
+{
+    fn build(&self, siv: &mut Cursive) -> Box<View> {
+        self(siv)
+    }
+}
+
+/// Returns the name of `part`, in the component instance `instance`.
+///
+/// Use it for IDs and topics, as described in the [module] documentation.
+///
+/// [module]: index.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::component::instance_id;
+/// assert_eq!(instance_id("age", "field"), "age/field");
+/// ```
+pub fn instance_id(instance: &str, part: &str) -> String {
+    format!("{}/{}", instance, part)
+}
+
+/// Components, by name.
+#[derive(Default)]
+pub struct Registry {
+    components: HashMap<String, Rc<Component>>,
+}
+
+impl Registry {
+    /// Creates a new, empty registry.
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Registers `component` under `name`.
+    ///
+    /// Returns the component previously registered under this name, if
+    /// any.
+    pub fn register<S, C>(&mut self, name: S, component: C)
+                          -> Option<Rc<Component>>
+        where S: Into<String>,
+              C: Component + 'static
+    {
+        self.components.insert(name.into(), Rc::new(component))
+    }
+
+    /// Removes the component registered under `name`, and returns it.
+    pub fn unregister(&mut self, name: &str) -> Option<Rc<Component>> {
+        self.components.remove(name)
+    }
+
+    /// Returns the component registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Rc<Component>> {
+        self.components.get(name).cloned()
+    }
+
+    /// Returns `true` if a component is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    /// Returns the names of the registered components, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<_> =
+            self.components.keys().map(|name| &name[..]).collect();
+        names.sort();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+    use Cursive;
+    use view::View;
+    use views::TextView;
+
+    fn label(_: &mut Cursive) -> Box<View> {
+        Box::new(TextView::new("label"))
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        assert!(registry.register("label", label).is_none());
+        assert!(registry.register("other", label).is_none());
+        assert!(registry.register("label", label).is_some());
+        assert_eq!(registry.names(), ["label", "other"]);
+
+        assert!(registry.unregister("other").is_some());
+        assert!(!registry.contains("other"));
+        assert!(registry.get("label").is_some());
+    }
+}
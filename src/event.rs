@@ -9,14 +9,33 @@
 //!       executed.
 //!     * Otherwise, it ignores the event, and the view parent can in turn
 //!       choose to consume it or not.
+//!     * It may also ask one of its ancestors to act, with
+//!       [`EventResult::with_ancestor`].
 //! * If no view consumes the event, the
 //!   [global callback](../struct.Cursive.html#method.add_global_callback)
 //!   table is checked.
-
+//!
+//! [`EventResult::with_ancestor`]:
+//! ./enum.EventResult.html#method.with_ancestor
+//!
+//! # Capturing events
+//!
+//! `LinearLayout`, `ListView` and `StackView` accept a capture callback,
+//! with `set_on_capture`. It sees each event before their focused child
+//! (or top layer) does, and can change it, or consume it by returning
+//! `EventResult::Consumed`: the child then never sees it.
+//!
+//! There is no bubbling phase: the callback only runs once, on the way
+//! down. If the child ignores the event, the container handles it as
+//! usual.
 
 use Cursive;
+use std::any::Any;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::rc::Rc;
+use std::sync::Arc;
 
 /// Callback is a function that can be triggered by an event.
 /// It has a mutable access to the cursive root.
@@ -63,18 +82,93 @@ pub enum EventResult {
     Ignored,
     /// The event was consumed. An optionnal callback to run is attached.
     Consumed(Option<Callback>), // TODO: make this a FnOnce?
+    /// The event was consumed, and a callback should run on an ancestor.
+    ///
+    /// See [`EventResult::with_ancestor`].
+    ///
+    /// [`EventResult::with_ancestor`]: #method.with_ancestor
+    Ancestor(AncestorCallback),
 }
 
+/// Callback to run on the closest ancestor of a given type.
+///
+/// It is given each ancestor in turn, from the closest one, and returns
+/// `None` until it finds the right type.
+pub struct AncestorCallback(Box<FnMut(&mut Any) -> Option<EventResult>>);
+
 impl EventResult {
     /// Convenient method to create `Consumed(Some(f))`
     pub fn with_cb<F: 'static + Fn(&mut Cursive)>(f: F) -> Self {
         EventResult::Consumed(Some(Callback::from_fn(f)))
     }
 
+    /// Runs `f` on the closest ancestor of type `V`.
+    ///
+    /// This lets a view act on the views containing it, for instance to
+    /// close its dialog. Every view containing others can be reached this
+    /// way, including [`ViewWrapper`]s like `Panel` or `BoxView`, and
+    /// views wrapped in an [`IdView`].
+    ///
+    /// `f` returns the result of the event as seen by the ancestor's own
+    /// parent. If no ancestor has the right type, the event is consumed and
+    /// nothing happens.
+    ///
+    /// [`ViewWrapper`]: ../view/trait.ViewWrapper.html
+    /// [`IdView`]: ../views/struct.IdView.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::event::{Event, EventResult};
+    /// # use cursive::views::{Canvas, StackView};
+    /// // Closes the layer containing this view.
+    /// let view = Canvas::new(()).with_on_event(|event, _| match event {
+    ///     Event::Char('q') => {
+    ///         EventResult::with_ancestor(|stack: &mut StackView| {
+    ///             stack.pop_layer();
+    ///             EventResult::Consumed(None)
+    ///         })
+    ///     }
+    ///     _ => EventResult::Ignored,
+    /// });
+    /// ```
+    pub fn with_ancestor<V, F>(f: F) -> Self
+        where V: Any,
+              F: FnOnce(&mut V) -> EventResult + 'static
+    {
+        let mut f = Some(f);
+        EventResult::Ancestor(AncestorCallback(Box::new(move |view| {
+            let view = view.downcast_mut::<V>()?;
+            f.take().map(|f| f(view))
+        })))
+    }
+
+    /// Gives a pending ancestor callback to `view`.
+    ///
+    /// Views containing others call this on the results of their children,
+    /// to be reachable with [`EventResult::with_ancestor`]. Other results
+    /// are returned unchanged.
+    ///
+    /// [`EventResult::with_ancestor`]: #method.with_ancestor
+    pub fn resolve_on(self, view: &mut Any) -> Self {
+        match self {
+            EventResult::Ancestor(mut cb) => {
+                match (cb.0)(view) {
+                    Some(result) => result,
+                    None => EventResult::Ancestor(cb),
+                }
+            }
+            result => result,
+        }
+    }
+
     /// Returns `true` if `self` is `EventResult::Consumed`.
+    ///
+    /// Results waiting for an ancestor are consumed too.
     pub fn is_consumed(&self) -> bool {
         match *self {
-            EventResult::Consumed(_) => true,
+            EventResult::Consumed(_) |
+            EventResult::Ancestor(_) => true,
             EventResult::Ignored => false,
         }
     }
@@ -89,6 +183,35 @@ impl EventResult {
     }
 }
 
+/// Where an event went, as seen by a [post-event hook].
+///
+/// [post-event hook]: ../struct.Cursive.html#method.set_post_event_hook
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventRoute {
+    /// The event was used to record or play a keyboard macro.
+    Macro,
+    /// The event was consumed by the menubar.
+    Menubar,
+    /// The event was consumed by a view of the current screen.
+    ///
+    /// This is the focused view, or one of its parents.
+    View {
+        /// `true` if the view returned a callback.
+        callback: bool,
+        /// Index of the layer which got the event, from the bottom.
+        ///
+        /// `None` if the screen has no layer.
+        layer: Option<usize>,
+        /// Description of the focused view when the event came,
+        /// from `View::describe`.
+        focused: Option<String>,
+    },
+    /// No view used the event, but a global callback did.
+    GlobalCallback,
+    /// Nothing used the event.
+    Ignored,
+}
+
 /// A non-character key on the keyboard
 #[derive(PartialEq,Eq,Clone,Copy,Hash,Debug)]
 pub enum Key {
@@ -185,6 +308,173 @@ impl Key {
     }
 }
 
+/// What happened to a key, for terminals reporting it.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum KeyAction {
+    /// The key was pressed.
+    Press,
+    /// The key is held down, and auto-repeats.
+    Repeat,
+    /// The key was released.
+    Release,
+}
+
+/// Modifier keys held during a key event.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct Modifiers {
+    /// A Shift key is held.
+    pub shift: bool,
+    /// An Alt key is held.
+    pub alt: bool,
+    /// A Ctrl key is held.
+    pub ctrl: bool,
+    /// A Super key (often the Windows or Command key) is held.
+    pub super_key: bool,
+    /// A Hyper key is held.
+    pub hyper: bool,
+    /// A Meta key is held.
+    pub meta: bool,
+    /// Caps Lock is on.
+    pub caps_lock: bool,
+    /// Num Lock is on.
+    pub num_lock: bool,
+}
+
+/// The key part of a `KeyEvent`.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum KeyCode {
+    /// A key entering a character.
+    Char(char),
+    /// A non-character key.
+    Key(Key),
+}
+
+/// A detailed key event.
+///
+/// Only available with terminals supporting the [kitty keyboard protocol],
+/// when enabled with [`Cursive::set_kitty_keyboard`].
+///
+/// [kitty keyboard protocol]:
+/// https://sw.kovidgoyal.net/kitty/keyboard-protocol/
+/// [`Cursive::set_kitty_keyboard`]:
+/// ../struct.Cursive.html#method.set_kitty_keyboard
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct KeyEvent {
+    /// The key.
+    pub code: KeyCode,
+    /// Modifiers held.
+    pub modifiers: Modifiers,
+    /// What happened to the key.
+    pub action: KeyAction,
+}
+
+impl KeyEvent {
+    /// Returns the equivalent simple event.
+    ///
+    /// Key presses and repeats become the same event as without the kitty
+    /// protocol, so views keep working. Releases, and combinations other
+    /// events can't describe, become `Event::Extended`.
+    pub fn to_event(&self) -> Event {
+        if self.action == KeyAction::Release {
+            return Event::Extended(*self);
+        }
+
+        let m = self.modifiers;
+        let event = if m.super_key || m.hyper || m.meta {
+            None
+        } else {
+            match (self.code, m.ctrl, m.alt, m.shift) {
+                (KeyCode::Char(c), false, false, _) => Some(Event::Char(c)),
+                (KeyCode::Char(c), true, false, false) => {
+                    Some(Event::CtrlChar(c))
+                }
+                (KeyCode::Char(c), false, true, false) => {
+                    Some(Event::AltChar(c))
+                }
+                (KeyCode::Key(k), false, false, false) => Some(Event::Key(k)),
+                (KeyCode::Key(k), false, false, true) => {
+                    Some(Event::Shift(k))
+                }
+                (KeyCode::Key(k), false, true, false) => Some(Event::Alt(k)),
+                (KeyCode::Key(k), false, true, true) => {
+                    Some(Event::AltShift(k))
+                }
+                (KeyCode::Key(k), true, false, false) => Some(Event::Ctrl(k)),
+                (KeyCode::Key(k), true, false, true) => {
+                    Some(Event::CtrlShift(k))
+                }
+                (KeyCode::Key(k), true, true, false) => {
+                    Some(Event::CtrlAlt(k))
+                }
+                _ => None,
+            }
+        };
+
+        event.unwrap_or(Event::Extended(*self))
+    }
+}
+
+/// A message sent by the application itself, rather than by the user.
+///
+/// It can hold any value, for custom views to react to.
+///
+/// Two `AppEvent`s are equal only if they are clones of each other.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::event::AppEvent;
+/// struct Downloaded(usize);
+///
+/// let event = AppEvent::new(Downloaded(42));
+/// if let Some(&Downloaded(n)) = event.downcast_ref::<Downloaded>() {
+///     assert_eq!(n, 42);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct AppEvent(Arc<Any + Send + Sync>);
+
+impl AppEvent {
+    /// Wraps the given value into an `AppEvent`.
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        AppEvent(Arc::new(value))
+    }
+
+    /// Returns `true` if the value is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+
+    /// Returns the value, if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+
+    fn address(&self) -> usize {
+        &*self.0 as *const (Any + Send + Sync) as *const () as usize
+    }
+}
+
+impl PartialEq for AppEvent {
+    fn eq(&self, other: &AppEvent) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl Eq for AppEvent {}
+
+impl Hash for AppEvent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address().hash(state);
+    }
+}
+
+impl fmt::Debug for AppEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AppEvent({:#x})", self.address())
+    }
+}
+
 /// Represents an event as seen by the application.
 #[derive(PartialEq,Eq,Clone,Hash,Debug)]
 pub enum Event {
@@ -217,19 +507,106 @@ pub enum Event {
     CtrlAlt(Key),
 
     /// An unknown event was received.
+    ///
+    /// Contains the raw bytes, like an escape sequence the backend couldn't
+    /// parse. Use [`Cursive::add_sequence_parser`] to recognize more
+    /// sequences.
+    ///
+    /// [`Cursive::add_sequence_parser`]:
+    /// ../struct.Cursive.html#method.add_sequence_parser
     Unknown(Vec<u8>),
 
+    /// A key event other events can't describe, like a key release.
+    ///
+    /// See [`KeyEvent::to_event`](struct.KeyEvent.html#method.to_event).
+    Extended(KeyEvent),
+
+    /// A message from the application.
+    ///
+    /// See [`Cursive::post_event`](../struct.Cursive.html#method.post_event)
+    /// and [`Cursive::event_sink`](../struct.Cursive.html#method.event_sink).
+    AppEvent(AppEvent),
+
     #[doc(hidden)]
     /// The application is about to exit.
     Exit,
 }
 
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Key::Ins => write!(f, "Insert"),
+            Key::Del => write!(f, "Delete"),
+            // Variant names are readable enough for the others.
+            ref key => write!(f, "{:?}", key),
+        }
+    }
+}
+
+/// Shows the key combination, like `Ctrl-P` or `Shift-Tab`.
+///
+/// Events that are not key presses use their debug representation.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::Char(' ') => write!(f, "Space"),
+            Event::Char(c) => write!(f, "{}", c),
+            Event::CtrlChar(c) => write!(f, "Ctrl-{}", c.to_uppercase()),
+            Event::AltChar(c) => write!(f, "Alt-{}", c),
+            Event::Key(key) => write!(f, "{}", key),
+            Event::Shift(key) => write!(f, "Shift-{}", key),
+            Event::Alt(key) => write!(f, "Alt-{}", key),
+            Event::AltShift(key) => write!(f, "Alt-Shift-{}", key),
+            Event::Ctrl(key) => write!(f, "Ctrl-{}", key),
+            Event::CtrlShift(key) => write!(f, "Ctrl-Shift-{}", key),
+            Event::CtrlAlt(key) => write!(f, "Ctrl-Alt-{}", key),
+            Event::Extended(ref event) => write!(f, "{}", event),
+            ref event => write!(f, "{:?}", event),
+        }
+    }
+}
+
+/// Shows the key combination, like `Super-Ctrl-S`, and the action if it
+/// isn't a press.
+impl fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let m = self.modifiers;
+        let names = [(m.super_key, "Super"),
+                     (m.hyper, "Hyper"),
+                     (m.meta, "Meta"),
+                     (m.ctrl, "Ctrl"),
+                     (m.alt, "Alt"),
+                     (m.shift, "Shift")];
+        for &(_, name) in names.iter().filter(|&&(held, _)| held) {
+            write!(f, "{}-", name)?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space")?,
+            KeyCode::Char(c) => write!(f, "{}", c)?,
+            KeyCode::Key(key) => write!(f, "{}", key)?,
+        }
+
+        match self.action {
+            KeyAction::Press => Ok(()),
+            KeyAction::Repeat => write!(f, " (repeat)"),
+            KeyAction::Release => write!(f, " (release)"),
+        }
+    }
+}
+
 impl From<char> for Event {
     fn from(c: char) -> Event {
         Event::Char(c)
     }
 }
 
+impl From<AppEvent> for Event {
+    fn from(event: AppEvent) -> Event {
+        Event::AppEvent(event)
+    }
+}
+
 impl From<Key> for Event {
     fn from(k: Key) -> Event {
         Event::Key(k)
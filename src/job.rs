@@ -0,0 +1,196 @@
+//! Background jobs reporting to the UI.
+//!
+//! [`Cursive::spawn_job`] runs some work in another thread, behind a
+//! dialog showing its progress. The dialog's `Cancel` button sets a
+//! [`CancelToken`], which the work checks from time to time.
+//!
+//! [`Cursive::spawn_job`]: ../struct.Cursive.html#method.spawn_job
+//! [`CancelToken`]: struct.CancelToken.html
+
+use {Cursive, ScreenId};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::thread;
+use utils::i18n;
+use view::{Boxable, Identifiable};
+use views::{Counter, Dialog, ProgressBar};
+
+// Used to give each job dialog its own ID.
+static NEXT_JOB: AtomicUsize = AtomicUsize::new(0);
+
+/// Flag asking a background job to stop.
+///
+/// Clones share the same flag.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Creates a new token, not cancelled.
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+
+    /// Asks the job to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the job was asked to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Handle given to the work of a background job.
+pub struct Job {
+    token: CancelToken,
+    progress: Counter,
+    sink: Sender<Box<Fn(&mut Cursive) + Send>>,
+}
+
+// Makes a callback from a function to run once.
+fn once<F>(cb: F) -> Box<Fn(&mut Cursive) + Send>
+    where F: FnOnce(&mut Cursive) + Send + 'static
+{
+    let cb = Mutex::new(Some(cb));
+    Box::new(move |s| if let Some(cb) = cb.lock().unwrap().take() {
+                 cb(s);
+             })
+}
+
+impl Job {
+    /// Returns `true` if the user cancelled the job.
+    ///
+    /// The work should then return as soon as possible.
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Returns the token used to cancel this job.
+    pub fn token(&self) -> &CancelToken {
+        &self.token
+    }
+
+    /// Sets the progress shown in the dialog, in percent.
+    pub fn set_progress(&self, percent: usize) {
+        self.progress.set(percent);
+    }
+
+    /// Runs `cb` on the UI thread, on the next event cycle.
+    ///
+    /// Does nothing if the application already stopped.
+    pub fn post<F>(&self, cb: F)
+        where F: FnOnce(&mut Cursive) + Send + 'static
+    {
+        self.sink.send(once(cb)).ok();
+    }
+}
+
+impl Cursive {
+    /// Runs `work` in a new thread, showing its progress in a dialog.
+    ///
+    /// `work` is given a [`Job`] to report its progress and check if the
+    /// user pressed `Cancel`. Once it returns, the dialog is removed and
+    /// `on_done` runs on the UI thread with the result, even if the job
+    /// was cancelled.
+    ///
+    /// As with [`cb_sink`], call [`set_fps`] so updates show up.
+    ///
+    /// Returns the token cancelling the job.
+    ///
+    /// [`Job`]: job/struct.Job.html
+    /// [`cb_sink`]: #method.cb_sink
+    /// [`set_fps`]: #method.set_fps
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// # use cursive::views::Dialog;
+    /// # use std::thread;
+    /// # use std::time::Duration;
+    /// let mut siv = Cursive::new();
+    /// siv.set_fps(10);
+    ///
+    /// siv.spawn_job("Counting", |job| {
+    ///     let mut count = 0;
+    ///     while count < 100 && !job.is_cancelled() {
+    ///         count += 1;
+    ///         job.set_progress(count);
+    ///         thread::sleep(Duration::from_millis(50));
+    ///     }
+    ///     count
+    /// }, |s, count| {
+    ///     s.add_layer(Dialog::info(format!("Counted to {}", count)));
+    /// });
+    /// ```
+    pub fn spawn_job<S, F, R, D>(&mut self, title: S, work: F, on_done: D)
+                                 -> CancelToken
+        where S: Into<String>,
+              F: FnOnce(&Job) -> R + Send + 'static,
+              R: Send + 'static,
+              D: FnOnce(&mut Cursive, R) + Send + 'static
+    {
+        let token = CancelToken::new();
+        let progress = Counter::new(0);
+        let id = format!("cursive-job-{}",
+                         NEXT_JOB.fetch_add(1, Ordering::Relaxed));
+
+        let screen = self.active_screen;
+        let cancel = token.clone();
+        let dialog_id = id.clone();
+        let dialog = Dialog::around(ProgressBar::new()
+                                        .with_value(progress.clone())
+                                        .min_width(30))
+            .title(title)
+            .button(i18n::translate("Cancel"), move |s| {
+                cancel.cancel();
+                remove_dialog(s, screen, &dialog_id);
+            })
+            .with_id(id.clone());
+        self.add_layer(dialog);
+
+        let job = Job {
+            token: token.clone(),
+            progress: progress,
+            sink: self.cb_sink.clone(),
+        };
+        thread::spawn(move || {
+            let result = work(&job);
+            job.post(move |s| {
+                // The dialog is gone if the user cancelled.
+                remove_dialog(s, screen, &id);
+                on_done(s, result);
+            });
+        });
+
+        token
+    }
+}
+
+// Removes the job dialog `id` from `screen`, if it's still there.
+//
+// Other layers may have been pushed above it since, or another screen
+// may be active.
+fn remove_dialog(s: &mut Cursive, screen: ScreenId, id: &str) {
+    let stack = &mut s.screens[screen];
+    if let Some(i) = stack.find_layer_from_id(id) {
+        stack.remove_layer(i);
+        s.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CancelToken;
+
+    #[test]
+    fn test_token() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+}
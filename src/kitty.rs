@@ -0,0 +1,176 @@
+//! Parses key events sent with the kitty keyboard protocol.
+//!
+//! Once enabled, the terminal sends every key as a control sequence:
+//! `CSI code[:shifted] ; modifiers[:action] u` for most keys, or
+//! `CSI number ; modifiers[:action] ~` and `CSI 1 ; modifiers[:action] X` for
+//! the keys legacy terminals already encoded this way.
+//!
+//! See https://sw.kovidgoyal.net/kitty/keyboard-protocol/
+
+use event::{Key, KeyAction, KeyCode, KeyEvent, Modifiers};
+use std::str;
+
+/// Sequence enabling the protocol.
+///
+/// Asks for unambiguous keys, actions, shifted keys, and all keys as
+/// sequences (so releases are reported for text keys too).
+pub const ENABLE: &'static str = "\x1b[>15u";
+
+/// Sequence restoring the previous keyboard mode.
+pub const DISABLE: &'static str = "\x1b[<u";
+
+/// Parses a sequence, including the leading `ESC [`.
+///
+/// Returns `None` if it isn't a key event.
+pub fn parse(bytes: &[u8]) -> Option<KeyEvent> {
+    if bytes.len() < 3 || !bytes.starts_with(b"\x1b[") {
+        return None;
+    }
+    let last = bytes.len() - 1;
+    let params = str::from_utf8(&bytes[2..last]).ok()?;
+    let mut fields = params.split(';');
+
+    let mut codes = fields.next().unwrap_or("").split(':');
+    let code = number(codes.next(), 1)?;
+    let shifted = number(codes.next(), 0)?;
+
+    let mut states = fields.next().unwrap_or("").split(':');
+    let modifiers = number(states.next(), 1)?.checked_sub(1)?;
+    let action = match number(states.next(), 1)? {
+        1 => KeyAction::Press,
+        2 => KeyAction::Repeat,
+        3 => KeyAction::Release,
+        _ => return None,
+    };
+
+    let modifiers = Modifiers {
+        shift: modifiers & 1 != 0,
+        alt: modifiers & 2 != 0,
+        ctrl: modifiers & 4 != 0,
+        super_key: modifiers & 8 != 0,
+        hyper: modifiers & 16 != 0,
+        meta: modifiers & 32 != 0,
+        caps_lock: modifiers & 64 != 0,
+        num_lock: modifiers & 128 != 0,
+    };
+
+    let code = match bytes[last] {
+        b'u' => unicode_key(code, shifted, modifiers)?,
+        b'~' => KeyCode::Key(tilde_key(code)?),
+        b'A' => KeyCode::Key(Key::Up),
+        b'B' => KeyCode::Key(Key::Down),
+        b'C' => KeyCode::Key(Key::Right),
+        b'D' => KeyCode::Key(Key::Left),
+        b'E' => KeyCode::Key(Key::NumpadCenter),
+        b'F' => KeyCode::Key(Key::End),
+        b'H' => KeyCode::Key(Key::Home),
+        b'P' => KeyCode::Key(Key::F1),
+        b'Q' => KeyCode::Key(Key::F2),
+        b'S' => KeyCode::Key(Key::F4),
+        _ => return None,
+    };
+
+    Some(KeyEvent {
+             code: code,
+             modifiers: modifiers,
+             action: action,
+         })
+}
+
+// Parses a number, or returns `default` if it's missing.
+fn number(field: Option<&str>, default: u32) -> Option<u32> {
+    match field {
+        None | Some("") => Some(default),
+        Some(field) => field.parse().ok(),
+    }
+}
+
+// Returns the key for a `CSI ... u` sequence.
+fn unicode_key(code: u32, shifted: u32, modifiers: Modifiers)
+               -> Option<KeyCode> {
+    let key = match code {
+        9 => Key::Tab,
+        13 => Key::Enter,
+        27 => Key::Esc,
+        127 => Key::Backspace,
+        // Other functional keys, like modifiers alone, use the private
+        // use area.
+        57344..=63743 => return None,
+        _ => {
+            let c = ::std::char::from_u32(code)?;
+            let c = if !modifiers.shift {
+                c
+            } else if shifted != 0 {
+                ::std::char::from_u32(shifted)?
+            } else {
+                c.to_uppercase().next().unwrap_or(c)
+            };
+            return Some(KeyCode::Char(c));
+        }
+    };
+    Some(KeyCode::Key(key))
+}
+
+// Returns the key for a `CSI ... ~` sequence.
+fn tilde_key(code: u32) -> Option<Key> {
+    Some(match code {
+             2 => Key::Ins,
+             3 => Key::Del,
+             5 => Key::PageUp,
+             6 => Key::PageDown,
+             7 => Key::Home,
+             8 => Key::End,
+             11 => Key::F1,
+             12 => Key::F2,
+             13 => Key::F3,
+             14 => Key::F4,
+             15 => Key::F5,
+             17 => Key::F6,
+             18 => Key::F7,
+             19 => Key::F8,
+             20 => Key::F9,
+             21 => Key::F10,
+             23 => Key::F11,
+             24 => Key::F12,
+             _ => return None,
+         })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use event::{Event, Key, KeyAction, KeyCode};
+
+    #[test]
+    fn test_parse() {
+        let event = parse(b"\x1b[97u").unwrap();
+        assert_eq!(event.code, KeyCode::Char('a'));
+        assert_eq!(event.action, KeyAction::Press);
+        assert_eq!(event.to_event(), Event::Char('a'));
+
+        // Shift-a, with the shifted key.
+        let event = parse(b"\x1b[97:65;2u").unwrap();
+        assert_eq!(event.to_event(), Event::Char('A'));
+
+        // Ctrl-Up, repeated.
+        let event = parse(b"\x1b[1;5:2A").unwrap();
+        assert_eq!(event.action, KeyAction::Repeat);
+        assert_eq!(event.to_event(), Event::Ctrl(Key::Up));
+
+        // Releases don't look like presses.
+        let event = parse(b"\x1b[6;1:3~").unwrap();
+        assert_eq!(event.code, KeyCode::Key(Key::PageDown));
+        assert_eq!(event.to_event(), Event::Extended(event));
+        assert_eq!(event.to_string(), "PageDown (release)");
+
+        // Super isn't known to other events.
+        let event = parse(b"\x1b[115;9u").unwrap();
+        assert!(event.modifiers.super_key);
+        assert_eq!(event.to_string(), "Super-s");
+        assert_eq!(event.to_event(), Event::Extended(event));
+
+        assert!(parse(b"\x1b[57441u").is_none());
+        assert!(parse(b"\x1b[1;1:9A").is_none());
+        assert!(parse(b"\x1bOP").is_none());
+    }
+}
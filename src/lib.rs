@@ -66,6 +66,25 @@ extern crate odds;
 extern crate num;
 extern crate owning_ref;
 
+#[cfg(feature = "unicode-bidi")]
+extern crate unicode_bidi;
+#[cfg(feature = "syntect")]
+extern crate syntect;
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "qrcode")]
+extern crate qrcode;
+#[cfg(feature = "libc")]
+extern crate libc;
+#[cfg(feature = "terminal-view")]
+extern crate vte;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
+#[cfg(feature = "yaml-rust")]
+extern crate yaml_rust;
+
 #[cfg(feature = "termion")]
 #[macro_use]
 extern crate chan;
@@ -91,7 +110,11 @@ macro_rules! new_default(
 
 pub mod traits;
 
+pub mod accessibility;
+pub mod component;
+
 pub mod event;
+pub mod job;
 #[macro_use]
 pub mod view;
 
@@ -101,7 +124,11 @@ pub mod theme;
 pub mod align;
 pub mod menu;
 pub mod direction;
+pub mod metrics;
+pub mod shortcuts;
 pub mod utils;
+#[cfg(feature = "markup")]
+pub mod markup;
 
 // This probably doesn't need to be public?
 mod printer;
@@ -110,6 +137,8 @@ mod with;
 
 mod div;
 mod utf8;
+mod recorder;
+mod kitty;
 
 #[doc(hidden)]
 pub mod backend;
@@ -117,15 +146,18 @@ pub mod backend;
 
 use backend::Backend;
 
-use event::{Callback, Event, EventResult};
+use event::{AppEvent, Callback, Event, EventResult, EventRoute};
 
-pub use printer::Printer;
+pub use printer::{CellBuffer, Printer};
 
 use std::any::Any;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 use std::sync::mpsc;
+use std::time::{Instant, SystemTime};
 
 use vec::Vec2;
 use view::Finder;
@@ -134,9 +166,17 @@ pub use with::With;
 pub use xy::XY;
 
 
+// Returns the last modification time of a file, if it can be known.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
 /// Identifies a screen in the cursive root.
 pub type ScreenId = usize;
 
+// Number of frames kept when collecting metrics.
+const METRICS_FRAMES: usize = 120;
+
 /// Central part of the cursive library.
 ///
 /// It initializes ncurses on creation and cleans up on drop.
@@ -146,13 +186,27 @@ pub type ScreenId = usize;
 /// It uses a list of screen, with one screen active at a time.
 pub struct Cursive {
     theme: theme::Theme,
+    // Theme file to reload when modified, with its last modification time.
+    theme_file: Option<(PathBuf, Option<SystemTime>)>,
+    terminal_background: Option<theme::Brightness>,
     screens: Vec<views::StackView>,
+    screen_names: HashMap<String, ScreenId>,
+    // Callbacks for each screen, checked before the global ones.
+    screen_callbacks: Vec<HashMap<Event, Callback>>,
+    on_screen_switch: Option<Rc<Fn(&mut Cursive, ScreenId, ScreenId)>>,
     global_callbacks: HashMap<Event, Callback>,
+    // Category and description of global callbacks, for the help view.
+    global_callback_help: HashMap<Event, (String, String)>,
+    shortcuts: shortcuts::Registry,
+    components: component::Registry,
+    // Callbacks subscribed to each topic.
+    subscriptions: HashMap<String, Vec<Rc<Fn(&mut Cursive, &Any)>>>,
     menubar: views::Menubar,
 
-    // Last layer sizes of the stack view.
-    // If it changed, clear the screen.
+    // Last layer sizes and offsets of the stack view.
+    // If they changed, clear the screen.
     last_sizes: Vec<Vec2>,
+    last_offsets: Vec<Vec2>,
 
     active_screen: ScreenId,
 
@@ -162,6 +216,32 @@ pub struct Cursive {
 
     cb_source: mpsc::Receiver<Box<Fn(&mut Cursive) + Send>>,
     cb_sink: mpsc::Sender<Box<Fn(&mut Cursive) + Send>>,
+
+    event_source: mpsc::Receiver<AppEvent>,
+    event_sink: mpsc::Sender<AppEvent>,
+
+    recorder: recorder::Recorder,
+    // Events from a macro, to handle before polling the backend.
+    pending_events: VecDeque<Event>,
+
+    pre_event_hook: Option<Rc<Fn(&mut Cursive, &Event)>>,
+    post_event_hook: Option<Rc<Fn(&mut Cursive, &Event, EventRoute)>>,
+
+    // Parsers for sequences the backend doesn't know.
+    sequence_parsers: Vec<Box<Fn(&[u8]) -> Option<Event>>>,
+    kitty_keyboard: bool,
+    // Details about the key being handled, with the kitty protocol.
+    key_event: Option<event::KeyEvent>,
+
+    metrics: Option<metrics::Metrics>,
+    metrics_overlay: bool,
+
+    announcer: Option<Box<accessibility::Announcer>>,
+    // Last description given to the announcer.
+    announced: Option<String>,
+
+    // Text to send to the terminal clipboard on the next draw.
+    clipboard: Option<String>,
 }
 
 new_default!(Cursive);
@@ -176,25 +256,66 @@ impl Cursive {
         // let theme = theme::load_theme("assets/style.toml").unwrap();
 
         let (tx, rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
 
         let mut res = Cursive {
             theme: theme,
+            theme_file: None,
+            terminal_background: None,
             screens: Vec::new(),
+            screen_names: HashMap::new(),
+            screen_callbacks: Vec::new(),
+            on_screen_switch: None,
             last_sizes: Vec::new(),
+            last_offsets: Vec::new(),
             global_callbacks: HashMap::new(),
+            global_callback_help: HashMap::new(),
+            shortcuts: shortcuts::Registry::new(),
+            components: component::Registry::new(),
+            subscriptions: HashMap::new(),
             menubar: views::Menubar::new(),
             active_screen: 0,
             running: true,
             cb_source: rx,
             cb_sink: tx,
+            event_source: event_rx,
+            event_sink: event_tx,
             backend: backend,
+            recorder: recorder::Recorder::new(),
+            pending_events: VecDeque::new(),
+            pre_event_hook: None,
+            post_event_hook: None,
+            sequence_parsers: Vec::new(),
+            kitty_keyboard: false,
+            key_event: None,
+            metrics: None,
+            metrics_overlay: false,
+            announcer: None,
+            announced: None,
+            clipboard: None,
         };
 
-        res.screens.push(views::StackView::new());
+        res.add_screen();
 
         res
     }
 
+    /// Creates a new Cursive root, after detecting the terminal background.
+    ///
+    /// The terminal may take up to 0.2 seconds to answer,
+    /// see [`theme::detect_background`].
+    /// The result is then available from [`terminal_background`].
+    ///
+    /// [`theme::detect_background`]: theme/fn.detect_background.html
+    /// [`terminal_background`]: #method.terminal_background
+    pub fn with_background_detection() -> Self {
+        // Ask the terminal before the backend takes it over.
+        let terminal_background = theme::detect_background();
+        let mut res = Cursive::new();
+        res.terminal_background = terminal_background;
+        res
+    }
+
     /// Returns a sink for asynchronous callbacks.
     ///
     /// Returns the sender part of a channel, that allows to send
@@ -211,6 +332,40 @@ impl Cursive {
         &self.cb_sink
     }
 
+    /// Returns a sink for application events.
+    ///
+    /// Returns the sender part of a channel, that allows to send
+    /// [`AppEvent`]s to `self` from other threads.
+    ///
+    /// Each event will go down the view tree like user input, as an
+    /// `Event::AppEvent`, and reach global callbacks if no view uses it.
+    ///
+    /// As with [`cb_sink`], you currently need to call [`set_fps`] to force
+    /// cursive to regularly check for messages.
+    ///
+    /// [`AppEvent`]: event/struct.AppEvent.html
+    /// [`cb_sink`]: #method.cb_sink
+    /// [`set_fps`]: #method.set_fps
+    pub fn event_sink(&self) -> &mpsc::Sender<AppEvent> {
+        &self.event_sink
+    }
+
+    /// Posts an application event.
+    ///
+    /// It will be handled like user input, on the next event cycle.
+    pub fn post_event(&mut self, event: AppEvent) {
+        self.pending_events.push_back(Event::AppEvent(event));
+    }
+
+    /// Copies `text` to the terminal clipboard, on the next draw.
+    ///
+    /// This uses an escape sequence (`OSC 52`) that many terminals
+    /// support, even over ssh; others ignore it. The ncurses backends
+    /// can't send it.
+    pub fn copy_to_clipboard<S: Into<String>>(&mut self, text: S) {
+        self.clipboard = Some(text.into());
+    }
+
     /// Selects the menubar.
     pub fn select_menubar(&mut self) {
         self.menubar.take_focus(direction::Direction::none());
@@ -283,6 +438,20 @@ impl Cursive {
         &self.theme
     }
 
+    /// Returns the brightness of the terminal background, if it was
+    /// detected at startup.
+    ///
+    /// Detection only happens with [`with_background_detection`].
+    ///
+    /// Use it to pick a theme with [`Theme::default_for_background`].
+    ///
+    /// [`with_background_detection`]: #method.with_background_detection
+    /// [`Theme::default_for_background`]:
+    /// theme/struct.Theme.html#method.default_for_background
+    pub fn terminal_background(&self) -> Option<theme::Brightness> {
+        self.terminal_background
+    }
+
     /// Sets the current theme.
     pub fn set_theme(&mut self, theme: theme::Theme) {
         self.theme = theme;
@@ -306,6 +475,49 @@ impl Cursive {
         Ok(())
     }
 
+    /// Loads a theme from the given file, and reloads it when it changes.
+    ///
+    /// In debug builds, the file is checked on each step of the event
+    /// loop; use [`set_fps`] to check regularly. An invalid file is
+    /// ignored, keeping the current theme.
+    ///
+    /// In release builds, this is the same as [`load_theme_file`].
+    ///
+    /// [`set_fps`]: #method.set_fps
+    /// [`load_theme_file`]: #method.load_theme_file
+    pub fn watch_theme_file<P: Into<PathBuf>>(&mut self, filename: P)
+                                              -> Result<(), theme::Error> {
+        let filename = filename.into();
+        let modified = modified(&filename);
+        self.load_theme_file(&filename)?;
+        if cfg!(debug_assertions) {
+            self.theme_file = Some((filename, modified));
+        }
+        Ok(())
+    }
+
+    /// Stops reloading the theme file given to [`watch_theme_file`].
+    ///
+    /// [`watch_theme_file`]: #method.watch_theme_file
+    pub fn unwatch_theme_file(&mut self) {
+        self.theme_file = None;
+    }
+
+    // Reloads the theme file if it was modified.
+    fn check_theme_file(&mut self) {
+        let filename = match self.theme_file {
+            Some((ref filename, last)) if modified(filename) != last => {
+                filename.clone()
+            }
+            _ => return,
+        };
+        let modified = modified(&filename);
+        if let Ok(theme) = theme::load_theme_file(&filename) {
+            self.set_theme(theme);
+        }
+        self.theme_file = Some((filename, modified));
+    }
+
     /// Loads a theme from the given string content.
     ///
     /// Content must be valid toml.
@@ -319,15 +531,153 @@ impl Cursive {
     /// Regularly redraws everything, even when no input is given.
     ///
     /// You currently need this to regularly check
-    /// for events sent using [`cb_sink`].
+    /// for events sent using [`cb_sink`] or [`event_sink`].
     ///
     /// Between 0 and 1000. Call with `fps = 0` to disable (default value).
     ///
     /// [`cb_sink`]: #method.cb_sink
+    /// [`event_sink`]: #method.event_sink
     pub fn set_fps(&mut self, fps: u32) {
         self.backend.set_refresh_rate(fps)
     }
 
+    /// Sets how long to wait after an escape character, in milliseconds.
+    ///
+    /// Terminals send the escape character alone for the `Esc` key, but
+    /// also to start sequences, like for arrow keys or `Alt` combinations.
+    /// If nothing follows within this delay, it was the `Esc` key.
+    ///
+    /// A short delay makes `Esc` responsive, but slow links (like SSH
+    /// connections) may then split sequences. Defaults to 25.
+    pub fn set_esc_delay(&mut self, delay: u32) {
+        self.backend.set_esc_delay(delay)
+    }
+
+    /// Enables or disables synchronized output.
+    ///
+    /// When enabled, each frame is sent between markers telling the
+    /// terminal to show it all at once, so complex frames don't tear over
+    /// slow connections. Terminals not supporting it ignore the markers.
+    ///
+    /// Enabled by default. Disable it for terminals misbehaving with the
+    /// markers.
+    pub fn set_synchronized_output(&mut self, enabled: bool) {
+        self.backend.set_synchronized_output(enabled)
+    }
+
+    /// Enables or disables collecting frame timings.
+    ///
+    /// Once enabled, the timings of the last frames are available with
+    /// [`metrics`]. Disabling forgets them.
+    ///
+    /// [`metrics`]: #method.metrics
+    pub fn set_metrics(&mut self, enabled: bool) {
+        if !enabled {
+            self.metrics = None;
+            self.metrics_overlay = false;
+        } else if self.metrics.is_none() {
+            self.metrics = Some(metrics::Metrics::new(METRICS_FRAMES));
+        }
+    }
+
+    /// Shows or hides the average frame timings in the top-right corner.
+    ///
+    /// Showing them enables collecting if needed: see [`set_metrics`].
+    ///
+    /// [`set_metrics`]: #method.set_metrics
+    pub fn set_metrics_overlay(&mut self, visible: bool) {
+        if visible {
+            self.set_metrics(true);
+        }
+        self.metrics_overlay = visible;
+    }
+
+    /// Returns the timings of the last frames, if collecting is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// let mut siv = Cursive::new();
+    /// siv.set_metrics(true);
+    ///
+    /// siv.add_global_callback('m', |s| {
+    ///     if let Some(slowest) = s.metrics().and_then(|m| m.slowest()) {
+    ///         eprintln!("Slowest recent frame: {:?}", slowest);
+    ///     }
+    /// });
+    /// ```
+    pub fn metrics(&self) -> Option<&metrics::Metrics> {
+        self.metrics.as_ref()
+    }
+
+    /// Announces the focused element with `announcer`, for screen readers.
+    ///
+    /// The element is announced once now, then each time its description
+    /// changes, for instance when the focus moves or when its content is
+    /// edited. See the [`accessibility`] module.
+    ///
+    /// [`accessibility`]: accessibility/index.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// # use cursive::accessibility::SpeechDispatcher;
+    /// let mut siv = Cursive::new();
+    /// siv.set_announcer(SpeechDispatcher::new());
+    /// ```
+    pub fn set_announcer<A: accessibility::Announcer + 'static>(&mut self,
+                                                                announcer: A) {
+        self.announcer = Some(Box::new(announcer));
+        self.announced = None;
+    }
+
+    /// Stops announcing the focused element.
+    pub fn clear_announcer(&mut self) {
+        self.announcer = None;
+    }
+
+    /// Describes the focused element as plain text.
+    ///
+    /// This is the active menu if the menubar is selected, and the focused
+    /// view of the current screen otherwise.
+    pub fn describe_focus(&self) -> Option<String> {
+        if self.menubar.receive_events() {
+            self.menubar.describe()
+        } else {
+            self.screen().describe()
+        }
+    }
+
+    // Gives the focused element to the announcer, if it changed.
+    fn announce_focus(&mut self) {
+        if self.announcer.is_none() {
+            return;
+        }
+
+        let description = self.describe_focus();
+        if description == self.announced {
+            return;
+        }
+        if let (Some(announcer), Some(text)) =
+            (self.announcer.as_mut(), description.as_ref()) {
+            announcer.announce(text);
+        }
+        self.announced = description;
+    }
+
+    /// Enables or disables smooth scrolling.
+    ///
+    /// When enabled, paging through text views and pagers is animated
+    /// over a few frames instead of jumping. The animation advances on each
+    /// refresh, so this should be used with [`set_fps`].
+    ///
+    /// [`set_fps`]: #method.set_fps
+    pub fn set_smooth_scrolling(&mut self, enabled: bool) {
+        view::ScrollBase::set_smooth_scrolling(enabled);
+    }
+
     /// Returns a reference to the currently active screen.
     pub fn screen(&self) -> &views::StackView {
         let id = self.active_screen;
@@ -344,9 +694,90 @@ impl Cursive {
     pub fn add_screen(&mut self) -> ScreenId {
         let res = self.screens.len();
         self.screens.push(views::StackView::new());
+        self.screen_callbacks.push(HashMap::new());
+        res
+    }
+
+    /// Adds a new screen with the given name, and returns its ID.
+    ///
+    /// If another screen had this name, it loses it.
+    pub fn add_named_screen<S: Into<String>>(&mut self, name: S)
+                                             -> ScreenId {
+        let res = self.add_screen();
+        self.screen_names.insert(name.into(), res);
         res
     }
 
+    /// Returns the ID of the screen with the given name.
+    pub fn screen_id(&self, name: &str) -> Option<ScreenId> {
+        self.screen_names.get(name).cloned()
+    }
+
+    /// Returns the ID of the active screen.
+    pub fn active_screen(&self) -> ScreenId {
+        self.active_screen
+    }
+
+    /// Sets the active screen from its name.
+    ///
+    /// Returns `Err(())` if no screen has this name.
+    pub fn set_active_screen_by_name(&mut self, name: &str)
+                                     -> Result<(), ()> {
+        let id = self.screen_id(name).ok_or(())?;
+        self.set_screen(id);
+        Ok(())
+    }
+
+    /// Sets a callback to be used when the active screen changes.
+    ///
+    /// It will be given the previous and the new screen IDs.
+    pub fn set_on_screen_switch<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, ScreenId, ScreenId) + 'static
+    {
+        self.on_screen_switch = Some(Rc::new(cb));
+    }
+
+    /// Adds a callback only active on the given screen.
+    ///
+    /// Like global callbacks, it is triggered when no view catches the
+    /// event; it takes precedence over a global callback for the same event.
+    /// Shortcuts from `register_shortcut` still take precedence over it.
+    ///
+    /// Panics if no such screen exist.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # use cursive::event::Key;
+    /// # fn main() {
+    /// let mut siv = Cursive::new();
+    ///
+    /// // F1-F4 switch between workspaces.
+    /// for (i, name) in ["mail", "news", "chat", "logs"].iter().enumerate() {
+    ///     siv.add_named_screen(*name);
+    ///     siv.add_global_callback(event::Key::from_f(i as u8 + 1),
+    ///                             move |s| {
+    ///         s.set_active_screen_by_name(name).unwrap();
+    ///     });
+    /// }
+    ///
+    /// let mail = siv.screen_id("mail").unwrap();
+    /// siv.add_screen_callback(mail, 'r', |s| {
+    ///     // Refresh the mailbox
+    /// });
+    /// # }
+    /// ```
+    pub fn add_screen_callback<F, E>(&mut self, screen_id: ScreenId,
+                                     event: E, cb: F)
+        where F: Fn(&mut Cursive) + 'static,
+              E: Into<Event>
+    {
+        self.screen_callbacks[screen_id]
+            .insert(event.into(), Callback::from_fn(cb));
+    }
+
     /// Convenient method to create a new screen, and set it as active.
     pub fn add_active_screen(&mut self) -> ScreenId {
         let res = self.add_screen();
@@ -362,7 +793,16 @@ impl Cursive {
                    screen_id,
                    self.screens.len());
         }
+        let previous = self.active_screen;
+        if previous == screen_id {
+            return;
+        }
+
         self.active_screen = screen_id;
+        self.clear();
+        if let Some(cb) = self.on_screen_switch.clone() {
+            cb(self, previous, screen_id);
+        }
     }
 
     /// Tries to find the view pointed to by the given selector.
@@ -442,6 +882,20 @@ impl Cursive {
         self.call_on_id(id, views::IdView::<V>::get_mut)
     }
 
+    /// Finds a view wrapped in [`IdView`], and returns a handle to it.
+    ///
+    /// Unlike [`find_id`], the handle doesn't borrow the view, and doesn't
+    /// keep it alive: it can be stored, and used after the view tree
+    /// changed.
+    ///
+    /// [`IdView`]: views/struct.IdView.html
+    /// [`find_id`]: #method.find_id
+    pub fn find_handle<V>(&mut self, id: &str) -> Option<views::ViewHandle<V>>
+        where V: View + Any
+    {
+        self.call_on_id(id, |view: &mut views::IdView<V>| view.handle())
+    }
+
     /// Moves the focus to the view identified by `id`.
     ///
     /// Convenient method to call `focus` with a `view::Selector::Id`.
@@ -458,6 +912,9 @@ impl Cursive {
     ///
     /// Will be triggered on the given key press when no view catches it.
     ///
+    /// The shortcut registry is not checked: a shortcut registered for the
+    /// same key with `register_shortcut` takes priority.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -475,6 +932,449 @@ impl Cursive {
         self.global_callbacks.insert(event.into(), Callback::from_fn(cb));
     }
 
+    /// Registers a keyboard shortcut.
+    ///
+    /// Unlike `add_global_callback`, this fails if the key is already used
+    /// in the same scope, including by callbacks added without the
+    /// registry, or if the scope is a screen that doesn't exist.
+    /// See the [`shortcuts`](shortcuts/index.html) module.
+    pub fn register_shortcut<E, C, D, F>(&mut self, scope: shortcuts::Scope,
+                                         event: E, category: C,
+                                         description: D, cb: F)
+                                         -> Result<(), shortcuts::Error>
+        where E: Into<Event>,
+              C: Into<String>,
+              D: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        let event = event.into();
+        let taken = match scope {
+            shortcuts::Scope::Global => {
+                self.global_callbacks.contains_key(&event)
+            }
+            shortcuts::Scope::Screen(id) => {
+                match self.screen_callbacks.get(id) {
+                    Some(callbacks) => callbacks.contains_key(&event),
+                    None => return Err(shortcuts::Error::NoSuchScreen(id)),
+                }
+            }
+            shortcuts::Scope::Context(_) => false,
+        };
+        if taken {
+            let conflict = shortcuts::Conflict {
+                shortcut: shortcuts::Shortcut {
+                    scope: scope,
+                    event: event,
+                    category: category.into(),
+                    description: description.into(),
+                },
+                existing: None,
+            };
+            return Err(Box::new(conflict).into());
+        }
+
+        self.shortcuts
+            .register(scope, event, category, description, cb)
+            .map_err(Into::into)
+    }
+
+    /// Returns the shortcut registry.
+    pub fn shortcuts(&self) -> &shortcuts::Registry {
+        &self.shortcuts
+    }
+
+    /// Returns a mutable reference to the shortcut registry.
+    ///
+    /// Use it to activate contexts, or remove shortcuts.
+    pub fn shortcuts_mut(&mut self) -> &mut shortcuts::Registry {
+        &mut self.shortcuts
+    }
+
+    /// Describes a global callback, for the help view.
+    ///
+    /// Callbacks are grouped by `category` in the view returned by
+    /// `help_view`.
+    pub fn describe_global_callback<E, C, D>(&mut self, event: E,
+                                             category: C, description: D)
+        where E: Into<Event>,
+              C: Into<String>,
+              D: Into<String>
+    {
+        self.global_callback_help
+            .insert(event.into(), (category.into(), description.into()));
+    }
+
+    /// Returns a help view listing the global callbacks and menu items.
+    ///
+    /// Global callbacks are shown with the description given to
+    /// `describe_global_callback`; undescribed ones are listed under
+    /// "Global". Registered shortcuts come next, then each menu of the
+    /// menubar gets its own category.
+    ///
+    /// The view is built from the current state, so call this method each
+    /// time the help is shown.
+    ///
+    /// See [`views::HelpView`](views/struct.HelpView.html) for an example.
+    pub fn help_view(&self) -> views::HelpView {
+        let mut entries: Vec<_> = self.global_callbacks
+            .keys()
+            .map(|event| {
+                let (category, description) = self.global_callback_help
+                    .get(event)
+                    .cloned()
+                    .unwrap_or_else(|| ("Global".to_string(), String::new()));
+                (category, event.to_string(), description)
+            })
+            .collect();
+        entries.sort();
+
+        let mut view = views::HelpView::new();
+        for (category, key, description) in entries {
+            view.add_entry(category, key, description);
+        }
+        for shortcut in self.shortcuts.shortcuts() {
+            view.add_entry(shortcut.category.clone(),
+                           shortcut.event.to_string(),
+                           shortcut.description.clone());
+        }
+        for i in 0..self.menubar.len() {
+            if let Some((label, menu)) = self.menubar.get(i) {
+                view.add_menu(label, menu);
+            }
+        }
+
+        view
+    }
+
+    /// Subscribes to messages published on the given topic.
+    ///
+    /// The callback only sees payloads of type `T`; other messages on this
+    /// topic are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// # use cursive::traits::*;
+    /// # use cursive::views::{EditView, TextView};
+    /// let mut siv = Cursive::new();
+    ///
+    /// siv.add_layer(TextView::new("").with_id("status"));
+    /// siv.add_layer(EditView::new().on_edit(|s, text, _| {
+    ///     s.publish("search", text.to_string());
+    /// }));
+    ///
+    /// siv.subscribe("search", |s, query: &String| {
+    ///     let status = format!("Looking for {}", query);
+    ///     s.call_on_id("status", |view: &mut TextView| {
+    ///         view.set_content(status)
+    ///     });
+    /// });
+    /// ```
+    pub fn subscribe<T, F>(&mut self, topic: &str, cb: F)
+        where T: Any,
+              F: Fn(&mut Cursive, &T) + 'static
+    {
+        let cb = move |s: &mut Cursive, payload: &Any| {
+            if let Some(payload) = payload.downcast_ref::<T>() {
+                cb(s, payload);
+            }
+        };
+        self.subscriptions
+            .entry(topic.to_string())
+            .or_insert_with(Vec::new)
+            .push(Rc::new(cb));
+    }
+
+    /// Subscribes the view with the given id to a topic.
+    ///
+    /// Convenient method to use `subscribe` with `call_on_id`: the callback
+    /// is given the view, if it can be found.
+    pub fn subscribe_id<V, T, F>(&mut self, topic: &str, id: &str, cb: F)
+        where V: View + Any,
+              T: Any,
+              F: Fn(&mut V, &T) + 'static
+    {
+        let id = id.to_string();
+        self.subscribe(topic, move |s, payload: &T| {
+            s.call_on_id(&id, |view: &mut V| cb(view, payload));
+        });
+    }
+
+    /// Removes all subscriptions to the given topic.
+    pub fn clear_subscriptions(&mut self, topic: &str) {
+        self.subscriptions.remove(topic);
+    }
+
+    /// Publishes a message on the given topic.
+    ///
+    /// Subscribers are called right away, in the order they subscribed.
+    pub fn publish<T: Any>(&mut self, topic: &str, payload: T) {
+        let subscribers = match self.subscriptions.get(topic) {
+            Some(subscribers) => subscribers.clone(),
+            None => return,
+        };
+        for cb in subscribers {
+            cb(self, &payload);
+        }
+    }
+
+    /// Registers a component under the given name.
+    ///
+    /// Returns the component previously registered under this name, if
+    /// any. See the [`component`](component/index.html) module.
+    pub fn register_component<S, C>(&mut self, name: S, component: C)
+                                    -> Option<Rc<component::Component>>
+        where S: Into<String>,
+              C: component::Component + 'static
+    {
+        self.components.register(name, component)
+    }
+
+    /// Builds a new instance of the component registered under `name`.
+    ///
+    /// Returns `None` if no such component was registered.
+    pub fn build_component(&mut self, name: &str) -> Option<Box<View>> {
+        // The component may register other components while building.
+        let component = self.components.get(name)?;
+        Some(component.build(self))
+    }
+
+    /// Returns the component registry.
+    pub fn components(&self) -> &component::Registry {
+        &self.components
+    }
+
+    /// Returns a mutable reference to the component registry.
+    pub fn components_mut(&mut self) -> &mut component::Registry {
+        &mut self.components
+    }
+
+    /// Sets the keys used for keyboard macros.
+    ///
+    /// Pressing `record` then a character starts recording events to the
+    /// register named by this character; pressing `record` again stops.
+    /// Pressing `play` then a character replays the events from this
+    /// register.
+    ///
+    /// No key is bound by default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # fn main() {
+    /// let mut siv = Cursive::new();
+    ///
+    /// // Vim-style: `qa` ... `q` to record, `@a` to play.
+    /// siv.set_macro_keys('q', '@');
+    /// # }
+    /// ```
+    pub fn set_macro_keys<R, P>(&mut self, record: R, play: P)
+        where R: Into<Event>,
+              P: Into<Event>
+    {
+        self.recorder.set_keys(Some(record.into()), Some(play.into()));
+    }
+
+    /// Unbinds the keys used for keyboard macros.
+    ///
+    /// Macros can still be recorded and played with the other methods.
+    pub fn clear_macro_keys(&mut self) {
+        self.recorder.set_keys(None, None);
+    }
+
+    /// Starts recording events to the given register.
+    ///
+    /// If a macro was already being recorded, it is saved first.
+    pub fn start_macro(&mut self, register: char) {
+        self.recorder.start(register);
+    }
+
+    /// Stops recording events, and saves the macro.
+    pub fn stop_macro(&mut self) {
+        self.recorder.stop();
+    }
+
+    /// Returns the register being recorded, if any.
+    pub fn recording_macro(&self) -> Option<char> {
+        self.recorder.recording()
+    }
+
+    /// Returns the events saved in the given register.
+    pub fn macro_events(&self, register: char) -> Option<&[Event]> {
+        self.recorder.get(register)
+    }
+
+    /// Saves a list of events to the given register.
+    pub fn set_macro(&mut self, register: char, events: Vec<Event>) {
+        self.recorder.set(register, events);
+    }
+
+    /// Replays the events saved in the given register.
+    ///
+    /// They will be handled like user input, before any new one and any
+    /// event already pending.
+    ///
+    /// Does nothing if the register is empty.
+    pub fn play_macro(&mut self, register: char) {
+        if let Some(events) = self.recorder.get(register) {
+            // Like macros played with the play key: before anything
+            // already pending.
+            for event in events.iter().rev() {
+                self.pending_events.push_front(event.clone());
+            }
+        }
+    }
+
+    /// Sets a function to call before handling each event.
+    ///
+    /// It sees every event, including the ones used by keyboard macros.
+    pub fn set_pre_event_hook<F>(&mut self, hook: F)
+        where F: Fn(&mut Cursive, &Event) + 'static
+    {
+        self.pre_event_hook = Some(Rc::new(hook));
+    }
+
+    /// Sets a function to call after handling each event.
+    ///
+    /// It is given the event and where it went, once any resulting callback
+    /// has run.
+    ///
+    /// This can help understand why a callback didn't fire.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # use cursive::event::EventRoute;
+    /// # fn main() {
+    /// let mut siv = Cursive::new();
+    ///
+    /// siv.set_post_event_hook(|_, event, route| {
+    ///     if route == EventRoute::Ignored {
+    ///         eprintln!("Nobody wanted {:?}", event);
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn set_post_event_hook<F>(&mut self, hook: F)
+        where F: Fn(&mut Cursive, &Event, EventRoute) + 'static
+    {
+        self.post_event_hook = Some(Rc::new(hook));
+    }
+
+    /// Removes the hooks set with [`set_pre_event_hook`] and
+    /// [`set_post_event_hook`].
+    ///
+    /// [`set_pre_event_hook`]: #method.set_pre_event_hook
+    /// [`set_post_event_hook`]: #method.set_post_event_hook
+    pub fn clear_event_hooks(&mut self) {
+        self.pre_event_hook = None;
+        self.post_event_hook = None;
+    }
+
+    /// Adds a parser for input sequences the backend doesn't recognize.
+    ///
+    /// Such sequences are received as `Event::Unknown`, with their raw
+    /// bytes. Before dispatching one, each parser is given these bytes, in
+    /// the order they were added; the first event returned replaces the
+    /// unknown one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// # use cursive::event::{Event, Key};
+    /// let mut siv = Cursive::new();
+    ///
+    /// // Some terminals send this for Ctrl-Shift-Up.
+    /// siv.add_sequence_parser(|bytes| if bytes == b"\x1b[1;6A" {
+    ///                             Some(Event::CtrlShift(Key::Up))
+    ///                         } else {
+    ///                             None
+    ///                         });
+    /// ```
+    pub fn add_sequence_parser<F>(&mut self, parser: F)
+        where F: Fn(&[u8]) -> Option<Event> + 'static
+    {
+        self.sequence_parsers.push(Box::new(parser));
+    }
+
+    // Gives unknown sequences to the registered parsers.
+    fn parse_sequence(&mut self, event: Event) -> Event {
+        if let Event::Unknown(ref bytes) = event {
+            for parser in &self.sequence_parsers {
+                if let Some(event) = parser(bytes) {
+                    return event;
+                }
+            }
+            if self.kitty_keyboard {
+                if let Some(key_event) = kitty::parse(bytes) {
+                    self.key_event = Some(key_event);
+                    return key_event.to_event();
+                }
+            }
+        }
+        event
+    }
+
+    /// Enables or disables the kitty keyboard protocol.
+    ///
+    /// With terminals supporting it, keys are reported with all held
+    /// modifiers, and as they are pressed, repeated and released.
+    ///
+    /// Presses and repeats are still given as the usual events, when they
+    /// can describe them; use [`key_event`] for the details. Other key
+    /// events, like releases, are given as `Event::Extended`.
+    ///
+    /// Returns `false` if the backend can't enable it. Terminals without
+    /// support just keep sending usual keys.
+    ///
+    /// [`key_event`]: #method.key_event
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// # use cursive::event::{Event, Key, KeyAction, KeyCode};
+    /// let mut siv = Cursive::new();
+    /// siv.set_kitty_keyboard(true);
+    ///
+    /// siv.set_pre_event_hook(|_, event| {
+    ///     if let Event::Extended(key_event) = *event {
+    ///         if key_event.code == KeyCode::Key(Key::Down) &&
+    ///            key_event.action == KeyAction::Release {
+    ///             // Stop scrolling.
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn set_kitty_keyboard(&mut self, enabled: bool) -> bool {
+        if enabled == self.kitty_keyboard {
+            return true;
+        }
+        let sequence = if enabled { kitty::ENABLE } else { kitty::DISABLE };
+        if !self.backend.send_sequence(sequence) {
+            return false;
+        }
+        self.kitty_keyboard = enabled;
+        true
+    }
+
+    /// Returns details about the key being handled.
+    ///
+    /// Only available with the kitty keyboard protocol: see
+    /// [`set_kitty_keyboard`]. This tells, for instance, if an
+    /// `Event::Key(Key::Down)` is a repeat.
+    ///
+    /// [`set_kitty_keyboard`]: #method.set_kitty_keyboard
+    pub fn key_event(&self) -> Option<event::KeyEvent> {
+        self.key_event
+    }
+
     /// Add a layer to the current screen.
     ///
     /// # Examples
@@ -501,20 +1401,75 @@ impl Cursive {
         self.screen_mut().add_fullscreen_layer(view);
     }
 
+    /// Shows a popup next to `anchor`, on the current screen.
+    ///
+    /// The popup goes on the `preferred` side of `anchor`, but flips to
+    /// the opposite side when it wouldn't fit near the edge of the screen.
+    ///
+    /// Views can find their own area with `Printer::offset` and
+    /// `Printer::size`, to open dropdowns under themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// # use cursive::direction::Absolute;
+    /// # use cursive::view::Rect;
+    /// # use cursive::views::TextView;
+    /// let mut siv = Cursive::new();
+    ///
+    /// let anchor = Rect::new((10, 4), (12, 1));
+    /// siv.show_anchored_popup(anchor,
+    ///                         TextView::new("Completions"),
+    ///                         Absolute::Down);
+    /// ```
+    pub fn show_anchored_popup<T>(&mut self, anchor: view::Rect, view: T,
+                                  preferred: direction::Absolute)
+        where T: 'static + View
+    {
+        self.screen_mut().add_anchored_layer(anchor, view, preferred);
+    }
+
+    /// Adds a modal layer to the current screen, dimming the layers below.
+    ///
+    /// The dimming comes from the `dim` style of the theme.
+    pub fn add_modal_layer<T: 'static + View>(&mut self, view: T) {
+        self.screen_mut().add_modal_layer(view);
+    }
+
+    /// Adds a layer always on top of the others, on the current screen.
+    ///
+    /// It never receives input, which makes it fit for status overlays.
+    /// See [`StackView::add_overlay`].
+    ///
+    /// [`StackView::add_overlay`]:
+    /// views/struct.StackView.html#method.add_overlay
+    pub fn add_overlay<T>(&mut self, position: view::Position, view: T)
+        where T: 'static + View
+    {
+        self.screen_mut().add_overlay(position, view);
+    }
+
     /// Convenient method to remove a layer from the current screen.
     pub fn pop_layer(&mut self) {
         self.screen_mut().pop_layer();
         self.clear();
     }
 
-    // Handles a key event when it was ignored by the current view
-    fn on_event(&mut self, event: Event) {
-        let cb = match self.global_callbacks.get(&event) {
-            None => return,
-            Some(cb) => cb.clone(),
+    // Handles a key event when it was ignored by the current view.
+    // Returns `true` if a global callback was found.
+    fn on_event(&mut self, event: Event) -> bool {
+        let screen = self.active_screen;
+        let cb = match self.shortcuts
+            .callback(screen, &event)
+            .or_else(|| self.screen_callbacks[screen].get(&event).cloned())
+            .or_else(|| self.global_callbacks.get(&event).cloned()) {
+            None => return false,
+            Some(cb) => cb,
         };
         // Not from a view, so no viewpath here
         cb(self);
+        true
     }
 
     /// Returns the size of the screen, in characters.
@@ -537,9 +1492,11 @@ impl Cursive {
         // Reference it or something
 
         let sizes = self.screen().layer_sizes();
-        if self.last_sizes != sizes {
+        let offsets = self.screen().layer_offsets();
+        if self.last_sizes != sizes || self.last_offsets != offsets {
             self.clear();
             self.last_sizes = sizes;
+            self.last_offsets = offsets;
         }
 
         let printer = Printer::new(self.screen_size(),
@@ -564,6 +1521,18 @@ impl Cursive {
         let id = self.active_screen;
         self.screens[id].draw(&printer);
 
+        if let Some(text) = self.clipboard.take() {
+            printer.print_raw((0, 0), &utils::clipboard_sequence(&text));
+        }
+
+        if let Some(ref metrics) = self.metrics {
+            if self.metrics_overlay {
+                let summary = metrics.summary();
+                let x = printer.size.x.saturating_sub(summary.len());
+                printer.with_color(theme::ColorStyle::Highlight,
+                                   |printer| printer.print((x, 0), &summary));
+            }
+        }
     }
 
     /// Returns `true` until [`quit(&mut self)`] is called.
@@ -602,23 +1571,91 @@ impl Cursive {
             cb(self);
         }
 
+        self.check_theme_file();
+
         // Do we need to redraw everytime?
         // Probably, actually.
         // TODO: Do we need to re-layout everytime?
+        let start = Instant::now();
         self.layout();
+        let layout = start.elapsed();
 
         // TODO: Do we need to redraw every view every time?
         // (Is this getting repetitive? :p)
+        let start = Instant::now();
         self.draw();
+        let draw = start.elapsed();
+
+        let start = Instant::now();
         self.backend.refresh();
+        let flush = start.elapsed();
+
+        self.announce_focus();
 
-        // Wait for next event.
+        // Wait for next event, unless a macro is being played or the
+        // application sent one.
         // (If set_fps was called, this returns -1 now and then)
-        let event = self.backend.poll_event();
+        self.key_event = None;
+        let event = match self.pending_events.pop_front() {
+            Some(event) => event,
+            None => {
+                match self.event_source.try_recv() {
+                    Ok(event) => Event::AppEvent(event),
+                    Err(_) => {
+                        let event = self.backend.poll_event();
+                        self.parse_sequence(event)
+                    }
+                }
+            }
+        };
         if event == Event::Exit {
             self.quit();
         }
 
+        // Only user input goes to macros.
+        let start = Instant::now();
+        let action = match event {
+            Event::AppEvent(_) => recorder::Action::Pass,
+            _ => self.recorder.on_event(&event),
+        };
+        if let Some(hook) = self.pre_event_hook.clone() {
+            hook(self, &event);
+        }
+
+        let route = match action {
+            recorder::Action::Pass => {
+                // Describing the focused view is only useful to the hook.
+                let trace = self.post_event_hook.is_some();
+                self.dispatch(event.clone(), trace)
+            }
+            recorder::Action::Consumed => EventRoute::Macro,
+            recorder::Action::Play(events) => {
+                // Play them before anything already pending.
+                for event in events.into_iter().rev() {
+                    self.pending_events.push_front(event);
+                }
+                EventRoute::Macro
+            }
+        };
+
+        if let Some(hook) = self.post_event_hook.clone() {
+            hook(self, &event, route);
+        }
+
+        if let Some(ref mut metrics) = self.metrics {
+            metrics.record(metrics::FrameTimings {
+                               event: start.elapsed(),
+                               layout: layout,
+                               draw: draw,
+                               flush: flush,
+                           });
+        }
+    }
+
+    // Sends an event to the views or the global callbacks.
+    //
+    // With `trace`, the route describes the focused view.
+    fn dispatch(&mut self, event: Event, trace: bool) -> EventRoute {
         if event == Event::WindowResize {
             self.backend.clear();
         }
@@ -629,14 +1666,48 @@ impl Cursive {
         //     * Current screen (top layer)
         // * Global callbacks
         if self.menubar.receive_events() {
-            self.menubar.on_event(event).process(self);
+            let result = self.menubar.on_event(event);
+            let consumed = result.is_consumed();
+            result.process(self);
+            if consumed {
+                EventRoute::Menubar
+            } else {
+                EventRoute::Ignored
+            }
         } else {
+            let layer = self.screen().focused_layer();
+            let focused = if trace {
+                self.screen().describe()
+            } else {
+                None
+            };
             match self.screen_mut().on_event(event.clone()) {
                 // If the event was ignored,
                 // it is our turn to play with it.
-                EventResult::Ignored => self.on_event(event),
-                EventResult::Consumed(None) => (),
-                EventResult::Consumed(Some(cb)) => cb(self),
+                EventResult::Ignored => {
+                    if self.on_event(event) {
+                        EventRoute::GlobalCallback
+                    } else {
+                        EventRoute::Ignored
+                    }
+                }
+                // No ancestor wanted it: the view still used the event.
+                EventResult::Consumed(None) |
+                EventResult::Ancestor(_) => {
+                    EventRoute::View {
+                        callback: false,
+                        layer: layer,
+                        focused: focused,
+                    }
+                }
+                EventResult::Consumed(Some(cb)) => {
+                    cb(self);
+                    EventRoute::View {
+                        callback: true,
+                        layer: layer,
+                        focused: focused,
+                    }
+                }
             }
         }
     }
@@ -649,6 +1720,7 @@ impl Cursive {
 
 impl Drop for Cursive {
     fn drop(&mut self) {
+        self.set_kitty_keyboard(false);
         self.backend.finish();
     }
 }
@@ -0,0 +1,852 @@
+//! Builds view trees from declarative descriptions.
+//!
+//! A view is described by a table with a `type`, and optionally an `id`,
+//! some `children` and any number of properties:
+//!
+//! ```toml
+//! type = "Dialog"
+//! title = "Hello"
+//! buttons = [{ label = "Quit", on_press = "quit" }]
+//!
+//! [[children]]
+//! type = "TextView"
+//! content = "Hello world!"
+//! width = 20
+//! ```
+//!
+//! Callbacks are referred to by name, and resolved from a [`Registry`].
+//! The registry can also build custom view types.
+//!
+//! Descriptions can be loaded from TOML, JSON, or YAML with the `yaml-rust`
+//! feature. Any format supported by `serde` works too, since [`Node`]
+//! implements `Deserialize`.
+//!
+//! During development, a [`ReloadView`] rebuilds a view when its file
+//! changes.
+//!
+//! Requires the `markup` feature.
+//!
+//! # Built-in types
+//!
+//! | Type | Properties | Children |
+//! |------|------------|----------|
+//! | `Button` | `label`, `on_press` | |
+//! | `Checkbox` | `checked`, `on_change` | |
+//! | `Dialog` | `title`, `buttons` (tables with `label` and `on_press`) | 1 |
+//! | `DummyView` | | |
+//! | `EditView` | `content`, `secret`, `on_submit` | |
+//! | `LinearLayout` | `orientation` (`"vertical"` or `"horizontal"`) | any |
+//! | `ListView` | | any, each with a `label` |
+//! | `Panel` | | 1 |
+//! | `SelectView` | `items` (strings), `popup`, `on_submit` | |
+//! | `SliderView` | `orientation`, `max` | |
+//! | `TextArea` | `content` | |
+//! | `TextView` | `content` | |
+//!
+//! Any view can also have a `width` or `height`, either fixed (`20`) or
+//! relative to the available size (`"50%"`).
+//!
+//! [`Registry`]: struct.Registry.html
+//! [`Node`]: struct.Node.html
+//! [`ReloadView`]: struct.ReloadView.html
+
+use {Cursive, Printer, modified};
+use direction::{Direction, Orientation};
+use event::{Callback, Event, EventResult};
+use serde::{Deserialize, Deserializer};
+use serde::de::Error as DeError;
+use serde_json;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+use toml;
+use vec::Vec2;
+use view::{Finder, Identifiable, Selector, SizeConstraint, View};
+#[cfg(feature = "yaml-rust")]
+use yaml_rust::{Yaml, YamlLoader};
+use views::{BoxView, Button, Checkbox, Dialog, DummyView, EditView,
+            LinearLayout, ListView, Panel, SelectView, SliderView, TextArea,
+            TextView};
+
+/// Error while building a view tree.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occured when reading a file.
+    Io(io::Error),
+    /// The description could not be parsed.
+    Parse(String),
+    /// No view type has this name.
+    UnknownType(String),
+    /// No callback has this name.
+    UnknownCallback(String),
+    /// A property has the wrong type (`Type.property`).
+    InvalidProp(String),
+    /// A view is missing its child (`Type`).
+    MissingChild(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Value of a property.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Prop {
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+    /// A floating-point number.
+    Float(f64),
+    /// A string.
+    Str(String),
+    /// A list of values.
+    List(Vec<Prop>),
+    /// A table of named values.
+    Map(BTreeMap<String, Prop>),
+}
+
+impl Prop {
+    fn from_toml(value: toml::Value) -> Self {
+        match value {
+            toml::Value::String(s) => Prop::Str(s),
+            toml::Value::Integer(i) => Prop::Int(i),
+            toml::Value::Float(f) => Prop::Float(f),
+            toml::Value::Boolean(b) => Prop::Bool(b),
+            toml::Value::Datetime(d) => Prop::Str(d.to_string()),
+            toml::Value::Array(values) => {
+                Prop::List(values.into_iter().map(Prop::from_toml).collect())
+            }
+            toml::Value::Table(table) => {
+                Prop::Map(table.into_iter()
+                    .map(|(key, value)| (key, Prop::from_toml(value)))
+                    .collect())
+            }
+        }
+    }
+
+    #[cfg(feature = "yaml-rust")]
+    fn from_yaml(value: Yaml) -> Result<Self, Error> {
+        Ok(match value {
+            Yaml::String(s) => Prop::Str(s),
+            Yaml::Integer(i) => Prop::Int(i),
+            Yaml::Boolean(b) => Prop::Bool(b),
+            Yaml::Real(f) => {
+                Prop::Float(f.parse()
+                    .map_err(|_| Error::Parse(format!("invalid number: {}",
+                                                      f)))?)
+            }
+            Yaml::Array(values) => {
+                Prop::List(values.into_iter()
+                    .map(Prop::from_yaml)
+                    .collect::<Result<_, _>>()?)
+            }
+            Yaml::Hash(hash) => {
+                let mut map = BTreeMap::new();
+                for (key, value) in hash {
+                    let key = match key {
+                        Yaml::String(key) => key,
+                        key => {
+                            let key = format!("invalid key: {:?}", key);
+                            return Err(Error::Parse(key));
+                        }
+                    };
+                    map.insert(key, Prop::from_yaml(value)?);
+                }
+                Prop::Map(map)
+            }
+            value => {
+                return Err(Error::Parse(format!("unsupported value: {:?}",
+                                                value)))
+            }
+        })
+    }
+}
+
+/// Description of a single view.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    /// Type of the view, like `"TextView"`.
+    pub kind: String,
+    /// Optional id, to find the view later.
+    pub id: Option<String>,
+    /// Other properties.
+    pub props: BTreeMap<String, Prop>,
+    /// Child views.
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Reads a node from a table of properties.
+    pub fn from_prop(prop: Prop) -> Result<Self, Error> {
+        let mut props = match prop {
+            Prop::Map(props) => props,
+            _ => {
+                return Err(Error::Parse("a view must be a table".to_string()))
+            }
+        };
+        let kind = match props.remove("type") {
+            Some(Prop::Str(kind)) => kind,
+            _ => {
+                return Err(Error::Parse("a view needs a type".to_string()))
+            }
+        };
+        let id = match props.remove("id") {
+            None => None,
+            Some(Prop::Str(id)) => Some(id),
+            Some(_) => return Err(Error::InvalidProp(format!("{}.id", kind))),
+        };
+        let children = match props.remove("children") {
+            None => Vec::new(),
+            Some(Prop::List(children)) => {
+                children.into_iter()
+                    .map(Node::from_prop)
+                    .collect::<Result<_, _>>()?
+            }
+            Some(_) => {
+                return Err(Error::InvalidProp(format!("{}.children", kind)))
+            }
+        };
+
+        Ok(Node {
+            kind: kind,
+            id: id,
+            props: props,
+            children: children,
+        })
+    }
+
+    /// Parses a node from a TOML string.
+    pub fn from_toml(content: &str) -> Result<Self, Error> {
+        let value: toml::Value = content.parse()
+            .map_err(|err: toml::de::Error| Error::Parse(err.to_string()))?;
+        Node::from_prop(Prop::from_toml(value))
+    }
+
+    /// Parses a node from a JSON string.
+    pub fn from_json(content: &str) -> Result<Self, Error> {
+        serde_json::from_str(content)
+            .map_err(|err| Error::Parse(err.to_string()))
+    }
+
+    /// Parses a node from a YAML string.
+    ///
+    /// Only the first document is used.
+    ///
+    /// Only available with the `yaml-rust` feature.
+    #[cfg(feature = "yaml-rust")]
+    pub fn from_yaml(content: &str) -> Result<Self, Error> {
+        let document = YamlLoader::load_from_str(content)
+            .map_err(|err| Error::Parse(err.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Parse("empty document".to_string()))?;
+        Node::from_prop(Prop::from_yaml(document)?)
+    }
+
+    /// Reads a node from a file.
+    ///
+    /// The format is deduced from the extension: `.json`, `.toml`, or
+    /// `.yaml` with the `yaml-rust` feature.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut content = String::new();
+        File::open(path)?.read_to_string(&mut content)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Node::from_json(&content),
+            Some("toml") => Node::from_toml(&content),
+            #[cfg(feature = "yaml-rust")]
+            Some("yaml") |
+            Some("yml") => Node::from_yaml(&content),
+            _ => Err(Error::Parse(format!("unknown format: {:?}", path))),
+        }
+    }
+
+    fn invalid(&self, name: &str) -> Error {
+        Error::InvalidProp(format!("{}.{}", self.kind, name))
+    }
+
+    /// Returns a string property.
+    pub fn str_prop(&self, name: &str) -> Result<Option<&str>, Error> {
+        match self.props.get(name) {
+            None => Ok(None),
+            Some(&Prop::Str(ref s)) => Ok(Some(s)),
+            Some(_) => Err(self.invalid(name)),
+        }
+    }
+
+    /// Returns a boolean property.
+    pub fn bool_prop(&self, name: &str) -> Result<Option<bool>, Error> {
+        match self.props.get(name) {
+            None => Ok(None),
+            Some(&Prop::Bool(b)) => Ok(Some(b)),
+            Some(_) => Err(self.invalid(name)),
+        }
+    }
+
+    /// Returns a non-negative integer property.
+    pub fn usize_prop(&self, name: &str) -> Result<Option<usize>, Error> {
+        match self.props.get(name) {
+            None => Ok(None),
+            Some(&Prop::Int(i)) if i >= 0 => Ok(Some(i as usize)),
+            Some(_) => Err(self.invalid(name)),
+        }
+    }
+
+    /// Returns a list property, or an empty list.
+    pub fn list_prop(&self, name: &str) -> Result<&[Prop], Error> {
+        match self.props.get(name) {
+            None => Ok(&[]),
+            Some(&Prop::List(ref list)) => Ok(list),
+            Some(_) => Err(self.invalid(name)),
+        }
+    }
+
+    // Reads a size: a number of cells, or a percentage like `"50%"`.
+    fn size_prop(&self, name: &str) -> Result<SizeConstraint, Error> {
+        if let Some(&Prop::Str(ref size)) = self.props.get(name) {
+            return size.trim_end_matches('%')
+                .parse()
+                .ok()
+                .filter(|_| size.ends_with('%'))
+                .map(SizeConstraint::Percent)
+                .ok_or_else(|| self.invalid(name));
+        }
+        Ok(self.usize_prop(name)?
+            .map_or(SizeConstraint::Free, SizeConstraint::Fixed))
+    }
+
+    fn orientation(&self) -> Result<Orientation, Error> {
+        match self.str_prop("orientation")? {
+            None | Some("vertical") => Ok(Orientation::Vertical),
+            Some("horizontal") => Ok(Orientation::Horizontal),
+            Some(_) => Err(self.invalid("orientation")),
+        }
+    }
+
+    // Collects the ids of this node and all its descendants.
+    fn ids<'a>(&'a self, ids: &mut Vec<&'a str>) {
+        ids.extend(self.id.as_ref().map(String::as_str));
+        for child in &self.children {
+            child.ids(ids);
+        }
+    }
+
+    fn child(&self) -> Result<&Node, Error> {
+        self.children
+            .first()
+            .ok_or_else(|| Error::MissingChild(self.kind.clone()))
+    }
+
+    /// Applies the common properties (`id`, `width` and `height`) to a
+    /// built view.
+    ///
+    /// Custom builders should use this, so the view can be found by id.
+    pub fn wrap<V: View + 'static>(&self, view: V)
+                                   -> Result<Box<View>, Error> {
+        let width = self.size_prop("width")?;
+        let height = self.size_prop("height")?;
+        let sized = |view| -> Box<View> {
+            match (width, height) {
+                (SizeConstraint::Free, SizeConstraint::Free) => view,
+                (width, height) => Box::new(BoxView::new(width, height, view)),
+            }
+        };
+
+        Ok(match self.id {
+            Some(ref id) => sized(Box::new(view.with_id(id.as_str()))),
+            None => sized(Box::new(view)),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D)
+                                         -> Result<Self, D::Error> {
+        let prop = Prop::deserialize(deserializer)?;
+        Node::from_prop(prop).map_err(|err| D::Error::custom(format!("{:?}",
+                                                                     err)))
+    }
+}
+
+/// Builds views from [`Node`]s, resolving callback names.
+///
+/// It knows the `quit` and `pop_layer` callbacks by default.
+///
+/// [`Node`]: struct.Node.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::markup::{Node, Registry};
+/// # use cursive::views::TextView;
+/// let mut registry = Registry::new();
+/// registry.add_callback("hello", |s| {
+///     s.add_layer(TextView::new("Hello!"));
+/// });
+///
+/// let node = Node::from_json(r#"{
+///     "type": "Button",
+///     "label": "Greet",
+///     "on_press": "hello"
+/// }"#).unwrap();
+/// let view = registry.build(&node).unwrap();
+/// ```
+pub struct Registry {
+    callbacks: HashMap<String, Callback>,
+    builders: HashMap<String, Rc<Fn(&Node, &Registry)
+                                  -> Result<Box<View>, Error>>>,
+}
+
+new_default!(Registry);
+
+impl Registry {
+    /// Creates a new registry, with the default callbacks.
+    pub fn new() -> Self {
+        let mut registry = Registry {
+            callbacks: HashMap::new(),
+            builders: HashMap::new(),
+        };
+        registry.add_callback("quit", |s| s.quit());
+        registry.add_callback("pop_layer", |s| s.pop_layer());
+        registry
+    }
+
+    /// Adds a named callback.
+    pub fn add_callback<F>(&mut self, name: &str, cb: F)
+        where F: Fn(&mut Cursive) + 'static
+    {
+        self.callbacks.insert(name.to_string(), Callback::from_fn(cb));
+    }
+
+    /// Adds a builder for a custom view type.
+    ///
+    /// It takes precedence over built-in types with the same name.
+    pub fn add_view<F>(&mut self, kind: &str, builder: F)
+        where F: Fn(&Node, &Registry) -> Result<Box<View>, Error> + 'static
+    {
+        self.builders.insert(kind.to_string(), Rc::new(builder));
+    }
+
+    /// Returns the callback with the given name.
+    pub fn callback(&self, name: &str) -> Result<Callback, Error> {
+        self.callbacks
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::UnknownCallback(name.to_string()))
+    }
+
+    // Returns the callback named by the given property, if any.
+    fn callback_prop(&self, node: &Node, name: &str)
+                     -> Result<Option<Callback>, Error> {
+        match node.str_prop(name)? {
+            Some(name) => self.callback(name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Builds a view tree.
+    pub fn build(&self, node: &Node) -> Result<Box<View>, Error> {
+        if let Some(builder) = self.builders.get(&node.kind) {
+            return builder(node, self);
+        }
+
+        match node.kind.as_str() {
+            "Button" => {
+                let label = node.str_prop("label")?.unwrap_or("");
+                let cb = self.callback_prop(node, "on_press")?;
+                node.wrap(Button::new(label, move |s| {
+                    if let Some(ref cb) = cb {
+                        cb(s);
+                    }
+                }))
+            }
+            "Checkbox" => {
+                let mut view = Checkbox::new();
+                view.set_checked(node.bool_prop("checked")?.unwrap_or(false));
+                if let Some(cb) = self.callback_prop(node, "on_change")? {
+                    view.set_on_change(move |s, _| cb(s));
+                }
+                node.wrap(view)
+            }
+            "Dialog" => {
+                let mut view = Dialog::around(self.build(node.child()?)?);
+                if let Some(title) = node.str_prop("title")? {
+                    view.set_title(title);
+                }
+                for button in node.list_prop("buttons")? {
+                    let button = match *button {
+                        Prop::Map(ref button) => button,
+                        _ => return Err(node.invalid("buttons")),
+                    };
+                    let label = match button.get("label") {
+                        Some(&Prop::Str(ref label)) => label.clone(),
+                        _ => return Err(node.invalid("buttons")),
+                    };
+                    let cb = match button.get("on_press") {
+                        Some(&Prop::Str(ref name)) => self.callback(name)?,
+                        _ => return Err(node.invalid("buttons")),
+                    };
+                    view = view.button(label, move |s| cb(s));
+                }
+                node.wrap(view)
+            }
+            "DummyView" => node.wrap(DummyView),
+            "EditView" => {
+                let mut view = EditView::new();
+                if let Some(content) = node.str_prop("content")? {
+                    view.set_content(content);
+                }
+                view.set_secret(node.bool_prop("secret")?.unwrap_or(false));
+                if let Some(cb) = self.callback_prop(node, "on_submit")? {
+                    view.set_on_submit(move |s, _| cb(s));
+                }
+                node.wrap(view)
+            }
+            "LinearLayout" => {
+                let mut view = LinearLayout::new(node.orientation()?);
+                for child in &node.children {
+                    view.add_child(self.build(child)?);
+                }
+                node.wrap(view)
+            }
+            "ListView" => {
+                let mut view = ListView::new();
+                for child in &node.children {
+                    let label = child.str_prop("label")?.unwrap_or("");
+                    view.add_child(label, self.build(child)?);
+                }
+                node.wrap(view)
+            }
+            "Panel" => node.wrap(Panel::new(self.build(node.child()?)?)),
+            "SelectView" => {
+                let mut view = SelectView::new();
+                for item in node.list_prop("items")? {
+                    match *item {
+                        Prop::Str(ref item) => view.add_item_str(item.clone()),
+                        _ => return Err(node.invalid("items")),
+                    }
+                }
+                view.set_popup(node.bool_prop("popup")?.unwrap_or(false));
+                if let Some(cb) = self.callback_prop(node, "on_submit")? {
+                    view.set_on_submit(move |s, _: &String| cb(s));
+                }
+                node.wrap(view)
+            }
+            "SliderView" => {
+                let max = node.usize_prop("max")?.unwrap_or(10);
+                node.wrap(SliderView::new(node.orientation()?, max))
+            }
+            "TextArea" => {
+                let content = node.str_prop("content")?.unwrap_or("");
+                node.wrap(TextArea::new().content(content))
+            }
+            "TextView" => {
+                let content = node.str_prop("content")?.unwrap_or("");
+                node.wrap(TextView::new(content))
+            }
+            _ => Err(Error::UnknownType(node.kind.clone())),
+        }
+    }
+
+    /// Builds a view tree from a file.
+    ///
+    /// The format is deduced from the extension: `.json`, `.toml`, or
+    /// `.yaml` with the `yaml-rust` feature.
+    pub fn load_file<P: AsRef<Path>>(&self, path: P)
+                                     -> Result<Box<View>, Error> {
+        self.build(&Node::from_file(path)?)
+    }
+}
+
+// State of a named view, kept when a layout is reloaded.
+enum Saved {
+    Content(String),
+    Checked(bool),
+    Selection(usize),
+}
+
+impl Saved {
+    fn take<V: Finder>(view: &mut V, id: &str) -> Option<Self> {
+        view.find_id(id, |v: &mut EditView| {
+                Saved::Content(v.get_content().to_string())
+            })
+            .or_else(|| {
+                view.find_id(id, |v: &mut TextArea| {
+                    Saved::Content(v.get_content().to_string())
+                })
+            })
+            .or_else(|| {
+                view.find_id(id, |v: &mut Checkbox| {
+                    Saved::Checked(v.is_checked())
+                })
+            })
+            .or_else(|| {
+                view.find_id(id, |v: &mut SelectView| v.selected_id())
+                    .and_then(|selection| selection.map(Saved::Selection))
+            })
+    }
+
+    fn restore<V: Finder>(&self, view: &mut V, id: &str) {
+        match *self {
+            Saved::Content(ref content) => {
+                view.find_id(id, |v: &mut EditView| {
+                        v.set_content(content.clone())
+                    })
+                    .or_else(|| {
+                        view.find_id(id, |v: &mut TextArea| {
+                            v.set_content(content.clone())
+                        })
+                    });
+            }
+            Saved::Checked(checked) => {
+                view.find_id(id, |v: &mut Checkbox| {
+                    v.set_checked(checked);
+                });
+            }
+            Saved::Selection(i) => {
+                view.find_id(id, |v: &mut SelectView| if i < v.len() {
+                    v.set_selection(i)
+                });
+            }
+        }
+    }
+}
+
+/// Wraps a view loaded from a file, and rebuilds it when the file changes.
+///
+/// In debug builds, the file is checked on each layout. When it was
+/// modified, the view is built again, and the content of named
+/// `EditView`s and `TextArea`s, the state of named `Checkbox`es and the
+/// selection of named `SelectView`s are carried over to the new view.
+///
+/// If the new file cannot be loaded, the current view is kept, and the
+/// error is available from [`error`].
+///
+/// In release builds, the file is only read once.
+///
+/// Changes are noticed on the next event; use [`Cursive::set_fps`] to
+/// check regularly.
+///
+/// [`error`]: #method.error
+/// [`Cursive::set_fps`]: ../struct.Cursive.html#method.set_fps
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::Cursive;
+/// # use cursive::markup::{Registry, ReloadView};
+/// let mut siv = Cursive::new();
+/// siv.set_fps(2);
+/// siv.add_layer(ReloadView::new(Registry::new(), "layout.toml").unwrap());
+/// ```
+pub struct ReloadView {
+    registry: Registry,
+    path: PathBuf,
+    node: Node,
+    modified: Option<SystemTime>,
+    error: Option<Error>,
+    view: Box<View>,
+    reloaded: bool,
+}
+
+impl ReloadView {
+    /// Loads a view from the given file.
+    ///
+    /// The format is deduced from the extension: `.json`, `.toml`, or
+    /// `.yaml` with the `yaml-rust` feature.
+    pub fn new<P: Into<PathBuf>>(registry: Registry, path: P)
+                                 -> Result<Self, Error> {
+        let path = path.into();
+        let modified = modified(&path);
+        let node = Node::from_file(&path)?;
+        let view = registry.build(&node)?;
+
+        Ok(ReloadView {
+            registry: registry,
+            path: path,
+            node: node,
+            modified: modified,
+            error: None,
+            view: view,
+            reloaded: false,
+        })
+    }
+
+    /// Returns the error that prevented the last reload, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    /// Loads the file again, keeping the state of named views.
+    pub fn reload(&mut self) -> Result<(), Error> {
+        self.modified = modified(&self.path);
+        let node = Node::from_file(&self.path)?;
+        let mut view = self.registry.build(&node)?;
+
+        let mut ids = Vec::new();
+        self.node.ids(&mut ids);
+        for id in ids {
+            if let Some(saved) = Saved::take(&mut self.view, id) {
+                saved.restore(&mut view, id);
+            }
+        }
+
+        self.node = node;
+        self.view = view;
+        self.reloaded = true;
+        Ok(())
+    }
+
+    fn check(&mut self) {
+        if cfg!(debug_assertions) && modified(&self.path) != self.modified {
+            self.error = self.reload().err();
+        }
+    }
+}
+
+impl View for ReloadView {
+    fn draw(&self, printer: &Printer) {
+        self.view.draw(printer);
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        self.check();
+        self.view.required_size(req)
+    }
+
+    fn needs_relayout(&self) -> bool {
+        self.reloaded || self.view.needs_relayout()
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.reloaded = false;
+        self.view.layout(size);
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        self.view.on_event(event).resolve_on(self)
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        self.view.take_focus(source)
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       callback: Box<FnMut(&mut Any) + 'a>) {
+        self.view.call_on_any(selector, callback);
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        self.view.focus_view(selector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_formats() {
+        let toml = Node::from_toml(r#"
+            type = "Dialog"
+            title = "Hello"
+            buttons = [{ label = "Quit", on_press = "quit" }]
+
+            [[children]]
+            type = "EditView"
+            id = "name"
+            width = "50%"
+        "#)
+            .unwrap();
+        let json = Node::from_json(r#"{
+            "type": "Dialog",
+            "title": "Hello",
+            "buttons": [{ "label": "Quit", "on_press": "quit" }],
+            "children": [{ "type": "EditView", "id": "name", "width": "50%" }]
+        }"#)
+            .unwrap();
+        assert_eq!(toml, json);
+        assert_eq!(json.children[0].id, Some("name".to_string()));
+
+        #[cfg(feature = "yaml-rust")]
+        {
+            let yaml = Node::from_yaml(r#"
+                type: Dialog
+                title: Hello
+                buttons:
+                  - { label: Quit, on_press: quit }
+                children:
+                  - { type: EditView, id: name, width: 50% }
+            "#)
+                .unwrap();
+            assert_eq!(yaml, json);
+        }
+
+        let mut view = Registry::new().build(&json).unwrap();
+        assert!(view.find_id("name", |_: &mut EditView| ()).is_some());
+    }
+
+    #[test]
+    fn test_errors() {
+        let registry = Registry::new();
+        let node = Node::from_json(r#"{ "type": "Foo" }"#).unwrap();
+        match registry.build(&node) {
+            Err(Error::UnknownType(kind)) => assert_eq!(kind, "Foo"),
+            _ => panic!("expected an unknown type"),
+        }
+
+        let node = r#"{ "type": "Button", "on_press": "nope" }"#;
+        let node = Node::from_json(node).unwrap();
+        match registry.build(&node) {
+            Err(Error::UnknownCallback(name)) => assert_eq!(name, "nope"),
+            _ => panic!("expected an unknown callback"),
+        }
+    }
+
+    #[test]
+    fn test_reload() {
+        use std::io::Write;
+        use std::thread;
+        use std::time::Duration;
+
+        let path = ::std::env::temp_dir().join("cursive_test_reload.json");
+        let write = |content: &str| {
+            let mut file = File::create(&path).unwrap();
+            file.write_all(content.as_bytes()).unwrap();
+        };
+
+        write(r#"{ "type": "EditView", "id": "name" }"#);
+        let mut view = ReloadView::new(Registry::new(), &path).unwrap();
+        view.find_id("name", |v: &mut EditView| v.set_content("Bob"));
+
+        thread::sleep(Duration::from_millis(10));
+        write(r#"{ "type": "LinearLayout", "children": [
+            { "type": "TextView", "id": "label", "content": "Name:" },
+            { "type": "EditView", "id": "name" }
+        ] }"#);
+        view.required_size(Vec2::new(20, 5));
+        assert!(view.error().is_none());
+        assert!(view.find_id("label", |_: &mut TextView| ()).is_some());
+        let content = view.find_id("name", |v: &mut EditView| v.get_content());
+        assert_eq!(content.unwrap().as_str(), "Bob");
+
+        // An invalid file keeps the current view.
+        thread::sleep(Duration::from_millis(10));
+        write("{");
+        view.required_size(Vec2::new(20, 5));
+        assert!(view.error().is_some());
+        assert!(view.find_id("label", |_: &mut TextView| ()).is_some());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,162 @@
+//! Timings of the last frames, to find slow parts of an application.
+//!
+//! Collecting is opt-in: see [`Cursive::set_metrics`]. Each frame runs the
+//! same phases, each timed separately:
+//!
+//! * `event`: handling the event, including callbacks.
+//! * `layout`: computing the size and position of each view.
+//! * `draw`: drawing views.
+//! * `flush`: sending the result to the terminal.
+//!
+//! Time spent waiting for input is not counted.
+//!
+//! [`Cursive::set_metrics`]: ../struct.Cursive.html#method.set_metrics
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Time spent in each phase of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct FrameTimings {
+    /// Handling the event.
+    pub event: Duration,
+    /// Computing the layout.
+    pub layout: Duration,
+    /// Drawing the views.
+    pub draw: Duration,
+    /// Sending the output to the terminal.
+    pub flush: Duration,
+}
+
+impl FrameTimings {
+    /// Returns the time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.event + self.layout + self.draw + self.flush
+    }
+}
+
+/// Timings of the last frames.
+pub struct Metrics {
+    frames: VecDeque<FrameTimings>,
+    capacity: usize,
+}
+
+impl Metrics {
+    /// Creates a new collector, keeping up to `capacity` frames.
+    pub fn new(capacity: usize) -> Self {
+        Metrics {
+            frames: VecDeque::with_capacity(capacity),
+            capacity: capacity,
+        }
+    }
+
+    /// Records a frame, forgetting the oldest one if needed.
+    pub fn record(&mut self, frame: FrameTimings) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Forgets all recorded frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frame was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the recorded frames, oldest first.
+    pub fn frames(&self) -> Vec<FrameTimings> {
+        self.frames.iter().cloned().collect()
+    }
+
+    /// Returns the last recorded frame.
+    pub fn last(&self) -> Option<FrameTimings> {
+        self.frames.back().cloned()
+    }
+
+    /// Returns the recorded frame which took the longest.
+    pub fn slowest(&self) -> Option<FrameTimings> {
+        self.frames.iter().cloned().max_by_key(FrameTimings::total)
+    }
+
+    /// Returns the average time spent in each phase.
+    pub fn average(&self) -> FrameTimings {
+        if self.frames.is_empty() {
+            return FrameTimings::default();
+        }
+
+        let n = self.frames.len() as u32;
+        let mut sum = FrameTimings::default();
+        for frame in &self.frames {
+            sum.event += frame.event;
+            sum.layout += frame.layout;
+            sum.draw += frame.draw;
+            sum.flush += frame.flush;
+        }
+
+        FrameTimings {
+            event: sum.event / n,
+            layout: sum.layout / n,
+            draw: sum.draw / n,
+            flush: sum.flush / n,
+        }
+    }
+
+    /// Describes the average timings on one line, in milliseconds.
+    pub fn summary(&self) -> String {
+        let average = self.average();
+        format!("event {:.2}ms layout {:.2}ms draw {:.2}ms flush {:.2}ms",
+                millis(average.event),
+                millis(average.layout),
+                millis(average.draw),
+                millis(average.flush))
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1000.0 +
+    f64::from(duration.subsec_nanos()) / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameTimings, Metrics};
+    use std::time::Duration;
+
+    fn frame(draw: u64) -> FrameTimings {
+        FrameTimings {
+            draw: Duration::from_millis(draw),
+            flush: Duration::from_millis(1),
+            ..FrameTimings::default()
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut metrics = Metrics::new(3);
+        assert_eq!(metrics.average(), FrameTimings::default());
+
+        for &draw in &[10, 2, 4, 6] {
+            metrics.record(frame(draw));
+        }
+        // The first frame was forgotten.
+        assert_eq!(metrics.len(), 3);
+        assert_eq!(metrics.slowest(), Some(frame(6)));
+        assert_eq!(metrics.last().unwrap().total(), Duration::from_millis(7));
+        assert_eq!(metrics.average(), frame(4));
+        assert_eq!(metrics.summary(),
+                   "event 0.00ms layout 0.00ms draw 4.00ms flush 1.00ms");
+    }
+}
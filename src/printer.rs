@@ -2,31 +2,225 @@
 
 
 use backend::{self, Backend};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::cmp::min;
+use std::fmt::{self, Write};
+use std::mem;
+use std::ops::Range;
 use std::rc::Rc;
 
-use theme::{BorderStyle, ColorStyle, Effect, Theme};
+use direction::Orientation;
+use theme::{self, BorderStyle, Color, ColorStyle, Effect, Palette, Style,
+            Theme};
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-use utils::prefix;
+use utils::{StyledString, prefix};
 use vec::Vec2;
+use view::Rect;
 
 /// Convenient interface to draw on a subset of the screen.
 pub struct Printer<'a> {
     /// Offset into the window this printer should start drawing at.
+    ///
+    /// This is the absolute position of the view on the screen, which can
+    /// be used to open popups next to it.
     pub offset: Vec2,
     /// Size of the area we are allowed to draw on.
     pub size: Vec2,
     /// Whether the view to draw is currently focused or not.
     pub focused: bool,
     /// Currently used theme
-    pub theme: Theme,
+    ///
+    /// It is shared with sub-printers, rather than copied for each.
+    pub theme: Rc<Theme>,
 
+    /// Size of the whole screen.
+    screen_size: Vec2,
     /// `true` if nothing has been drawn yet.
     new: Rc<Cell<bool>>,
-    /// Backend used to actually draw things
-    backend: &'a backend::Concrete,
+    /// `true` if the palette differs from the active theme.
+    ///
+    /// Roles like `Primary` then need to be drawn with custom colors.
+    overridden: bool,
+    /// Applied to every color before drawing, if any.
+    filter: Option<Rc<Fn(Color) -> Color>>,
+    /// Styles pushed with `push_style`, shared with sub-printers.
+    styles: Rc<RefCell<Vec<Style>>>,
+    /// Number of pushed styles already applied by the backend.
+    applied: Rc<Cell<usize>>,
+    /// Reusable buffer for text built while drawing.
+    scratch: Rc<RefCell<String>>,
+    /// Where things are actually drawn.
+    target: Target<'a>,
+}
+
+// Where a printer draws.
+#[derive(Clone)]
+enum Target<'a> {
+    // On the screen, with the given backend.
+    Screen(&'a backend::Concrete),
+    // Into a `CellBuffer`.
+    Buffer(Rc<Capture>),
+    // Nowhere: only the area drawn on is recorded.
+    Bounds(Rc<Cell<Option<Rect>>>),
+}
+
+// Off-screen drawing state, shared with sub-printers.
+struct Capture {
+    buffer: RefCell<CellBuffer>,
+    // Depth of the screen the buffer is meant for.
+    depth: theme::ColorDepth,
+    // Style set by `with_color` and `with_effect`.
+    color: Cell<Option<ColorStyle>>,
+    effects: RefCell<Vec<Effect>>,
+}
+
+// A single cell of a `CellBuffer`.
+#[derive(Clone, Debug, PartialEq)]
+struct BufferCell {
+    // Empty for the second half of a wide character.
+    text: String,
+    color: Option<ColorStyle>,
+    effects: Vec<Effect>,
+}
+
+impl BufferCell {
+    fn blank(color: Option<ColorStyle>) -> Self {
+        BufferCell {
+            text: " ".to_string(),
+            color: color,
+            effects: Vec::new(),
+        }
+    }
+
+    // Replaces the content, keeping the allocated memory.
+    fn set(&mut self, text: &str, color: Option<ColorStyle>,
+           effects: &[Effect]) {
+        self.text.clear();
+        self.text.push_str(text);
+        self.color = color;
+        self.effects.clear();
+        self.effects.extend_from_slice(effects);
+    }
+}
+
+/// Grid of cells a view can be drawn into, off-screen.
+///
+/// Draw into it with [`Printer::draw_offscreen`], and copy it to the
+/// screen with [`Printer::blit`]. Drawing again only redraws the rows
+/// invalidated since the last time, so a view that rarely changes can
+/// keep the buffer between frames and be copied cheaply.
+///
+/// [`Printer::draw_offscreen`]: struct.Printer.html#method.draw_offscreen
+/// [`Printer::blit`]: struct.Printer.html#method.blit
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::{CellBuffer, Printer};
+/// # use cursive::theme;
+/// # use cursive::backend::{self, Backend};
+/// # let b = backend::Concrete::init();
+/// # let printer = Printer::new((20,4), theme::load_default(), &b);
+/// let mut buffer = CellBuffer::new((10, 2));
+/// printer.draw_offscreen(&mut buffer, |printer| {
+///     printer.print((0, 0), "Hello");
+///     printer.print((0, 1), "World");
+/// });
+/// // Only the second row is drawn again.
+/// buffer.invalidate_rows(1..2);
+/// printer.draw_offscreen(&mut buffer, |printer| {
+///     printer.print((0, 1), "There");
+/// });
+/// printer.blit(&buffer, (5, 1));
+/// ```
+pub struct CellBuffer {
+    size: Vec2,
+    cells: Vec<BufferCell>,
+    // `true` for each row that doesn't need to be drawn again.
+    valid: Vec<bool>,
+}
+
+impl CellBuffer {
+    /// Creates a new buffer of the given size, with every row invalid.
+    pub fn new<S: Into<Vec2>>(size: S) -> Self {
+        let size = size.into();
+        CellBuffer {
+            size: size,
+            cells: vec![BufferCell::blank(None); size.x * size.y],
+            valid: vec![false; size.y],
+        }
+    }
+
+    /// Returns the size of this buffer.
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Changes the size of this buffer.
+    ///
+    /// If the size is different, the content is lost and every row is
+    /// invalid.
+    pub fn resize<S: Into<Vec2>>(&mut self, size: S) {
+        let size = size.into();
+        if size != self.size {
+            *self = CellBuffer::new(size);
+        }
+    }
+
+    /// Marks every row to be drawn again.
+    pub fn invalidate(&mut self) {
+        for valid in &mut self.valid {
+            *valid = false;
+        }
+    }
+
+    /// Marks the given rows to be drawn again.
+    pub fn invalidate_rows(&mut self, rows: Range<usize>) {
+        let end = min(rows.end, self.size.y);
+        for valid in &mut self.valid[min(rows.start, end)..end] {
+            *valid = false;
+        }
+    }
+
+    /// Returns `true` if the given row doesn't need to be drawn again.
+    pub fn is_valid(&self, row: usize) -> bool {
+        self.valid.get(row).cloned().unwrap_or(false)
+    }
+
+    // Blanks the invalid rows.
+    fn clear_invalid(&mut self, color: Option<ColorStyle>) {
+        let width = self.size.x;
+        for (row, cells) in self.cells.chunks_mut(width.max(1)).enumerate() {
+            if !self.valid[row] {
+                for cell in cells {
+                    cell.set(" ", color, &[]);
+                }
+            }
+        }
+    }
+
+    // Writes `text` from `pos`, unless that row is valid.
+    fn write(&mut self, pos: Vec2, text: &str, color: Option<ColorStyle>,
+             effects: &[Effect]) {
+        if pos.y >= self.size.y || self.valid[pos.y] {
+            return;
+        }
+        let mut x = pos.x;
+        for grapheme in text.graphemes(true) {
+            let width = grapheme.width();
+            if x + width > self.size.x {
+                break;
+            }
+            for i in 0..width {
+                let text = if i == 0 { grapheme } else { "" };
+                self.cells[pos.y * self.size.x + x + i]
+                    .set(text, color, effects);
+            }
+            x += width;
+        }
+    }
 }
 
 impl<'a> Printer<'a> {
@@ -37,23 +231,80 @@ impl<'a> Printer<'a> {
     pub fn new<T: Into<Vec2>>(size: T, theme: Theme,
                               backend: &'a backend::Concrete)
                               -> Self {
+        let size = size.into();
         Printer {
             offset: Vec2::zero(),
-            size: size.into(),
+            size: size,
             focused: true,
-            theme: theme,
+            theme: Rc::new(theme),
+            screen_size: size,
             new: Rc::new(Cell::new(true)),
-            backend: backend,
+            overridden: false,
+            filter: None,
+            styles: Rc::new(RefCell::new(Vec::new())),
+            applied: Rc::new(Cell::new(0)),
+            scratch: Rc::new(RefCell::new(String::new())),
+            target: Target::Screen(backend),
         }
     }
 
+    /// Returns the area `f` draws on, without drawing anything.
+    ///
+    /// `f` is given a printer of the given size, which only records where
+    /// text is printed. This lets views compute their size by running
+    /// their drawing code, instead of repeating its layout math. Returns
+    /// `None` if nothing is printed.
+    ///
+    /// Like when drawing, text out of `size` is cut, and `print_raw` has
+    /// no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::Printer;
+    /// # use cursive::theme::Theme;
+    /// # use cursive::vec::Vec2;
+    /// let theme = Theme::default();
+    /// let bounds = Printer::draw_bounds((20, 10), &theme, |printer| {
+    ///     printer.print((2, 1), "Hello");
+    ///     printer.print_box((0, 0), (9, 3), false);
+    /// });
+    /// let bounds = bounds.unwrap();
+    /// assert_eq!(bounds.offset + bounds.size, Vec2::new(9, 3));
+    /// ```
+    pub fn draw_bounds<S, F>(size: S, theme: &Theme, f: F) -> Option<Rect>
+        where S: Into<Vec2>,
+              F: FnOnce(&Printer)
+    {
+        let size = size.into();
+        let bounds = Rc::new(Cell::new(None));
+        f(&Printer {
+              offset: Vec2::zero(),
+              size: size,
+              focused: true,
+              theme: Rc::new(theme.clone()),
+              screen_size: size,
+              new: Rc::new(Cell::new(true)),
+              overridden: false,
+              filter: None,
+              styles: Rc::new(RefCell::new(Vec::new())),
+              applied: Rc::new(Cell::new(0)),
+              scratch: Rc::new(RefCell::new(String::new())),
+              target: Target::Bounds(bounds.clone()),
+          });
+        bounds.get()
+    }
+
     /// Clear the screen.
     ///
     /// It will discard anything drawn before.
     ///
     /// Users rarely need to call this directly.
     pub fn clear(&self) {
-        self.backend.clear();
+        // Off-screen, invalid rows are already blank.
+        if let Target::Screen(backend) = self.target {
+            backend.clear();
+        }
     }
 
     /// Returns `true` if nothing has been printed yet.
@@ -61,6 +312,14 @@ impl<'a> Printer<'a> {
         self.new.get()
     }
 
+    /// Returns the size of the whole screen.
+    ///
+    /// Together with `offset`, this tells where the view stands on the
+    /// screen, and how much room is left around it.
+    pub fn screen_size(&self) -> Vec2 {
+        self.screen_size
+    }
+
     // TODO: use &mut self? We don't *need* it, but it may make sense.
     // We don't want people to start calling prints in parallel?
     /// Prints some text at the given position relative to the window.
@@ -79,7 +338,177 @@ impl<'a> Printer<'a> {
         let text = &text[..prefix_len];
 
         let p = p + self.offset;
-        self.backend.print_at((p.x, p.y), text);
+        self.with_pushed_styles(|| self.print_at(p, text));
+    }
+
+    /// Prints formatted text at the given position.
+    ///
+    /// Like `print(pos, &format!(..))`, without allocating a new string
+    /// each time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Printer;
+    /// # use cursive::theme;
+    /// # use cursive::backend::{self, Backend};
+    /// # let b = backend::Concrete::init();
+    /// # let printer = Printer::new((6,4), theme::load_default(), &b);
+    /// printer.print_fmt((0, 0), format_args!("{:02x}", 255));
+    /// ```
+    pub fn print_fmt<S: Into<Vec2>>(&self, pos: S, args: fmt::Arguments) {
+        let pos = pos.into();
+        self.with_scratch(|text| {
+            // Writing to a `String` can't fail.
+            let _ = text.write_fmt(args);
+            self.print(pos, text);
+        });
+    }
+
+    /// Prints some text as a hyperlink to `url`.
+    ///
+    /// On backends without hyperlink support, this is the same as `print`.
+    pub fn print_link<S: Into<Vec2>>(&self, pos: S, text: &str, url: &str) {
+        self.new.set(false);
+
+        let p = pos.into();
+        if p.y >= self.size.y || p.x >= self.size.x {
+            return;
+        }
+        let room = self.size.x - p.x;
+        let prefix_len = prefix(text.graphemes(true), room, "").length;
+        let text = &text[..prefix_len];
+
+        let p = p + self.offset;
+        self.with_pushed_styles(|| match self.target {
+                                    Target::Screen(backend) => {
+                                        backend.print_link((p.x, p.y),
+                                                           text,
+                                                           url)
+                                    }
+                                    _ => self.print_at(p, text),
+                                });
+    }
+
+    /// Prints some text, with a color style for each grapheme.
+    ///
+    /// `color` is given the index of each grapheme.
+    pub fn print_colored<S, F>(&self, pos: S, text: &str, color: F)
+        where S: Into<Vec2>,
+              F: Fn(usize) -> ColorStyle
+    {
+        let mut p = pos.into();
+        for (i, grapheme) in text.graphemes(true).enumerate() {
+            self.with_color(color(i), |printer| printer.print(p, grapheme));
+            p.x += grapheme.width();
+        }
+    }
+
+    /// Prints a styled string at the given position.
+    ///
+    /// Parts without any span use the current style.
+    pub fn print_styled<S: Into<Vec2>>(&self, pos: S, text: &StyledString) {
+        let mut p = pos.into();
+        let source = text.source();
+        let mut start = 0;
+        for span in text.spans() {
+            // Unstyled text before this span.
+            let plain = &source[start..span.start];
+            self.print(p, plain);
+            p.x += plain.width();
+
+            let styled = &source[span.start..span.end];
+            self.with_style(span.style, |printer| match span.link {
+                Some(ref url) => printer.print_link(p, styled, url),
+                None => printer.print(p, styled),
+            });
+            p.x += styled.width();
+            start = span.end;
+        }
+        self.print(p, &source[start..]);
+    }
+
+    /// Prints some text, with text colors going from `start` to `end`.
+    ///
+    /// The background is the view color of the theme.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Printer;
+    /// # use cursive::theme::{self, BaseColor, Color};
+    /// # use cursive::backend::{self, Backend};
+    /// # let b = backend::Concrete::init();
+    /// # let printer = Printer::new((6,4), theme::load_default(), &b);
+    /// printer.print_gradient((0, 0),
+    ///                        "Cursive",
+    ///                        Color::Dark(BaseColor::Red),
+    ///                        Color::Rgb(255, 200, 0));
+    /// ```
+    pub fn print_gradient<S: Into<Vec2>>(&self, pos: S, text: &str,
+                                         start: Color, end: Color) {
+        let colors = theme::gradient(start, end, text.graphemes(true).count());
+        let back = self.theme.colors.view;
+        self.print_colored(pos, text, |i| {
+            ColorStyle::Custom {
+                front: colors[i],
+                back: back,
+            }
+        });
+    }
+
+    /// Fills a rectangle with background colors going from `start` to
+    /// `end`.
+    ///
+    /// With `Orientation::Horizontal`, colors change from left to right;
+    /// with `Orientation::Vertical`, from top to bottom.
+    pub fn fill_gradient<T, S>(&self, pos: T, size: S, start: Color,
+                               end: Color, orientation: Orientation)
+        where T: Into<Vec2>,
+              S: Into<Vec2>
+    {
+        let pos = pos.into();
+        let size = size.into();
+        let colors = theme::gradient(start, end, *size.get(orientation));
+
+        for (i, &color) in colors.iter().enumerate() {
+            let style = ColorStyle::Custom {
+                front: color,
+                back: color,
+            };
+            self.with_color(style, |printer| match orientation {
+                Orientation::Horizontal => {
+                    printer.print_vline(pos + (i, 0), size.y, " ")
+                }
+                Orientation::Vertical => {
+                    printer.print_hline(pos + (0, i), size.x, " ")
+                }
+            });
+        }
+    }
+
+    /// Sends raw bytes to the terminal, starting at the given position.
+    ///
+    /// This can be used for escape sequences not otherwise supported, like
+    /// inline images. Nothing prevents them from drawing out of this
+    /// printer's area.
+    ///
+    /// Returns `false` if the backend doesn't support it, or when not
+    /// drawing on the screen.
+    pub fn print_raw<S: Into<Vec2>>(&self, pos: S, data: &[u8]) -> bool {
+        self.new.set(false);
+        let backend = match self.target {
+            Target::Screen(backend) => backend,
+            _ => return false,
+        };
+
+        let p = pos.into();
+        if p.y >= self.size.y || p.x >= self.size.x {
+            return false;
+        }
+
+        let p = p + self.offset;
+        backend.print_raw((p.x, p.y), data)
     }
 
     /// Prints a vertical line using the given character.
@@ -93,9 +522,9 @@ impl<'a> Printer<'a> {
         let len = min(len, self.size.y - p.y);
 
         let p = p + self.offset;
-        for y in 0..len {
-            self.backend.print_at((p.x, (p.y + y)), c);
-        }
+        self.with_pushed_styles(|| for y in 0..len {
+            self.print_at(p + (0, y), c);
+        });
     }
 
     /// Prints a horizontal line using the given character.
@@ -107,10 +536,14 @@ impl<'a> Printer<'a> {
             return;
         }
         let len = min(len, self.size.x - p.x);
-        let text: String = ::std::iter::repeat(c).take(len).collect();
 
         let p = p + self.offset;
-        self.backend.print_at((p.x, p.y), &text);
+        self.with_scratch(|text| {
+            for _ in 0..len {
+                text.push_str(c);
+            }
+            self.with_pushed_styles(|| self.print_at(p, text));
+        });
     }
 
     /// Call the given closure with a colored printer,
@@ -131,7 +564,69 @@ impl<'a> Printer<'a> {
     pub fn with_color<F>(&self, c: ColorStyle, f: F)
         where F: FnOnce(&Printer)
     {
-        self.backend.with_color(c, || f(self));
+        if self.theme.highlight_effects {
+            let effect = match c {
+                ColorStyle::Highlight => Some(Effect::Reverse),
+                ColorStyle::HighlightInactive => Some(Effect::Underline),
+                _ => None,
+            };
+            if let Some(effect) = effect {
+                let c = self.backend_color(ColorStyle::Primary);
+                return self.with_pushed_styles(|| {
+                    self.apply_color(c, || {
+                        self.apply_effect(effect, || f(self))
+                    })
+                });
+            }
+        }
+
+        let c = self.backend_color(c);
+        self.with_pushed_styles(|| self.apply_color(c, || f(self)));
+    }
+
+    // Returns the number of colors the target can show.
+    fn color_depth(&self) -> theme::ColorDepth {
+        match self.target {
+            Target::Screen(backend) => backend.color_depth(),
+            Target::Buffer(ref capture) => capture.depth,
+            // Nothing is shown.
+            Target::Bounds(_) => theme::ColorDepth::TrueColor,
+        }
+    }
+
+    // Returns the color to give the backend for `c`.
+    fn backend_color(&self, c: ColorStyle) -> ColorStyle {
+        let c = c.resolve(&self.theme.colors);
+        let c = if self.overridden || self.filter.is_some() {
+            let (front, back) = c.colors(&self.theme.colors);
+            match self.filter {
+                Some(ref filter) => {
+                    ColorStyle::Custom {
+                        front: filter(front),
+                        back: filter(back),
+                    }
+                }
+                None => {
+                    ColorStyle::Custom {
+                        front: front,
+                        back: back,
+                    }
+                }
+            }
+        } else {
+            c
+        };
+        match c {
+            ColorStyle::Custom { front, back } => {
+                let depth = self.color_depth();
+                let approximation = self.theme.approximation;
+                ColorStyle::Custom {
+                    front: front.approximate(depth, approximation),
+                    back: back.approximate(depth, approximation),
+                }
+            }
+            c => c,
+        }
     }
 
     /// Same as `with_color`, but apply a ncurses style instead,
@@ -141,7 +636,160 @@ impl<'a> Printer<'a> {
     pub fn with_effect<F>(&self, effect: Effect, f: F)
         where F: FnOnce(&Printer)
     {
-        self.backend.with_effect(effect, || f(self));
+        self.with_pushed_styles(|| self.apply_effect(effect, || f(self)));
+    }
+
+    /// Applies `style` to everything printed until the matching
+    /// `pop_style`.
+    ///
+    /// Unlike `with_style`, this doesn't need a closure, so drawing code
+    /// can switch styles without restoring the previous ones by hand.
+    /// Styles are shared with sub-printers, and the last pushed one wins.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Printer;
+    /// # use cursive::theme::{self, ColorStyle, Style};
+    /// # use cursive::backend::{self, Backend};
+    /// # let b = backend::Concrete::init();
+    /// # let printer = Printer::new((6,4), theme::load_default(), &b);
+    /// printer.push_style(Style::from(ColorStyle::Secondary));
+    /// printer.print((0, 0), "Notes:");
+    /// printer.pop_style();
+    /// ```
+    pub fn push_style(&self, style: Style) {
+        self.styles.borrow_mut().push(style);
+    }
+
+    /// Removes the last pushed style, and returns it.
+    ///
+    /// Returns `None` if no style was pushed.
+    pub fn pop_style(&self) -> Option<Style> {
+        self.styles.borrow_mut().pop()
+    }
+
+    /// Returns the width of `text` once printed, in cells.
+    ///
+    /// Wide characters, like most CJK ones, take two cells.
+    pub fn measure(&self, text: &str) -> usize {
+        text.width()
+    }
+
+    // Runs `f` with the pushed styles not applied yet.
+    fn with_pushed_styles<F: FnOnce()>(&self, f: F) {
+        let applied = self.applied.get();
+        let len = self.styles.borrow().len();
+        if applied >= len {
+            return f();
+        }
+
+        // Styles applied by callers mustn't be applied again inside, or
+        // they would override closer ones, like from `with_color`.
+        self.applied.set(len);
+        self.apply_styles(applied..len, f);
+        self.applied.set(applied);
+    }
+
+    // Runs `f` with an empty string, reusing the scratch buffer.
+    //
+    // The buffer is taken out while `f` runs, so nested calls just get a
+    // new string.
+    fn with_scratch<F: FnOnce(&mut String)>(&self, f: F) {
+        let mut text = mem::replace(&mut *self.scratch.borrow_mut(),
+                                    String::new());
+        text.clear();
+        f(&mut text);
+        *self.scratch.borrow_mut() = text;
+    }
+
+    // Prints `text` at the absolute position `p`.
+    fn print_at(&self, p: Vec2, text: &str) {
+        match self.target {
+            Target::Screen(backend) => backend.print_at((p.x, p.y), text),
+            Target::Buffer(ref capture) => {
+                let color = capture.color.get();
+                let effects = capture.effects.borrow();
+                capture.buffer
+                    .borrow_mut()
+                    .write(p, text, color, &effects);
+            }
+            Target::Bounds(ref bounds) => {
+                let end = p + (text.width(), 1);
+                let (start, end) = match bounds.get() {
+                    Some(rect) => {
+                        (Vec2::min(rect.offset, p),
+                         Vec2::max(rect.offset + rect.size, end))
+                    }
+                    None => (p, end),
+                };
+                bounds.set(Some(Rect::new(start, end - start)));
+            }
+        }
+    }
+
+    // Runs `f` with the backend color `c`.
+    fn apply_color<F: FnOnce()>(&self, c: ColorStyle, f: F) {
+        match self.target {
+            Target::Screen(backend) => backend.with_color(c, f),
+            Target::Buffer(ref capture) => {
+                let previous = capture.color.replace(Some(c));
+                f();
+                capture.color.set(previous);
+            }
+            Target::Bounds(_) => f(),
+        }
+    }
+
+    // Runs `f` with the backend effect `effect`.
+    fn apply_effect<F: FnOnce()>(&self, effect: Effect, f: F) {
+        match self.target {
+            Target::Screen(backend) => backend.with_effect(effect, f),
+            Target::Buffer(ref capture) => {
+                capture.effects.borrow_mut().push(effect);
+                f();
+                capture.effects.borrow_mut().pop();
+            }
+            Target::Bounds(_) => f(),
+        }
+    }
+
+    // Applies the pushed styles in `range`, then runs `f`.
+    //
+    // Each style is read right before applying it, so nothing needs to be
+    // copied.
+    fn apply_styles<F: FnOnce()>(&self, range: Range<usize>, f: F) {
+        let style = self.styles.borrow().get(range.start).cloned();
+        let style = match style {
+            Some(style) if range.start < range.end => style,
+            _ => return f(),
+        };
+        let rest = range.start + 1..range.end;
+        let apply = || {
+            self.apply_effect(style.effect, || self.apply_styles(rest, f))
+        };
+        match style.color {
+            Some(color) => {
+                self.apply_color(self.backend_color(color), apply)
+            }
+            None => apply(),
+        }
+    }
+
+    /// Call the given closure with a styled printer.
+    ///
+    /// Applies both the color, if any, and the effect from `style`.
+    pub fn with_style<F>(&self, style: Style, f: F)
+        where F: FnOnce(&Printer)
+    {
+        match style.color {
+            Some(color) => {
+                self.with_color(color, |printer| {
+                    printer.with_effect(style.effect, f)
+                })
+            }
+            None => self.with_effect(style.effect, f),
+        }
     }
 
     /// Prints a rectangular box.
@@ -170,18 +818,59 @@ impl<'a> Printer<'a> {
         }
         let size = size - (1, 1);
 
+        let glyphs = &self.theme.glyphs;
+        self.with_high_border(invert, |s| {
+            s.print(start, &glyphs.top_left);
+            s.print(start + size.keep_y(), &glyphs.bottom_left);
+            s.print_hline(start + (1, 0), size.x - 1, &glyphs.horizontal);
+            s.print_vline(start + (0, 1), size.y - 1, &glyphs.vertical);
+        });
+
+        self.with_low_border(invert, |s| {
+            s.print(start + size.keep_x(), &glyphs.top_right);
+            s.print(start + size, &glyphs.bottom_right);
+            s.print_hline(start + (1, 0) + size.keep_y(),
+                          size.x - 1,
+                          &glyphs.horizontal);
+            s.print_vline(start + (0, 1) + size.keep_x(),
+                          size.y - 1,
+                          &glyphs.vertical);
+        });
+    }
+
+    /// Prints a rectangular frame, with glyphs from `frame`.
+    ///
+    /// Like `print_box`, but ignores the theme's glyphs.
+    pub fn print_frame<T, S>(&self, start: T, size: S, frame: &theme::Frame,
+                             invert: bool)
+        where T: Into<Vec2>,
+              S: Into<Vec2>
+    {
+        self.new.set(false);
+
+        let start = start.into();
+        let size = size.into();
+        if size.x < 2 || size.y < 2 {
+            return;
+        }
+        let size = size - (1, 1);
+
         self.with_high_border(invert, |s| {
-            s.print(start, "┌");
-            s.print(start + size.keep_y(), "└");
-            s.print_hline(start + (1, 0), size.x - 1, "─");
-            s.print_vline(start + (0, 1), size.y - 1, "│");
+            s.print(start, &frame.top_left);
+            s.print(start + size.keep_y(), &frame.bottom_left);
+            s.print_hline(start + (1, 0), size.x - 1, &frame.top);
+            s.print_vline(start + (0, 1), size.y - 1, &frame.left);
         });
 
         self.with_low_border(invert, |s| {
-            s.print(start + size.keep_x(), "┐");
-            s.print(start + size, "┘");
-            s.print_hline(start + (1, 0) + size.keep_y(), size.x - 1, "─");
-            s.print_vline(start + (0, 1) + size.keep_x(), size.y - 1, "│");
+            s.print(start + size.keep_x(), &frame.top_right);
+            s.print(start + size, &frame.bottom_right);
+            s.print_hline(start + (1, 0) + size.keep_y(),
+                          size.x - 1,
+                          &frame.bottom);
+            s.print_vline(start + (0, 1) + size.keep_x(),
+                          size.y - 1,
+                          &frame.right);
         });
     }
 
@@ -242,11 +931,14 @@ impl<'a> Printer<'a> {
     }
 
     /// Prints a horizontal delimiter with side border `├` and `┤`.
+    ///
+    /// The characters come from the theme's `glyphs`.
     pub fn print_hdelim<T: Into<Vec2>>(&self, start: T, len: usize) {
         let start = start.into();
-        self.print(start, "├");
-        self.print_hline(start + (1, 0), len - 2, "─");
-        self.print(start + (len - 1, 0), "┤");
+        let glyphs = &self.theme.glyphs;
+        self.print(start, &glyphs.left_junction);
+        self.print_hline(start + (1, 0), len - 2, &glyphs.horizontal);
+        self.print(start + (len - 1, 0), &glyphs.right_junction);
     }
 
     /// Returns a printer on a subset of this one's area.
@@ -261,13 +953,220 @@ impl<'a> Printer<'a> {
             size: Vec2::min(self.size - offset, size),
             focused: self.focused && focused,
             theme: self.theme.clone(),
-            backend: self.backend,
+            screen_size: self.screen_size,
             new: self.new.clone(),
+            overridden: self.overridden,
+            filter: self.filter.clone(),
+            styles: self.styles.clone(),
+            applied: self.applied.clone(),
+            scratch: self.scratch.clone(),
+            target: self.target.clone(),
         }
     }
 
+    /// Calls `f` with a printer using a modified palette.
+    ///
+    /// Styles like `ColorStyle::Primary` then use the modified colors, for
+    /// everything drawn with this printer and its sub-printers.
+    pub fn with_palette<C, F>(&self, change: C, f: F)
+        where C: FnOnce(&mut Palette),
+              F: FnOnce(&Printer)
+    {
+        let mut printer = self.sub_printer(Vec2::zero(), self.size, true);
+        change(&mut Rc::make_mut(&mut printer.theme).colors);
+        printer.overridden = true;
+        f(&printer);
+    }
+
+    /// Calls `f` with a printer passing every color through `filter`.
+    ///
+    /// This applies to theme roles and custom colors alike, for everything
+    /// drawn with this printer and its sub-printers. If this printer
+    /// already has a filter, `filter` applies before it.
+    pub fn with_color_filter<C, F>(&self, filter: C, f: F)
+        where C: Fn(Color) -> Color + 'static,
+              F: FnOnce(&Printer)
+    {
+        let mut printer = self.sub_printer(Vec2::zero(), self.size, true);
+        printer.filter = Some(match self.filter.clone() {
+            Some(outer) => Rc::new(move |color| outer(filter(color))),
+            None => Rc::new(filter),
+        });
+        f(&printer);
+    }
+
     /// Returns a sub-printer with the given offset.
     pub fn offset<S: Into<Vec2>>(&self, offset: S, focused: bool) -> Printer {
         self.sub_printer(offset, self.size, focused)
     }
+
+    /// Calls `f` with a printer drawing into `buffer` rather than the
+    /// screen.
+    ///
+    /// Only the rows invalidated since the last call are blanked and drawn
+    /// again: anything printed on other rows is dropped. `f` isn't called
+    /// at all if every row is valid. Afterwards, every row is valid.
+    ///
+    /// The printer given to `f` covers the whole buffer, at offset zero.
+    /// Styles pushed with `push_style` don't carry over, and `print_raw`
+    /// has no effect.
+    pub fn draw_offscreen<F>(&self, buffer: &mut CellBuffer, f: F)
+        where F: FnOnce(&Printer)
+    {
+        if buffer.valid.iter().all(|&valid| valid) {
+            return;
+        }
+        buffer.clear_invalid(Some(self.backend_color(ColorStyle::Background)));
+
+        let empty = CellBuffer::new(Vec2::zero());
+        let capture = Rc::new(Capture {
+                                  buffer: RefCell::new(mem::replace(buffer,
+                                                                    empty)),
+                                  depth: self.color_depth(),
+                                  color: Cell::new(None),
+                                  effects: RefCell::new(Vec::new()),
+                              });
+        let size = capture.buffer.borrow().size;
+        f(&Printer {
+              offset: Vec2::zero(),
+              size: size,
+              focused: self.focused,
+              theme: self.theme.clone(),
+              screen_size: size,
+              new: self.new.clone(),
+              overridden: self.overridden,
+              filter: self.filter.clone(),
+              styles: Rc::new(RefCell::new(Vec::new())),
+              applied: Rc::new(Cell::new(0)),
+              scratch: self.scratch.clone(),
+              target: Target::Buffer(capture.clone()),
+          });
+
+        let empty = CellBuffer::new(Vec2::zero());
+        *buffer = mem::replace(&mut *capture.buffer.borrow_mut(), empty);
+        for valid in &mut buffer.valid {
+            *valid = true;
+        }
+    }
+
+    /// Copies the content of `buffer` to this printer, from `offset`.
+    ///
+    /// Cells keep the style they were drawn with. Like with `print`,
+    /// anything outside this printer's area is cut.
+    pub fn blit<S: Into<Vec2>>(&self, buffer: &CellBuffer, offset: S) {
+        self.new.set(false);
+
+        let offset = offset.into();
+        if offset.x >= self.size.x || offset.y >= self.size.y {
+            return;
+        }
+        let width = min(buffer.size.x, self.size.x - offset.x);
+        let height = min(buffer.size.y, self.size.y - offset.y);
+        let row_width = buffer.size.x;
+
+        for y in 0..height {
+            let row = &buffer.cells[y * row_width..(y + 1) * row_width];
+            let mut x = 0;
+            while x < width {
+                // Prints together the cells sharing the same style.
+                let first = &row[x];
+                let start = x;
+                self.with_scratch(|text| {
+                    while x < width && row[x].color == first.color &&
+                          row[x].effects == first.effects {
+                        // Half a wide character is drawn as a space.
+                        let cut_left = x == start && row[x].text.is_empty();
+                        let cut_right = x + 1 == width &&
+                                        x + 1 < row_width &&
+                                        row[x + 1].text.is_empty();
+                        if cut_left || cut_right {
+                            text.push(' ');
+                        } else {
+                            text.push_str(&row[x].text);
+                        }
+                        x += 1;
+                    }
+
+                    let p = self.offset + offset + (start, y);
+                    let print = || {
+                        self.with_effects(&first.effects,
+                                          || self.print_at(p, text))
+                    };
+                    match first.color {
+                        Some(color) => self.apply_color(color, print),
+                        None => print(),
+                    }
+                });
+            }
+        }
+    }
+
+    // Runs `f` with every effect in `effects`.
+    fn with_effects<F: FnOnce()>(&self, effects: &[Effect], f: F) {
+        match effects.split_first() {
+            Some((&effect, rest)) => {
+                self.apply_effect(effect, || self.with_effects(rest, f))
+            }
+            None => f(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CellBuffer, Printer};
+    use theme::Theme;
+    use vec::Vec2;
+    use view::Rect;
+
+    fn row(buffer: &CellBuffer, y: usize) -> String {
+        let width = buffer.size.x;
+        buffer.cells[y * width..(y + 1) * width]
+            .iter()
+            .map(|cell| cell.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_cell_buffer() {
+        let mut buffer = CellBuffer::new((4, 2));
+        assert!(!buffer.is_valid(0));
+
+        buffer.write(Vec2::new(1, 0), "ab", None, &[]);
+        // Wide characters take two cells, and are cut as a whole.
+        buffer.write(Vec2::new(0, 1), "中文", None, &[]);
+        buffer.write(Vec2::new(3, 1), "中", None, &[]);
+        assert_eq!(row(&buffer, 0), " ab ");
+        assert_eq!(row(&buffer, 1), "中文");
+
+        buffer.valid = vec![true, true];
+        buffer.invalidate_rows(1..5);
+        assert!(buffer.is_valid(0));
+        assert!(!buffer.is_valid(1));
+
+        // Valid rows are kept.
+        buffer.write(Vec2::new(0, 0), "xyz", None, &[]);
+        buffer.clear_invalid(None);
+        assert_eq!(row(&buffer, 0), " ab ");
+        assert_eq!(row(&buffer, 1), "    ");
+
+        buffer.resize((4, 2));
+        assert!(buffer.is_valid(0));
+        buffer.resize((5, 2));
+        assert!(!buffer.is_valid(0));
+    }
+
+    #[test]
+    fn test_draw_bounds() {
+        let theme = Theme::default();
+        assert_eq!(Printer::draw_bounds((10, 10), &theme, |_| ()), None);
+
+        let bounds = Printer::draw_bounds((10, 10), &theme, |printer| {
+            printer.offset((3, 2), true).print((0, 0), "中文");
+            printer.print_vline((1, 4), 2, "|");
+            // Cut at the edge of the printer.
+            printer.print((6, 5), "Hello world");
+        });
+        assert_eq!(bounds, Some(Rect::new((1, 2), (9, 4))));
+    }
 }
@@ -0,0 +1,193 @@
+//! Keyboard macros: records events to registers, and plays them back.
+
+use event::Event;
+use std::collections::HashMap;
+
+// What the recorder is waiting for.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Idle,
+    // The record key was pressed; the next char names the register.
+    RecordRegister,
+    // The play key was pressed; the next char names the register.
+    PlayRegister,
+}
+
+/// What to do with an event after the recorder saw it.
+pub enum Action {
+    /// Handle the event as usual.
+    Pass,
+    /// The event was used by the recorder.
+    Consumed,
+    /// Handle these events, in order.
+    Play(Vec<Event>),
+}
+
+/// Records events to named registers.
+pub struct Recorder {
+    registers: HashMap<char, Vec<Event>>,
+    // Register being recorded, and the events so far.
+    recording: Option<(char, Vec<Event>)>,
+    record_key: Option<Event>,
+    play_key: Option<Event>,
+    state: State,
+}
+
+new_default!(Recorder);
+
+impl Recorder {
+    /// Creates a new recorder, without any key bound.
+    pub fn new() -> Self {
+        Recorder {
+            registers: HashMap::new(),
+            recording: None,
+            record_key: None,
+            play_key: None,
+            state: State::Idle,
+        }
+    }
+
+    /// Sets the keys used to start or stop recording, and to play a macro.
+    pub fn set_keys(&mut self, record: Option<Event>, play: Option<Event>) {
+        self.record_key = record;
+        self.play_key = play;
+        self.state = State::Idle;
+    }
+
+    /// Starts recording to the given register.
+    ///
+    /// If a macro was already being recorded, it is saved first.
+    pub fn start(&mut self, register: char) {
+        self.stop();
+        self.recording = Some((register, Vec::new()));
+    }
+
+    /// Stops recording, and saves the macro.
+    pub fn stop(&mut self) {
+        if let Some((register, events)) = self.recording.take() {
+            self.registers.insert(register, events);
+        }
+    }
+
+    /// Returns the register being recorded, if any.
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|&(register, _)| register)
+    }
+
+    /// Returns the events saved in the given register.
+    pub fn get(&self, register: char) -> Option<&[Event]> {
+        self.registers.get(&register).map(|events| &events[..])
+    }
+
+    /// Saves events to the given register.
+    pub fn set(&mut self, register: char, events: Vec<Event>) {
+        self.registers.insert(register, events);
+    }
+
+    /// Looks at an event before it is handled.
+    pub fn on_event(&mut self, event: &Event) -> Action {
+        // Not user input: it mustn't cancel a pending register key.
+        match *event {
+            Event::Refresh | Event::WindowResize => return Action::Pass,
+            _ => (),
+        }
+
+        let state = self.state;
+        self.state = State::Idle;
+
+        match (state, event) {
+            (State::RecordRegister, &Event::Char(register)) => {
+                self.start(register);
+                return Action::Consumed;
+            }
+            (State::PlayRegister, &Event::Char(register)) => {
+                return match self.registers.get(&register) {
+                    Some(events) => Action::Play(events.clone()),
+                    None => Action::Consumed,
+                };
+            }
+            // Any other key cancels.
+            (State::RecordRegister, _) |
+            (State::PlayRegister, _) => return Action::Consumed,
+            (State::Idle, _) => (),
+        }
+
+        if self.record_key.as_ref() == Some(event) {
+            if self.recording.is_some() {
+                self.stop();
+            } else {
+                self.state = State::RecordRegister;
+            }
+            return Action::Consumed;
+        }
+
+        if self.play_key.as_ref() == Some(event) {
+            self.state = State::PlayRegister;
+            return Action::Consumed;
+        }
+
+        if let Some((_, ref mut events)) = self.recording {
+            match *event {
+                Event::Exit | Event::AppEvent(_) => (),
+                _ => events.push(event.clone()),
+            }
+        }
+
+        Action::Pass
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event::Key;
+
+    #[test]
+    fn test_record() {
+        let mut recorder = Recorder::new();
+        recorder.set_keys(Some(Event::CtrlChar('r')), Some(Event::Char('@')));
+
+        let events = vec![Event::CtrlChar('r'),
+                          Event::Char('a'),
+                          Event::Char('x'),
+                          Event::Refresh,
+                          Event::Key(Key::Down),
+                          Event::CtrlChar('r')];
+        for event in &events {
+            recorder.on_event(event);
+        }
+        assert_eq!(recorder.recording(), None);
+        assert_eq!(recorder.get('a'),
+                   Some(&[Event::Char('x'), Event::Key(Key::Down)][..]));
+
+        recorder.on_event(&Event::Char('@'));
+        match recorder.on_event(&Event::Char('a')) {
+            Action::Play(events) => assert_eq!(events.len(), 2),
+            _ => panic!("macro not played"),
+        }
+    }
+
+    #[test]
+    fn test_refresh_keeps_state() {
+        let mut recorder = Recorder::new();
+        recorder.set_keys(Some(Event::CtrlChar('r')), Some(Event::Char('@')));
+        recorder.set('a', vec![Event::Char('x')]);
+
+        recorder.on_event(&Event::CtrlChar('r'));
+        assert!(match recorder.on_event(&Event::Refresh) {
+                    Action::Pass => true,
+                    _ => false,
+                });
+        recorder.on_event(&Event::WindowResize);
+        recorder.on_event(&Event::Char('b'));
+        assert_eq!(recorder.recording(), Some('b'));
+        recorder.on_event(&Event::CtrlChar('r'));
+
+        recorder.on_event(&Event::Char('@'));
+        recorder.on_event(&Event::Refresh);
+        match recorder.on_event(&Event::Char('a')) {
+            Action::Play(events) => assert_eq!(events, vec![Event::Char('x')]),
+            _ => panic!("macro not played"),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Keyboard shortcuts, registered in a single place.
+//!
+//! Each shortcut has a scope:
+//!
+//! * `Scope::Global` shortcuts are always available.
+//! * `Scope::Screen` shortcuts are only available on a given screen.
+//! * `Scope::Context` shortcuts are only available while their context is
+//!   active, for instance while a given dialog is open.
+//!
+//! Registering a key twice in the same scope is an error, so conflicts are
+//! found as soon as they are written. Callbacks added later with
+//! `Cursive::add_global_callback` or `Cursive::add_screen_callback` are not
+//! checked, and registered shortcuts take priority over them. Narrower scopes can still shadow the
+//! same key in a broader one: active contexts first (the last activated
+//! first), then the active screen, then global shortcuts.
+//!
+//! The registry also describes each shortcut, to build the help screen or
+//! documentation.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use cursive::Cursive;
+//! # use cursive::event::Event;
+//! # use cursive::shortcuts::Scope;
+//! let mut siv = Cursive::new();
+//!
+//! siv.register_shortcut(Scope::Global, 'q', "General", "Quit", |s| s.quit())
+//!     .unwrap();
+//! siv.register_shortcut(Scope::context("editor"),
+//!                       Event::CtrlChar('s'),
+//!                       "Editor",
+//!                       "Save",
+//!                       |_| ())
+//!     .unwrap();
+//!
+//! // Ctrl-S now saves.
+//! siv.shortcuts_mut().push_context("editor");
+//! ```
+
+use Cursive;
+use ScreenId;
+use event::{Callback, Event};
+
+/// Where a shortcut is available.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Always available.
+    Global,
+    /// Available on the given screen.
+    Screen(ScreenId),
+    /// Available while the named context is active.
+    Context(String),
+}
+
+impl Scope {
+    /// Convenient method to create a `Scope::Context`.
+    pub fn context<S: Into<String>>(name: S) -> Self {
+        Scope::Context(name.into())
+    }
+}
+
+/// Description of a registered shortcut.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Where the shortcut is available.
+    pub scope: Scope,
+    /// Key combination triggering the shortcut.
+    pub event: Event,
+    /// Group this shortcut belongs to, like "File".
+    pub category: String,
+    /// What the shortcut does.
+    pub description: String,
+}
+
+/// Error returned when a key is already used in the same scope.
+#[derive(Debug)]
+pub struct Conflict {
+    /// The shortcut that could not be registered.
+    pub shortcut: Shortcut,
+    /// The shortcut already using this key.
+    ///
+    /// `None` if the key is used by a callback registered without the
+    /// registry, like with `Cursive::add_global_callback`.
+    pub existing: Option<Shortcut>,
+}
+
+/// Error returned by `Cursive::register_shortcut`.
+#[derive(Debug)]
+pub enum Error {
+    /// The key is already used in the same scope.
+    Conflict(Box<Conflict>),
+    /// The scope is a screen that doesn't exist.
+    NoSuchScreen(ScreenId),
+}
+
+impl From<Box<Conflict>> for Error {
+    fn from(conflict: Box<Conflict>) -> Self {
+        Error::Conflict(conflict)
+    }
+}
+
+/// Registered shortcuts, and the active contexts.
+///
+/// The [`Cursive`] root has a registry, accessible with
+/// `Cursive::shortcuts` and `Cursive::shortcuts_mut`.
+///
+/// [`Cursive`]: ../struct.Cursive.html
+pub struct Registry {
+    entries: Vec<(Shortcut, Callback)>,
+    // Active contexts, in activation order.
+    contexts: Vec<String>,
+}
+
+new_default!(Registry);
+
+impl Registry {
+    /// Creates a new, empty registry.
+    pub fn new() -> Self {
+        Registry {
+            entries: Vec::new(),
+            contexts: Vec::new(),
+        }
+    }
+
+    /// Registers a shortcut.
+    ///
+    /// Returns an error if `event` is already used in this scope.
+    pub fn register<E, C, D, F>(&mut self, scope: Scope, event: E,
+                                category: C, description: D, cb: F)
+                                -> Result<(), Box<Conflict>>
+        where E: Into<Event>,
+              C: Into<String>,
+              D: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        let shortcut = Shortcut {
+            scope: scope,
+            event: event.into(),
+            category: category.into(),
+            description: description.into(),
+        };
+
+        if let Some(existing) = self.get(&shortcut.scope, &shortcut.event) {
+            return Err(Box::new(Conflict {
+                                    shortcut: shortcut,
+                                    existing: Some(existing.clone()),
+                                }));
+        }
+
+        self.entries.push((shortcut, Callback::from_fn(cb)));
+        Ok(())
+    }
+
+    /// Removes the shortcut for `event` in the given scope.
+    ///
+    /// Returns `false` if there was no such shortcut.
+    pub fn unregister(&mut self, scope: &Scope, event: &Event) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|&(ref shortcut, _)| {
+            shortcut.scope != *scope || shortcut.event != *event
+        });
+        self.entries.len() != len
+    }
+
+    /// Returns the shortcut for `event` in the given scope, if any.
+    pub fn get(&self, scope: &Scope, event: &Event) -> Option<&Shortcut> {
+        self.find(scope, event).map(|&(ref shortcut, _)| shortcut)
+    }
+
+    /// Returns all registered shortcuts, in registration order.
+    pub fn shortcuts(&self) -> Vec<&Shortcut> {
+        self.entries.iter().map(|&(ref shortcut, _)| shortcut).collect()
+    }
+
+    /// Activates a context.
+    ///
+    /// Its shortcuts take priority over those of previous contexts.
+    pub fn push_context<S: Into<String>>(&mut self, name: S) {
+        self.contexts.push(name.into());
+    }
+
+    /// Deactivates the last activated context, and returns its name.
+    pub fn pop_context(&mut self) -> Option<String> {
+        self.contexts.pop()
+    }
+
+    /// Returns the active contexts, in activation order.
+    pub fn contexts(&self) -> &[String] {
+        &self.contexts
+    }
+
+    /// Returns the callback to run for `event` on the given screen.
+    ///
+    /// Only the shortcuts in scope are considered.
+    pub fn callback(&self, screen: ScreenId, event: &Event)
+                    -> Option<Callback> {
+        self.contextual(event)
+            .or_else(|| self.scoped(&Scope::Screen(screen), event))
+            .or_else(|| self.scoped(&Scope::Global, event))
+    }
+
+    // Returns the callback for `event` in the active contexts.
+    fn contextual(&self, event: &Event) -> Option<Callback> {
+        self.contexts
+            .iter()
+            .rev()
+            .filter_map(|name| {
+                self.scoped(&Scope::Context(name.clone()), event)
+            })
+            .next()
+    }
+
+    // Returns the callback for `event` in exactly this scope.
+    fn scoped(&self, scope: &Scope, event: &Event) -> Option<Callback> {
+        self.find(scope, event).map(|&(_, ref cb)| cb.clone())
+    }
+
+    fn find(&self, scope: &Scope, event: &Event)
+            -> Option<&(Shortcut, Callback)> {
+        self.entries.iter().find(|&&(ref shortcut, _)| {
+            shortcut.scope == *scope && shortcut.event == *event
+        })
+    }
+
+    /// Lists the shortcuts as Markdown tables, one per category.
+    ///
+    /// This can be used to keep the documentation of an application up to
+    /// date.
+    pub fn to_markdown(&self) -> String {
+        let mut categories: Vec<&str> = Vec::new();
+        for &(ref shortcut, _) in &self.entries {
+            if !categories.contains(&&shortcut.category[..]) {
+                categories.push(&shortcut.category);
+            }
+        }
+
+        let mut result = String::new();
+        for category in categories {
+            if !result.is_empty() {
+                result.push('\n');
+            }
+            result.push_str(&format!("## {}\n\n", category));
+            result.push_str("| Key | Description |\n|---|---|\n");
+            for &(ref shortcut, _) in &self.entries {
+                if shortcut.category == category {
+                    result.push_str(&format!("| {} | {} |\n",
+                                             shortcut.event,
+                                             shortcut.description));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Registry, Scope};
+    use event::Event;
+
+    #[test]
+    fn conflicts() {
+        let mut registry = Registry::new();
+        registry.register(Scope::Global, 'q', "General", "Quit", |_| ())
+            .unwrap();
+        registry.register(Scope::Screen(1), 'q', "Mail", "Close", |_| ())
+            .unwrap();
+
+        let conflict = registry.register(Scope::Global, 'q', "", "", |_| ())
+            .unwrap_err();
+        assert_eq!(conflict.existing.unwrap().description, "Quit");
+
+        assert!(registry.unregister(&Scope::Global, &Event::Char('q')));
+        assert!(registry.register(Scope::Global, 'q', "", "", |_| ()).is_ok());
+    }
+
+    #[test]
+    fn scopes() {
+        let mut registry = Registry::new();
+        let q = Event::Char('q');
+        registry.register(Scope::Global, 'q', "General", "Quit", |_| ())
+            .unwrap();
+        registry.register(Scope::context("a"), 'q', "General", "Back", |_| ())
+            .unwrap();
+        registry.register(Scope::context("b"), 'w', "Edit", "Wrap", |_| ())
+            .unwrap();
+
+        assert!(registry.callback(0, &q).is_some());
+        assert!(registry.callback(0, &Event::Char('w')).is_none());
+
+        registry.push_context("a");
+        registry.push_context("b");
+        assert!(registry.callback(0, &Event::Char('w')).is_some());
+        assert_eq!(registry.contexts(), ["a", "b"]);
+
+        assert_eq!(registry.to_markdown(),
+                   "## General\n\n\
+                    | Key | Description |\n|---|---|\n\
+                    | q | Quit |\n| q | Back |\n\n\
+                    ## Edit\n\n\
+                    | Key | Description |\n|---|---|\n\
+                    | w | Wrap |\n");
+    }
+}
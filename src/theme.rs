@@ -29,6 +29,15 @@
 //! * **`highlight_inactive`**: used to highlight selected but inactive items.
 //!   Defaults to **blue**.
 //!
+//! Applications can also define their own colors in the `custom` table, and
+//! use them with [`ColorStyle::custom`].
+//!
+//! The palette can also be changed for part of the view tree only, with a
+//! [`ThemedView`].
+//!
+//! [`ThemedView`]: ../views/struct.ThemedView.html
+//! [`ColorStyle::custom`]: enum.ColorStyle.html#method.custom
+//!
 //! # Color Styles
 //!
 //! Each cell of the terminal uses two colors: *foreground* and *background*.
@@ -89,6 +98,13 @@
 //! # First come some various options
 //! shadow = false  # Don't draw shadows around stacked views
 //! borders = "simple"  # Alternatives are "none" and "outset"
+//! # Characters for borders and scrollbars: "unicode", "ascii" (`+-|`), or
+//! # "auto" to use ascii unless the locale is UTF-8
+//! glyphs = "unicode"
+//! # Show selected items reversed and underlined, not only colored
+//! highlight_effects = false
+//! # How to replace colors the terminal can't show
+//! approximation = "perceptual"  # Alternative is "nearest"
 //!
 //! # Here we define the color palette.
 //! [colors]
@@ -109,13 +125,67 @@
 //! 	title_primary   = "#ff5555"
 //! 	title_secondary = "#ffff55"
 //!
+//! 	# "default" keeps the terminal's own color (and transparency).
+//! 	# background = "default"
+//!
 //! 	# Lower precision values can use only 3 digits.
 //! 	highlight          = "#F00"
 //! 	highlight_inactive = "#5555FF"
+//!
+//! # Colors for the application's own views.
+//! [colors.custom]
+//! 	warning = ["light yellow", "yellow"]
+//!
+//! # How scrollbars are drawn.
+//! [scrollbar]
+//! 	track = "│"
+//! 	thumb = "█"
+//! 	# Without these, the track uses `primary`, and the thumb `highlight`.
+//! 	track_color = "light black"
+//! 	thumb_color = "blue"
+//!
+//! # How layers below a modal one are dimmed.
+//! [dim]
+//! 	# From 0 (not dimmed, the default) to 1 (only `color` is left).
+//! 	amount = 0.5
+//! 	# Without it, colors fade toward the `background` color.
+//! 	color = "black"
+//!
+//! # Border characters can also be replaced one by one.
+//! # [glyphs]
+//! # 	top_left = "╭"
+//! ```
+//!
+//! # Serde
+//!
+//! With the `serde` feature, themes can also be serialized and
+//! deserialized with any `serde` format, to embed them in a larger
+//! configuration file. They use the same names as the toml format above,
+//! and missing fields keep their default value, but palette entries must
+//! be a single color.
+//!
+//! # Builder
+//!
+//! Themes can also be built from code with a [`ThemeBuilder`]:
+//!
 //! ```
+//! # use cursive::theme::{BaseColor, BorderStyle, Color, ThemeBuilder};
+//! let theme = ThemeBuilder::new()
+//!     .shadow(false)
+//!     .borders(Some(BorderStyle::Outset))
+//!     .background(Color::Dark(BaseColor::Black))
+//!     .view(Color::Rgb(0xd3, 0xd7, 0xcf))
+//!     .build();
+//! ```
+//!
+//! [`ThemeBuilder`]: struct.ThemeBuilder.html
 
 
+use With;
 use backend::{self, Backend};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
 use std::fs::File;
 use std::io;
 use std::io::Read;
@@ -123,14 +193,27 @@ use std::path::Path;
 
 use toml;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use serde::de::Error as DeError;
+
 /// Text effect
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Effect {
     /// No effect
     Simple,
     /// Reverses foreground and background colors
     Reverse, 
-    // TODO: bold, italic, underline
+    /// Prints in bold, or brighter on some terminals
+    Bold,
+    /// Underlines the text
+    Underline,
+    /// Prints in a fainter color, on terminals supporting it
+    Dim,
+    /// Hides the text, still taking space, on terminals supporting it
+    Invisible,
+    // TODO: italic
 }
 
 /// Possible color style for a cell.
@@ -138,7 +221,7 @@ pub enum Effect {
 /// Represents a color pair role to use when printing something.
 ///
 /// The current theme will assign each role a foreground and background color.
-#[derive(Clone,Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ColorStyle {
     /// Application background, where no view is present.
     Background,
@@ -158,10 +241,26 @@ pub enum ColorStyle {
     Highlight,
     /// Highlight color for inactive views (not in focus).
     HighlightInactive,
+    /// Arbitrary colors, not taken from the theme.
+    ///
+    /// Backends allocate a color pair the first time it is used.
+    Custom {
+        /// Foreground (text) color.
+        front: Color,
+        /// Background color.
+        back: Color,
+    },
+    /// Named entry from the `custom` table of the palette, as text color
+    /// with default background.
+    ///
+    /// If the palette has no such entry, `Primary` is used instead.
+    Named(&'static str),
 }
 
 impl ColorStyle {
     /// Returns the ncurses pair ID associated with this color pair.
+    ///
+    /// Custom styles don't have a fixed ID, and return `0`.
     pub fn id(self) -> i16 {
         match self {
             ColorStyle::Background => 1,
@@ -173,19 +272,155 @@ impl ColorStyle {
             ColorStyle::TitleSecondary => 7,
             ColorStyle::Highlight => 8,
             ColorStyle::HighlightInactive => 9,
+            ColorStyle::Custom { .. } |
+            ColorStyle::Named(_) => 0,
+        }
+    }
+
+    /// Returns the style for the given entry of the palette's `custom`
+    /// table.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Printer;
+    /// # use cursive::theme::{self, ColorStyle};
+    /// # use cursive::backend::{self, Backend};
+    /// # let b = backend::Concrete::init();
+    /// # let printer = Printer::new((6,4), theme::load_default(), &b);
+    /// // With `warning = "yellow"` in the theme's `[colors.custom]` table
+    /// printer.with_color(ColorStyle::custom("warning"), |printer| {
+    ///     printer.print((0, 0), "Careful!");
+    /// });
+    /// ```
+    pub fn custom(name: &'static str) -> Self {
+        ColorStyle::Named(name)
+    }
+
+    /// Replaces a `Named` style with the actual colors from `palette`.
+    ///
+    /// Other styles are returned unchanged.
+    pub fn resolve(self, palette: &Palette) -> Self {
+        match self {
+            ColorStyle::Named(name) => {
+                match palette.custom.get(name) {
+                    Some(&color) => {
+                        ColorStyle::Custom {
+                            front: color,
+                            back: palette.view,
+                        }
+                    }
+                    None => ColorStyle::Primary,
+                }
+            }
+            style => style,
+        }
+    }
+
+    /// Returns the foreground and background colors for this style.
+    ///
+    /// Roles like `Primary` or `Highlight` take their colors from
+    /// `palette`.
+    pub fn colors(self, palette: &Palette) -> (Color, Color) {
+        match self.resolve(palette) {
+            ColorStyle::Background => (palette.view, palette.background),
+            ColorStyle::Shadow => (palette.shadow, palette.shadow),
+            ColorStyle::Primary |
+            ColorStyle::Named(_) => (palette.primary, palette.view),
+            ColorStyle::Secondary => (palette.secondary, palette.view),
+            ColorStyle::Tertiary => (palette.tertiary, palette.view),
+            ColorStyle::TitlePrimary => (palette.title_primary, palette.view),
+            ColorStyle::TitleSecondary => {
+                (palette.title_secondary, palette.view)
+            }
+            ColorStyle::Highlight => (palette.view, palette.highlight),
+            ColorStyle::HighlightInactive => {
+                (palette.view, palette.highlight_inactive)
+            }
+            ColorStyle::Custom { front, back } => (front, back),
+        }
+    }
+
+    // All styles taking their colors from the palette.
+    fn roles() -> [ColorStyle; 9] {
+        [ColorStyle::Background,
+         ColorStyle::Shadow,
+         ColorStyle::Primary,
+         ColorStyle::Secondary,
+         ColorStyle::Tertiary,
+         ColorStyle::TitlePrimary,
+         ColorStyle::TitleSecondary,
+         ColorStyle::Highlight,
+         ColorStyle::HighlightInactive]
+    }
+}
+
+/// Combination of a color style and an effect.
+///
+/// Used to print styled parts of a text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Style {
+    /// Color style to use, if any.
+    ///
+    /// If `None`, the current color is kept.
+    pub color: Option<ColorStyle>,
+    /// Effect to apply.
+    pub effect: Effect,
+}
+
+impl Style {
+    /// Returns a style that doesn't change anything.
+    pub fn none() -> Self {
+        Style {
+            color: None,
+            effect: Effect::Simple,
+        }
+    }
+}
+
+impl From<ColorStyle> for Style {
+    fn from(color: ColorStyle) -> Self {
+        Style {
+            color: Some(color),
+            effect: Effect::Simple,
+        }
+    }
+}
+
+impl From<Effect> for Style {
+    fn from(effect: Effect) -> Self {
+        Style {
+            color: None,
+            effect: effect,
         }
     }
 }
 
 /// Represents the style a Cursive application will use.
 #[derive(Clone,Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Theme {
     /// Whether views in a StackView should have shadows.
     pub shadow: bool,
     /// How view borders should be drawn.
+    #[cfg_attr(feature = "serde", serde(with = "borders"))]
     pub borders: Option<BorderStyle>,
     /// What colors should be used through the application?
     pub colors: Palette,
+    /// How to replace colors the terminal cannot show.
+    pub approximation: Approximation,
+    /// How scrollbars should be drawn.
+    pub scrollbar: ScrollbarStyle,
+    /// Characters used to draw borders and delimiters.
+    pub glyphs: Borders,
+    /// Whether highlighted items are shown with effects instead of colors.
+    ///
+    /// `Highlight` then reverses the primary text, and `HighlightInactive`
+    /// underlines it, so they can be told apart without colors.
+    pub highlight_effects: bool,
+    /// How layers below a modal one are dimmed.
+    pub dim: DimStyle,
 }
 
 impl Default for Theme {
@@ -193,23 +428,101 @@ impl Default for Theme {
         Theme {
             shadow: true,
             borders: Some(BorderStyle::Simple),
-            colors: Palette {
-                background: Color::Dark(BaseColor::Blue),
-                shadow: Color::Dark(BaseColor::Black),
-                view: Color::Dark(BaseColor::White),
-                primary: Color::Dark(BaseColor::Black),
-                secondary: Color::Dark(BaseColor::Blue),
-                tertiary: Color::Light(BaseColor::White),
-                title_primary: Color::Dark(BaseColor::Red),
-                title_secondary: Color::Dark(BaseColor::Yellow),
-                highlight: Color::Dark(BaseColor::Red),
-                highlight_inactive: Color::Dark(BaseColor::Blue),
-            },
+            colors: Palette::default(),
+            approximation: Approximation::default(),
+            scrollbar: ScrollbarStyle::default(),
+            glyphs: Borders::default(),
+            highlight_effects: false,
+            dim: DimStyle::default(),
         }
     }
 }
 
 impl Theme {
+    /// Returns a default theme suited to the given terminal background.
+    ///
+    /// The light theme is the usual default theme. The dark one uses dark
+    /// grey views on a black background.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use cursive::Cursive;
+    /// # use cursive::theme::{Brightness, Theme};
+    /// let mut siv = Cursive::new();
+    /// let background = siv.terminal_background()
+    ///     .unwrap_or(Brightness::Light);
+    /// siv.set_theme(Theme::default_for_background(background));
+    /// ```
+    pub fn default_for_background(background: Brightness) -> Self {
+        match background {
+            Brightness::Light => Theme::default(),
+            Brightness::Dark => {
+                ThemeBuilder::new()
+                    .background(Color::Dark(BaseColor::Black))
+                    .shadow_color(Color::Dark(BaseColor::Black))
+                    .view(Color::Light(BaseColor::Black))
+                    .primary(Color::Light(BaseColor::White))
+                    .secondary(Color::Light(BaseColor::Cyan))
+                    .tertiary(Color::Dark(BaseColor::White))
+                    .title_primary(Color::Light(BaseColor::Red))
+                    .title_secondary(Color::Light(BaseColor::Yellow))
+                    .highlight(Color::Dark(BaseColor::Cyan))
+                    .highlight_inactive(Color::Dark(BaseColor::Blue))
+                    .build()
+            }
+        }
+    }
+
+    /// Returns a theme using only the terminal's default colors.
+    ///
+    /// Highlighted items are shown with effects, so this works on
+    /// monochrome terminals.
+    pub fn monochrome() -> Self {
+        let default = Color::TerminalDefault;
+        ThemeBuilder::new()
+            .shadow(false)
+            .highlight_effects(true)
+            .colors(Palette {
+                        background: default,
+                        shadow: default,
+                        view: default,
+                        primary: default,
+                        secondary: default,
+                        tertiary: default,
+                        title_primary: default,
+                        title_secondary: default,
+                        highlight: default,
+                        highlight_inactive: default,
+                        custom: BTreeMap::new(),
+                    })
+            .build()
+    }
+
+    /// Returns a high-contrast theme: bright text on black.
+    ///
+    /// Highlighted items are shown with effects, so they don't rely on
+    /// colors alone.
+    pub fn high_contrast() -> Self {
+        let black = Color::Dark(BaseColor::Black);
+        let white = Color::Light(BaseColor::White);
+        let yellow = Color::Light(BaseColor::Yellow);
+        ThemeBuilder::new()
+            .shadow(false)
+            .highlight_effects(true)
+            .background(black)
+            .shadow_color(black)
+            .view(black)
+            .primary(white)
+            .secondary(yellow)
+            .tertiary(white)
+            .title_primary(yellow)
+            .title_secondary(white)
+            .highlight(white)
+            .highlight_inactive(white)
+            .build()
+    }
+
     fn load(&mut self, table: &toml::value::Table) {
         if let Some(&toml::Value::Boolean(shadow)) = table.get("shadow") {
             self.shadow = shadow;
@@ -219,9 +532,64 @@ impl Theme {
             self.borders = BorderStyle::from(borders);
         }
 
+        if let Some(&toml::Value::Boolean(enabled)) =
+            table.get("highlight_effects") {
+            self.highlight_effects = enabled;
+        }
+
         if let Some(&toml::Value::Table(ref table)) = table.get("colors") {
             self.colors.load(table);
         }
+
+        if let Some(&toml::Value::String(ref approximation)) =
+            table.get("approximation") {
+            if let Some(approximation) = Approximation::from(approximation) {
+                self.approximation = approximation;
+            }
+        }
+
+        // Presets come first, so the scrollbar table can still override
+        // them.
+        match table.get("glyphs") {
+            Some(&toml::Value::String(ref preset)) => {
+                let ascii = match &preset[..] {
+                    "ascii" => Some(true),
+                    "unicode" => Some(false),
+                    "auto" => Some(!Borders::supports_unicode()),
+                    _ => None,
+                };
+                if let Some(ascii) = ascii {
+                    self.set_ascii(ascii);
+                }
+            }
+            Some(&toml::Value::Table(ref table)) => self.glyphs.load(table),
+            _ => (),
+        }
+
+        if let Some(&toml::Value::Table(ref table)) = table.get("scrollbar") {
+            self.scrollbar.load(table);
+        }
+
+        if let Some(&toml::Value::Table(ref table)) = table.get("dim") {
+            self.dim.load(table);
+        }
+    }
+
+    /// Uses ascii characters for borders and scrollbars, or unicode ones
+    /// if `ascii` is `false`.
+    ///
+    /// Scrollbar colors are kept.
+    pub fn set_ascii(&mut self, ascii: bool) {
+        if ascii {
+            self.glyphs = Borders::ascii();
+            self.scrollbar.track = "|".to_string();
+            self.scrollbar.thumb = "#".to_string();
+        } else {
+            let default = ScrollbarStyle::default();
+            self.glyphs = Borders::unicode();
+            self.scrollbar.track = default.track;
+            self.scrollbar.thumb = default.thumb;
+        }
     }
 
     /// Sets a theme as active.
@@ -230,38 +598,153 @@ impl Theme {
     ///
     /// [`Cursive::set_theme`]: ../struct.Cursive.html#method.set_theme
     pub fn activate(&self, backend: &mut backend::Concrete) {
+        let colors = self.colors
+            .approximate(backend.color_depth(), self.approximation);
+
         // Initialize each color with the backend
-        backend.init_color_style(ColorStyle::Background,
-                                 &self.colors.view,
-                                 &self.colors.background);
-        backend.init_color_style(ColorStyle::Shadow,
-                                 &self.colors.shadow,
-                                 &self.colors.shadow);
-        backend.init_color_style(ColorStyle::Primary,
-                                 &self.colors.primary,
-                                 &self.colors.view);
-        backend.init_color_style(ColorStyle::Secondary,
-                                 &self.colors.secondary,
-                                 &self.colors.view);
-        backend.init_color_style(ColorStyle::Tertiary,
-                                 &self.colors.tertiary,
-                                 &self.colors.view);
-        backend.init_color_style(ColorStyle::TitlePrimary,
-                                 &self.colors.title_primary,
-                                 &self.colors.view);
-        backend.init_color_style(ColorStyle::TitleSecondary,
-                                 &self.colors.title_secondary,
-                                 &self.colors.view);
-        backend.init_color_style(ColorStyle::Highlight,
-                                 &self.colors.view,
-                                 &self.colors.highlight);
-        backend.init_color_style(ColorStyle::HighlightInactive,
-                                 &self.colors.view,
-                                 &self.colors.highlight_inactive);
+        for &style in &ColorStyle::roles() {
+            let (front, back) = style.colors(&colors);
+            backend.init_color_style(style, &front, &back);
+        }
         backend.clear();
     }
 }
 
+/// Builds a [`Theme`] from code.
+///
+/// Starts from the default theme, or from the given one.
+///
+/// [`Theme`]: struct.Theme.html
+#[derive(Clone, Debug, Default)]
+pub struct ThemeBuilder {
+    theme: Theme,
+}
+
+impl ThemeBuilder {
+    /// Creates a new builder, starting from the default theme.
+    pub fn new() -> Self {
+        ThemeBuilder::default()
+    }
+
+    /// Sets whether views in a StackView should have shadows.
+    pub fn shadow(self, shadow: bool) -> Self {
+        self.with(|s| s.theme.shadow = shadow)
+    }
+
+    /// Sets how view borders should be drawn.
+    pub fn borders(self, borders: Option<BorderStyle>) -> Self {
+        self.with(|s| s.theme.borders = borders)
+    }
+
+    /// Sets how to replace colors the terminal cannot show.
+    pub fn approximation(self, approximation: Approximation) -> Self {
+        self.with(|s| s.theme.approximation = approximation)
+    }
+
+    /// Sets how scrollbars should be drawn.
+    pub fn scrollbar(self, scrollbar: ScrollbarStyle) -> Self {
+        self.with(|s| s.theme.scrollbar = scrollbar)
+    }
+
+    /// Sets the characters used to draw borders.
+    pub fn glyphs(self, glyphs: Borders) -> Self {
+        self.with(|s| s.theme.glyphs = glyphs)
+    }
+
+    /// Uses ascii characters for borders and scrollbars.
+    ///
+    /// See `Theme::set_ascii`.
+    pub fn ascii(self, ascii: bool) -> Self {
+        self.with(|s| s.theme.set_ascii(ascii))
+    }
+
+    /// Sets whether highlighted items are shown with effects.
+    ///
+    /// See `Theme::highlight_effects`.
+    pub fn highlight_effects(self, enabled: bool) -> Self {
+        self.with(|s| s.theme.highlight_effects = enabled)
+    }
+
+    /// Sets how layers below a modal one are dimmed.
+    pub fn dim(self, dim: DimStyle) -> Self {
+        self.with(|s| s.theme.dim = dim)
+    }
+
+    /// Replaces the whole palette.
+    pub fn colors(self, colors: Palette) -> Self {
+        self.with(|s| s.theme.colors = colors)
+    }
+
+    /// Sets the color used for the application background.
+    pub fn background(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.background = color)
+    }
+
+    /// Sets the color used for view shadows.
+    pub fn shadow_color(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.shadow = color)
+    }
+
+    /// Sets the color used for view backgrounds.
+    pub fn view(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.view = color)
+    }
+
+    /// Sets the color used for primary text.
+    pub fn primary(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.primary = color)
+    }
+
+    /// Sets the color used for secondary text.
+    pub fn secondary(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.secondary = color)
+    }
+
+    /// Sets the color used for tertiary text.
+    pub fn tertiary(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.tertiary = color)
+    }
+
+    /// Sets the color used for primary titles.
+    pub fn title_primary(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.title_primary = color)
+    }
+
+    /// Sets the color used for secondary titles.
+    pub fn title_secondary(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.title_secondary = color)
+    }
+
+    /// Sets the color used for highlighted items.
+    pub fn highlight(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.highlight = color)
+    }
+
+    /// Sets the color used for highlighted but inactive items.
+    pub fn highlight_inactive(self, color: Color) -> Self {
+        self.with(|s| s.theme.colors.highlight_inactive = color)
+    }
+
+    /// Sets a color of the palette's `custom` table.
+    pub fn custom_color<S: Into<String>>(self, name: S, color: Color)
+                                         -> Self {
+        self.with(|s| {
+            s.theme.colors.custom.insert(name.into(), color);
+        })
+    }
+
+    /// Returns the theme.
+    pub fn build(self) -> Theme {
+        self.theme
+    }
+}
+
+impl From<Theme> for ThemeBuilder {
+    fn from(theme: Theme) -> Self {
+        ThemeBuilder { theme: theme }
+    }
+}
+
 /// Specifies how some borders should be drawn.
 ///
 /// Borders are used around Dialogs, select popups, and panels.
@@ -283,12 +766,379 @@ impl BorderStyle {
             None
         }
     }
+
+    #[cfg(feature = "serde")]
+    fn name(&self) -> &'static str {
+        match *self {
+            BorderStyle::Simple => "simple",
+            BorderStyle::Outset => "outset",
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for BorderStyle {
+    fn serialize<S: Serializer>(&self, serializer: S)
+                                -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for BorderStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D)
+                                         -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        BorderStyle::from(&name).ok_or_else(|| {
+            D::Error::custom(format!("invalid border style: {}", name))
+        })
+    }
+}
+
+// Theme borders are written as `"none"` when missing, like in toml files.
+#[cfg(feature = "serde")]
+mod borders {
+    use super::BorderStyle;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer>(borders: &Option<BorderStyle>,
+                                    serializer: S)
+                                    -> Result<S::Ok, S::Error> {
+        let name = borders.as_ref().map_or("none", BorderStyle::name);
+        serializer.serialize_str(name)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>
+        (deserializer: D)
+         -> Result<Option<BorderStyle>, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        match BorderStyle::from(&name) {
+            Some(borders) => Ok(Some(borders)),
+            None if name == "none" => Ok(None),
+            None => {
+                Err(D::Error::custom(format!("invalid border style: {}",
+                                             name)))
+            }
+        }
+    }
+}
+
+/// Glyphs and colors used to draw scrollbars.
+///
+/// Colors are optional: by default, the track uses the `primary` color,
+/// and the thumb is highlighted like a selected item.
+#[derive(Clone,Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollbarStyle {
+    /// Text repeated along the scrollbar.
+    pub track: String,
+    /// Text repeated along the thumb, showing the visible part.
+    pub thumb: String,
+    /// Foreground color of the track.
+    pub track_color: Option<Color>,
+    /// Foreground color of the thumb.
+    pub thumb_color: Option<Color>,
+}
+
+impl Default for ScrollbarStyle {
+    fn default() -> Self {
+        ScrollbarStyle {
+            track: "|".to_string(),
+            thumb: "▒".to_string(),
+            track_color: None,
+            thumb_color: None,
+        }
+    }
+}
+
+impl ScrollbarStyle {
+    fn load(&mut self, table: &toml::value::Table) {
+        if let Some(&toml::Value::String(ref track)) = table.get("track") {
+            self.track = track.clone();
+        }
+
+        if let Some(&toml::Value::String(ref thumb)) = table.get("thumb") {
+            self.thumb = thumb.clone();
+        }
+
+        load_optional_color(&mut self.track_color, table.get("track_color"));
+        load_optional_color(&mut self.thumb_color, table.get("thumb_color"));
+    }
+}
+
+/// How layers below a modal one are dimmed.
+///
+/// Colors of dimmed layers move toward `color`, so the modal layer stands
+/// out. Colors like `TerminalDefault` can't be mixed and are kept.
+///
+/// By default, layers are not dimmed.
+#[derive(Clone,Debug,PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DimStyle {
+    /// How far colors move toward `color`, from `0` (unchanged) to `1`.
+    pub amount: f32,
+    /// Color dimmed layers fade to. Uses the palette's `background` if
+    /// `None`.
+    pub color: Option<Color>,
+}
+
+impl Default for DimStyle {
+    fn default() -> Self {
+        DimStyle {
+            amount: 0.0,
+            color: None,
+        }
+    }
+}
+
+impl DimStyle {
+    /// Returns a style fading colors by `amount` toward the background.
+    pub fn new(amount: f32) -> Self {
+        DimStyle {
+            amount: amount,
+            color: None,
+        }
+    }
+
+    /// Returns `true` if this style changes colors.
+    pub fn is_visible(&self) -> bool {
+        self.amount > 0.0
+    }
+
+    fn load(&mut self, table: &toml::value::Table) {
+        match table.get("amount") {
+            Some(&toml::Value::Float(amount)) => self.amount = amount as f32,
+            Some(&toml::Value::Integer(amount)) => {
+                self.amount = amount as f32
+            }
+            _ => (),
+        }
+
+        load_optional_color(&mut self.color, table.get("color"));
+    }
+}
+
+/// Characters used to draw borders and delimiters.
+///
+/// The default set uses unicode box-drawing characters. Terminals or fonts
+/// without them can use `Borders::ascii()` instead, which draws with `+`,
+/// `-` and `|`.
+#[derive(Clone,Debug,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Borders {
+    /// Top-left corner, like `┌`.
+    pub top_left: String,
+    /// Top-right corner, like `┐`.
+    pub top_right: String,
+    /// Bottom-left corner, like `└`.
+    pub bottom_left: String,
+    /// Bottom-right corner, like `┘`.
+    pub bottom_right: String,
+    /// Horizontal line, like `─`.
+    pub horizontal: String,
+    /// Vertical line, like `│`.
+    pub vertical: String,
+    /// Horizontal line meeting a vertical one on its left, like `├`.
+    pub left_junction: String,
+    /// Horizontal line meeting a vertical one on its right, like `┤`.
+    pub right_junction: String,
+    /// Vertical line meeting a horizontal one at its top, like `┬`.
+    pub top_junction: String,
+    /// Vertical line meeting a horizontal one at its bottom, like `┴`.
+    pub bottom_junction: String,
+    /// Crossing lines, like `┼`.
+    pub cross: String,
+}
+
+impl Default for Borders {
+    fn default() -> Self {
+        Borders::unicode()
+    }
+}
+
+impl Borders {
+    /// Returns the unicode box-drawing set.
+    pub fn unicode() -> Self {
+        Borders::from_chars(["┌", "┐", "└", "┘", "─", "│", "├", "┤", "┬", "┴",
+                             "┼"])
+    }
+
+    /// Returns a set using only ascii characters.
+    pub fn ascii() -> Self {
+        Borders::from_chars(["+", "+", "+", "+", "-", "|", "+", "+", "+", "+",
+                             "+"])
+    }
+
+    /// Returns the unicode set if the locale is UTF-8, and the ascii set
+    /// otherwise.
+    pub fn detect() -> Self {
+        if Borders::supports_unicode() {
+            Borders::unicode()
+        } else {
+            Borders::ascii()
+        }
+    }
+
+    // Looks at the locale, like `LANG=en_US.UTF-8`.
+    fn supports_unicode() -> bool {
+        // The first variable set wins, like for the C library.
+        ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(false, |locale| {
+                let locale = locale.to_lowercase();
+                locale.contains("utf-8") || locale.contains("utf8")
+            })
+    }
+
+    fn from_chars(chars: [&str; 11]) -> Self {
+        Borders {
+            top_left: chars[0].to_string(),
+            top_right: chars[1].to_string(),
+            bottom_left: chars[2].to_string(),
+            bottom_right: chars[3].to_string(),
+            horizontal: chars[4].to_string(),
+            vertical: chars[5].to_string(),
+            left_junction: chars[6].to_string(),
+            right_junction: chars[7].to_string(),
+            top_junction: chars[8].to_string(),
+            bottom_junction: chars[9].to_string(),
+            cross: chars[10].to_string(),
+        }
+    }
+
+    fn load(&mut self, table: &toml::value::Table) {
+        let fields = [("top_left", &mut self.top_left),
+                      ("top_right", &mut self.top_right),
+                      ("bottom_left", &mut self.bottom_left),
+                      ("bottom_right", &mut self.bottom_right),
+                      ("horizontal", &mut self.horizontal),
+                      ("vertical", &mut self.vertical),
+                      ("left_junction", &mut self.left_junction),
+                      ("right_junction", &mut self.right_junction),
+                      ("top_junction", &mut self.top_junction),
+                      ("bottom_junction", &mut self.bottom_junction),
+                      ("cross", &mut self.cross)];
+        for (name, field) in fields {
+            if let Some(&toml::Value::String(ref glyph)) = table.get(name) {
+                *field = glyph.clone();
+            }
+        }
+    }
+}
+
+/// Glyphs of a frame drawn around a single view.
+///
+/// Unlike [`Borders`], each edge and corner has its own glyph, and the
+/// title of a dialog can be decorated. Each glyph should take a single
+/// cell.
+///
+/// Views like `Panel` and `Dialog` use the theme's borders, unless they
+/// are given a frame.
+///
+/// [`Borders`]: struct.Borders.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::theme::Frame;
+/// // A BBS-style frame.
+/// let mut frame = Frame::new("*", "=", ":");
+/// frame.bottom = "-".to_string();
+/// frame.title_left = "[ ".to_string();
+/// frame.title_right = " ]".to_string();
+/// ```
+#[derive(Clone,Debug,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Frame {
+    /// Top-left corner.
+    pub top_left: String,
+    /// Top-right corner.
+    pub top_right: String,
+    /// Bottom-left corner.
+    pub bottom_left: String,
+    /// Bottom-right corner.
+    pub bottom_right: String,
+    /// Top edge.
+    pub top: String,
+    /// Bottom edge.
+    pub bottom: String,
+    /// Left edge.
+    pub left: String,
+    /// Right edge.
+    pub right: String,
+    /// Printed before a title, like `┤ `.
+    pub title_left: String,
+    /// Printed after a title, like ` ├`.
+    pub title_right: String,
+}
+
+impl Frame {
+    /// Creates a frame using the same glyph for all corners, horizontal
+    /// edges and vertical edges.
+    ///
+    /// Titles are surrounded by spaces.
+    pub fn new(corner: &str, horizontal: &str, vertical: &str) -> Self {
+        Frame {
+            top_left: corner.to_string(),
+            top_right: corner.to_string(),
+            bottom_left: corner.to_string(),
+            bottom_right: corner.to_string(),
+            top: horizontal.to_string(),
+            bottom: horizontal.to_string(),
+            left: vertical.to_string(),
+            right: vertical.to_string(),
+            title_left: " ".to_string(),
+            title_right: " ".to_string(),
+        }
+    }
+
+    /// Creates a frame from a set of borders.
+    ///
+    /// Titles are set between junctions, like `┤ Title ├`.
+    pub fn from_borders(borders: &Borders) -> Self {
+        Frame {
+            top_left: borders.top_left.clone(),
+            top_right: borders.top_right.clone(),
+            bottom_left: borders.bottom_left.clone(),
+            bottom_right: borders.bottom_right.clone(),
+            top: borders.horizontal.clone(),
+            bottom: borders.horizontal.clone(),
+            left: borders.vertical.clone(),
+            right: borders.vertical.clone(),
+            title_left: format!("{} ", borders.right_junction),
+            title_right: format!(" {}", borders.left_junction),
+        }
+    }
+
+    /// Returns a frame drawn with double lines, like `╔═╗`.
+    pub fn double() -> Self {
+        Frame {
+            top_left: "╔".to_string(),
+            top_right: "╗".to_string(),
+            bottom_left: "╚".to_string(),
+            bottom_right: "╝".to_string(),
+            top: "═".to_string(),
+            bottom: "═".to_string(),
+            left: "║".to_string(),
+            right: "║".to_string(),
+            title_left: "╡ ".to_string(),
+            title_right: " ╞".to_string(),
+        }
+    }
 }
 
 /// Color configuration for the application.
 ///
 /// Assign each color role an actual color.
 #[derive(Clone,Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Palette {
     /// Color used for the application background.
     pub background: Color,
@@ -310,9 +1160,90 @@ pub struct Palette {
     pub highlight: Color,
     /// Color used for highlighting inactive text.
     pub highlight_inactive: Color,
+    /// Colors defined by the application, used with
+    /// [`ColorStyle::custom`].
+    ///
+    /// [`ColorStyle::custom`]: enum.ColorStyle.html#method.custom
+    pub custom: BTreeMap<String, Color>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            background: Color::Dark(BaseColor::Blue),
+            shadow: Color::Dark(BaseColor::Black),
+            view: Color::Dark(BaseColor::White),
+            primary: Color::Dark(BaseColor::Black),
+            secondary: Color::Dark(BaseColor::Blue),
+            tertiary: Color::Light(BaseColor::White),
+            title_primary: Color::Dark(BaseColor::Red),
+            title_secondary: Color::Dark(BaseColor::Yellow),
+            highlight: Color::Dark(BaseColor::Red),
+            highlight_inactive: Color::Dark(BaseColor::Blue),
+            custom: BTreeMap::new(),
+        }
+    }
 }
 
 impl Palette {
+    /// Returns a copy of this palette, with colors the terminal cannot show
+    /// replaced with the closest ones it can.
+    pub fn approximate(&self, depth: ColorDepth, approximation: Approximation)
+                       -> Self {
+        let approx = |color: Color| color.approximate(depth, approximation);
+        Palette {
+            background: approx(self.background),
+            shadow: approx(self.shadow),
+            view: approx(self.view),
+            primary: approx(self.primary),
+            secondary: approx(self.secondary),
+            tertiary: approx(self.tertiary),
+            title_primary: approx(self.title_primary),
+            title_secondary: approx(self.title_secondary),
+            highlight: approx(self.highlight),
+            highlight_inactive: approx(self.highlight_inactive),
+            custom: self.custom
+                .iter()
+                .map(|(name, &color)| (name.clone(), approx(color)))
+                .collect(),
+        }
+    }
+
+    /// Returns a copy of this palette, dimmed with the given style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::theme::{BaseColor, Color, DimStyle, Palette};
+    /// let mut palette = Palette::default();
+    /// palette.view = Color::Rgb(200, 200, 200);
+    /// palette.background = Color::Rgb(0, 0, 100);
+    ///
+    /// let dimmed = palette.dimmed(&DimStyle::new(0.5));
+    /// assert_eq!(dimmed.view, Color::Rgb(100, 100, 150));
+    /// ```
+    pub fn dimmed(&self, style: &DimStyle) -> Self {
+        let target = style.color.unwrap_or(self.background);
+        let amount = style.amount.max(0.0).min(1.0);
+        let dim = |color: Color| color.mix(target, amount);
+        Palette {
+            background: dim(self.background),
+            shadow: dim(self.shadow),
+            view: dim(self.view),
+            primary: dim(self.primary),
+            secondary: dim(self.secondary),
+            tertiary: dim(self.tertiary),
+            title_primary: dim(self.title_primary),
+            title_secondary: dim(self.title_secondary),
+            highlight: dim(self.highlight),
+            highlight_inactive: dim(self.highlight_inactive),
+            custom: self.custom
+                .iter()
+                .map(|(name, &color)| (name.clone(), dim(color)))
+                .collect(),
+        }
+    }
+
     /// Fills `self` with the colors from the given `table`.
     fn load(&mut self, table: &toml::value::Table) {
         load_color(&mut self.background, table.get("background"));
@@ -326,6 +1257,15 @@ impl Palette {
         load_color(&mut self.highlight, table.get("highlight"));
         load_color(&mut self.highlight_inactive,
                    table.get("highlight_inactive"));
+
+        if let Some(&toml::Value::Table(ref table)) = table.get("custom") {
+            for (name, value) in table {
+                let mut color = Color::Dark(BaseColor::Black);
+                if load_color(&mut color, Some(value)) {
+                    self.custom.insert(name.clone(), color);
+                }
+            }
+        }
     }
 }
 
@@ -351,8 +1291,17 @@ fn load_color(target: &mut Color, value: Option<&toml::Value>) -> bool {
     }
 }
 
+/// Like `load_color`, for a color that may be missing.
+fn load_optional_color(target: &mut Option<Color>,
+                       value: Option<&toml::Value>) {
+    let mut color = Color::Dark(BaseColor::Black);
+    if load_color(&mut color, value) {
+        *target = Some(color);
+    }
+}
+
 /// One of the 8 base colors.
-#[derive(Clone,Copy,Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BaseColor {
     /// Black color
     ///
@@ -388,8 +1337,37 @@ pub enum BaseColor {
     White,
 }
 
+impl BaseColor {
+    // Returns the base color with the given number, modulo 8.
+    fn from(n: u8) -> Self {
+        match n % 8 {
+            0 => BaseColor::Black,
+            1 => BaseColor::Red,
+            2 => BaseColor::Green,
+            3 => BaseColor::Yellow,
+            4 => BaseColor::Blue,
+            5 => BaseColor::Magenta,
+            6 => BaseColor::Cyan,
+            _ => BaseColor::White,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            BaseColor::Black => "black",
+            BaseColor::Red => "red",
+            BaseColor::Green => "green",
+            BaseColor::Yellow => "yellow",
+            BaseColor::Blue => "blue",
+            BaseColor::Magenta => "magenta",
+            BaseColor::Cyan => "cyan",
+            BaseColor::White => "white",
+        }
+    }
+}
+
 /// Represents a color used by the theme.
-#[derive(Clone,Copy,Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Color {
     /// One of the 8 base colors.
     Dark(BaseColor),
@@ -403,9 +1381,239 @@ pub enum Color {
     ///
     /// These 216 possible colors are part of the default color palette.
     RgbLowRes(u8, u8, u8),
+    /// The terminal's own default color.
+    ///
+    /// As a background, this keeps the terminal background, including
+    /// any transparency.
+    TerminalDefault,
+}
+
+impl Color {
+    /// Returns the color with the given number in the usual 256-color
+    /// terminal palette.
+    ///
+    /// * `0` to `7` are the dark base colors.
+    /// * `8` to `15` are the light base colors.
+    /// * `16` to `231` are a `6x6x6` color cube.
+    /// * `232` to `255` are shades of grey.
+    pub fn from_256(n: u8) -> Self {
+        match n {
+            0...7 => Color::Dark(BaseColor::from(n)),
+            8...15 => Color::Light(BaseColor::from(n - 8)),
+            16...231 => {
+                let n = n - 16;
+                Color::RgbLowRes(n / 36, (n / 6) % 6, n % 6)
+            }
+            _ => {
+                let value = 8 + 10 * (n - 232);
+                Color::Rgb(value, value, value)
+            }
+        }
+    }
+
+    /// Returns the red, green and blue values of this color.
+    ///
+    /// Base colors use the usual VGA values. Returns `None` for
+    /// `TerminalDefault`.
+    pub fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        // Intensities of the 6x6x6 cube.
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        Some(match self {
+            Color::Dark(BaseColor::Black) => (0, 0, 0),
+            Color::Dark(BaseColor::Red) => (170, 0, 0),
+            Color::Dark(BaseColor::Green) => (0, 170, 0),
+            Color::Dark(BaseColor::Yellow) => (170, 85, 0),
+            Color::Dark(BaseColor::Blue) => (0, 0, 170),
+            Color::Dark(BaseColor::Magenta) => (170, 0, 170),
+            Color::Dark(BaseColor::Cyan) => (0, 170, 170),
+            Color::Dark(BaseColor::White) => (170, 170, 170),
+            Color::Light(BaseColor::Black) => (85, 85, 85),
+            Color::Light(BaseColor::Red) => (255, 85, 85),
+            Color::Light(BaseColor::Green) => (85, 255, 85),
+            Color::Light(BaseColor::Yellow) => (255, 255, 85),
+            Color::Light(BaseColor::Blue) => (85, 85, 255),
+            Color::Light(BaseColor::Magenta) => (255, 85, 255),
+            Color::Light(BaseColor::Cyan) => (85, 255, 255),
+            Color::Light(BaseColor::White) => (255, 255, 255),
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::RgbLowRes(r, g, b) => {
+                (CUBE[r as usize], CUBE[g as usize], CUBE[b as usize])
+            }
+            Color::TerminalDefault => return None,
+        })
+    }
+
+    /// Moves `self` toward `other`, from `0` (unchanged) to `1` (`other`).
+    ///
+    /// Colors without RGB values are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::theme::Color;
+    /// let black = Color::Rgb(0, 0, 0);
+    /// let white = Color::Rgb(255, 255, 255);
+    /// assert_eq!(black.mix(white, 0.5), Color::Rgb(128, 128, 128));
+    /// ```
+    pub fn mix(self, other: Color, amount: f32) -> Self {
+        if amount <= 0.0 {
+            return self;
+        }
+        let (from, to) = match (self.to_rgb(), other.to_rgb()) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return self,
+        };
+        let mix = |a: u8, b: u8| {
+            let (a, b) = (f32::from(a), f32::from(b));
+            (a + (b - a) * amount).round() as u8
+        };
+        Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+    }
+
+    /// Returns the closest color that can be shown with the given depth.
+    ///
+    /// Colors are only changed when needed: base colors stay the same
+    /// with 16 colors, and everything stays the same with true colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::theme::{Approximation, BaseColor, Color, ColorDepth};
+    /// let orange = Color::Rgb(255, 128, 0);
+    /// assert_eq!(orange.approximate(ColorDepth::Colors256,
+    ///                               Approximation::Nearest),
+    ///            Color::RgbLowRes(5, 2, 0));
+    /// assert_eq!(orange.approximate(ColorDepth::Colors8,
+    ///                               Approximation::Nearest),
+    ///            Color::Dark(BaseColor::Yellow));
+    /// ```
+    pub fn approximate(self, depth: ColorDepth, approximation: Approximation)
+                       -> Self {
+        let candidates = match (depth, self) {
+            (ColorDepth::Colors8, Color::Light(base)) => {
+                return Color::Dark(base);
+            }
+            // The first 16 colors depend on the terminal configuration,
+            // so only use them for base colors.
+            (ColorDepth::Colors256, Color::Rgb(..)) => 16..=255,
+            (ColorDepth::Colors16, Color::Rgb(..)) |
+            (ColorDepth::Colors16, Color::RgbLowRes(..)) => 0..=15,
+            (ColorDepth::Colors8, Color::Rgb(..)) |
+            (ColorDepth::Colors8, Color::RgbLowRes(..)) => 0..=7,
+            _ => return self,
+        };
+
+        let rgb = match self.to_rgb() {
+            Some(rgb) => rgb,
+            None => return self,
+        };
+        candidates.map(Color::from_256)
+            .min_by_key(|candidate| {
+                candidate.to_rgb()
+                    .map_or(u32::max_value(),
+                            |other| approximation.distance(rgb, other))
+            })
+            .unwrap_or(self)
+    }
 }
 
-impl Color {}
+/// Number of colors a terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// Only the 8 dark base colors.
+    Colors8,
+    /// Dark and light base colors.
+    Colors16,
+    /// The usual 256 colors, with a color cube and shades of grey.
+    Colors256,
+    /// Any 24-bit color.
+    TrueColor,
+}
+
+/// How colors are replaced when the terminal cannot show them.
+///
+/// In theme files, this is the `approximation` entry, with `"nearest"` or
+/// `"perceptual"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Approximation {
+    /// Closest color, treating red, green and blue the same.
+    Nearest,
+    /// Closest color as seen by humans, more sensitive to green and to
+    /// differences in dark colors.
+    ///
+    /// This is the default.
+    Perceptual,
+}
+
+impl Default for Approximation {
+    fn default() -> Self {
+        Approximation::Perceptual
+    }
+}
+
+impl Approximation {
+    fn from(s: &str) -> Option<Self> {
+        match s {
+            "nearest" => Some(Approximation::Nearest),
+            "perceptual" => Some(Approximation::Perceptual),
+            _ => None,
+        }
+    }
+
+    // Returns a distance between two colors, to compare candidates.
+    fn distance(&self, (r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8))
+                -> u32 {
+        let (r1, g1, b1) = (i32::from(r1), i32::from(g1), i32::from(b1));
+        let (r2, g2, b2) = (i32::from(r2), i32::from(g2), i32::from(b2));
+        let (dr, dg, db) = (r1 - r2, g1 - g2, b1 - b2);
+        let distance = match *self {
+            Approximation::Nearest => dr * dr + dg * dg + db * db,
+            // "Redmean" approximation of perceived differences.
+            Approximation::Perceptual => {
+                let mean = (r1 + r2) / 2;
+                ((512 + mean) * dr * dr >> 8) + 4 * dg * dg +
+                ((767 - mean) * db * db >> 8)
+            }
+        };
+        distance as u32
+    }
+}
+
+/// Writes colors like in theme files: `"red"`, `"light red"`,
+/// `"#ff0000"`, `"500"` or `"default"`.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Color::Dark(base) => write!(f, "{}", base.name()),
+            Color::Light(base) => write!(f, "light {}", base.name()),
+            Color::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            Color::RgbLowRes(r, g, b) => write!(f, "{}{}{}", r, g, b),
+            Color::TerminalDefault => write!(f, "default"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S)
+                                -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D)
+                                         -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Color::parse(&value).ok_or_else(|| {
+            D::Error::custom(format!("invalid color: {}", value))
+        })
+    }
+}
 
 /// Possible error returned when loading a theme.
 #[derive(Debug)]
@@ -447,6 +1655,7 @@ impl Color {
             "light magenta" => Color::Light(BaseColor::Magenta),
             "light cyan" => Color::Light(BaseColor::Cyan),
             "light white" => Color::Light(BaseColor::White),
+            "default" => Color::TerminalDefault,
             value => return Color::parse_special(value),
         })
     }
@@ -459,7 +1668,7 @@ impl Color {
             let (l, multiplier) = match value.len() {
                 6 => (2, 1),
                 3 => (1, 17),
-                _ => panic!("Cannot parse color: {}", value),
+                _ => return None,
             };
             let r = load_hex(&value[0..l]) * multiplier;
             let g = load_hex(&value[l..2 * l]) * multiplier;
@@ -509,6 +1718,168 @@ pub fn load_default() -> Theme {
     Theme::default()
 }
 
+/// Returns `steps` colors going smoothly from `start` to `end`.
+///
+/// Both `start` and `end` are included. Colors are mixed in RGB; with
+/// `Color::TerminalDefault`, which cannot be mixed, the first half uses
+/// `start` and the second half `end`.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::theme::{self, Color};
+/// let colors = theme::gradient(Color::Rgb(0, 0, 0),
+///                              Color::Rgb(255, 255, 255),
+///                              3);
+/// assert_eq!(colors[1], Color::Rgb(128, 128, 128));
+/// ```
+pub fn gradient(start: Color, end: Color, steps: usize) -> Vec<Color> {
+    let (from, to) = match (start.to_rgb(), end.to_rgb()) {
+        (Some(from), Some(to)) => (from, to),
+        _ => {
+            return (0..steps)
+                .map(|i| if 2 * i < steps { start } else { end })
+                .collect()
+        }
+    };
+
+    let mix = |a: u8, b: u8, i: usize| {
+        let (a, b) = (f32::from(a), f32::from(b));
+        let t = i as f32 / (steps - 1) as f32;
+        (a + (b - a) * t).round() as u8
+    };
+    (0..steps)
+        .map(|i| match i {
+            0 => start,
+            i if i + 1 == steps => end,
+            i => Color::Rgb(mix(from.0, to.0, i),
+                            mix(from.1, to.1, i),
+                            mix(from.2, to.2, i)),
+        })
+        .collect()
+}
+
+/// Brightness of a terminal background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Brightness {
+    /// Light background, usually with dark text.
+    Light,
+    /// Dark background, usually with light text.
+    Dark,
+}
+
+/// Tries to find out whether the terminal has a light or dark background.
+///
+/// Looks at the `COLORFGBG` environment variable first, then asks the
+/// terminal directly (with the `OSC 11` escape sequence). This last part
+/// waits up to 0.2 seconds for an answer, and must be done before the
+/// backend is initialized: [`Cursive::with_background_detection`] calls it
+/// at startup.
+///
+/// Returns `None` if the background is unknown.
+///
+/// [`Cursive::with_background_detection`]:
+/// ../struct.Cursive.html#method.with_background_detection
+pub fn detect_background() -> Option<Brightness> {
+    env::var("COLORFGBG")
+        .ok()
+        .and_then(|value| parse_colorfgbg(&value))
+        .or_else(query_background)
+}
+
+// `COLORFGBG` looks like `"15;0"`, or `"15;default;0"`: the last field is
+// the background color number.
+fn parse_colorfgbg(value: &str) -> Option<Brightness> {
+    match value.rsplit(';').next().and_then(|bg| bg.parse::<u8>().ok()) {
+        Some(0...6) | Some(8) => Some(Brightness::Dark),
+        Some(7) | Some(9...15) => Some(Brightness::Light),
+        _ => None,
+    }
+}
+
+// Parses an answer to `OSC 11`, like `"\x1b]11;rgb:ffff/ffff/dddd\x07"`.
+fn parse_osc11(answer: &str) -> Option<Brightness> {
+    let start = answer.find("rgb:")? + 4;
+    let end = answer[start..]
+        .find(|c: char| c != '/' && !c.is_digit(16))
+        .map_or(answer.len(), |end| start + end);
+
+    let channels: Vec<f32> = answer[start..end]
+        .split('/')
+        .filter(|channel| !channel.is_empty() && channel.len() <= 4)
+        .filter_map(|channel| {
+            let max = (1 << (4 * channel.len())) - 1;
+            u32::from_str_radix(channel, 16)
+                .ok()
+                .map(|value| value as f32 / max as f32)
+        })
+        .collect();
+    if channels.len() != 3 {
+        return None;
+    }
+
+    let luminance = 0.299 * channels[0] + 0.587 * channels[1] +
+                    0.114 * channels[2];
+    Some(if luminance > 0.5 {
+        Brightness::Light
+    } else {
+        Brightness::Dark
+    })
+}
+
+#[cfg(unix)]
+fn query_background() -> Option<Brightness> {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let mut io = tty.try_clone().ok()?;
+
+    // Runs `stty` on the terminal, and returns its output.
+    let stty = |args: &[&str]| -> Option<String> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(Stdio::from(tty.try_clone().ok()?))
+            .output()
+            .ok()?;
+        if output.status.success() {
+            String::from_utf8(output.stdout).ok()
+        } else {
+            None
+        }
+    };
+
+    let saved = stty(&["-g"])?;
+    // Don't wait more than 0.2s for each read.
+    stty(&["raw", "-echo", "min", "0", "time", "2"])?;
+
+    let mut answer = Vec::new();
+    if io.write_all(b"\x1b]11;?\x07").and_then(|_| io.flush()).is_ok() {
+        let mut buffer = [0; 64];
+        // The answer ends with BEL or ST (`ESC \`).
+        while let Ok(n) = io.read(&mut buffer) {
+            answer.extend_from_slice(&buffer[..n]);
+            if n == 0 || answer.ends_with(b"\x07") ||
+               answer.ends_with(b"\x1b\\") {
+                break;
+            }
+        }
+    }
+
+    stty(&[saved.trim()]);
+    parse_osc11(&String::from_utf8_lossy(&answer))
+}
+
+#[cfg(not(unix))]
+fn query_background() -> Option<Brightness> {
+    None
+}
+
 /// Loads a hexadecimal code
 fn load_hex(s: &str) -> u16 {
     let mut sum = 0;
@@ -524,3 +1895,206 @@ fn load_hex(s: &str) -> u16 {
 
     sum as u16
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_names() {
+        let colors = [Color::Dark(BaseColor::Red),
+                      Color::Light(BaseColor::Cyan),
+                      Color::Rgb(0x12, 0xab, 0xff),
+                      Color::RgbLowRes(0, 2, 5),
+                      Color::TerminalDefault];
+        for color in &colors {
+            assert_eq!(Color::parse(&color.to_string()), Some(*color));
+        }
+        assert_eq!(Color::parse("#12"), None);
+    }
+
+    #[test]
+    fn test_style_colors() {
+        let mut palette = Palette::default();
+        palette.view = Color::Dark(BaseColor::Black);
+        assert_eq!(ColorStyle::Highlight.colors(&palette),
+                   (palette.view, palette.highlight));
+        assert_eq!(ColorStyle::custom("missing").colors(&palette),
+                   (palette.primary, palette.view));
+    }
+
+    #[test]
+    fn test_gradient() {
+        let red = Color::Dark(BaseColor::Red);
+        let blue = Color::Rgb(0, 0, 200);
+        assert_eq!(gradient(red, blue, 0), vec![]);
+        assert_eq!(gradient(red, blue, 1), vec![red]);
+        assert_eq!(gradient(red, blue, 3),
+                   vec![red, Color::Rgb(85, 0, 100), blue]);
+
+        let default = Color::TerminalDefault;
+        assert_eq!(gradient(default, blue, 4),
+                   vec![default, default, blue, blue]);
+    }
+
+    #[test]
+    fn test_approximate() {
+        assert_eq!(Color::from_256(3), Color::Dark(BaseColor::Yellow));
+        assert_eq!(Color::from_256(12), Color::Light(BaseColor::Blue));
+        assert_eq!(Color::from_256(16 + 36 + 2), Color::RgbLowRes(1, 0, 2));
+        assert_eq!(Color::from_256(255), Color::Rgb(238, 238, 238));
+
+        let grey = Color::Rgb(0x60, 0x60, 0x60);
+        for &approximation in &[Approximation::Nearest,
+                                Approximation::Perceptual] {
+            assert_eq!(grey.approximate(ColorDepth::TrueColor, approximation),
+                       grey);
+            assert_eq!(grey.approximate(ColorDepth::Colors256, approximation),
+                       Color::RgbLowRes(1, 1, 1));
+            assert_eq!(grey.approximate(ColorDepth::Colors16, approximation),
+                       Color::Light(BaseColor::Black));
+        }
+
+        let light_red = Color::Light(BaseColor::Red);
+        assert_eq!(light_red.approximate(ColorDepth::Colors16,
+                                         Approximation::Nearest),
+                   light_red);
+        assert_eq!(light_red.approximate(ColorDepth::Colors8,
+                                         Approximation::Nearest),
+                   Color::Dark(BaseColor::Red));
+    }
+
+    #[test]
+    fn test_detect_background() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Brightness::Dark));
+        assert_eq!(parse_colorfgbg("0;default;15"), Some(Brightness::Light));
+        assert_eq!(parse_colorfgbg("default"), None);
+
+        assert_eq!(parse_osc11("\x1b]11;rgb:ffff/ffff/dddd\x07"),
+                   Some(Brightness::Light));
+        assert_eq!(parse_osc11("\x1b]11;rgb:28/2c/34\x1b\\"),
+                   Some(Brightness::Dark));
+        assert_eq!(parse_osc11(""), None);
+    }
+
+    #[test]
+    fn test_custom_colors() {
+        let theme = load_theme(r#"
+            [colors.custom]
+            warning = ["nope", "yellow"]
+            broken = "nope"
+        "#)
+            .unwrap();
+        let colors = &theme.colors;
+        assert_eq!(colors.custom.len(), 1);
+        assert_eq!(ColorStyle::custom("warning").resolve(colors),
+                   ColorStyle::Custom {
+                       front: Color::Dark(BaseColor::Yellow),
+                       back: colors.view,
+                   });
+        assert_eq!(ColorStyle::custom("broken").resolve(colors),
+                   ColorStyle::Primary);
+    }
+
+    #[test]
+    fn test_scrollbar_style() {
+        let theme = load_theme(r#"
+            [scrollbar]
+            thumb = "="
+            thumb_color = ["nope", "red"]
+        "#)
+            .unwrap();
+        let style = &theme.scrollbar;
+        assert_eq!(style.track, "|");
+        assert_eq!(style.thumb, "=");
+        assert_eq!(style.track_color, None);
+        assert_eq!(style.thumb_color, Some(Color::Dark(BaseColor::Red)));
+    }
+
+    #[test]
+    fn test_dim() {
+        let theme = load_theme(r#"
+            [dim]
+            amount = 1
+            color = "black"
+        "#)
+            .unwrap();
+        assert_eq!(theme.dim.amount, 1.0);
+        let dimmed = theme.colors.dimmed(&theme.dim);
+        assert_eq!(dimmed.view, Color::Rgb(0, 0, 0));
+
+        let mut palette = Palette::default();
+        palette.view = Color::TerminalDefault;
+        assert!(!Theme::default().dim.is_visible());
+        let dimmed = palette.dimmed(&DimStyle::new(0.5));
+        assert_eq!(dimmed.view, Color::TerminalDefault);
+    }
+
+    #[test]
+    fn test_presets() {
+        let theme = Theme::monochrome();
+        assert!(theme.highlight_effects);
+        assert_eq!(theme.colors.highlight, Color::TerminalDefault);
+        assert!(Theme::high_contrast().highlight_effects);
+
+        let theme = load_theme("highlight_effects = true").unwrap();
+        assert!(theme.highlight_effects);
+        assert!(!Theme::default().highlight_effects);
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = Frame::from_borders(&Borders::ascii());
+        assert_eq!(frame.top_left, "+");
+        assert_eq!(frame.left, "|");
+        assert_eq!(frame.title_left, "+ ");
+        assert_eq!(frame.title_right, " +");
+
+        let frame = Frame::new("*", "=", ":");
+        assert_eq!(frame.bottom_right, "*");
+        assert_eq!(frame.bottom, "=");
+        assert_eq!(frame.right, ":");
+    }
+
+    #[test]
+    fn test_glyphs() {
+        let theme = load_theme(r#"
+            glyphs = "ascii"
+            [scrollbar]
+            thumb = "="
+        "#)
+            .unwrap();
+        assert_eq!(theme.glyphs, Borders::ascii());
+        assert_eq!(theme.scrollbar.track, "|");
+        assert_eq!(theme.scrollbar.thumb, "=");
+
+        let theme = load_theme(r#"
+            [glyphs]
+            top_left = "╭"
+        "#)
+            .unwrap();
+        assert_eq!(theme.glyphs.top_left, "╭");
+        assert_eq!(theme.glyphs.top_right, "┐");
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+
+        let theme = ThemeBuilder::new()
+            .borders(None)
+            .primary(Color::Rgb(0x11, 0x11, 0x11))
+            .build();
+        let json = serde_json::to_string(&theme).unwrap();
+        let parsed: Theme = serde_json::from_str(&json).unwrap();
+        assert!(parsed.borders.is_none());
+        assert_eq!(parsed.colors.primary, theme.colors.primary);
+
+        let parsed: Theme =
+            serde_json::from_str(r#"{ "colors": { "view": "light blue" } }"#)
+                .unwrap();
+        assert!(parsed.shadow);
+        assert_eq!(parsed.colors.view, Color::Light(BaseColor::Blue));
+    }
+}
@@ -0,0 +1,345 @@
+//! Bidirectional text support.
+//!
+//! Right-to-left scripts (Hebrew, Arabic, ...) are stored in logical order,
+//! but must be displayed in a different, visual order.
+//!
+//! Text is first split into rows in logical order, and each row is then
+//! reordered for display according to the Unicode Bidirectional Algorithm.
+//!
+//! The actual reordering requires the `unicode-bidi` feature. Without it,
+//! text is always displayed in logical order.
+
+use std::borrow::Cow;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use utils::Row;
+
+#[cfg(feature = "unicode-bidi")]
+use unicode_bidi::{BidiInfo, Level};
+
+/// Base direction of a paragraph.
+///
+/// It determines how neutral characters are resolved, and on which side
+/// the text is anchored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BaseDirection {
+    /// Detects the direction from the first strong character.
+    Auto,
+    /// Left-to-right paragraph.
+    LeftToRight,
+    /// Right-to-left paragraph.
+    RightToLeft,
+}
+
+impl Default for BaseDirection {
+    fn default() -> Self {
+        BaseDirection::Auto
+    }
+}
+
+/// Returns the byte range of the paragraph containing `line`.
+///
+/// Paragraphs are delimited by newlines.
+#[cfg(feature = "unicode-bidi")]
+fn paragraph_range(content: &str, line: &Range<usize>) -> Range<usize> {
+    let start = content[..line.start].rfind('\n').map_or(0, |i| i + 1);
+    let end = content[line.end..]
+        .find('\n')
+        .map_or(content.len(), |i| line.end + i);
+    start..end
+}
+
+#[cfg(feature = "unicode-bidi")]
+fn bidi_info<'a>(paragraph: &'a str, base: BaseDirection) -> BidiInfo<'a> {
+    let level = match base {
+        BaseDirection::Auto => None,
+        BaseDirection::LeftToRight => Some(Level::ltr()),
+        BaseDirection::RightToLeft => Some(Level::rtl()),
+    };
+    BidiInfo::new(paragraph, level)
+}
+
+/// Returns `true` if the paragraph containing `offset` is right-to-left.
+///
+/// Views can use this to anchor text to the right side.
+#[cfg(feature = "unicode-bidi")]
+pub fn is_rtl(content: &str, offset: usize, base: BaseDirection) -> bool {
+    match base {
+        BaseDirection::LeftToRight => false,
+        BaseDirection::RightToLeft => true,
+        BaseDirection::Auto => {
+            let range = paragraph_range(content, &(offset..offset));
+            bidi_info(&content[range], base)
+                .paragraphs
+                .first()
+                .map_or(false, |para| para.level.is_rtl())
+        }
+    }
+}
+
+/// Returns `true` if the paragraph containing `offset` is right-to-left.
+///
+/// Without the `unicode-bidi` feature, only an explicit `RightToLeft`
+/// direction is considered right-to-left.
+#[cfg(not(feature = "unicode-bidi"))]
+pub fn is_rtl(_: &str, _: usize, base: BaseDirection) -> bool {
+    base == BaseDirection::RightToLeft
+}
+
+/// Returns the graphemes from `content[line]` in visual order.
+///
+/// Each grapheme comes with its byte offset in `content`.
+///
+/// `line` should not span multiple paragraphs; the rest of the paragraph
+/// is used as context to resolve directions.
+#[cfg(feature = "unicode-bidi")]
+pub fn visual_graphemes(content: &str, line: Range<usize>,
+                        base: BaseDirection)
+                        -> Vec<(usize, &str)> {
+    let para_range = paragraph_range(content, &line);
+    let paragraph = &content[para_range.clone()];
+    let info = bidi_info(paragraph, base);
+    let para = match info.paragraphs.first() {
+        Some(para) => para,
+        None => return logical_graphemes(content, line),
+    };
+
+    let shift = para_range.start;
+    let (levels, runs) =
+        info.visual_runs(para, (line.start - shift)..(line.end - shift));
+
+    let mut result = Vec::new();
+    for run in runs {
+        let graphemes = paragraph[run.clone()]
+            .grapheme_indices(true)
+            .map(|(i, g)| (i + run.start + shift, g));
+        if levels[run.start].is_rtl() {
+            let mut graphemes: Vec<_> = graphemes.collect();
+            graphemes.reverse();
+            result.extend(graphemes);
+        } else {
+            result.extend(graphemes);
+        }
+    }
+    result
+}
+
+/// Returns the graphemes from `content[line]` in visual order.
+///
+/// Without the `unicode-bidi` feature, this is the logical order.
+#[cfg(not(feature = "unicode-bidi"))]
+pub fn visual_graphemes(content: &str, line: Range<usize>, _: BaseDirection)
+                        -> Vec<(usize, &str)> {
+    logical_graphemes(content, line)
+}
+
+fn logical_graphemes(content: &str, line: Range<usize>) -> Vec<(usize, &str)> {
+    let start = line.start;
+    content[line]
+        .grapheme_indices(true)
+        .map(|(i, g)| (i + start, g))
+        .collect()
+}
+
+/// Returns the text from `content[line]`, reordered for display.
+#[cfg(feature = "unicode-bidi")]
+pub fn reorder_line<'a>(content: &'a str, line: Range<usize>,
+                        base: BaseDirection)
+                        -> Cow<'a, str> {
+    let text = &content[line.clone()];
+    if base != BaseDirection::RightToLeft &&
+       !bidi_info(text, BaseDirection::LeftToRight).has_rtl() {
+        // Pure left-to-right text doesn't need any work.
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(visual_graphemes(content, line, base)
+                   .into_iter()
+                   .map(|(_, g)| g)
+                   .collect())
+}
+
+/// Returns the text from `content[line]`, reordered for display.
+///
+/// Without the `unicode-bidi` feature, this returns the text untouched.
+#[cfg(not(feature = "unicode-bidi"))]
+pub fn reorder_line<'a>(content: &'a str, line: Range<usize>,
+                        _: BaseDirection)
+                        -> Cow<'a, str> {
+    Cow::Borrowed(&content[line])
+}
+
+/// Display order of a row, computed once for all the rows of a text.
+///
+/// Views can keep it next to their rows, instead of running the bidi
+/// algorithm again for each row on every draw.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowOrder {
+    /// `true` if the paragraph containing the row is right-to-left.
+    pub rtl: bool,
+    /// Byte ranges of the graphemes, relative to the start of the row and
+    /// in visual order, or `None` if the row is shown in logical order.
+    pub visual: Option<Vec<Range<usize>>>,
+}
+
+impl RowOrder {
+    fn logical(rtl: bool) -> Self {
+        RowOrder {
+            rtl: rtl,
+            visual: None,
+        }
+    }
+
+    /// Returns the graphemes of `row` in visual order.
+    ///
+    /// Each grapheme comes with its byte offset in `content`.
+    pub fn graphemes<'a>(&self, content: &'a str, row: &Row)
+                         -> Vec<(usize, &'a str)> {
+        match self.visual {
+            Some(ref visual) => {
+                visual.iter()
+                    .map(|g| {
+                             (row.start + g.start,
+                              &content[row.start + g.start..
+                                       row.start + g.end])
+                         })
+                    .collect()
+            }
+            None => logical_graphemes(content, row.start..row.end),
+        }
+    }
+
+    /// Returns the text of `row`, reordered for display.
+    pub fn text<'a>(&self, content: &'a str, row: &Row) -> Cow<'a, str> {
+        match self.visual {
+            Some(_) => {
+                Cow::Owned(self.graphemes(content, row)
+                               .into_iter()
+                               .map(|(_, g)| g)
+                               .collect())
+            }
+            None => Cow::Borrowed(&content[row.start..row.end]),
+        }
+    }
+}
+
+/// Computes the display order of each row from `content`.
+///
+/// The bidi algorithm runs once over the whole content.
+#[cfg(feature = "unicode-bidi")]
+pub fn row_orders(content: &str, rows: &[Row], base: BaseDirection)
+                  -> Vec<RowOrder> {
+    if base != BaseDirection::RightToLeft && content.is_ascii() {
+        // Pure left-to-right text doesn't need any work.
+        return vec![RowOrder::logical(false); rows.len()];
+    }
+
+    let info = bidi_info(content, base);
+    let mut para = 0;
+    rows.iter()
+        .map(|row| {
+            // Rows come in order, and so do paragraphs.
+            while para + 1 < info.paragraphs.len() &&
+                  info.paragraphs[para + 1].range.start <= row.start {
+                para += 1;
+            }
+            let paragraph = match info.paragraphs.get(para) {
+                Some(paragraph) => paragraph,
+                None => return RowOrder::logical(false),
+            };
+            let rtl = paragraph.level.is_rtl();
+            if row.start == row.end ||
+               info.levels[row.start..row.end]
+                .iter()
+                .all(|level| level.is_ltr()) {
+                return RowOrder::logical(rtl);
+            }
+
+            let (levels, runs) = info.visual_runs(paragraph,
+                                                  row.start..row.end);
+            let mut visual = Vec::new();
+            for run in runs {
+                let graphemes = content[run.clone()]
+                    .grapheme_indices(true)
+                    .map(|(i, g)| {
+                             let start = run.start + i - row.start;
+                             start..start + g.len()
+                         });
+                if levels[run.start].is_rtl() {
+                    let mut graphemes: Vec<_> = graphemes.collect();
+                    graphemes.reverse();
+                    visual.extend(graphemes);
+                } else {
+                    visual.extend(graphemes);
+                }
+            }
+            RowOrder {
+                rtl: rtl,
+                visual: Some(visual),
+            }
+        })
+        .collect()
+}
+
+/// Computes the display order of each row from `content`.
+///
+/// Without the `unicode-bidi` feature, rows keep their logical order.
+#[cfg(not(feature = "unicode-bidi"))]
+pub fn row_orders(_: &str, rows: &[Row], base: BaseDirection)
+                  -> Vec<RowOrder> {
+    let rtl = base == BaseDirection::RightToLeft;
+    vec![RowOrder::logical(rtl); rows.len()]
+}
+
+#[cfg(all(test, feature = "unicode-bidi"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorder() {
+        let text = "abc אבג";
+        let len = text.len();
+        assert_eq!(reorder_line(text, 0..len, BaseDirection::Auto),
+                   "abc גבא");
+        assert!(!is_rtl(text, 0, BaseDirection::Auto));
+        assert!(is_rtl("אבג abc", 0, BaseDirection::Auto));
+
+        let ltr = "hello world";
+        assert_eq!(reorder_line(ltr, 0..ltr.len(), BaseDirection::Auto),
+                   ltr);
+    }
+
+    #[test]
+    fn test_visual_offsets() {
+        let text = "אב";
+        let graphemes = visual_graphemes(text, 0..text.len(),
+                                         BaseDirection::Auto);
+        assert_eq!(graphemes, vec![(2, "ב"), (0, "א")]);
+    }
+
+    #[test]
+    fn test_row_orders() {
+        let text = "abc אבג\nאב cd";
+        let rows = vec![Row {
+                            start: 0,
+                            end: 10,
+                            width: 7,
+                            hyphenated: false,
+                        },
+                        Row {
+                            start: 11,
+                            end: 18,
+                            width: 5,
+                            hyphenated: false,
+                        }];
+        let orders = row_orders(text, &rows, BaseDirection::Auto);
+        assert!(!orders[0].rtl);
+        assert!(orders[1].rtl);
+        for (order, row) in orders.iter().zip(&rows) {
+            assert_eq!(order.graphemes(text, row),
+                       visual_graphemes(text, row.start..row.end,
+                                        BaseDirection::Auto));
+        }
+        assert_eq!(orders[0].text(text, &rows[0]), "abc גבא");
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Shared value that views can be bound to.
+///
+/// Cloning a binding still points to the same value, so it can be updated
+/// from anywhere, including other threads. Bound views pick up changes on
+/// the next event cycle, and only then lay themselves out again.
+///
+/// If you update the value from another thread, call
+/// [`Cursive::set_fps`] to regularly check for changes.
+///
+/// [`Cursive::set_fps`]: ../struct.Cursive.html#method.set_fps
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::utils::Binding;
+/// # use cursive::views::{Checkbox, TextView};
+/// let status = Binding::new(String::from("Idle"));
+/// let view = TextView::empty().binding(status.clone());
+///
+/// let enabled = Binding::new(false);
+/// let checkbox = Checkbox::new().binding(enabled.clone());
+///
+/// // Later, possibly in a different thread
+/// status.set(String::from("Working"));
+/// enabled.set(true);
+/// ```
+pub struct Binding<T> {
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+struct Inner<T> {
+    value: T,
+    // Incremented every time the value changes.
+    generation: usize,
+}
+
+impl<T> Clone for Binding<T> {
+    fn clone(&self) -> Self {
+        Binding { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Binding<T> {
+    /// Creates a new binding with the given value.
+    pub fn new(value: T) -> Self {
+        Binding {
+            inner: Arc::new(Mutex::new(Inner {
+                                           value: value,
+                                           generation: 0,
+                                       })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Inner<T>> {
+        self.inner.lock().unwrap()
+    }
+
+    /// Replaces the value.
+    pub fn set(&self, value: T) {
+        self.update(|v| *v = value);
+    }
+
+    /// Changes the value in place.
+    pub fn update<F: FnOnce(&mut T)>(&self, f: F) {
+        let mut inner = self.lock();
+        f(&mut inner.value);
+        inner.generation += 1;
+    }
+
+    /// Runs a closure on the current value.
+    pub fn read<F, R>(&self, f: F) -> R
+        where F: FnOnce(&T) -> R
+    {
+        f(&self.lock().value)
+    }
+
+    /// Returns a number incremented every time the value changes.
+    ///
+    /// Views use it to know if they are up to date.
+    pub fn generation(&self) -> usize {
+        self.lock().generation
+    }
+}
+
+impl<T: Clone> Binding<T> {
+    /// Returns a copy of the current value.
+    pub fn get(&self) -> T {
+        self.lock().value.clone()
+    }
+}
@@ -0,0 +1,242 @@
+//! Locale-aware formatting of numbers and dates.
+//!
+//! Views showing numbers, like the label of a `ProgressBar`, format them
+//! with the current locale. It defaults to `Locale::english()`, and can be
+//! replaced with `set_locale`.
+//!
+//! Only available with the `format` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use cursive::utils::format::{self, Locale};
+//! assert_eq!(format::number(1234.5, 2), "1,234.50");
+//!
+//! format::set_locale(Locale::french());
+//! assert_eq!(format::number(1234.5, 2), "1 234,50");
+//! assert_eq!(format::percentage(0.25, 0), "25 %");
+//! assert_eq!(format::date(2017, 3, 14), "14/03/2017");
+//! # format::reset_locale();
+//! ```
+
+use std::cell::RefCell;
+
+thread_local! {
+    static LOCALE: RefCell<Locale> = RefCell::new(Locale::english());
+}
+
+/// Conventions used to format numbers and dates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Locale {
+    /// Separates the integer part of a number from its decimals.
+    pub decimal_separator: char,
+    /// Separates groups of three digits, if any.
+    pub group_separator: Option<char>,
+    /// Pattern for percentages, where `{}` is replaced with the number.
+    pub percent: String,
+    /// Pattern for dates.
+    ///
+    /// `%Y` is replaced with the year, `%m` and `%d` with the month and
+    /// day on two digits, and `%B` with the name of the month.
+    pub date: String,
+    /// Pattern for a month of a year, like in calendar headers.
+    ///
+    /// Uses the same placeholders as `date`.
+    pub month: String,
+    /// Names of the months, starting with January.
+    pub month_names: Vec<String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::english()
+    }
+}
+
+impl Locale {
+    /// American English: `1,234.5`, `25%`, `03/14/2017`.
+    pub fn english() -> Self {
+        Locale {
+            decimal_separator: '.',
+            group_separator: Some(','),
+            percent: "{}%".to_string(),
+            date: "%m/%d/%Y".to_string(),
+            month: "%B %Y".to_string(),
+            month_names: names(&["January", "February", "March", "April",
+                                 "May", "June", "July", "August",
+                                 "September", "October", "November",
+                                 "December"]),
+        }
+    }
+
+    /// French: `1 234,5`, `25 %`, `14/03/2017`.
+    pub fn french() -> Self {
+        Locale {
+            decimal_separator: ',',
+            group_separator: Some(' '),
+            percent: "{} %".to_string(),
+            date: "%d/%m/%Y".to_string(),
+            month: "%B %Y".to_string(),
+            month_names: names(&["janvier", "février", "mars", "avril",
+                                 "mai", "juin", "juillet", "août",
+                                 "septembre", "octobre", "novembre",
+                                 "décembre"]),
+        }
+    }
+
+    /// German: `1.234,5`, `25 %`, `14.03.2017`.
+    pub fn german() -> Self {
+        Locale {
+            decimal_separator: ',',
+            group_separator: Some('.'),
+            percent: "{} %".to_string(),
+            date: "%d.%m.%Y".to_string(),
+            month: "%B %Y".to_string(),
+            month_names: names(&["Januar", "Februar", "März", "April",
+                                 "Mai", "Juni", "Juli", "August",
+                                 "September", "Oktober", "November",
+                                 "Dezember"]),
+        }
+    }
+
+    /// Locale-neutral formats: `1234.5`, `25%`, `2017-03-14`.
+    pub fn iso() -> Self {
+        Locale {
+            group_separator: None,
+            date: "%Y-%m-%d".to_string(),
+            month: "%Y-%m".to_string(),
+            ..Locale::english()
+        }
+    }
+
+    /// Formats `value` with `decimals` digits after the separator.
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let digits = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = match digits.find('.') {
+            Some(i) => (&digits[..i], Some(&digits[i + 1..])),
+            None => (&digits[..], None),
+        };
+
+        let mut result = String::new();
+        // Don't show `-0`.
+        if value < 0.0 && digits.chars().any(|c| c != '0' && c != '.') {
+            result.push('-');
+        }
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                if let Some(separator) = self.group_separator {
+                    result.push(separator);
+                }
+            }
+            result.push(c);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// Formats `ratio` as a percentage: `1.0` is `100%`.
+    pub fn percentage(&self, ratio: f64, decimals: usize) -> String {
+        self.percent.replace("{}", &self.number(ratio * 100.0, decimals))
+    }
+
+    /// Formats a date. `month` and `day` start at 1.
+    pub fn date(&self, year: i32, month: u32, day: u32) -> String {
+        self.fill(&self.date, year, month, day)
+    }
+
+    /// Formats a month of a year. `month` starts at 1.
+    pub fn month(&self, year: i32, month: u32) -> String {
+        self.fill(&self.month, year, month, 1)
+    }
+
+    /// Returns the name of `month`, starting at 1.
+    pub fn month_name(&self, month: u32) -> &str {
+        month.checked_sub(1)
+            .and_then(|i| self.month_names.get(i as usize))
+            .map(|name| &name[..])
+            .unwrap_or("")
+    }
+
+    fn fill(&self, pattern: &str, year: i32, month: u32, day: u32)
+            -> String {
+        pattern.replace("%Y", &year.to_string())
+            .replace("%m", &format!("{:02}", month))
+            .replace("%d", &format!("{:02}", day))
+            .replace("%B", self.month_name(month))
+    }
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+/// Sets the locale used by views drawn from this thread.
+pub fn set_locale(locale: Locale) {
+    LOCALE.with(|l| *l.borrow_mut() = locale);
+}
+
+/// Goes back to the default locale.
+pub fn reset_locale() {
+    set_locale(Locale::default());
+}
+
+/// Returns the current locale.
+pub fn locale() -> Locale {
+    LOCALE.with(|l| l.borrow().clone())
+}
+
+/// Formats a number with the current locale.
+///
+/// See `Locale::number`.
+pub fn number(value: f64, decimals: usize) -> String {
+    LOCALE.with(|l| l.borrow().number(value, decimals))
+}
+
+/// Formats a percentage with the current locale.
+///
+/// See `Locale::percentage`.
+pub fn percentage(ratio: f64, decimals: usize) -> String {
+    LOCALE.with(|l| l.borrow().percentage(ratio, decimals))
+}
+
+/// Formats a date with the current locale.
+///
+/// See `Locale::date`.
+pub fn date(year: i32, month: u32, day: u32) -> String {
+    LOCALE.with(|l| l.borrow().date(year, month, day))
+}
+
+/// Formats a month of a year with the current locale.
+///
+/// See `Locale::month`.
+pub fn month(year: i32, month: u32) -> String {
+    LOCALE.with(|l| l.borrow().month(year, month))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Locale;
+
+    #[test]
+    fn test_number() {
+        let english = Locale::english();
+        assert_eq!(english.number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(english.number(-999.0, 0), "-999");
+        assert_eq!(english.number(-0.001, 1), "0.0");
+        assert_eq!(Locale::german().number(1234.5, 1), "1.234,5");
+        assert_eq!(Locale::iso().number(1234.5, 1), "1234.5");
+        assert_eq!(english.percentage(0.125, 1), "12.5%");
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(Locale::english().date(2017, 3, 4), "03/04/2017");
+        assert_eq!(Locale::german().date(2017, 3, 4), "04.03.2017");
+        assert_eq!(Locale::iso().month(2017, 3), "2017-03");
+        assert_eq!(Locale::french().month(2017, 8), "août 2017");
+        assert_eq!(Locale::english().month_name(13), "");
+    }
+}
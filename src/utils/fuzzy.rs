@@ -0,0 +1,217 @@
+//! Fuzzy matching of a pattern against text.
+//!
+//! A pattern matches a text if all its characters appear in the text, in
+//! the same order, ignoring case. Matches are then scored so the best ones
+//! can be shown first: consecutive characters and characters at the start
+//! of a word score higher, while gaps cost a little.
+//!
+//! # Examples
+//!
+//! ```
+//! # use cursive::utils::fuzzy;
+//! let open = fuzzy::find("of", "Open File").unwrap();
+//! let lift = fuzzy::find("of", "Lift off").unwrap();
+//! assert_eq!(open.positions, vec![0, 5]);
+//! assert!(open.score > lift.score);
+//! assert!(fuzzy::find("xyz", "Open File").is_none());
+//! ```
+//!
+//! Use [`highlight`] to show which characters matched.
+//!
+//! [`highlight`]: fn.highlight.html
+
+use theme::Style;
+use utils::StyledString;
+
+/// A successful match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// How good the match is. Higher is better.
+    pub score: i64,
+    /// Byte offsets in the text of the matched characters.
+    pub positions: Vec<usize>,
+}
+
+// Points for each matched character.
+const SCORE_MATCH: i64 = 16;
+// Bonus when the character starts a word.
+const BONUS_BOUNDARY: i64 = 12;
+// Bonus when the character follows the previous match.
+const BONUS_CONSECUTIVE: i64 = 8;
+// Maximum penalty for characters before the first match.
+const MAX_LEADING_PENALTY: i64 = 8;
+
+/// Matches `pattern` against `text`.
+///
+/// Whitespace in the pattern is ignored. An empty pattern matches
+/// everything, with a score of 0.
+///
+/// Returns `None` if the text doesn't contain all the characters of the
+/// pattern, in order.
+pub fn find(pattern: &str, text: &str) -> Option<Match> {
+    let pattern: Vec<char> =
+        pattern.chars().filter(|c| !c.is_whitespace()).collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+
+    if pattern.is_empty() {
+        return Some(Match {
+                        score: 0,
+                        positions: Vec::new(),
+                    });
+    }
+
+    // First, find where the earliest match ends.
+    let mut p = 0;
+    let mut end = None;
+    for (i, &(_, c)) in chars.iter().enumerate() {
+        if same(c, pattern[p]) {
+            p += 1;
+            if p == pattern.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = match end {
+        Some(end) => end,
+        None => return None,
+    };
+
+    // Then go back to find the latest start for this end,
+    // to get the tightest match.
+    let mut p = pattern.len();
+    let mut start = end;
+    for i in (0..end + 1).rev() {
+        if same(chars[i].1, pattern[p - 1]) {
+            p -= 1;
+            if p == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    // Finally, collect matched characters from there.
+    let mut indices = Vec::with_capacity(pattern.len());
+    let mut p = 0;
+    for i in start..end + 1 {
+        if p < pattern.len() && same(chars[i].1, pattern[p]) {
+            indices.push(i);
+            p += 1;
+        }
+    }
+
+    let mut score = -(start as i64).min(MAX_LEADING_PENALTY);
+    for (k, &i) in indices.iter().enumerate() {
+        score += SCORE_MATCH;
+        if i == 0 || is_boundary(chars[i - 1].1, chars[i].1) {
+            score += BONUS_BOUNDARY;
+        }
+        if k > 0 {
+            let previous = indices[k - 1];
+            if i == previous + 1 {
+                score += BONUS_CONSECUTIVE;
+            } else {
+                score -= (i - previous - 1) as i64;
+            }
+        }
+    }
+
+    Some(Match {
+             score: score,
+             positions: indices.into_iter().map(|i| chars[i].0).collect(),
+         })
+}
+
+/// Returns `text` with the characters from `m` in the given style.
+///
+/// `m` should come from matching against this same `text`. Consecutive
+/// matched characters share a single span.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::utils::fuzzy;
+/// # use cursive::theme::ColorStyle;
+/// let text = "Open File";
+/// let m = fuzzy::find("opf", text).unwrap();
+/// let styled = fuzzy::highlight(text, &m, ColorStyle::TitlePrimary);
+///
+/// assert_eq!(styled.source(), text);
+/// assert_eq!(styled.spans().len(), 2);
+/// ```
+pub fn highlight<S: Into<Style>>(text: &str, m: &Match, style: S)
+                                 -> StyledString {
+    let style = style.into();
+    let mut result = StyledString::new();
+    let mut start = 0;
+    let mut positions = m.positions.iter().peekable();
+
+    while let Some(&first) = positions.next() {
+        let mut end = first + char_len(text, first);
+        // Merge following characters into the same span.
+        while positions.peek().map_or(false, |&&next| next == end) {
+            end += char_len(text, end);
+            positions.next();
+        }
+
+        result.append_plain(&text[start..first]);
+        result.append_styled(&text[first..end], style);
+        start = end;
+    }
+    result.append_plain(&text[start..]);
+
+    result
+}
+
+// Returns the length in bytes of the character at `offset`.
+fn char_len(text: &str, offset: usize) -> usize {
+    text[offset..].chars().next().map_or(0, |c| c.len_utf8())
+}
+
+// Compares two characters, ignoring case.
+fn same(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+// Returns `true` if `c` starts a word, after `previous`.
+fn is_boundary(previous: char, c: char) -> bool {
+    !previous.is_alphanumeric() ||
+    (previous.is_lowercase() && c.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, highlight};
+    use theme::ColorStyle;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("", "abc").unwrap().score, 0);
+        assert!(find("abd", "abc").is_none());
+        assert_eq!(find("ABC", "xaybzc").unwrap().positions, vec![1, 3, 5]);
+
+        // The tightest match is used.
+        assert_eq!(find("ab", "a-xab").unwrap().positions, vec![3, 4]);
+
+        // Word starts and consecutive characters win.
+        let score = |pattern, text| find(pattern, text).unwrap().score;
+        assert!(score("sf", "save file") > score("sf", "sulfur"));
+        assert!(score("sa", "save") > score("sa", "sofa"));
+        assert!(score("of", "openFile") > score("of", "lift off"));
+    }
+
+    #[test]
+    fn test_highlight() {
+        let text = "héllo wörld";
+        let m = find("éllw", text).unwrap();
+        let styled = highlight(text, &m, ColorStyle::Highlight);
+        assert_eq!(styled.source(), text);
+
+        let spans: Vec<_> = styled.spans()
+            .iter()
+            .map(|span| &text[span.start..span.end])
+            .collect();
+        assert_eq!(spans, vec!["éll", "w"]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Translation of the strings built into views.
+//!
+//! A few views show text of their own, like the `Ok` button of
+//! `Dialog::info`, or the `No match` message of a `HelpView`. Setting a
+//! translator replaces these strings, so an application doesn't show
+//! several languages at once.
+//!
+//! The translator is given the English string, and returns the text to
+//! show instead. Strings it doesn't know should be returned unchanged.
+//!
+//! # Examples
+//!
+//! ```
+//! # use cursive::utils::i18n;
+//! i18n::set_translator(|text| match text {
+//!     "Ok" => "D'accord".to_string(),
+//!     "No match" => "Aucun résultat".to_string(),
+//!     other => other.to_string(),
+//! });
+//! assert_eq!(i18n::translate("Ok"), "D'accord");
+//! assert_eq!(i18n::translate("Quit"), "Quit");
+//! # i18n::clear_translator();
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+thread_local! {
+    static TRANSLATOR: RefCell<Option<Rc<Fn(&str) -> String>>> =
+        RefCell::new(None);
+}
+
+/// Sets the function translating built-in strings.
+///
+/// It applies to views drawn from this thread, which is where the event
+/// loop runs.
+pub fn set_translator<F>(translator: F)
+    where F: Fn(&str) -> String + 'static
+{
+    TRANSLATOR.with(|t| *t.borrow_mut() = Some(Rc::new(translator)));
+}
+
+/// Removes the translator: built-in strings are shown in English again.
+pub fn clear_translator() {
+    TRANSLATOR.with(|t| *t.borrow_mut() = None);
+}
+
+/// Returns the translation of `text`.
+///
+/// Without a translator, this is `text` itself.
+pub fn translate(text: &str) -> String {
+    // The translator may translate other strings itself.
+    let translator = TRANSLATOR.with(|t| t.borrow().clone());
+    match translator {
+        Some(translator) => translator(text),
+        None => text.to_string(),
+    }
+}
@@ -49,14 +49,18 @@ impl<'a> LinesIterator<'a> {
 ///
 /// A row is made of offsets into a parent `String`.
 /// The corresponding substring should take `width` cells when printed.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Row {
     /// Beginning of the row in the parent `String`.
     pub start: usize,
     /// End of the row (excluded)
     pub end: usize,
     /// Width of the row, in cells.
+    ///
+    /// This includes the hyphen, if any.
     pub width: usize,
+    /// Whether a hyphen is printed after this row.
+    pub hyphenated: bool,
 }
 
 impl Row {
@@ -114,6 +118,7 @@ impl<'a> Iterator for LinesIterator<'a> {
                 start: start,
                 end: start + next,
                 width: line_width,
+                hyphenated: false,
             });
         }
 
@@ -147,6 +152,7 @@ impl<'a> Iterator for LinesIterator<'a> {
             start: start,
             end: start + prefix_length,
             width: self.width,
+            hyphenated: false,
         })
     }
 }
@@ -1,13 +1,27 @@
 //! Toolbox to make text layout easier.
 
+use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+pub mod bidi;
+#[cfg(feature = "format")]
+pub mod format;
+pub mod fuzzy;
+pub mod i18n;
+
+mod binding;
 mod lines_iterator;
 mod reader;
+mod styled_string;
+mod wrap;
 
+pub use self::binding::Binding;
 pub use self::lines_iterator::{LinesIterator, Row};
-pub use self::reader::ProgressReader;
+pub use self::reader::{ProgressReader, ProgressWriter};
+pub use self::styled_string::{Span, StyledString};
+pub use self::wrap::{CharWrap, Hyphenate, NoWrap, WordWrap, WrapStrategy,
+                     rewrap};
 
 /// The length and width of a part of a string.
 pub struct Prefix {
@@ -100,14 +114,117 @@ pub fn simple_suffix(text: &str, width: usize) -> Prefix {
     suffix(text.graphemes(true), width, "")
 }
 
+/// Where to cut text when truncating it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncateStyle {
+    /// Keep the beginning of the text: `"abc…"`.
+    End,
+    /// Keep both ends of the text: `"ab…z"`.
+    Middle,
+    /// Keep the end of the text: `"…xyz"`.
+    Start,
+}
+
+/// Truncates `text` to fit in `width` cells, marking the cut with an ellipsis.
+///
+/// Returns `text` unchanged if it already fits.
+///
+/// Example:
+///
+/// ```
+/// # use cursive::utils::{truncate, TruncateStyle};
+/// assert_eq!(truncate("abcdef", 4, TruncateStyle::End), "abc…");
+/// assert_eq!(truncate("abcdef", 4, TruncateStyle::Middle), "ab…f");
+/// assert_eq!(truncate("abcdef", 4, TruncateStyle::Start), "…def");
+/// ```
+pub fn truncate<'a>(text: &'a str, width: usize, style: TruncateStyle)
+                    -> Cow<'a, str> {
+    if text.width() <= width {
+        return Cow::Borrowed(text);
+    }
+
+    if width == 0 {
+        return Cow::Borrowed("");
+    }
+
+    // Keep a cell for the ellipsis.
+    let available = width - 1;
+
+    let result = match style {
+        TruncateStyle::End => {
+            let head = prefix(text.graphemes(true), available, "");
+            format!("{}…", &text[..head.length])
+        }
+        TruncateStyle::Start => {
+            let tail = simple_suffix(text, available);
+            format!("…{}", &text[text.len() - tail.length..])
+        }
+        TruncateStyle::Middle => {
+            let head = prefix(text.graphemes(true), (available + 1) / 2, "");
+            let tail = simple_suffix(&text[head.length..],
+                                     available - head.width);
+            format!("{}…{}",
+                    &text[..head.length],
+                    &text[text.len() - tail.length..])
+        }
+    };
+
+    Cow::Owned(result)
+}
+
+/// Returns the escape sequence setting the terminal clipboard to `text`.
+///
+/// This is `OSC 52`, which many terminals support, even over ssh. Use
+/// `Cursive::copy_to_clipboard` to send it.
+pub fn clipboard_sequence(text: &str) -> Vec<u8> {
+    const BASE64: &'static [u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                                        abcdefghijklmnopqrstuvwxyz\
+                                        0123456789+/";
+
+    let mut sequence = b"\x1b]52;c;".to_vec();
+    for chunk in text.as_bytes().chunks(3) {
+        let n = chunk.iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                sequence.push(BASE64[(n >> (18 - 6 * i) & 63) as usize]);
+            } else {
+                sequence.push(b'=');
+            }
+        }
+    }
+    sequence.push(b'\x07');
+    sequence
+}
+
+
 #[cfg(test)]
 mod tests {
     use utils;
 
+    #[test]
+    fn test_clipboard_sequence() {
+        assert_eq!(utils::clipboard_sequence("Ma"), b"\x1b]52;c;TWE=\x07");
+        assert_eq!(utils::clipboard_sequence("Man"), b"\x1b]52;c;TWFu\x07");
+    }
+
     #[test]
     fn test_prefix() {
         assert_eq!(utils::prefix(" abra ".split(' '), 5, " ").length, 5);
         assert_eq!(utils::prefix("abra a".split(' '), 5, " ").length, 4);
         assert_eq!(utils::prefix("a a br".split(' '), 5, " ").length, 3);
     }
+
+    #[test]
+    fn test_truncate() {
+        use utils::TruncateStyle::*;
+
+        assert_eq!(utils::truncate("abc", 3, End), "abc");
+        assert_eq!(utils::truncate("abc", 0, End), "");
+        assert_eq!(utils::truncate("abcdefg", 5, Middle), "ab…fg");
+        assert_eq!(utils::truncate("abcdef", 5, Middle), "ab…ef");
+        assert_eq!(utils::truncate("ａｂｃ", 4, End), "ａ…");
+        assert_eq!(utils::truncate("ａｂｃ", 4, Start), "…ｃ");
+    }
 }
@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use views::Counter;
 
@@ -39,3 +39,58 @@ impl<R: Read> Read for ProgressReader<R> {
         Ok(result)
     }
 }
+
+/// Wrapper around a `Write` that reports the progress made.
+///
+/// Like `ProgressReader`, for uploads or copies: `counter` can be shared
+/// with a `ProgressBar` while another thread writes.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::utils::{ProgressReader, ProgressWriter};
+/// # use cursive::views::Counter;
+/// # use std::io;
+/// let read = Counter::new(0);
+/// let written = Counter::new(0);
+///
+/// let mut source = ProgressReader::new(read.clone(), &b"hello"[..]);
+/// let mut target = ProgressWriter::new(written.clone(), Vec::new());
+/// io::copy(&mut source, &mut target).unwrap();
+///
+/// assert_eq!(read.get(), 5);
+/// assert_eq!(written.get(), 5);
+/// ```
+pub struct ProgressWriter<W: Write> {
+    writer: W,
+    counter: Counter,
+}
+
+impl<W: Write> ProgressWriter<W> {
+    /// Creates a new `ProgressWriter` around `writer`.
+    ///
+    /// `counter` will be updated with the number of bytes written.
+    pub fn new(counter: Counter, writer: W) -> Self {
+        ProgressWriter {
+            writer: writer,
+            counter: counter,
+        }
+    }
+
+    /// Unwraps this `ProgressWriter`, returning the writer and counter.
+    pub fn deconstruct(self) -> (W, Counter) {
+        (self.writer, self.counter)
+    }
+}
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = try!(self.writer.write(buf));
+        self.counter.tick(result);
+        Ok(result)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,176 @@
+use theme::Style;
+
+/// A styled part of a `StyledString`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// Beginning of the span in the source text.
+    pub start: usize,
+    /// End of the span (excluded).
+    pub end: usize,
+    /// Style to apply to this span.
+    pub style: Style,
+    /// Target of the link, if this span is a hyperlink.
+    pub link: Option<String>,
+}
+
+impl Span {
+    /// Shift a span start and end by `offset`.
+    pub fn shift(&mut self, offset: usize) {
+        self.start += offset;
+        self.end += offset;
+    }
+
+    /// Shift back a span start and end by `offset`.
+    ///
+    /// Parts of the span before `offset` are dropped.
+    pub fn rev_shift(&mut self, offset: usize) {
+        self.start = self.start.saturating_sub(offset);
+        self.end = self.end.saturating_sub(offset);
+    }
+}
+
+/// A string with some styled parts.
+///
+/// Parts without any span use the default style of the view printing them.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::utils::StyledString;
+/// # use cursive::theme::ColorStyle;
+/// let mut s = StyledString::plain("Status: ");
+/// s.append_styled("OK", ColorStyle::Highlight);
+///
+/// assert_eq!(s.source(), "Status: OK");
+/// assert_eq!(s.spans().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyledString {
+    source: String,
+    spans: Vec<Span>,
+}
+
+impl StyledString {
+    /// Creates a new empty string.
+    pub fn new() -> Self {
+        StyledString::default()
+    }
+
+    /// Creates a new string without any style.
+    pub fn plain<S: Into<String>>(content: S) -> Self {
+        StyledString {
+            source: content.into(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Creates a new string entirely in the given style.
+    pub fn styled<S, T>(content: S, style: T) -> Self
+        where S: Into<String>,
+              T: Into<Style>
+    {
+        StyledString::new().with_styled(content, style)
+    }
+
+    /// Appends some text without any style.
+    pub fn append_plain<S: Into<String>>(&mut self, content: S) {
+        self.source.push_str(&content.into());
+    }
+
+    /// Appends some text in the given style.
+    pub fn append_styled<S, T>(&mut self, content: S, style: T)
+        where S: Into<String>,
+              T: Into<Style>
+    {
+        let content = content.into();
+        let start = self.source.len();
+        self.source.push_str(&content);
+        self.spans.push(Span {
+            start: start,
+            end: self.source.len(),
+            style: style.into(),
+            link: None,
+        });
+    }
+
+    /// Appends a hyperlink to `url`, in the given style.
+    ///
+    /// Views supporting links let the user activate them; terminals
+    /// supporting OSC 8 hyperlinks also make them clickable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::utils::StyledString;
+    /// # use cursive::theme::ColorStyle;
+    /// let mut s = StyledString::plain("See ");
+    /// s.append_link("the docs", "https://docs.rs", ColorStyle::Tertiary);
+    ///
+    /// assert_eq!(s.spans()[0].link, Some("https://docs.rs".to_string()));
+    /// ```
+    pub fn append_link<S, U, T>(&mut self, content: S, url: U, style: T)
+        where S: Into<String>,
+              U: Into<String>,
+              T: Into<Style>
+    {
+        self.append_styled(content, style);
+        if let Some(span) = self.spans.last_mut() {
+            span.link = Some(url.into());
+        }
+    }
+
+    /// Appends some text in the given style.
+    ///
+    /// Chainable variant.
+    pub fn with_styled<S, T>(mut self, content: S, style: T) -> Self
+        where S: Into<String>,
+              T: Into<Style>
+    {
+        self.append_styled(content, style);
+        self
+    }
+
+    /// Appends another styled string.
+    pub fn append(&mut self, other: StyledString) {
+        let offset = self.source.len();
+        self.source.push_str(&other.source);
+        self.spans.extend(other.spans.into_iter().map(|mut span| {
+            span.shift(offset);
+            span
+        }));
+    }
+
+    /// Returns the raw text, without any style.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the styled spans, in order.
+    ///
+    /// Spans never overlap.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Splits this string into its raw text and its spans.
+    pub fn into_parts(self) -> (String, Vec<Span>) {
+        (self.source, self.spans)
+    }
+
+    /// Returns `true` if this string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+}
+
+impl From<String> for StyledString {
+    fn from(content: String) -> Self {
+        StyledString::plain(content)
+    }
+}
+
+impl<'a> From<&'a str> for StyledString {
+    fn from(content: &'a str) -> Self {
+        StyledString::plain(content)
+    }
+}
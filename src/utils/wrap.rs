@@ -0,0 +1,335 @@
+//! Strategies to split text into rows.
+
+use std::cmp::max;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use utils::{LinesIterator, Row, prefix};
+
+/// Splits text into rows fitting in a given width.
+///
+/// Used by `TextView` to layout its content, and can be used by custom
+/// views as well.
+pub trait WrapStrategy {
+    /// Splits `content` into rows of `width` cells or less.
+    ///
+    /// The width of a row includes its marker, if any.
+    fn wrap(&self, content: &str, width: usize) -> Vec<Row>;
+
+    /// Returns the marker to print right after the given row, if any.
+    ///
+    /// This can be a hyphen or an ellipsis, for instance.
+    fn marker(&self, content: &str, row: &Row) -> Option<&str> {
+        let _ = (content, row);
+        None
+    }
+}
+
+/// Calls `f` on each line of `content`, with the line's offset.
+fn for_each_line<F>(content: &str, mut f: F)
+    where F: FnMut(usize, &str)
+{
+    let mut start = 0;
+    for line in content.split('\n') {
+        f(start, line);
+        start += line.len() + 1;
+    }
+}
+
+// Returns `true` if `row` ends before the end of its line.
+fn is_cut(content: &str, row: &Row) -> bool {
+    row.end < content.len() && !content[row.end..].starts_with('\n')
+}
+
+/// Breaks lines between words when possible.
+///
+/// Words too long to fit on a row are broken between graphemes.
+///
+/// This is the default strategy used by `TextView`.
+pub struct WordWrap;
+
+impl WrapStrategy for WordWrap {
+    fn wrap(&self, content: &str, width: usize) -> Vec<Row> {
+        LinesIterator::new(content, width).collect()
+    }
+}
+
+/// Breaks lines between any two graphemes.
+pub struct CharWrap;
+
+impl WrapStrategy for CharWrap {
+    fn wrap(&self, content: &str, width: usize) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for_each_line(content, |start, line| {
+            let mut offset = 0;
+            loop {
+                let rest = &line[offset..];
+                let full = prefix(rest.graphemes(true), width, "").length;
+                // Keep at least one grapheme, even if it doesn't fit.
+                let first = rest.graphemes(true).next().map_or(0, str::len);
+                let length = max(full, first);
+                rows.push(Row {
+                    start: start + offset,
+                    end: start + offset + length,
+                    width: rest[..length].width(),
+                    hyphenated: false,
+                });
+                offset += length;
+                if offset >= line.len() {
+                    return;
+                }
+            }
+        });
+        rows
+    }
+}
+
+/// Never breaks lines.
+///
+/// Lines too long to fit are truncated, and end with an ellipsis.
+pub struct NoWrap;
+
+impl WrapStrategy for NoWrap {
+    fn wrap(&self, content: &str, width: usize) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for_each_line(content, |start, line| {
+            let line_width = line.width();
+            let row = if line_width <= width {
+                Row {
+                    start: start,
+                    end: start + line.len(),
+                    width: line_width,
+                    hyphenated: false,
+                }
+            } else {
+                // Keep a cell for the ellipsis.
+                let p = prefix(line.graphemes(true),
+                               width.saturating_sub(1),
+                               "");
+                Row {
+                    start: start,
+                    end: start + p.length,
+                    width: line[..p.length].width() + 1,
+                    hyphenated: false,
+                }
+            };
+            rows.push(row);
+        });
+        rows
+    }
+
+    fn marker(&self, content: &str, row: &Row) -> Option<&str> {
+        if is_cut(content, row) { Some("…") } else { None }
+    }
+}
+
+/// Breaks lines between words, hyphenating words when that saves space.
+///
+/// Words are split between any two graphemes, as long as at least two of
+/// them stay on the row.
+pub struct Hyphenate;
+
+impl Hyphenate {
+    // Returns the length of the next row in `line`, the number of bytes
+    // to skip after it (for a space), and whether it is hyphenated.
+    fn next_row(line: &str, width: usize) -> (usize, usize, bool) {
+        if line.width() <= width {
+            return (line.len(), 0, false);
+        }
+
+        let full = prefix(line.graphemes(true), width, "").length;
+        if line[full..].starts_with(' ') {
+            // We stopped right at a word boundary.
+            return (full, 1, false);
+        }
+
+        // Start of the word we stopped in.
+        let word = line[..full].rfind(' ').map_or(0, |i| i + 1);
+
+        // Keep a cell for the hyphen.
+        let hyphenated =
+            prefix(line.graphemes(true), width.saturating_sub(1), "").length;
+        if hyphenated > word &&
+           line[word..hyphenated].graphemes(true).count() >= 2 {
+            (hyphenated, 0, true)
+        } else if word > 1 {
+            (word - 1, 1, false)
+        } else {
+            // Can't do much here, just cut the word. Keep at least one
+            // grapheme, even if it doesn't fit, so the rest isn't lost.
+            let first = line.graphemes(true).next().map_or(0, str::len);
+            let cut = max(full, first);
+            let skip = if line[cut..].starts_with(' ') { 1 } else { 0 };
+            (cut, skip, false)
+        }
+    }
+}
+
+impl WrapStrategy for Hyphenate {
+    fn wrap(&self, content: &str, width: usize) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for_each_line(content, |start, line| {
+            let mut offset = 0;
+            loop {
+                let rest = &line[offset..];
+                let (length, skip, hyphen) = Hyphenate::next_row(rest, width);
+                let hyphen_width = if hyphen { 1 } else { 0 };
+                rows.push(Row {
+                    start: start + offset,
+                    end: start + offset + length,
+                    width: rest[..length].width() + hyphen_width,
+                    hyphenated: hyphen,
+                });
+                offset += length + skip;
+                if offset >= line.len() {
+                    return;
+                }
+            }
+        });
+        rows
+    }
+
+    fn marker(&self, content: &str, row: &Row) -> Option<&str> {
+        let _ = content;
+        if row.hyphenated { Some("-") } else { None }
+    }
+}
+
+/// Updates `rows` after some text was replaced.
+///
+/// `content` is the new text, where the replacement spans
+/// `start..new_end`; it used to span `start..old_end`. Only the lines
+/// touched by the change are split again with `wrap`, and the rows after
+/// them are shifted.
+///
+/// Strategies splitting each line on its own (like the ones here) give
+/// the same rows as splitting everything again.
+pub fn rewrap<F>(rows: &mut Vec<Row>, content: &str, start: usize,
+                 old_end: usize, new_end: usize, wrap: F)
+    where F: FnOnce(&str) -> Vec<Row>
+{
+    // The change may split characters, but not newlines.
+    let bytes = content.as_bytes();
+    let line_start = bytes[..start]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let line_end = bytes[new_end..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(content.len(), |i| new_end + i);
+    // Where the touched lines ended before the change.
+    let old_line_end = line_end - new_end + old_end;
+
+    let first = rows.iter()
+        .position(|row| row.start >= line_start)
+        .unwrap_or_else(|| rows.len());
+    let last = rows.iter()
+        .position(|row| row.start > old_line_end)
+        .unwrap_or_else(|| rows.len());
+
+    let mut lines: Vec<Row> = wrap(&content[line_start..line_end])
+        .into_iter()
+        .map(|row| row.shifted(line_start))
+        .collect();
+    if lines.is_empty() && line_end < content.len() {
+        // Some strategies skip an empty last line, but it isn't last here.
+        lines.push(Row {
+                       start: line_start,
+                       end: line_start,
+                       width: 0,
+                       hyphenated: false,
+                   });
+    }
+
+    let tail: Vec<Row> = rows.drain(last..)
+        .map(|mut row| {
+            if new_end >= old_end {
+                row.shift(new_end - old_end);
+            } else {
+                row.rev_shift(old_end - new_end);
+            }
+            row
+        })
+        .collect();
+    rows.truncate(first);
+    rows.extend(lines);
+    rows.extend(tail);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(content: &'a str, rows: &[Row]) -> Vec<&'a str> {
+        rows.iter().map(|row| &content[row.start..row.end]).collect()
+    }
+
+    #[test]
+    fn test_char_wrap() {
+        let content = "abcdef\nab";
+        let rows = CharWrap.wrap(content, 4);
+        assert_eq!(texts(content, &rows), vec!["abcd", "ef", "ab"]);
+
+        // Characters too wide for a row still get one.
+        let content = "中文 ab";
+        let rows = CharWrap.wrap(content, 1);
+        assert_eq!(texts(content, &rows), vec!["中", "文", " ", "a", "b"]);
+    }
+
+    #[test]
+    fn test_no_wrap() {
+        let content = "abcdef\nab";
+        let rows = NoWrap.wrap(content, 4);
+        assert_eq!(texts(content, &rows), vec!["abc", "ab"]);
+        assert_eq!(rows[0].width, 4);
+        assert_eq!(NoWrap.marker(content, &rows[0]), Some("…"));
+        assert_eq!(NoWrap.marker(content, &rows[1]), None);
+    }
+
+    #[test]
+    fn test_hyphenate() {
+        let content = "ab abcdefg";
+        let rows = Hyphenate.wrap(content, 6);
+        assert_eq!(texts(content, &rows), vec!["ab ab", "cdefg"]);
+        assert_eq!(Hyphenate.marker(content, &rows[0]), Some("-"));
+        assert_eq!(Hyphenate.marker(content, &rows[1]), None);
+
+        // Don't leave a single letter before the hyphen.
+        let content = "abcd efgh";
+        let rows = Hyphenate.wrap(content, 6);
+        assert_eq!(texts(content, &rows), vec!["abcd", "efgh"]);
+        assert_eq!(Hyphenate.marker(content, &rows[0]), None);
+
+        // A word cut without room for a hyphen doesn't get one.
+        let content = "abcdef";
+        let rows = Hyphenate.wrap(content, 2);
+        assert_eq!(texts(content, &rows), vec!["ab", "cd", "ef"]);
+        assert!(rows.iter().all(|row| row.width == 2 && !row.hyphenated));
+        assert_eq!(Hyphenate.marker(content, &rows[0]), None);
+
+        // Wide characters are kept, even when none fits.
+        let content = "中文 ab";
+        let rows = Hyphenate.wrap(content, 1);
+        assert_eq!(texts(content, &rows), vec!["中", "文", "a", "b"]);
+    }
+
+    #[test]
+    fn test_rewrap() {
+        let old = "abc def\n\nghi jkl\nmno";
+        let mut rows = WordWrap.wrap(old, 4);
+
+        // Replace "def\n\ng" with "x".
+        let content = "abc xhi jkl\nmno";
+        rewrap(&mut rows, content, 4, 10, 5, |text| WordWrap.wrap(text, 4));
+        assert_eq!(rows, WordWrap.wrap(content, 4));
+
+        // Make an empty line in the middle.
+        let old = content;
+        let content = "abc xhi jkl\n\nmno";
+        let mut rows = WordWrap.wrap(old, 4);
+        rewrap(&mut rows, content, 12, 12, 13, |text| WordWrap.wrap(text, 4));
+        assert_eq!(rows, WordWrap.wrap(content, 4));
+        assert_eq!(texts(content, &rows),
+                   vec!["abc", "xhi", "jkl", "", "mno"]);
+    }
+}
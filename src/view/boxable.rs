@@ -57,6 +57,24 @@ pub trait Boxable: View + Sized {
         BoxView::with_max_height(max_height, self)
     }
 
+    /// Wraps `self` into a `BoxView` using a percentage of the available
+    /// size.
+    fn percent_size<S: Into<Vec2>>(self, percent: S) -> BoxView<Self> {
+        BoxView::with_percent_size(percent, self)
+    }
+
+    /// Wraps `self` into a `BoxView` using a percentage of the available
+    /// width.
+    fn percent_width(self, percent: usize) -> BoxView<Self> {
+        BoxView::with_percent_width(percent, self)
+    }
+
+    /// Wraps `self` into a `BoxView` using a percentage of the available
+    /// height.
+    fn percent_height(self, percent: usize) -> BoxView<Self> {
+        BoxView::with_percent_height(percent, self)
+    }
+
     /// Wraps `self` into a `BoxView` at least sized `size`.
     fn min_size<S: Into<Vec2>>(self, size: S) -> BoxView<Self> {
         BoxView::with_min_size(size, self)
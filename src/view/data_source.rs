@@ -0,0 +1,59 @@
+use std::ops::Range;
+
+/// Rows fetched on demand, as the user scrolls.
+///
+/// Views showing long lists, like `TableView`, only ask for the rows they
+/// show. The data can then live in a database or a file instead of memory.
+///
+/// `get_rows` is called from `View::layout`, so it should be fast enough
+/// to run on each frame, or cache results.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::view::ScrollableDataSource;
+/// # use std::ops::Range;
+/// // Squares of all numbers, computed when needed.
+/// struct Squares;
+///
+/// impl ScrollableDataSource for Squares {
+///     type Row = Vec<String>;
+///
+///     fn len(&self) -> usize {
+///         1_000_000
+///     }
+///
+///     fn get_rows(&mut self, range: Range<usize>) -> Vec<Vec<String>> {
+///         range.map(|i| vec![i.to_string(), (i * i).to_string()]).collect()
+///     }
+/// }
+///
+/// assert_eq!(Squares.get_rows(3..5)[1], vec!["4", "16"]);
+/// ```
+pub trait ScrollableDataSource {
+    /// Type of a row.
+    type Row;
+
+    /// Returns the total number of rows.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there is no row.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the rows in `range`, which is within `0..self.len()`.
+    fn get_rows(&mut self, range: Range<usize>) -> Vec<Self::Row>;
+}
+
+impl<T: Clone> ScrollableDataSource for Vec<T> {
+    type Row = T;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn get_rows(&mut self, range: Range<usize>) -> Vec<T> {
+        self[range].to_vec()
+    }
+}
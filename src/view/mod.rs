@@ -45,17 +45,20 @@ mod size_constraint;
 mod view_path;
 
 // Helper bases
+mod data_source;
 mod scroll;
 mod identifiable;
 mod boxable;
 
 
 pub use self::boxable::Boxable;
+pub use self::data_source::ScrollableDataSource;
 pub use self::identifiable::Identifiable;
 
-pub use self::position::{Offset, Position};
+pub use self::position::{Offset, Position, Rect};
 
-pub use self::scroll::{ScrollBase, ScrollStrategy};
+pub use self::scroll::{ScrollBase, ScrollStrategy, Scrollable,
+                       ScrollbarGeometry, ScrollbarVisibility};
 
 pub use self::size_cache::SizeCache;
 pub use self::size_constraint::SizeConstraint;
@@ -144,6 +147,55 @@ pub trait View {
         let _ = source;
         false
     }
+
+    /// Describes this view as plain text, for screen readers.
+    ///
+    /// Views with children describe their focused child instead, so the
+    /// root describes the element focused on the whole screen.
+    ///
+    /// Default implementation returns `None`.
+    fn describe(&self) -> Option<String> {
+        None
+    }
+}
+
+impl View for Box<View> {
+    fn on_event(&mut self, event: Event) -> EventResult {
+        (**self).on_event(event)
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        (**self).required_size(constraint)
+    }
+
+    fn needs_relayout(&self) -> bool {
+        (**self).needs_relayout()
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        (**self).layout(size)
+    }
+
+    fn draw(&self, printer: &Printer) {
+        (**self).draw(printer)
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       callback: Box<FnMut(&mut Any) + 'a>) {
+        (**self).call_on_any(selector, callback)
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        (**self).focus_view(selector)
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        (**self).take_focus(source)
+    }
+
+    fn describe(&self) -> Option<String> {
+        (**self).describe()
+    }
 }
 
 /// Provides `call_on<V: View>` to views.
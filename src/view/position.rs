@@ -1,4 +1,5 @@
 use XY;
+use direction::Absolute;
 use std::cmp::min;
 use vec::Vec2;
 
@@ -11,6 +12,26 @@ impl Position {
         Position::new(Offset::Center, Offset::Center)
     }
 
+    /// Returns a position in the top-left corner.
+    pub fn top_left() -> Self {
+        Position::new(Offset::Absolute(0), Offset::Absolute(0))
+    }
+
+    /// Returns a position in the top-right corner.
+    pub fn top_right() -> Self {
+        Position::new(Offset::FromEnd(0), Offset::Absolute(0))
+    }
+
+    /// Returns a position in the bottom-left corner.
+    pub fn bottom_left() -> Self {
+        Position::new(Offset::Absolute(0), Offset::FromEnd(0))
+    }
+
+    /// Returns a position in the bottom-right corner.
+    pub fn bottom_right() -> Self {
+        Position::new(Offset::FromEnd(0), Offset::FromEnd(0))
+    }
+
     /// Returns a position absolute on both axis.
     pub fn absolute<T: Into<Vec2>>(offset: T) -> Self {
         let offset = offset.into();
@@ -44,6 +65,93 @@ impl Position {
     }
 }
 
+/// Area of the screen, like the one a view was drawn on.
+///
+/// See `Printer::offset` and `Printer::size`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    /// Top-left corner.
+    pub offset: Vec2,
+    /// Size of the area.
+    pub size: Vec2,
+}
+
+impl Rect {
+    /// Creates a new `Rect` from its top-left corner and its size.
+    pub fn new<O: Into<Vec2>, S: Into<Vec2>>(offset: O, size: S) -> Self {
+        Rect {
+            offset: offset.into(),
+            size: size.into(),
+        }
+    }
+
+    /// Places a view of `size` next to this area.
+    ///
+    /// The view goes on the `preferred` side (below for
+    /// `Absolute::None`), unless it only fits on the opposite one. If it
+    /// fits on neither, it takes the side with the most room, and is
+    /// shrunk to this room. Along the other axis, it starts with this
+    /// area, and is moved back if it would cross the end of `available`.
+    ///
+    /// Returns the offset and the size of the view.
+    pub fn place_beside<S, A>(&self, size: S, available: A,
+                              preferred: Absolute)
+                              -> (Vec2, Vec2)
+        where S: Into<Vec2>,
+              A: Into<Vec2>
+    {
+        let size = size.into();
+        let available = available.into();
+        let start = self.offset;
+        let end = self.offset + self.size;
+
+        match preferred {
+            Absolute::Left | Absolute::Right => {
+                let after = preferred == Absolute::Right;
+                let (x, w) =
+                    beside(start.x, end.x, size.x, available.x, after);
+                let (y, h) = along(start.y, size.y, available.y);
+                (Vec2::new(x, y), Vec2::new(w, h))
+            }
+            Absolute::Up | Absolute::Down | Absolute::None => {
+                let after = preferred != Absolute::Up;
+                let (y, h) =
+                    beside(start.y, end.y, size.y, available.y, after);
+                let (x, w) = along(start.x, size.x, available.x);
+                (Vec2::new(x, y), Vec2::new(w, h))
+            }
+        }
+    }
+}
+
+// Places `size` before `start` or after `end`, on a single axis.
+fn beside(start: usize, end: usize, size: usize, available: usize,
+          after: bool)
+          -> (usize, usize) {
+    let before_room = min(start, available);
+    let end = min(end, available);
+    let after_room = available - end;
+
+    let after = if after {
+        size <= after_room || after_room >= before_room
+    } else {
+        size > before_room && after_room > before_room
+    };
+
+    if after {
+        (end, min(size, after_room))
+    } else {
+        let size = min(size, before_room);
+        (before_room - size, size)
+    }
+}
+
+// Places `size` from `start`, moved back to fit in `available`.
+fn along(start: usize, size: usize, available: usize) -> (usize, usize) {
+    let size = min(size, available);
+    (min(start, available - size), size)
+}
+
 /// Single-dimensional offset policy.
 #[derive(PartialEq,Debug,Clone)]
 pub enum Offset {
@@ -56,6 +164,17 @@ pub enum Offset {
     ///
     /// If this is the first layer, behaves like `Absolute`.
     Parent(isize),
+
+    /// Place bottom-right corner at the given distance from the end.
+    ///
+    /// `FromEnd(0)` sticks the view to the right or bottom edge.
+    FromEnd(usize),
+
+    /// Move by the given percentage of the free space.
+    ///
+    /// `Percent(0)` is the start, `Percent(100)` the end, and
+    /// `Percent(50)` the center.
+    Percent(usize),
 }
 
 impl Offset {
@@ -72,6 +191,12 @@ impl Offset {
                 Offset::Parent(offset) => {
                     min((parent as isize + offset) as usize, available - size)
                 }
+                Offset::FromEnd(offset) => {
+                    (available - size).saturating_sub(offset)
+                }
+                Offset::Percent(percent) => {
+                    (available - size) * min(percent, 100) / 100
+                }
             }
         }
     }
@@ -80,7 +205,8 @@ impl Offset {
 #[cfg(test)]
 mod tests {
 
-    use super::Position;
+    use super::{Offset, Position, Rect};
+    use direction::Absolute;
     use vec::Vec2;
 
     #[test]
@@ -93,4 +219,41 @@ mod tests {
         assert_eq!(Vec2::new(0, 0), c.compute_offset((5, 3), (5, 3), (0, 0)));
         assert_eq!(Vec2::new(0, 0), c.compute_offset((5, 3), (3, 1), (0, 0)));
     }
+
+    #[test]
+    fn test_from_end() {
+        let p = Position::new(Offset::FromEnd(0), Offset::FromEnd(2));
+        assert_eq!(Vec2::new(4, 1), p.compute_offset((1, 1), (5, 4), (0, 0)));
+        assert_eq!(Vec2::new(0, 0), p.compute_offset((5, 3), (5, 4), (0, 0)));
+    }
+
+    #[test]
+    fn test_percent() {
+        let p = Position::new(Offset::Percent(25), Offset::Percent(200));
+        assert_eq!(Vec2::new(2, 3), p.compute_offset((2, 1), (10, 4), (0, 0)));
+    }
+
+    #[test]
+    fn test_place_beside() {
+        let available = Vec2::new(20, 10);
+        let anchor = Rect::new((15, 2), (4, 1));
+
+        // Below, moved back from the right edge.
+        assert_eq!(anchor.place_beside((8, 3), available, Absolute::Down),
+                   (Vec2::new(12, 3), Vec2::new(8, 3)));
+
+        // Too tall for below: flipped above.
+        let anchor = Rect::new((0, 7), (4, 1));
+        assert_eq!(anchor.place_beside((8, 4), available, Absolute::None),
+                   (Vec2::new(0, 3), Vec2::new(8, 4)));
+
+        // No room to the right.
+        assert_eq!(anchor.place_beside((8, 4), available, Absolute::Left),
+                   (Vec2::new(4, 6), Vec2::new(8, 4)));
+
+        // Fits nowhere: shrunk on the largest side.
+        let anchor = Rect::new((0, 3), (4, 1));
+        assert_eq!(anchor.place_beside((8, 9), available, Absolute::Up),
+                   (Vec2::new(0, 4), Vec2::new(8, 6)));
+    }
 }
@@ -1,9 +1,23 @@
 use Printer;
 use std::cmp::{max, min};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use theme::ColorStyle;
 use vec::Vec2;
 
+/// View with a scroll position, which can be read and set from outside.
+///
+/// `ScrollSync` uses it to keep several views scrolled together.
+pub trait Scrollable {
+    /// Returns the current scroll position.
+    fn scroll_offset(&self) -> Vec2;
+
+    /// Sets the scroll position.
+    ///
+    /// The view clamps it to its content.
+    fn set_scroll_offset(&mut self, offset: Vec2);
+}
+
 /// Provide scrolling functionalities to a view.
 ///
 /// You're not supposed to use this directly,
@@ -27,9 +41,78 @@ pub struct ScrollBase {
 
     /// Blank between the text and the scrollbar.
     pub right_padding: usize,
+
+    /// When to show the scrollbar.
+    pub visibility: ScrollbarVisibility,
+
+    /// How to adjust the scroll position when the heights change.
+    pub strategy: ScrollStrategy,
+
+    // Line we're smoothly scrolling to.
+    target: Option<usize>,
+}
+
+static SMOOTH_SCROLLING: AtomicBool = AtomicBool::new(false);
+
+/// Defines when a scrollbar is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollbarVisibility {
+    /// Always shows the scrollbar, even if the content fits.
+    Always,
+    /// Only shows the scrollbar when the content is too large.
+    Auto,
+    /// Never shows the scrollbar. The content can still be scrolled.
+    Never,
+}
+
+impl Default for ScrollbarVisibility {
+    fn default() -> Self {
+        ScrollbarVisibility::Auto
+    }
+}
+
+impl ScrollbarVisibility {
+    /// Returns `true` if a scrollbar is shown when `scrollable` is
+    /// whether the content is larger than the view.
+    pub fn shown(&self, scrollable: bool) -> bool {
+        match *self {
+            ScrollbarVisibility::Always => true,
+            ScrollbarVisibility::Auto => scrollable,
+            ScrollbarVisibility::Never => false,
+        }
+    }
+}
+
+/// Position of a scrollbar inside its view.
+///
+/// Can be used to find out if a position falls on the scrollbar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollbarGeometry {
+    /// Column of the scrollbar.
+    pub x: usize,
+    /// Height of the scrollbar.
+    pub height: usize,
+    /// First row of the thumb.
+    pub thumb_start: usize,
+    /// Number of rows covered by the thumb.
+    pub thumb_height: usize,
+}
+
+impl ScrollbarGeometry {
+    /// Returns `true` if `pos` is on the scrollbar.
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.x == self.x && pos.y < self.height
+    }
+
+    /// Returns `true` if `pos` is on the thumb.
+    pub fn thumb_contains(&self, pos: Vec2) -> bool {
+        pos.x == self.x && pos.y >= self.thumb_start &&
+        pos.y < self.thumb_start + self.thumb_height
+    }
 }
 
 /// Defines the scrolling behaviour on content or size change
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ScrollStrategy {
     /// Keeps the same row number
     KeepRow,
@@ -54,6 +137,63 @@ impl ScrollBase {
             view_height: 0,
             scrollbar_offset: 0,
             right_padding: 1,
+            visibility: ScrollbarVisibility::Auto,
+            strategy: ScrollStrategy::KeepRow,
+            target: None,
+        }
+    }
+
+    /// Enables or disables smooth scrolling for all views.
+    ///
+    /// When enabled, scrolling by more than one line is animated: each
+    /// call to [`animate`] moves a bit closer to the destination.
+    ///
+    /// [`animate`]: #method.animate
+    pub fn set_smooth_scrolling(enabled: bool) {
+        SMOOTH_SCROLLING.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if smooth scrolling is enabled.
+    ///
+    /// Defaults to `false`.
+    pub fn smooth_scrolling() -> bool {
+        SMOOTH_SCROLLING.load(Ordering::Relaxed)
+    }
+
+    /// Moves one frame further in a smooth scrolling animation.
+    ///
+    /// Views should call this on each `Event::Refresh`.
+    ///
+    /// Returns `true` if the view scrolled.
+    pub fn animate(&mut self) -> bool {
+        let target = match self.target {
+            Some(target) => target,
+            None => return false,
+        };
+
+        // Cover a third of the remaining distance, at least one line.
+        if target > self.start_line {
+            self.start_line += max(1, (target - self.start_line) / 3);
+        } else {
+            self.start_line -= max(1, (self.start_line - target) / 3);
+        }
+
+        if self.start_line == target {
+            self.target = None;
+        }
+
+        true
+    }
+
+    // Scrolls to the given line, smoothly if enabled.
+    fn scroll_smoothly(&mut self, line: usize) {
+        let distance = max(line, self.start_line) - min(line, self.start_line);
+        if distance > 1 && ScrollBase::smooth_scrolling() {
+            self.target = Some(line);
+            self.animate();
+        } else {
+            self.start_line = line;
+            self.target = None;
         }
     }
 
@@ -75,16 +215,123 @@ impl ScrollBase {
         self
     }
 
+    /// Sets when to show the scrollbar.
+    ///
+    /// Defaults to `ScrollbarVisibility::Auto`.
+    pub fn visibility(mut self, visibility: ScrollbarVisibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Returns `true` if the scrollbar is currently shown.
+    pub fn shows_scrollbar(&self) -> bool {
+        self.visibility.shown(self.scrollable())
+    }
+
+    /// Returns the position of the scrollbar in a view of the given size.
+    ///
+    /// Returns `None` if the scrollbar is hidden, or if there is no room
+    /// for it.
+    pub fn scrollbar_geometry(&self, size: Vec2) -> Option<ScrollbarGeometry> {
+        if !self.shows_scrollbar() || size.x <= self.scrollbar_offset ||
+           self.view_height == 0 {
+            return None;
+        }
+
+        // When the content fits, the thumb covers the whole bar.
+        if !self.scrollable() {
+            return Some(ScrollbarGeometry {
+                x: size.x - 1 - self.scrollbar_offset,
+                height: size.y,
+                thumb_start: 0,
+                thumb_height: self.view_height,
+            });
+        }
+
+        // We directly compute the size of the scrollbar
+        // (that way we avoid using floats).
+        // (ratio) * max_height
+        // Where ratio is ({start or end} / content.height)
+        let height = max(1,
+                         self.view_height * self.view_height /
+                         self.content_height);
+        // Number of different possible positions
+        let steps = self.view_height - height + 1;
+
+        // Now
+        let start = steps * self.start_line /
+                    (1 + self.content_height - self.view_height);
+
+        Some(ScrollbarGeometry {
+            x: size.x - 1 - self.scrollbar_offset,
+            height: size.y,
+            thumb_start: start,
+            thumb_height: height,
+        })
+    }
+
     /// Call this method whem the content or the view changes.
     pub fn set_heights(&mut self, view_height: usize, content_height: usize) {
         self.view_height = view_height;
         self.content_height = content_height;
 
         if self.scrollable() {
-            self.start_line = min(self.start_line,
-                                  self.content_height - self.view_height);
+            let max_line = self.content_height - self.view_height;
+            self.start_line = min(self.start_line, max_line);
+            self.target = self.target.map(|target| min(target, max_line));
         } else {
             self.start_line = 0;
+            self.target = None;
+        }
+
+        self.adjust_scroll();
+    }
+
+    /// Sets how to adjust the scroll position when the heights change.
+    pub fn set_strategy(&mut self, strategy: ScrollStrategy) {
+        self.strategy = strategy;
+        self.adjust_scroll();
+    }
+
+    /// Updates the strategy after the user scrolled manually.
+    ///
+    /// The strategy is reset to `ScrollStrategy::KeepRow`, unless it was
+    /// `ScrollStrategy::StickToBottom` and we are still at the bottom:
+    /// in this case, the view stays pinned to the bottom.
+    pub fn user_scrolled(&mut self) {
+        if self.strategy != ScrollStrategy::StickToBottom ||
+           self.can_scroll_down() {
+            self.strategy = ScrollStrategy::KeepRow;
+        }
+    }
+
+    // Applies the strategy to the current scroll position.
+    fn adjust_scroll(&mut self) {
+        match self.strategy {
+            ScrollStrategy::StickToTop => self.scroll_top(),
+            ScrollStrategy::StickToBottom => self.scroll_bottom(),
+            ScrollStrategy::KeepRow => (),
+        }
+    }
+
+    /// Returns the current scroll position.
+    ///
+    /// Only vertical scrolling is supported, so `x` is always 0.
+    pub fn scroll_offset(&self) -> Vec2 {
+        Vec2::new(0, self.start_line)
+    }
+
+    /// Sets the scroll position.
+    ///
+    /// `offset.x` is ignored. Before the first layout, the position is
+    /// kept as is, and checked once the heights are known.
+    pub fn set_scroll_offset(&mut self, offset: Vec2) {
+        self.target = None;
+        self.start_line = offset.y;
+        if self.view_height != 0 {
+            self.start_line = min(self.start_line,
+                                  self.content_height
+                                      .saturating_sub(self.view_height));
         }
     }
 
@@ -105,11 +352,13 @@ impl ScrollBase {
 
     /// Scroll to the top of the view.
     pub fn scroll_top(&mut self) {
+        self.target = None;
         self.start_line = 0;
     }
 
     /// Makes sure that the given line is visible, scrolling if needed.
     pub fn scroll_to(&mut self, y: usize) {
+        self.target = None;
         if y >= self.start_line + self.view_height {
             self.start_line = 1 + y - self.view_height;
         } else if y < self.start_line {
@@ -119,6 +368,7 @@ impl ScrollBase {
 
     /// Scroll to the bottom of the view.
     pub fn scroll_bottom(&mut self) {
+        self.target = None;
         if self.scrollable() {
             self.start_line = self.content_height - self.view_height;
         }
@@ -127,19 +377,25 @@ impl ScrollBase {
     /// Scroll down by the given number of line.
     ///
     /// Never further than the bottom of the view.
+    ///
+    /// Animated if smooth scrolling is enabled.
     pub fn scroll_down(&mut self, n: usize) {
         if self.scrollable() {
-            self.start_line = min(self.start_line + n,
-                                  self.content_height - self.view_height);
+            let start = self.target.unwrap_or(self.start_line);
+            let line = min(start + n, self.content_height - self.view_height);
+            self.scroll_smoothly(line);
         }
     }
 
     /// Scroll up by the given number of lines.
     ///
     /// Never above the top of the view.
+    ///
+    /// Animated if smooth scrolling is enabled.
     pub fn scroll_up(&mut self, n: usize) {
         if self.scrollable() {
-            self.start_line -= min(self.start_line, n);
+            let start = self.target.unwrap_or(self.start_line);
+            self.scroll_smoothly(start - min(start, n));
         }
     }
 
@@ -168,6 +424,36 @@ impl ScrollBase {
     /// ```
     pub fn draw<F>(&self, printer: &Printer, line_drawer: F)
         where F: Fn(&Printer, usize)
+    {
+        self.draw_sticky(printer, |_| false, line_drawer);
+    }
+
+    /// Returns the sticky line pinned at the top of the view, if any.
+    ///
+    /// This is the last line above the visible ones for which `is_sticky`
+    /// returns `true`, like the header of the current section.
+    pub fn pinned_line<S>(&self, is_sticky: S) -> Option<usize>
+        where S: Fn(usize) -> bool
+    {
+        if self.view_height < 2 || self.start_line >= self.content_height {
+            return None;
+        }
+        if is_sticky(self.start_line) {
+            // Already visible.
+            return None;
+        }
+        (0..self.start_line).rev().find(|&i| is_sticky(i))
+    }
+
+    /// Like `draw`, but keeps sticky lines pinned at the top of the view.
+    ///
+    /// When the user scrolls past a line for which `is_sticky` returns
+    /// `true`, it stays on the first row, over the content, until the
+    /// next sticky line replaces it. See `pinned_line`.
+    pub fn draw_sticky<S, F>(&self, printer: &Printer, is_sticky: S,
+                             line_drawer: F)
+        where S: Fn(usize) -> bool,
+              F: Fn(&Printer, usize)
     {
         if self.view_height == 0 {
             return;
@@ -175,7 +461,7 @@ impl ScrollBase {
         // Print the content in a sub_printer
         let max_y = min(self.view_height,
                         self.content_height - self.start_line);
-        let w = if self.scrollable() {
+        let w = if self.shows_scrollbar() {
             if printer.size.x < 2 {
                 return;
             }
@@ -184,44 +470,84 @@ impl ScrollBase {
         } else {
             printer.size.x
         };
+        let pinned = self.pinned_line(is_sticky);
         for y in 0..max_y {
             // Y is the actual coordinate of the line.
             // The item ID is then Y + self.start_line
+            let i = match pinned {
+                Some(i) if y == 0 => i,
+                _ => y + self.start_line,
+            };
             line_drawer(&printer.sub_printer(Vec2::new(0, y),
                                              Vec2::new(w, 1),
                                              true),
-                        y + self.start_line);
+                        i);
         }
 
 
         // And draw the scrollbar if needed
-        if self.view_height < self.content_height {
-            // We directly compute the size of the scrollbar
-            // (that way we avoid using floats).
-            // (ratio) * max_height
-            // Where ratio is ({start or end} / content.height)
-            let height = max(1,
-                             self.view_height * self.view_height /
-                             self.content_height);
-            // Number of different possible positions
-            let steps = self.view_height - height + 1;
-
-            // Now
-            let start = steps * self.start_line /
-                        (1 + self.content_height - self.view_height);
-
-            let color = if printer.focused {
-                ColorStyle::Highlight
-            } else {
-                ColorStyle::HighlightInactive
+        if let Some(bar) = self.scrollbar_geometry(printer.size) {
+            let style = &printer.theme.scrollbar;
+            let back = printer.theme.colors.view;
+
+            let track_color = match style.track_color {
+                Some(front) => ColorStyle::Custom { front: front, back: back },
+                None => ColorStyle::Primary,
+            };
+            let thumb_color = match style.thumb_color {
+                Some(front) => ColorStyle::Custom { front: front, back: back },
+                None if printer.focused => ColorStyle::Highlight,
+                None => ColorStyle::HighlightInactive,
             };
 
-            // TODO: use 1 instead of 2
-            let scrollbar_x = printer.size.x - 1 - self.scrollbar_offset;
-            printer.print_vline((scrollbar_x, 0), printer.size.y, "|");
-            printer.with_color(color, |printer| {
-                printer.print_vline((scrollbar_x, start), height, "▒");
+            printer.with_color(track_color, |printer| {
+                printer.print_vline((bar.x, 0), bar.height, &style.track);
+            });
+            printer.with_color(thumb_color, |printer| {
+                printer.print_vline((bar.x, bar.thumb_start),
+                                    bar.thumb_height,
+                                    &style.thumb);
             });
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ScrollBase;
+
+    #[test]
+    fn pinned_line() {
+        let mut scrollbase = ScrollBase::new();
+        scrollbase.set_heights(3, 20);
+        let is_sticky = |i| i % 5 == 0;
+        assert_eq!(scrollbase.pinned_line(is_sticky), None);
+
+        scrollbase.scroll_down(7);
+        assert_eq!(scrollbase.pinned_line(is_sticky), Some(5));
+        scrollbase.scroll_down(3);
+        assert_eq!(scrollbase.pinned_line(is_sticky), None);
+    }
+
+    #[test]
+    fn smooth_scrolling() {
+        let mut scrollbase = ScrollBase::new();
+        scrollbase.set_heights(5, 30);
+
+        ScrollBase::set_smooth_scrolling(true);
+        scrollbase.scroll_down(10);
+        ScrollBase::set_smooth_scrolling(false);
+
+        // The first step is taken right away.
+        assert_eq!(scrollbase.start_line, 3);
+        let mut frames = 0;
+        while scrollbase.animate() {
+            frames += 1;
+        }
+        assert_eq!(scrollbase.start_line, 10);
+        assert!(frames > 1);
+
+        scrollbase.scroll_down(10);
+        assert_eq!(scrollbase.start_line, 20);
+    }
+}
@@ -5,7 +5,7 @@ use std::cmp::min;
 /// This describes a possible behaviour for a [`BoxView`].
 ///
 /// [`BoxView`]: ../views/struct.BoxView.html
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SizeConstraint {
     /// No constraint imposed, the child view's response is used.
     Free,
@@ -17,6 +17,10 @@ pub enum SizeConstraint {
     AtMost(usize),
     /// Returns the maximum of the included value and the child view's size.
     AtLeast(usize),
+    /// Always uses the included percentage of the available size.
+    ///
+    /// Values above `100` are treated as `100`.
+    Percent(usize),
 }
 
 impl SizeConstraint {
@@ -31,6 +35,9 @@ impl SizeConstraint {
             // If the available space is too small, always give in.
             SizeConstraint::Fixed(value) |
             SizeConstraint::AtMost(value) => min(value, available),
+            SizeConstraint::Percent(percent) => {
+                available * min(percent, 100) / 100
+            }
         }
     }
 
@@ -42,7 +49,9 @@ impl SizeConstraint {
             SizeConstraint::AtLeast(value) if result < value => value,
             SizeConstraint::AtMost(value) if result > value => value,
             SizeConstraint::Fixed(value) => value,
-            SizeConstraint::Full => available,
+            // `available` already is the percentage given to the child.
+            SizeConstraint::Full |
+            SizeConstraint::Percent(_) => available,
             _ => result,
         }
     }
@@ -66,9 +66,14 @@ pub trait ViewWrapper {
     fn wrap_needs_relayout(&self) -> bool {
         self.with_view(|v| v.needs_relayout()).unwrap_or(true)
     }
+
+    /// Wraps the `describe` method.
+    fn wrap_describe(&self) -> Option<String> {
+        self.with_view(|v| v.describe()).unwrap_or(None)
+    }
 }
 
-impl<T: ViewWrapper> View for T {
+impl<T: ViewWrapper + Any> View for T {
     fn draw(&self, printer: &Printer) {
         self.wrap_draw(printer);
     }
@@ -78,7 +83,8 @@ impl<T: ViewWrapper> View for T {
     }
 
     fn on_event(&mut self, ch: Event) -> EventResult {
-        self.wrap_on_event(ch)
+        // Wrappers can be reached with `EventResult::with_ancestor`.
+        self.wrap_on_event(ch).resolve_on(self)
     }
 
     fn layout(&mut self, size: Vec2) {
@@ -101,6 +107,10 @@ impl<T: ViewWrapper> View for T {
     fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
         self.wrap_focus_view(selector)
     }
+
+    fn describe(&self) -> Option<String> {
+        self.wrap_describe()
+    }
 }
 
 /// Convenient macro to implement the [`ViewWrapper`] trait.
@@ -0,0 +1,194 @@
+use Printer;
+use With;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::any::Any;
+use vec::Vec2;
+use view::{Position, Selector, View};
+
+/// Places its children at explicit positions.
+///
+/// Each child gets its own [`Position`], which can be absolute, centered,
+/// relative to the right or bottom edge, or a percentage of the free
+/// space. Children added last are drawn on top.
+///
+/// The layout takes all the space it is given.
+///
+/// `<Tab>` and `<Shift+Tab>` move the focus between children.
+///
+/// [`Position`]: ../view/type.Position.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::view::{Offset, Position};
+/// # use cursive::views::{AbsoluteLayout, TextView};
+/// let hud = AbsoluteLayout::new()
+///     .child(Position::absolute((0, 0)), TextView::new("Score: 0"))
+///     .child(Position::new(Offset::FromEnd(0), Offset::FromEnd(0)),
+///            TextView::new("Lives: 3"))
+///     .child(Position::new(Offset::Percent(50), Offset::Percent(25)),
+///            TextView::new("Get ready!"));
+/// ```
+pub struct AbsoluteLayout {
+    children: Vec<Child>,
+    focus: usize,
+}
+
+struct Child {
+    view: Box<View>,
+    position: Position,
+    offset: Vec2,
+    size: Vec2,
+}
+
+new_default!(AbsoluteLayout);
+
+impl AbsoluteLayout {
+    /// Creates a new, empty layout.
+    pub fn new() -> Self {
+        AbsoluteLayout {
+            children: Vec::new(),
+            focus: 0,
+        }
+    }
+
+    /// Adds a child at the given position.
+    pub fn add_child<V: View + 'static>(&mut self, position: Position,
+                                        view: V) {
+        self.children.push(Child {
+            view: Box::new(view),
+            position: position,
+            offset: Vec2::zero(),
+            size: Vec2::zero(),
+        });
+    }
+
+    /// Adds a child at the given position.
+    ///
+    /// Chainable variant.
+    pub fn child<V: View + 'static>(self, position: Position, view: V)
+                                    -> Self {
+        self.with(|s| s.add_child(position, view))
+    }
+
+    /// Moves the child at index `i`.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn set_position(&mut self, i: usize, position: Position) {
+        self.children[i].position = position;
+    }
+
+    /// Removes the child at index `i`.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn remove_child(&mut self, i: usize) -> Box<View> {
+        if self.focus > i || self.focus + 1 == self.children.len() {
+            self.focus = self.focus.saturating_sub(1);
+        }
+        self.children.remove(i).view
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns `true` if this layout has no child.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    // Gives the focus to the next child accepting it, after the current one.
+    fn move_focus(&mut self, source: Direction) -> EventResult {
+        let len = self.children.len();
+        let start = self.focus;
+        let forward = source == Direction::front();
+        for step in 1..len {
+            let i = if forward {
+                (start + step) % len
+            } else {
+                (start + len - step) % len
+            };
+            if self.children[i].view.take_focus(source) {
+                self.focus = i;
+                return EventResult::Consumed(None);
+            }
+        }
+        EventResult::Ignored
+    }
+}
+
+impl View for AbsoluteLayout {
+    fn draw(&self, printer: &Printer) {
+        for (i, child) in self.children.iter().enumerate() {
+            let focused = printer.focused && i == self.focus;
+            child.view
+                .draw(&printer.sub_printer(child.offset, child.size, focused));
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        req
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        for child in &mut self.children {
+            child.size = Vec2::min(size, child.view.required_size(size));
+            child.offset =
+                child.position.compute_offset(child.size, size, Vec2::zero());
+            child.view.layout(child.size);
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.children.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        match self.children[self.focus].view.on_event(event.clone()) {
+            EventResult::Ignored => {
+                match event {
+                    Event::Key(Key::Tab) => {
+                        self.move_focus(Direction::front())
+                    }
+                    Event::Shift(Key::Tab) => {
+                        self.move_focus(Direction::back())
+                    }
+                    _ => EventResult::Ignored,
+                }
+            }
+            res => res.resolve_on(self),
+        }
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        match self.children
+            .iter_mut()
+            .position(|child| child.view.take_focus(source)) {
+            Some(i) => {
+                self.focus = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        for child in &mut self.children {
+            child.view.call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        for (i, child) in self.children.iter_mut().enumerate() {
+            if child.view.focus_view(selector).is_ok() {
+                self.focus = i;
+                return Ok(());
+            }
+        }
+
+        Err(())
+    }
+}
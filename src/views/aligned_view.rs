@@ -0,0 +1,98 @@
+use Printer;
+use align::Align;
+use vec::Vec2;
+use view::{View, ViewWrapper};
+
+/// Places its child somewhere in the available space.
+///
+/// The child keeps its required size, and is placed at the position
+/// given by an [`Align`]: one of the corners, the middle of an edge, or
+/// the center.
+///
+/// This view always takes all the space it is given. Use a [`BoxView`]
+/// around it to limit the area the child is aligned in.
+///
+/// [`Align`]: ../align/struct.Align.html
+/// [`BoxView`]: struct.BoxView.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::align::Align;
+/// # use cursive::views::{AlignedView, TextView};
+/// let view = AlignedView::new(Align::bot_right(), TextView::new("v1.0"));
+/// ```
+pub struct AlignedView<T: View> {
+    view: T,
+    align: Align,
+    offset: Vec2,
+    size: Vec2,
+}
+
+impl<T: View> AlignedView<T> {
+    /// Wraps `view` in a new `AlignedView` with the given alignment.
+    pub fn new(align: Align, view: T) -> Self {
+        AlignedView {
+            view: view,
+            align: align,
+            offset: Vec2::zero(),
+            size: Vec2::zero(),
+        }
+    }
+
+    /// Wraps `view` in a new `AlignedView`, centered in the available space.
+    pub fn center(view: T) -> Self {
+        AlignedView::new(Align::center(), view)
+    }
+
+    /// Sets the alignment for the child.
+    pub fn set_align(&mut self, align: Align) {
+        self.align = align;
+    }
+}
+
+impl<T: View> ViewWrapper for AlignedView<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_required_size(&mut self, req: Vec2) -> Vec2 {
+        req
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.size = Vec2::min(size, self.view.required_size(size));
+        self.offset = Vec2::new(self.align.h.get_offset(self.size.x, size.x),
+                                self.align.v.get_offset(self.size.y, size.y));
+        self.view.layout(self.size);
+    }
+
+    fn wrap_draw(&self, printer: &Printer) {
+        self.view.draw(&printer.sub_printer(self.offset, self.size, true));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlignedView;
+    use align::Align;
+    use vec::Vec2;
+    use view::View;
+    use views::TextView;
+
+    #[test]
+    fn offset() {
+        let size = Vec2::new(10, 5);
+
+        let mut view = AlignedView::new(Align::bot_right(),
+                                        TextView::new("abc"));
+        view.layout(size);
+        assert_eq!(view.offset, Vec2::new(7, 4));
+
+        view.set_align(Align::center());
+        view.layout(size);
+        assert_eq!(view.offset, Vec2::new(3, 2));
+
+        view.set_align(Align::center_left());
+        view.layout(size);
+        assert_eq!(view.offset, Vec2::new(0, 2));
+    }
+}
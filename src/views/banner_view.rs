@@ -0,0 +1,381 @@
+use Printer;
+use With;
+use std::cmp::max;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use theme::{Color, ColorStyle};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use vec::Vec2;
+use view::View;
+
+// Built-in block font, 5 rows high.
+const BLOCK: &[(char, [&str; 5])] =
+    &[('A', [" ## ", "#  #", "####", "#  #", "#  #"]),
+      ('B', ["### ", "#  #", "### ", "#  #", "### "]),
+      ('C', [" ###", "#   ", "#   ", "#   ", " ###"]),
+      ('D', ["### ", "#  #", "#  #", "#  #", "### "]),
+      ('E', ["####", "#   ", "### ", "#   ", "####"]),
+      ('F', ["####", "#   ", "### ", "#   ", "#   "]),
+      ('G', [" ###", "#   ", "# ##", "#  #", " ###"]),
+      ('H', ["#  #", "#  #", "####", "#  #", "#  #"]),
+      ('I', ["###", " # ", " # ", " # ", "###"]),
+      ('J', ["  ##", "   #", "   #", "#  #", " ## "]),
+      ('K', ["#  #", "# # ", "##  ", "# # ", "#  #"]),
+      ('L', ["#   ", "#   ", "#   ", "#   ", "####"]),
+      ('M', ["#   #", "## ##", "# # #", "#   #", "#   #"]),
+      ('N', ["#   #", "##  #", "# # #", "#  ##", "#   #"]),
+      ('O', [" ## ", "#  #", "#  #", "#  #", " ## "]),
+      ('P', ["### ", "#  #", "### ", "#   ", "#   "]),
+      ('Q', [" ## ", "#  #", "#  #", "# # ", " # #"]),
+      ('R', ["### ", "#  #", "### ", "# # ", "#  #"]),
+      ('S', [" ###", "#   ", " ## ", "   #", "### "]),
+      ('T', ["#####", "  #  ", "  #  ", "  #  ", "  #  "]),
+      ('U', ["#  #", "#  #", "#  #", "#  #", " ## "]),
+      ('V', ["#   #", "#   #", "#   #", " # # ", "  #  "]),
+      ('W', ["#   #", "#   #", "# # #", "## ##", "#   #"]),
+      ('X', ["#   #", " # # ", "  #  ", " # # ", "#   #"]),
+      ('Y', ["#   #", " # # ", "  #  ", "  #  ", "  #  "]),
+      ('Z', ["####", "   #", " ## ", "#   ", "####"]),
+      ('0', [" ## ", "# ##", "#  #", "## #", " ## "]),
+      ('1', [" # ", "## ", " # ", " # ", "###"]),
+      ('2', ["### ", "   #", " ## ", "#   ", "####"]),
+      ('3', ["### ", "   #", " ## ", "   #", "### "]),
+      ('4', ["#  #", "#  #", "####", "   #", "   #"]),
+      ('5', ["####", "#   ", "### ", "   #", "### "]),
+      ('6', [" ## ", "#   ", "### ", "#  #", " ## "]),
+      ('7', ["####", "   #", "  # ", " #  ", " #  "]),
+      ('8', [" ## ", "#  #", " ## ", "#  #", " ## "]),
+      ('9', [" ## ", "#  #", " ###", "   #", " ## "]),
+      (' ', ["  ", "  ", "  ", "  ", "  "]),
+      ('.', [" ", " ", " ", " ", "#"]),
+      (',', [" ", " ", " ", "#", "#"]),
+      ('!', ["#", "#", "#", " ", "#"]),
+      ('?', ["### ", "   #", " ## ", "    ", " #  "]),
+      ('-', ["   ", "   ", "###", "   ", "   "]),
+      (':', [" ", "#", " ", "#", " "]),
+      ('\'', ["#", "#", " ", " ", " "])];
+
+/// Error while loading a FIGlet font.
+#[derive(Debug)]
+pub enum FontError {
+    /// An error occured when reading the file.
+    Io(io::Error),
+    /// The file doesn't start with a valid FIGlet header.
+    InvalidHeader,
+    /// The file ends before all the required characters.
+    Truncated,
+}
+
+impl From<io::Error> for FontError {
+    fn from(err: io::Error) -> Self {
+        FontError::Io(err)
+    }
+}
+
+/// Font used by a [`BannerView`].
+///
+/// Each character is drawn on several rows. Characters missing from the
+/// font are drawn as `?`, or skipped if the font has no `?` either.
+///
+/// [`BannerView`]: struct.BannerView.html
+#[derive(Clone, Debug)]
+pub struct BannerFont {
+    height: usize,
+    glyphs: HashMap<char, Vec<String>>,
+    // Columns left between characters.
+    spacing: usize,
+    // Lower case letters are drawn as upper case ones.
+    upper_case: bool,
+}
+
+impl BannerFont {
+    /// Returns the built-in block font, 5 rows high.
+    ///
+    /// It only has upper case letters, digits and some punctuation. Lower
+    /// case letters are drawn as upper case ones.
+    pub fn block() -> Self {
+        let glyphs = BLOCK.iter()
+            .map(|&(c, ref rows)| {
+                     let rows = rows.iter()
+                         .map(|row| row.replace('#', "█"))
+                         .collect();
+                     (c, rows)
+                 })
+            .collect();
+
+        BannerFont {
+            height: 5,
+            glyphs: glyphs,
+            spacing: 1,
+            upper_case: true,
+        }
+    }
+
+    /// Parses a font in the FIGlet format (`.flf` files).
+    ///
+    /// Characters are drawn at full width: smushing rules are ignored.
+    pub fn from_figlet(content: &str) -> Result<Self, FontError> {
+        let mut lines = content.lines();
+        let header: Vec<&str> = lines.next()
+            .ok_or(FontError::InvalidHeader)?
+            .split_whitespace()
+            .collect();
+        if header.len() < 6 || !header[0].starts_with("flf2a") {
+            return Err(FontError::InvalidHeader);
+        }
+        let hardblank =
+            header[0][5..].chars().next().ok_or(FontError::InvalidHeader)?;
+        let parse = |field: &str| {
+            field.parse::<usize>().map_err(|_| FontError::InvalidHeader)
+        };
+        let height = parse(header[1])?;
+        let comments = parse(header[5])?;
+
+        let mut lines = lines.skip(comments);
+        let mut glyph = || -> Result<Vec<String>, FontError> {
+            let mut rows = Vec::with_capacity(height);
+            for _ in 0..height {
+                let line = lines.next().ok_or(FontError::Truncated)?;
+                // Each row ends with one or more end marks.
+                let line = line.trim_end();
+                let end = line.chars().last().unwrap_or(' ');
+                let row = line.trim_end_matches(end);
+                rows.push(row.replace(hardblank, " "));
+            }
+            Ok(rows)
+        };
+
+        // Printable ASCII characters come first, in order.
+        let mut glyphs = HashMap::new();
+        for code in 32u8..127 {
+            glyphs.insert(code as char, glyph()?);
+        }
+
+        Ok(BannerFont {
+               height: height,
+               glyphs: glyphs,
+               spacing: 0,
+               upper_case: false,
+           })
+    }
+
+    /// Loads a font in the FIGlet format from the given file.
+    pub fn load_figlet<P: AsRef<Path>>(filename: P)
+                                       -> Result<Self, FontError> {
+        let mut content = String::new();
+        File::open(filename)?.read_to_string(&mut content)?;
+        BannerFont::from_figlet(&content)
+    }
+
+    /// Returns the number of rows of each character.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Draws `text` with this font, and returns the rows.
+    ///
+    /// Each line of `text` takes `height()` rows. Rows are padded with
+    /// spaces to all have the same width.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        let mut result = Vec::new();
+        for line in text.lines() {
+            let mut rows = vec![String::new(); self.height];
+            for c in line.chars() {
+                let c = if self.upper_case {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                };
+                let glyph = match self.glyphs
+                    .get(&c)
+                    .or_else(|| self.glyphs.get(&'?')) {
+                    Some(glyph) => glyph,
+                    None => continue,
+                };
+                let width = glyph.iter().map(|row| row.width()).max();
+                let width = width.unwrap_or(0) + self.spacing;
+                for (row, part) in rows.iter_mut().zip(glyph) {
+                    row.push_str(part);
+                    let padding = width - part.width();
+                    row.extend((0..padding).map(|_| ' '));
+                }
+            }
+            for row in &mut rows {
+                // The last character doesn't need spacing after it.
+                for _ in 0..self.spacing {
+                    row.pop();
+                }
+            }
+            result.extend(rows);
+        }
+
+        let width = result.iter().map(|row| row.width()).max().unwrap_or(0);
+        for row in &mut result {
+            let padding = width - row.width();
+            row.extend((0..padding).map(|_| ' '));
+        }
+        result
+    }
+}
+
+/// Draws text in large letters, for title screens.
+///
+/// Uses the built-in block font by default, or any FIGlet font.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::theme::Color;
+/// # use cursive::views::BannerView;
+/// let banner = BannerView::new("Game over")
+///     .gradient(Color::Rgb(255, 0, 0), Color::Rgb(255, 200, 0));
+/// ```
+pub struct BannerView {
+    content: String,
+    font: BannerFont,
+    color: ColorStyle,
+    // Colors on the left and right ends.
+    gradient: Option<(Color, Color)>,
+    rows: Vec<String>,
+}
+
+impl BannerView {
+    /// Creates a new banner with the given content.
+    pub fn new<S: Into<String>>(content: S) -> Self {
+        let font = BannerFont::block();
+        let content = content.into();
+        BannerView {
+            rows: font.render(&content),
+            content: content,
+            font: font,
+            color: ColorStyle::Primary,
+            gradient: None,
+        }
+    }
+
+    /// Replaces the text shown.
+    pub fn set_content<S: Into<String>>(&mut self, content: S) {
+        self.content = content.into();
+        self.rows = self.font.render(&self.content);
+    }
+
+    /// Returns the text shown.
+    pub fn get_content(&self) -> &str {
+        &self.content
+    }
+
+    /// Sets the font used.
+    pub fn set_font(&mut self, font: BannerFont) {
+        self.font = font;
+        self.rows = self.font.render(&self.content);
+    }
+
+    /// Sets the font used.
+    ///
+    /// Chainable variant.
+    pub fn font(self, font: BannerFont) -> Self {
+        self.with(|s| s.set_font(font))
+    }
+
+    /// Draws the text with `color`, removing any gradient.
+    pub fn set_color(&mut self, color: ColorStyle) {
+        self.color = color;
+        self.gradient = None;
+    }
+
+    /// Draws the text with `color`, removing any gradient.
+    ///
+    /// Chainable variant.
+    pub fn color(self, color: ColorStyle) -> Self {
+        self.with(|s| s.set_color(color))
+    }
+
+    /// Draws the text with a horizontal gradient, from `left` to `right`.
+    ///
+    /// The gradient needs RGB colors: other colors are drawn as they are.
+    pub fn set_gradient(&mut self, left: Color, right: Color) {
+        self.gradient = Some((left, right));
+    }
+
+    /// Draws the text with a horizontal gradient, from `left` to `right`.
+    ///
+    /// Chainable variant.
+    pub fn gradient(self, left: Color, right: Color) -> Self {
+        self.with(|s| s.set_gradient(left, right))
+    }
+
+    fn width(&self) -> usize {
+        self.rows.first().map(|row| row.width()).unwrap_or(0)
+    }
+}
+
+impl View for BannerView {
+    fn draw(&self, printer: &Printer) {
+        let (left, right) = match self.gradient {
+            Some(gradient) => gradient,
+            None => {
+                printer.with_color(self.color, |printer| {
+                    for (y, row) in self.rows.iter().enumerate() {
+                        printer.print((0, y), row);
+                    }
+                });
+                return;
+            }
+        };
+
+        let last = max(1, self.width().saturating_sub(1)) as f32;
+        for (y, row) in self.rows.iter().enumerate() {
+            let mut x = 0;
+            let mut buffer = [0; 4];
+            for c in row.chars() {
+                let color = ColorStyle::Custom {
+                    front: left.mix(right, x as f32 / last),
+                    back: Color::TerminalDefault,
+                };
+                printer.with_color(color, |printer| {
+                    printer.print((x, y), c.encode_utf8(&mut buffer));
+                });
+                x += c.width().unwrap_or(0);
+            }
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new(self.width(), self.rows.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BannerFont;
+
+    #[test]
+    fn test_block() {
+        let font = BannerFont::block();
+        assert_eq!(font.render("hi"),
+                   vec!["█  █ ███",
+                        "█  █  █ ",
+                        "████  █ ",
+                        "█  █  █ ",
+                        "█  █ ███"]);
+        // Unknown characters are drawn as `?`.
+        assert_eq!(font.render("~"), font.render("?"));
+    }
+
+    #[test]
+    fn test_figlet() {
+        // Hardblanks are `$`, and all glyphs are the same.
+        let mut font = String::from("flf2a$ 2 1 4 0 1\nA comment\n");
+        for _ in 32..127 {
+            font.push_str("$#@\n#$@@\n");
+        }
+        let font = BannerFont::from_figlet(&font).unwrap();
+        assert_eq!(font.height(), 2);
+        assert_eq!(font.render("ab"), vec![" # #", "# # "]);
+
+        assert!(BannerFont::from_figlet("flf2a$ 2 1 4 0 0\n$#@\n").is_err());
+        assert!(BannerFont::from_figlet("hello").is_err());
+    }
+}
@@ -10,6 +10,7 @@ use view::{SizeConstraint, View, ViewWrapper};
 /// * Use **all** available size
 /// * Use **at most** a given size
 /// * Use **at least** a given size
+/// * Use a **percentage** of the available size
 /// * Let the wrapped view decide.
 ///
 /// # Examples
@@ -19,6 +20,25 @@ use view::{SizeConstraint, View, ViewWrapper};
 /// // Creates a 20x4 BoxView with a TextView content.
 /// let view = BoxView::with_fixed_size((20,4), TextView::new("Hello!"));
 /// ```
+///
+/// Constraints can be changed later, to grow or shrink a pane:
+///
+/// ```no_run
+/// # use cursive::Cursive;
+/// # use cursive::view::{Identifiable, SizeConstraint};
+/// # use cursive::views::{BoxView, TextView};
+/// let mut siv = Cursive::new();
+/// siv.add_layer(BoxView::with_fixed_width(20, TextView::new("Side"))
+///     .with_id("side"));
+///
+/// siv.add_global_callback('+', |s| {
+///     s.call_on_id("side", |view: &mut BoxView<TextView>| {
+///         if let SizeConstraint::Fixed(width) = view.get_width() {
+///             view.set_width(SizeConstraint::Fixed(width + 1));
+///         }
+///     });
+/// });
+/// ```
 pub struct BoxView<T: View> {
     /// Constraint on each axis
     size: XY<SizeConstraint>,
@@ -32,6 +52,9 @@ pub struct BoxView<T: View> {
 
     /// The actual view we're wrapping.
     view: T,
+
+    /// `true` if the constraints changed since the last layout.
+    invalidated: bool,
 }
 
 impl<T: View> BoxView<T> {
@@ -44,9 +67,39 @@ impl<T: View> BoxView<T> {
             size: (width, height).into(),
             squishable: false,
             view: view,
+            invalidated: true,
         }
     }
 
+    /// Sets the width and height constraints.
+    pub fn set_constraints(&mut self, width: SizeConstraint,
+                           height: SizeConstraint) {
+        self.size = (width, height).into();
+        self.invalidated = true;
+    }
+
+    /// Sets the width constraint.
+    pub fn set_width(&mut self, width: SizeConstraint) {
+        self.size.x = width;
+        self.invalidated = true;
+    }
+
+    /// Sets the height constraint.
+    pub fn set_height(&mut self, height: SizeConstraint) {
+        self.size.y = height;
+        self.invalidated = true;
+    }
+
+    /// Returns the width constraint.
+    pub fn get_width(&self) -> SizeConstraint {
+        self.size.x
+    }
+
+    /// Returns the height constraint.
+    pub fn get_height(&self) -> SizeConstraint {
+        self.size.y
+    }
+
     /// Sets `self` to be squishable.
     ///
     /// A squishable `BoxView` will take a smaller size than it should when
@@ -121,6 +174,32 @@ impl<T: View> BoxView<T> {
                      view)
     }
 
+    /// Wraps `view` in a `BoxView` using a percentage of the available
+    /// size on each axis.
+    pub fn with_percent_size<S: Into<Vec2>>(percent: S, view: T) -> Self {
+        let percent = percent.into();
+
+        BoxView::new(SizeConstraint::Percent(percent.x),
+                     SizeConstraint::Percent(percent.y),
+                     view)
+    }
+
+    /// Wraps `view` in a `BoxView` using a percentage of the available
+    /// width.
+    pub fn with_percent_width(percent: usize, view: T) -> Self {
+        BoxView::new(SizeConstraint::Percent(percent),
+                     SizeConstraint::Free,
+                     view)
+    }
+
+    /// Wraps `view` in a `BoxView` using a percentage of the available
+    /// height.
+    pub fn with_percent_height(percent: usize, view: T) -> Self {
+        BoxView::new(SizeConstraint::Free,
+                     SizeConstraint::Percent(percent),
+                     view)
+    }
+
     /// Wraps `view` in a `BoxView` which will never be smaller than `size`.
     pub fn with_min_size<S: Into<Vec2>>(size: S, view: T) -> Self {
         let size = size.into();
@@ -152,6 +231,15 @@ impl<T: View> BoxView<T> {
 impl<T: View> ViewWrapper for BoxView<T> {
     wrap_impl!(self.view: T);
 
+    fn wrap_needs_relayout(&self) -> bool {
+        self.invalidated || self.view.needs_relayout()
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.invalidated = false;
+        self.view.layout(size);
+    }
+
     fn wrap_required_size(&mut self, req: Vec2) -> Vec2 {
 
         let req = self.size.zip_map(req, SizeConstraint::available);
@@ -182,7 +270,7 @@ impl<T: View> ViewWrapper for BoxView<T> {
 mod tests {
 
     use vec::Vec2;
-    use view::{Boxable, View};
+    use view::{Boxable, SizeConstraint, View};
     use views::DummyView;
 
     // No need to test `draw()` method as it's directly forwarded.
@@ -235,6 +323,32 @@ mod tests {
         assert_eq!(Vec2::new(5, 5), max_s.required_size(Vec2::new(10, 10)));
     }
 
+    #[test]
+    fn percent_size() {
+        let mut half = DummyView.percent_width(50).max_height(5);
+
+        assert_eq!(Vec2::new(0, 1), half.required_size(Vec2::new(1, 1)));
+        assert_eq!(Vec2::new(5, 1), half.required_size(Vec2::new(10, 10)));
+        assert_eq!(Vec2::new(40, 1), half.required_size(Vec2::new(80, 10)));
+
+        let mut full = DummyView.percent_size((200, 100)).max_height(5);
+
+        assert_eq!(Vec2::new(10, 5), full.required_size(Vec2::new(10, 10)));
+    }
+
+    #[test]
+    fn set_constraints() {
+        let mut view = DummyView.fixed_width(5);
+        assert_eq!(Vec2::new(5, 1), view.required_size(Vec2::new(10, 10)));
+
+        view.set_width(SizeConstraint::Fixed(8));
+        assert!(view.needs_relayout());
+        assert_eq!(Vec2::new(8, 1), view.required_size(Vec2::new(10, 10)));
+
+        view.set_constraints(SizeConstraint::Full, SizeConstraint::Full);
+        assert_eq!(Vec2::new(10, 10), view.required_size(Vec2::new(10, 10)));
+    }
+
     #[test]
     fn full_screen() {
         let mut full = DummyView.full_screen();
@@ -0,0 +1,246 @@
+use {Cursive, Printer, With};
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::rc::Rc;
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::View;
+
+/// Shows a path made of segments, like `Home > Projects > cursive`.
+///
+/// Each segment can be focused with the arrow keys, and activated with
+/// `<Enter>`.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::BreadcrumbView;
+/// let breadcrumb = BreadcrumbView::new()
+///     .segment("Home")
+///     .segment("Projects")
+///     .segment("cursive")
+///     .on_submit(|s, i| {
+///         // Go back to the i-th segment
+///     });
+/// ```
+pub struct BreadcrumbView {
+    segments: Vec<String>,
+    separator: String,
+    focus: usize,
+    enabled: bool,
+    on_submit: Option<Rc<Fn(&mut Cursive, usize)>>,
+}
+
+new_default!(BreadcrumbView);
+
+impl BreadcrumbView {
+    /// Creates a new, empty breadcrumb view.
+    pub fn new() -> Self {
+        BreadcrumbView {
+            segments: Vec::new(),
+            separator: " > ".to_string(),
+            focus: 0,
+            enabled: true,
+            on_submit: None,
+        }
+    }
+
+    impl_enabled!(self.enabled);
+
+    /// Appends a segment at the end of the path.
+    ///
+    /// The focus moves to this new segment.
+    pub fn push<S: Into<String>>(&mut self, segment: S) {
+        self.segments.push(segment.into());
+        self.focus = self.segments.len() - 1;
+    }
+
+    /// Appends a segment at the end of the path.
+    ///
+    /// Chainable variant.
+    pub fn segment<S: Into<String>>(self, segment: S) -> Self {
+        self.with(|s| s.push(segment))
+    }
+
+    /// Removes the last segment, and returns it.
+    pub fn pop(&mut self) -> Option<String> {
+        let result = self.segments.pop();
+        self.fix_focus();
+        result
+    }
+
+    /// Only keeps the first `len` segments.
+    ///
+    /// Useful to go back to a segment once it was activated.
+    pub fn truncate(&mut self, len: usize) {
+        self.segments.truncate(len);
+        self.fix_focus();
+    }
+
+    /// Replaces all segments.
+    ///
+    /// The focus moves to the last segment.
+    pub fn set_segments<S, I>(&mut self, segments: I)
+        where S: Into<String>,
+              I: IntoIterator<Item = S>
+    {
+        self.segments = segments.into_iter().map(Into::into).collect();
+        self.focus = self.segments.len().saturating_sub(1);
+    }
+
+    /// Returns the current segments.
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Returns the index of the focused segment.
+    ///
+    /// Returns `None` if there is no segment.
+    pub fn focus(&self) -> Option<usize> {
+        if self.segments.is_empty() {
+            None
+        } else {
+            Some(self.focus)
+        }
+    }
+
+    /// Sets the text printed between segments.
+    ///
+    /// Defaults to `" > "`.
+    pub fn set_separator<S: Into<String>>(&mut self, separator: S) {
+        self.separator = separator.into();
+    }
+
+    /// Sets the text printed between segments.
+    ///
+    /// Chainable variant.
+    pub fn separator<S: Into<String>>(self, separator: S) -> Self {
+        self.with(|s| s.set_separator(separator))
+    }
+
+    /// Sets a callback to be used when a segment is activated.
+    ///
+    /// It will be given the index of the segment.
+    pub fn set_on_submit<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, usize) + 'static
+    {
+        self.on_submit = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when a segment is activated.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive, usize) + 'static
+    {
+        self.with(|s| s.set_on_submit(cb))
+    }
+
+    fn fix_focus(&mut self) {
+        if self.focus >= self.segments.len() {
+            self.focus = self.segments.len().saturating_sub(1);
+        }
+    }
+
+    // Total width, when starting with the `start`-th segment.
+    //
+    // Hidden segments are replaced with an ellipsis.
+    fn width_from(&self, start: usize) -> usize {
+        let segments: usize = self.segments[start..]
+            .iter()
+            .map(|segment| segment.width())
+            .sum();
+        let separators = self.segments.len() - start - 1;
+        let ellipsis = if start > 0 {
+            1 + self.separator.width()
+        } else {
+            0
+        };
+        segments + separators * self.separator.width() + ellipsis
+    }
+
+    // Returns the first segment to show in the given width.
+    //
+    // The focused segment is always visible.
+    fn first_visible(&self, width: usize) -> usize {
+        let mut start = 0;
+        while start < self.focus && self.width_from(start) > width {
+            start += 1;
+        }
+        start
+    }
+}
+
+impl View for BreadcrumbView {
+    fn draw(&self, printer: &Printer) {
+        if self.segments.is_empty() {
+            return;
+        }
+
+        let start = self.first_visible(printer.size.x);
+        let mut x = 0;
+        if start > 0 {
+            printer.print((x, 0), "…");
+            x += 1;
+            printer.print((x, 0), &self.separator);
+            x += self.separator.width();
+        }
+
+        for (i, segment) in self.segments.iter().enumerate().skip(start) {
+            if i > start {
+                printer.print((x, 0), &self.separator);
+                x += self.separator.width();
+            }
+
+            let style = if !self.enabled {
+                ColorStyle::Secondary
+            } else if i != self.focus {
+                ColorStyle::Primary
+            } else if printer.focused {
+                ColorStyle::Highlight
+            } else {
+                ColorStyle::HighlightInactive
+            };
+            printer.with_color(style, |printer| printer.print((x, 0), segment));
+            x += segment.width();
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        if self.segments.is_empty() {
+            Vec2::new(0, 1)
+        } else {
+            Vec2::new(self.width_from(0), 1)
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Left) if self.focus > 0 => self.focus -= 1,
+            Event::Key(Key::Right) if self.focus + 1 < self.segments.len() => {
+                self.focus += 1
+            }
+            Event::Key(Key::Home) if !self.segments.is_empty() => {
+                self.focus = 0
+            }
+            Event::Key(Key::End) if !self.segments.is_empty() => {
+                self.focus = self.segments.len() - 1
+            }
+            Event::Key(Key::Enter) if self.on_submit.is_some() &&
+                                      !self.segments.is_empty() => {
+                let cb = self.on_submit.clone().unwrap();
+                let focus = self.focus;
+                return EventResult::with_cb(move |s| cb(s, focus));
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.enabled && !self.segments.is_empty()
+    }
+}
+
@@ -6,6 +6,7 @@ use direction::Direction;
 use event::*;
 use theme::ColorStyle;
 use unicode_width::UnicodeWidthStr;
+use utils::i18n;
 use vec::Vec2;
 use view::View;
 
@@ -86,7 +87,7 @@ impl View for Button {
             HAlign::Center.get_offset(self.label.len() + 2, printer.size.x);
 
         printer.with_color(style, |printer| {
-            printer.print((offset, 0), &format!("<{}>", self.label));
+            printer.print_fmt((offset, 0), format_args!("<{}>", self.label));
         });
     }
 
@@ -108,4 +109,8 @@ impl View for Button {
     fn take_focus(&mut self, _: Direction) -> bool {
         self.enabled
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} {}", self.label, i18n::translate("button")))
+    }
 }
@@ -0,0 +1,131 @@
+use vec::Vec2;
+use view::{View, ViewWrapper};
+
+// Number of constraints remembered.
+//
+// Parents often try a few constraints during a single layout phase.
+const CAPACITY: usize = 8;
+
+/// Wrapper around a view that remembers its `required_size` results.
+///
+/// Results are kept for each constraint, and dropped once the view says
+/// its content changed with `needs_relayout`. This avoids measuring
+/// expensive views (like long wrapped texts) again on every frame.
+///
+/// Only useful around views reporting changes with `needs_relayout`:
+/// views always returning `true` (the default) are measured every time.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{CachedView, TextView};
+/// let text = CachedView::new(TextView::new("A very long text..."));
+/// ```
+pub struct CachedView<T: View> {
+    view: T,
+    // (constraint, size), most recent last.
+    cache: Vec<(Vec2, Vec2)>,
+    // The view changed, but the cache was already cleared for this change.
+    refreshed: bool,
+}
+
+impl<T: View> CachedView<T> {
+    /// Wraps `view` in a new `CachedView`.
+    pub fn new(view: T) -> Self {
+        CachedView {
+            view: view,
+            cache: Vec::with_capacity(CAPACITY),
+            refreshed: false,
+        }
+    }
+
+    /// Drops all cached results.
+    pub fn invalidate(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Returns the inner view.
+    pub fn get_inner(&self) -> &T {
+        &self.view
+    }
+
+    /// Returns a mutable reference to the inner view.
+    ///
+    /// This drops cached results, since the view may change.
+    pub fn get_inner_mut(&mut self) -> &mut T {
+        self.invalidate();
+        &mut self.view
+    }
+}
+
+impl<T: View> ViewWrapper for CachedView<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_required_size(&mut self, req: Vec2) -> Vec2 {
+        if self.view.needs_relayout() {
+            // Results from before the change are stale, but those from
+            // since then are still good.
+            if !self.refreshed {
+                self.invalidate();
+                self.refreshed = true;
+            }
+        }
+
+        if let Some(&(_, size)) =
+            self.cache.iter().find(|&&(constraint, _)| constraint == req) {
+            return size;
+        }
+
+        let size = self.view.required_size(req);
+        if self.cache.len() == CAPACITY {
+            self.cache.remove(0);
+        }
+        self.cache.push((req, size));
+        size
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.view.layout(size);
+        self.refreshed = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedView;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use vec::Vec2;
+    use view::View;
+    use views::Canvas;
+
+    #[test]
+    fn test_cache() {
+        // (measure count, changed)
+        let state = Rc::new((Cell::new(0), Cell::new(false)));
+        let canvas = Canvas::new(state.clone())
+            .with_required_size(|req, state| {
+                                    state.0.set(state.0.get() + 1);
+                                    Vec2::new(req.x / 2, req.y)
+                                })
+            .with_needs_relayout(|state| state.1.get());
+        let mut view = CachedView::new(canvas);
+
+        let req = Vec2::new(10, 4);
+        assert_eq!(view.required_size(req), Vec2::new(5, 4));
+        view.required_size(req);
+        view.required_size(Vec2::new(8, 4));
+        assert_eq!(state.0.get(), 2);
+
+        // A change drops previous results, once.
+        state.1.set(true);
+        view.required_size(req);
+        view.required_size(req);
+        assert_eq!(state.0.get(), 3);
+        view.layout(req);
+
+        state.1.set(false);
+        view.required_size(req);
+        assert_eq!(state.0.get(), 3);
+    }
+}
@@ -163,6 +163,10 @@ impl<T> View for Canvas<T> {
         (self.layout)(size, &mut self.state);
     }
 
+    fn needs_relayout(&self) -> bool {
+        (self.needs_relayout)(&self.state)
+    }
+
     fn take_focus(&mut self, source: Direction) -> bool {
         (self.take_focus)(source, &mut self.state)
     }
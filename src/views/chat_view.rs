@@ -0,0 +1,285 @@
+use {Printer, With};
+use align::HAlign;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::cmp::{max, min};
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use utils::{LinesIterator, Row};
+use vec::Vec2;
+use view::{ScrollBase, ScrollStrategy, View};
+
+/// A message shown in a `ChatView`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    /// Who sent the message.
+    pub author: String,
+    /// Content of the message. Long lines are wrapped.
+    pub text: String,
+    /// When the message was sent, already formatted, like `12:03`.
+    pub timestamp: Option<String>,
+    /// Side of the view the message is shown on.
+    pub align: HAlign,
+}
+
+impl Message {
+    /// Creates a new message, shown on the left.
+    pub fn new<A: Into<String>, T: Into<String>>(author: A, text: T) -> Self {
+        Message {
+            author: author.into(),
+            text: text.into(),
+            timestamp: None,
+            align: HAlign::Left,
+        }
+    }
+
+    /// Sets the timestamp shown next to the author.
+    pub fn timestamp<S: Into<String>>(self, timestamp: S) -> Self {
+        self.with(|s| s.timestamp = Some(timestamp.into()))
+    }
+
+    /// Sets the side of the view the message is shown on.
+    pub fn align(self, align: HAlign) -> Self {
+        self.with(|s| s.align = align)
+    }
+
+    /// Shows the message on the right, like messages sent by the user.
+    pub fn right(self) -> Self {
+        self.align(HAlign::Right)
+    }
+
+    fn header(&self) -> String {
+        match self.timestamp {
+            Some(ref timestamp) => format!("{}  {}", self.author, timestamp),
+            None => self.author.clone(),
+        }
+    }
+}
+
+enum Line {
+    Header(usize),
+    Text(usize, Row),
+    Blank,
+}
+
+// Blank between the messages and the scrollbar.
+const SCROLLBAR_WIDTH: usize = 2;
+
+/// Scrollback of chat messages.
+///
+/// Each message shows its author and timestamp above its text, on the left
+/// or on the right of the view. Messages take at most three quarters of
+/// the width, so both sides are easy to tell apart.
+///
+/// The view follows new messages, unless the user scrolled up; `<End>`
+/// goes back to following them. Adding a message only wraps this message.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{ChatView, Message};
+/// let chat = ChatView::new()
+///     .message(Message::new("alice", "Hi!").timestamp("12:01"))
+///     .message(Message::new("me", "Hello, how are you?")
+///                  .timestamp("12:02")
+///                  .right());
+/// ```
+pub struct ChatView {
+    messages: Vec<Message>,
+    // Wrapped messages, for the current width.
+    lines: Vec<Line>,
+    // Width of each wrapped message, header included.
+    widths: Vec<usize>,
+    // Number of messages already wrapped.
+    wrapped: usize,
+    // Width the messages are wrapped in.
+    width: Option<usize>,
+    scrollbase: ScrollBase,
+}
+
+new_default!(ChatView);
+
+impl ChatView {
+    /// Creates a new, empty chat view.
+    pub fn new() -> Self {
+        let mut scrollbase = ScrollBase::new();
+        scrollbase.set_strategy(ScrollStrategy::StickToBottom);
+        ChatView {
+            messages: Vec::new(),
+            lines: Vec::new(),
+            widths: Vec::new(),
+            wrapped: 0,
+            width: None,
+            scrollbase: scrollbase,
+        }
+    }
+
+    /// Adds a message at the end.
+    pub fn add_message(&mut self, message: Message) {
+        self.messages.push(message);
+    }
+
+    /// Adds a message at the end.
+    ///
+    /// Chainable variant.
+    pub fn message(self, message: Message) -> Self {
+        self.with(|s| s.add_message(message))
+    }
+
+    /// Returns the messages, oldest first.
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// Removes all messages.
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.lines.clear();
+        self.widths.clear();
+        self.wrapped = 0;
+    }
+
+    // Wraps the messages not wrapped yet in `width` cells.
+    fn wrap(&mut self, width: usize) {
+        if self.width != Some(width) {
+            self.lines.clear();
+            self.widths.clear();
+            self.wrapped = 0;
+            self.width = Some(width);
+        }
+
+        let bubble = max(1, width * 3 / 4);
+        for (i, message) in self.messages.iter().enumerate() {
+            if i < self.wrapped {
+                continue;
+            }
+            if i > 0 {
+                self.lines.push(Line::Blank);
+            }
+            self.lines.push(Line::Header(i));
+            let mut widest = min(message.header().width(), width);
+            for row in LinesIterator::new(&message.text, bubble) {
+                widest = max(widest, row.width);
+                self.lines.push(Line::Text(i, row));
+            }
+            self.widths.push(widest);
+        }
+        self.wrapped = self.messages.len();
+    }
+
+    fn offset(&self, i: usize, width: usize) -> usize {
+        self.messages[i].align.get_offset(self.widths[i], width)
+    }
+}
+
+impl View for ChatView {
+    fn draw(&self, printer: &Printer) {
+        // Messages added since the last layout are drawn on the next one.
+        self.scrollbase.draw(printer, |printer, y| {
+            let width = min(printer.size.x, self.width.unwrap_or(0));
+            match self.lines[y] {
+                Line::Header(i) => {
+                    let message = &self.messages[i];
+                    let x = self.offset(i, width);
+                    printer.with_color(ColorStyle::TitlePrimary, |printer| {
+                        printer.print((x, 0), &message.author)
+                    });
+                    if let Some(ref timestamp) = message.timestamp {
+                        let x = x + message.author.width() + 2;
+                        printer.with_color(ColorStyle::Secondary, |printer| {
+                            printer.print((x, 0), timestamp)
+                        });
+                    }
+                }
+                Line::Text(i, row) => {
+                    let text = &self.messages[i].text[row.start..row.end];
+                    printer.print((self.offset(i, width), 0), text);
+                }
+                Line::Blank => (),
+            }
+        });
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        // A chat takes all the space it's given.
+        req
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        let width = size.x.saturating_sub(SCROLLBAR_WIDTH);
+        self.wrap(width);
+        self.scrollbase.set_heights(size.y, self.lines.len());
+    }
+
+    fn needs_relayout(&self) -> bool {
+        self.wrapped != self.messages.len()
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if event == Event::Refresh {
+            // Smooth scrolling moves a bit further on each tick.
+            self.scrollbase.animate();
+            return EventResult::Ignored;
+        }
+
+        match event {
+            Event::Key(Key::Home) => self.scrollbase.scroll_top(),
+            Event::Key(Key::End) => {
+                // Follow new messages again.
+                self.scrollbase.set_strategy(ScrollStrategy::StickToBottom);
+                return EventResult::Consumed(None);
+            }
+            Event::Key(Key::Up) if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(1)
+            }
+            Event::Key(Key::Down) if self.scrollbase
+                .can_scroll_down() => self.scrollbase.scroll_down(1),
+            Event::Key(Key::PageDown) => self.scrollbase.scroll_down(10),
+            Event::Key(Key::PageUp) => self.scrollbase.scroll_up(10),
+            _ => return EventResult::Ignored,
+        }
+
+        // We just scrolled manually, so reset the scroll strategy.
+        self.scrollbase.user_scrolled();
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.scrollbase.scrollable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChatView, Message};
+    use event::{Event, Key};
+    use vec::Vec2;
+    use view::View;
+
+    #[test]
+    fn test_chat() {
+        let mut view = ChatView::new()
+            .message(Message::new("alice", "one two three four")
+                         .timestamp("12:01"))
+            .message(Message::new("me", "hi").right());
+
+        // 12 columns for the text, wrapped in 9.
+        view.layout(Vec2::new(14, 4));
+        // alice's header and 3 rows, a blank, my header and 1 row.
+        assert_eq!(view.lines.len(), 7);
+        assert_eq!(view.widths, [12, 2]);
+        assert_eq!(view.offset(1, 12), 10);
+        // Following the last messages.
+        assert_eq!(view.scrollbase.start_line, 3);
+
+        view.on_event(Event::Key(Key::Up));
+        view.add_message(Message::new("alice", "?"));
+        assert!(view.needs_relayout());
+        view.layout(Vec2::new(14, 4));
+        assert_eq!(view.lines.len(), 10);
+        assert_eq!(view.scrollbase.start_line, 2);
+
+        view.on_event(Event::Key(Key::End));
+        assert_eq!(view.scrollbase.start_line, 6);
+    }
+}
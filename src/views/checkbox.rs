@@ -6,6 +6,7 @@ use event::{Event, EventResult, Key};
 
 use std::rc::Rc;
 use theme::ColorStyle;
+use utils::{Binding, i18n};
 use vec::Vec2;
 use view::View;
 
@@ -16,6 +17,9 @@ pub struct Checkbox {
     enabled: bool,
 
     on_change: Option<Rc<Fn(&mut Cursive, bool)>>,
+
+    // Shared state, and the generation we last saw.
+    binding: Option<(Binding<bool>, usize)>,
 }
 
 new_default!(Checkbox);
@@ -29,9 +33,27 @@ impl Checkbox {
             checked: false,
             enabled: true,
             on_change: None,
+            binding: None,
         }
     }
 
+    /// Binds this checkbox to a shared value.
+    ///
+    /// The checkbox follows changes to the value, and toggling it updates
+    /// the value.
+    pub fn set_binding(&mut self, binding: Binding<bool>) {
+        self.checked = binding.get();
+        let generation = binding.generation();
+        self.binding = Some((binding, generation));
+    }
+
+    /// Binds this checkbox to a shared value.
+    ///
+    /// Chainable variant.
+    pub fn binding(self, binding: Binding<bool>) -> Self {
+        self.with(|s| s.set_binding(binding))
+    }
+
     /// Sets a callback to be used when the state changes.
     pub fn set_on_change<F: 'static + Fn(&mut Cursive, bool)>(&mut self,
                                                               on_change: F) {
@@ -84,6 +106,12 @@ impl Checkbox {
     /// Sets the checkbox state.
     pub fn set_checked(&mut self, checked: bool) -> EventResult {
         self.checked = checked;
+        if let Some((ref binding, ref mut seen)) = self.binding {
+            if binding.get() != checked {
+                binding.set(checked);
+            }
+            *seen = binding.generation();
+        }
         if let Some(ref on_change) = self.on_change {
             let on_change = on_change.clone();
             EventResult::with_cb(move |s| on_change(s, checked))
@@ -105,10 +133,31 @@ impl View for Checkbox {
         Vec2::new(3, 1)
     }
 
+    fn layout(&mut self, _: Vec2) {
+        if let Some((ref binding, ref mut seen)) = self.binding {
+            let generation = binding.generation();
+            if generation != *seen {
+                *seen = generation;
+                self.checked = binding.get();
+            }
+        }
+    }
+
     fn take_focus(&mut self, _: Direction) -> bool {
         self.enabled
     }
 
+    fn describe(&self) -> Option<String> {
+        let state = if self.is_checked() {
+            "checked"
+        } else {
+            "not checked"
+        };
+        Some(format!("{}, {}",
+                     i18n::translate("checkbox"),
+                     i18n::translate(state)))
+    }
+
     fn draw(&self, printer: &Printer) {
         if self.enabled {
             printer.with_selection(printer.focused,
@@ -0,0 +1,275 @@
+use Printer;
+use With;
+#[cfg(unix)]
+use libc;
+use std::cell::RefCell;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::View;
+
+const DAYS: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday",
+                         "Thursday", "Friday", "Saturday"];
+const MONTHS: [&str; 12] = ["January", "February", "March", "April", "May",
+                            "June", "July", "August", "September",
+                            "October", "November", "December"];
+
+// Broken-down time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tm {
+    year: i64,
+    // 1 to 12
+    month: u32,
+    // 1 to 31
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    // 0 is Sunday.
+    weekday: u32,
+}
+
+impl Tm {
+    // Converts seconds since the epoch, in UTC.
+    fn from_utc(timestamp: i64) -> Self {
+        let days = timestamp.div_euclid(86400);
+        let secs = timestamp.rem_euclid(86400) as u32;
+
+        // Days to civil date, from Howard Hinnant's algorithms.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Tm {
+            year: year,
+            month: month,
+            day: day,
+            hour: secs / 3600,
+            minute: secs / 60 % 60,
+            second: secs % 60,
+            // The epoch was a Thursday.
+            weekday: (days + 4).rem_euclid(7) as u32,
+        }
+    }
+
+    // Converts seconds since the epoch, in the local time zone.
+    #[cfg(unix)]
+    fn from_local(timestamp: i64) -> Self {
+        let time = timestamp as libc::time_t;
+        let mut tm: libc::tm = unsafe { ::std::mem::zeroed() };
+        if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+            return Tm::from_utc(timestamp);
+        }
+        Tm {
+            year: i64::from(tm.tm_year) + 1900,
+            month: tm.tm_mon as u32 + 1,
+            day: tm.tm_mday as u32,
+            hour: tm.tm_hour as u32,
+            minute: tm.tm_min as u32,
+            second: tm.tm_sec as u32,
+            weekday: tm.tm_wday as u32,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn from_local(timestamp: i64) -> Self {
+        Tm::from_utc(timestamp)
+    }
+
+    // Formats the time following `format`, with the `strftime` syntax.
+    fn format(&self, format: &str) -> String {
+        let mut result = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                result.push(c);
+                continue;
+            }
+            let hour12 = match self.hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            let day_name = DAYS[self.weekday as usize];
+            let month_name = MONTHS[self.month as usize - 1];
+            let field = match chars.next() {
+                Some('Y') => self.year.to_string(),
+                Some('y') => format!("{:02}", self.year.rem_euclid(100)),
+                Some('m') => format!("{:02}", self.month),
+                Some('d') => format!("{:02}", self.day),
+                Some('e') => format!("{:2}", self.day),
+                Some('H') => format!("{:02}", self.hour),
+                Some('I') => format!("{:02}", hour12),
+                Some('M') => format!("{:02}", self.minute),
+                Some('S') => format!("{:02}", self.second),
+                Some('p') if self.hour < 12 => "AM".to_string(),
+                Some('p') => "PM".to_string(),
+                Some('A') => day_name.to_string(),
+                Some('a') => day_name[..3].to_string(),
+                Some('B') => month_name.to_string(),
+                Some('b') => month_name[..3].to_string(),
+                Some('F') => self.format("%Y-%m-%d"),
+                Some('D') => self.format("%m/%d/%y"),
+                Some('T') => self.format("%H:%M:%S"),
+                Some('R') => self.format("%H:%M"),
+                Some('%') => "%".to_string(),
+                // Unknown conversions are kept as they are.
+                Some(c) => format!("%{}", c),
+                None => "%".to_string(),
+            };
+            result.push_str(&field);
+        }
+        result
+    }
+}
+
+/// Shows the current time.
+///
+/// The time is formatted with the `strftime` syntax. Supported
+/// conversions are `%Y`, `%y`, `%m`, `%d`, `%e`, `%H`, `%I`, `%M`, `%S`,
+/// `%p`, `%A`, `%a`, `%B`, `%b`, `%F`, `%D`, `%T`, `%R` and `%%`.
+///
+/// The time is read again when drawing, once the update interval is over.
+/// Call [`Cursive::set_fps`] so it keeps updating.
+///
+/// On unix, the time is shown in the local time zone. Elsewhere, it is
+/// shown in UTC.
+///
+/// Requires the `clock` feature.
+///
+/// [`Cursive::set_fps`]: ../struct.Cursive.html#method.set_fps
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::ClockView;
+/// # use std::time::Duration;
+/// // For a status bar: no need to update more than once a minute.
+/// let clock = ClockView::new()
+///     .format("%a %H:%M")
+///     .interval(Duration::from_secs(60));
+/// ```
+pub struct ClockView {
+    format: String,
+    interval: Duration,
+    utc: bool,
+    // The text shown, and when it was computed.
+    cache: RefCell<Option<(Instant, String)>>,
+}
+
+new_default!(ClockView);
+
+impl ClockView {
+    /// Creates a new clock showing the time as `%H:%M:%S`.
+    ///
+    /// It updates every second.
+    pub fn new() -> Self {
+        ClockView {
+            format: "%H:%M:%S".to_string(),
+            interval: Duration::from_secs(1),
+            utc: false,
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Sets the format of the time, with the `strftime` syntax.
+    pub fn set_format<S: Into<String>>(&mut self, format: S) {
+        self.format = format.into();
+        *self.cache.get_mut() = None;
+    }
+
+    /// Sets the format of the time, with the `strftime` syntax.
+    ///
+    /// Chainable variant.
+    pub fn format<S: Into<String>>(self, format: S) -> Self {
+        self.with(|s| s.set_format(format))
+    }
+
+    /// Sets how often the time is read again.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Sets how often the time is read again.
+    ///
+    /// Chainable variant.
+    pub fn interval(self, interval: Duration) -> Self {
+        self.with(|s| s.set_interval(interval))
+    }
+
+    /// Shows the time in UTC rather than the local time zone.
+    pub fn set_utc(&mut self, utc: bool) {
+        self.utc = utc;
+        *self.cache.get_mut() = None;
+    }
+
+    /// Shows the time in UTC rather than the local time zone.
+    ///
+    /// Chainable variant.
+    pub fn utc(self) -> Self {
+        self.with(|s| s.set_utc(true))
+    }
+
+    /// Returns the text currently shown.
+    pub fn text(&self) -> String {
+        let now = Instant::now();
+        let mut cache = self.cache.borrow_mut();
+        match *cache {
+            Some((time, ref text)) if now.duration_since(time) <
+                                      self.interval => {
+                return text.clone();
+            }
+            _ => (),
+        }
+
+        let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(err) => -(err.duration().as_secs() as i64),
+        };
+        let tm = if self.utc {
+            Tm::from_utc(timestamp)
+        } else {
+            Tm::from_local(timestamp)
+        };
+        let text = tm.format(&self.format);
+        *cache = Some((now, text.clone()));
+        text
+    }
+}
+
+impl View for ClockView {
+    fn draw(&self, printer: &Printer) {
+        printer.print((0, 0), &self.text());
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new(self.text().width(), 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tm;
+
+    #[test]
+    fn test_strftime() {
+        let epoch = Tm::from_utc(0);
+        assert_eq!(epoch.format("%A %F %T"), "Thursday 1970-01-01 00:00:00");
+
+        let tm = Tm::from_utc(1_700_000_000);
+        assert_eq!(tm.format("%a %d %b %Y %H:%M:%S"),
+                   "Tue 14 Nov 2023 22:13:20");
+        assert_eq!(tm.format("%I:%M %p, %D"), "10:13 PM, 11/14/23");
+        assert_eq!(tm.format("100%% %q"), "100% %q");
+
+        // Before the epoch, and on a leap day.
+        assert_eq!(Tm::from_utc(-1).format("%F %T"), "1969-12-31 23:59:59");
+        assert_eq!(Tm::from_utc(951_782_400).format("%F %a"),
+                   "2000-02-29 Tue");
+    }
+}
@@ -0,0 +1,292 @@
+use {Printer, With};
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{self, Theme as SyntaxTheme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use theme::{Color, ColorStyle, Style};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use utils::StyledString;
+use vec::Vec2;
+use view::View;
+
+/// Read-only view showing source code with syntax highlighting.
+///
+/// Each line is prefixed by its number, and by a gutter where markers
+/// (like breakpoints or errors) can be set.
+///
+/// Requires the `syntect` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::CodeView;
+/// # use cursive::theme::ColorStyle;
+/// # use cursive::utils::StyledString;
+/// let mut view = CodeView::from_extension("fn main() {}\n", "rs");
+/// view.set_marker(0, StyledString::styled("●", ColorStyle::Tertiary));
+/// view.scroll_to_line(0);
+/// ```
+pub struct CodeView {
+    lines: Vec<StyledString>,
+    markers: HashMap<usize, StyledString>,
+    background: Option<ColorStyle>,
+    line_numbers: bool,
+
+    // First visible line.
+    top: usize,
+    // Number of cells hidden on the left.
+    left: usize,
+    last_size: Vec2,
+}
+
+fn convert_color(color: highlighting::Color) -> Color {
+    Color::Rgb(color.r, color.g, color.b)
+}
+
+impl CodeView {
+    /// Creates a new view, highlighting `code` with the given syntax.
+    pub fn new(code: &str, syntax: &SyntaxReference, syntaxes: &SyntaxSet,
+               theme: &SyntaxTheme)
+               -> Self {
+        let mut highlighter = HighlightLines::new(syntax, theme);
+        let lines = code.lines()
+            .map(|line| {
+                let mut styled = StyledString::new();
+                // Highlighting can only fail on broken syntax definitions.
+                let regions = highlighter.highlight_line(line, syntaxes)
+                    .unwrap_or_else(|_| Vec::new());
+                for (style, text) in regions {
+                    let color = ColorStyle::Custom {
+                        front: convert_color(style.foreground),
+                        back: convert_color(style.background),
+                    };
+                    styled.append_styled(text.replace('\t', "    "), color);
+                }
+                styled
+            })
+            .collect();
+
+        let background = match (theme.settings.foreground,
+                                theme.settings.background) {
+            (Some(front), Some(back)) => {
+                Some(ColorStyle::Custom {
+                         front: convert_color(front),
+                         back: convert_color(back),
+                     })
+            }
+            _ => None,
+        };
+
+        CodeView {
+            lines: lines,
+            markers: HashMap::new(),
+            background: background,
+            line_numbers: true,
+            top: 0,
+            left: 0,
+            last_size: Vec2::zero(),
+        }
+    }
+
+    /// Creates a new view, guessing the syntax from a file extension.
+    ///
+    /// This loads the default syntaxes and themes, which takes some time.
+    /// Prefer `CodeView::new` when creating many views.
+    pub fn from_extension(code: &str, extension: &str) -> Self {
+        let syntaxes = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+        let syntax = syntaxes.find_syntax_by_extension(extension)
+            .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+        let theme = &themes.themes["base16-ocean.dark"];
+        CodeView::new(code, syntax, &syntaxes, theme)
+    }
+
+    /// Shows or hides line numbers.
+    ///
+    /// Defaults to `true`.
+    pub fn set_line_numbers(&mut self, line_numbers: bool) {
+        self.line_numbers = line_numbers;
+    }
+
+    /// Shows or hides line numbers.
+    ///
+    /// Chainable variant.
+    pub fn line_numbers(self, line_numbers: bool) -> Self {
+        self.with(|s| s.set_line_numbers(line_numbers))
+    }
+
+    /// Sets the marker shown in the gutter for the given line.
+    ///
+    /// Lines are counted from `0`.
+    pub fn set_marker<S: Into<StyledString>>(&mut self, line: usize,
+                                              marker: S) {
+        self.markers.insert(line, marker.into());
+    }
+
+    /// Removes the marker for the given line, and returns it.
+    pub fn remove_marker(&mut self, line: usize) -> Option<StyledString> {
+        self.markers.remove(&line)
+    }
+
+    /// Removes all markers.
+    pub fn clear_markers(&mut self) {
+        self.markers.clear();
+    }
+
+    /// Returns the number of lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns `true` if there is no code at all.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Scrolls so that the given line is at the top of the view.
+    ///
+    /// Lines are counted from `0`.
+    pub fn scroll_to_line(&mut self, line: usize) {
+        self.top = line;
+        self.fix_top();
+    }
+
+    /// Returns the first visible line.
+    pub fn first_visible_line(&self) -> usize {
+        self.top
+    }
+
+    fn fix_top(&mut self) {
+        let max_top = self.lines.len().saturating_sub(self.last_size.y);
+        self.top = min(self.top, max_top);
+    }
+
+    fn gutter_width(&self) -> usize {
+        let markers = self.markers
+            .values()
+            .map(|marker| marker.source().width())
+            .max()
+            .unwrap_or(0);
+        let numbers = if self.line_numbers {
+            self.lines.len().to_string().len() + 1
+        } else {
+            0
+        };
+        markers + numbers
+    }
+
+    fn max_width(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| line.source().width())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// Prints `content`, skipping the first `left` cells.
+fn print_styled(printer: &Printer, content: &StyledString, left: usize) {
+    let mut spans = content.spans().iter().peekable();
+    let mut x = 0;
+    for (i, g) in content.source().grapheme_indices(true) {
+        if x >= left + printer.size.x {
+            break;
+        }
+        while spans.peek().map_or(false, |span| span.end <= i) {
+            spans.next();
+        }
+        let style = match spans.peek() {
+            Some(span) if span.start <= i => span.style,
+            _ => Style::none(),
+        };
+
+        if x >= left {
+            printer.with_style(style,
+                               |printer| printer.print((x - left, 0), g));
+        }
+        x += g.width();
+    }
+}
+
+impl View for CodeView {
+    fn draw(&self, printer: &Printer) {
+        let gutter = self.gutter_width();
+        let numbers = if self.line_numbers {
+            self.lines.len().to_string().len()
+        } else {
+            0
+        };
+
+        let lines = self.lines.iter().enumerate().skip(self.top);
+        for (y, (i, line)) in lines.take(printer.size.y).enumerate() {
+            let printer =
+                printer.sub_printer((0, y), (printer.size.x, 1), true);
+
+            if let Some(marker) = self.markers.get(&i) {
+                print_styled(&printer, marker, 0);
+            }
+            if self.line_numbers {
+                printer.with_color(ColorStyle::Secondary, |printer| {
+                    let number = format!("{:>1$}", i + 1, numbers);
+                    printer.print((gutter - numbers - 1, 0), &number);
+                });
+            }
+
+            let printer = printer.offset((gutter, 0), true);
+            if let Some(background) = self.background {
+                printer.with_color(background, |printer| {
+                    printer.print_hline((0, 0), printer.size.x, " ");
+                });
+            }
+            print_styled(&printer, line, self.left);
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new(self.gutter_width() + self.max_width(), self.lines.len())
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.fix_top();
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let height = max(1, self.last_size.y);
+        let width = self.last_size.x.saturating_sub(self.gutter_width());
+
+        match event {
+            Event::Key(Key::Up) if self.top > 0 => self.top -= 1,
+            Event::Key(Key::Down) if self.top + height < self.lines.len() => {
+                self.top += 1
+            }
+            Event::Key(Key::PageUp) if self.top > 0 => {
+                self.top -= min(self.top, height)
+            }
+            Event::Key(Key::PageDown) if self.top + height <
+                                         self.lines.len() => {
+                self.top += height;
+                self.fix_top();
+            }
+            Event::Key(Key::Home) => self.top = 0,
+            Event::Key(Key::End) => {
+                self.top = self.lines.len();
+                self.fix_top();
+            }
+            Event::Key(Key::Left) if self.left > 0 => self.left -= 1,
+            Event::Key(Key::Right) if self.left + width <
+                                      self.max_width() => self.left += 1,
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
@@ -0,0 +1,305 @@
+use Cursive;
+use Printer;
+use With;
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key};
+use std::cmp::{max, min};
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use utils::{fuzzy, i18n};
+use vec::Vec2;
+use view::View;
+
+/// Popup to search and run commands.
+///
+/// Typing filters the registered commands with [`fuzzy::find`], best
+/// matches first. `<Up>` and `<Down>` select a command, `<Enter>` closes
+/// the palette and runs it, and `<Esc>` closes the palette.
+///
+/// Each command can show a key binding, as a reminder of the shortcut
+/// already registered for it. The palette doesn't register the shortcut.
+///
+/// [`fuzzy::find`]: ../utils/fuzzy/fn.find.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::Cursive;
+/// # use cursive::event::Event;
+/// # use cursive::views::CommandPalette;
+/// let mut siv = Cursive::new();
+///
+/// let palette = CommandPalette::new()
+///     .command_with_key("Quit", "q", |s| s.quit())
+///     .command("Close window", |s| s.pop_layer());
+///
+/// siv.add_global_callback('q', |s| s.quit());
+/// siv.add_global_callback(Event::CtrlChar('p'),
+///                         move |s| s.add_layer(palette.clone()));
+/// ```
+#[derive(Clone)]
+pub struct CommandPalette {
+    commands: Vec<Command>,
+    query: String,
+    // Matching commands, as (index in `commands`, match).
+    matches: Vec<(usize, fuzzy::Match)>,
+    focus: usize,
+}
+
+#[derive(Clone)]
+struct Command {
+    label: String,
+    key: Option<String>,
+    cb: Callback,
+}
+
+// The query line and the delimiter below it.
+const HEADER: usize = 2;
+
+new_default!(CommandPalette);
+
+impl CommandPalette {
+    /// Creates a new, empty palette.
+    pub fn new() -> Self {
+        CommandPalette {
+            commands: Vec::new(),
+            query: String::new(),
+            matches: Vec::new(),
+            focus: 0,
+        }
+    }
+
+    /// Adds a command to the palette.
+    pub fn add_command<S, F>(&mut self, label: S, cb: F)
+        where S: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        self.push(label.into(), None, cb);
+    }
+
+    /// Adds a command to the palette.
+    ///
+    /// Chainable variant.
+    pub fn command<S, F>(self, label: S, cb: F) -> Self
+        where S: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        self.with(|s| s.add_command(label, cb))
+    }
+
+    /// Adds a command to the palette, showing the given key binding.
+    pub fn add_command_with_key<S, K, F>(&mut self, label: S, key: K, cb: F)
+        where S: Into<String>,
+              K: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        self.push(label.into(), Some(key.into()), cb);
+    }
+
+    /// Adds a command to the palette, showing the given key binding.
+    ///
+    /// Chainable variant.
+    pub fn command_with_key<S, K, F>(self, label: S, key: K, cb: F) -> Self
+        where S: Into<String>,
+              K: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        self.with(|s| s.add_command_with_key(label, key, cb))
+    }
+
+    /// Sets the search query.
+    pub fn set_query<S: Into<String>>(&mut self, query: S) {
+        self.query = query.into();
+        self.update_matches();
+    }
+
+    /// Returns the current search query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the labels of the commands matching the query, best first.
+    pub fn matches(&self) -> Vec<&str> {
+        self.matches
+            .iter()
+            .map(|&(i, _)| &self.commands[i].label[..])
+            .collect()
+    }
+
+    fn push<F>(&mut self, label: String, key: Option<String>, cb: F)
+        where F: Fn(&mut Cursive) + 'static
+    {
+        self.commands.push(Command {
+            label: label,
+            key: key,
+            cb: Callback::from_fn(cb),
+        });
+        self.update_matches();
+    }
+
+    fn update_matches(&mut self) {
+        let mut matches: Vec<_> = self.commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, command)| {
+                fuzzy::find(&self.query, &command.label)
+                    .map(|m| (i, m))
+            })
+            .collect();
+        // Sorting is stable, so equal scores keep the registration order.
+        matches.sort_by(|a, b| b.1.score.cmp(&a.1.score));
+
+        self.matches = matches;
+        self.focus = 0;
+    }
+
+    fn draw_command(&self, printer: &Printer, i: usize) {
+        let (index, ref m) = self.matches[i];
+        let command = &self.commands[index];
+
+        if i == self.focus {
+            printer.print((1, 0), &command.label);
+        } else {
+            let label = fuzzy::highlight(&command.label,
+                                         m,
+                                         ColorStyle::TitlePrimary);
+            printer.print_styled((1, 0), &label);
+        }
+
+        if let Some(ref key) = command.key {
+            let x = printer.size.x.saturating_sub(key.width() + 1);
+            if i == self.focus {
+                printer.print((x, 0), key);
+            } else {
+                printer.with_color(ColorStyle::Secondary,
+                                   |printer| printer.print((x, 0), key));
+            }
+        }
+    }
+}
+
+impl View for CommandPalette {
+    fn draw(&self, printer: &Printer) {
+        if printer.size.x < 4 || printer.size.y < HEADER + 2 {
+            return;
+        }
+
+        printer.print_box((0, 0), printer.size, false);
+        printer.print((1, 1), "> ");
+        printer.print((3, 1), &self.query);
+        printer.print_hdelim((0, 2), printer.size.x);
+
+        let rows = printer.size.y - HEADER - 2;
+        if rows == 0 {
+            return;
+        }
+
+        let size = printer.size - (2, HEADER + 2);
+        let printer = printer.sub_printer((1, HEADER + 1), size, true);
+
+        if self.matches.is_empty() {
+            let text = i18n::translate("No match");
+            printer.with_color(ColorStyle::Secondary,
+                               |printer| printer.print((1, 0), &text));
+            return;
+        }
+
+        // Keep the focused command visible.
+        let start = (self.focus + 1).saturating_sub(rows);
+        let end = min(self.matches.len(), start + rows);
+        for i in start..end {
+            let printer = printer.sub_printer((0, i - start),
+                                              (printer.size.x, 1),
+                                              true);
+            printer.with_selection(i == self.focus, |printer| {
+                printer.print_hline((0, 0), printer.size.x, " ");
+                self.draw_command(printer, i);
+            });
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let widest = self.commands
+            .iter()
+            .map(|command| {
+                command.label.width() +
+                command.key.as_ref().map_or(0, |key| key.width() + 2)
+            })
+            .max()
+            .unwrap_or(0);
+
+        // Borders and padding around the widest row.
+        let w = 4 + max(widest, self.query.width() + 2);
+        let h = HEADER + 2 + max(self.matches.len(), 1);
+
+        Vec2::new(max(w, 30), min(h, req.y))
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Esc) => {
+                return EventResult::with_cb(|s| {
+                    s.pop_layer();
+                });
+            }
+            Event::Key(Key::Enter) if !self.matches.is_empty() => {
+                let cb = self.commands[self.matches[self.focus].0]
+                    .cb
+                    .clone();
+                return EventResult::with_cb(move |s| {
+                    s.pop_layer();
+                    cb(s);
+                });
+            }
+            Event::Key(Key::Up) if self.focus > 0 => self.focus -= 1,
+            Event::Key(Key::Down) if self.focus + 1 < self.matches.len() => {
+                self.focus += 1
+            }
+            Event::Key(Key::Backspace) if !self.query.is_empty() => {
+                self.query.pop();
+                self.update_matches();
+            }
+            Event::Char(c) => {
+                self.query.push(c);
+                self.update_matches();
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandPalette;
+    use event::{Event, Key};
+    use view::View;
+
+    #[test]
+    fn filter_commands() {
+        let mut palette = CommandPalette::new()
+            .command("Open file", |_| ())
+            .command_with_key("Save file", "Ctrl-S", |_| ())
+            .command("Quit", |_| ());
+        assert_eq!(palette.matches().len(), 3);
+
+        palette.on_event(Event::Char('f'));
+        assert_eq!(palette.matches(), vec!["Open file", "Save file"]);
+
+        palette.on_event(Event::Char('q'));
+        assert_eq!(palette.query(), "fq");
+        assert!(palette.matches().is_empty());
+
+        palette.on_event(Event::Key(Key::Backspace));
+        palette.on_event(Event::Key(Key::Backspace));
+        assert_eq!(palette.matches().len(), 3);
+
+        palette.set_query("sf");
+        assert_eq!(palette.matches(), vec!["Save file"]);
+    }
+}
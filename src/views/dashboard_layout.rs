@@ -0,0 +1,333 @@
+use Printer;
+use With;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::any::Any;
+use std::cmp::{max, min};
+use vec::Vec2;
+use view::{Selector, View};
+
+/// Tiles named children in a grid, like the panels of a dashboard.
+///
+/// Each child lives in a named slot: setting a slot again replaces its
+/// view, so a chart can be swapped without rebuilding the dashboard.
+/// Slots are placed left-to-right, then top-to-bottom, in the order they
+/// were first set.
+///
+/// The number of columns is either fixed, or chosen from the available
+/// width so each column is at least `min_width` wide; the grid is then
+/// rebalanced when the terminal is resized. All columns have the same
+/// width, and all rows the same height. Children on an incomplete last row
+/// share its whole width.
+///
+/// The layout takes all the space it's given. `<Tab>`, `<Shift+Tab>` and
+/// the arrow keys move the focus between children.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{DashboardLayout, GaugeView, Panel, TextView};
+/// let mut dashboard = DashboardLayout::new().min_width(30);
+/// dashboard.set_slot("cpu", Panel::new(GaugeView::new().value(42.0)));
+/// dashboard.set_slot("logs", Panel::new(TextView::new("Starting...")));
+///
+/// // Later, replace a slot.
+/// dashboard.set_slot("logs", Panel::new(TextView::new("Ready")));
+/// assert_eq!(dashboard.len(), 2);
+/// ```
+pub struct DashboardLayout {
+    slots: Vec<Slot>,
+    // Fixed number of columns, if any.
+    columns: Option<usize>,
+    min_width: usize,
+    focus: usize,
+    // Number of columns from the last layout.
+    current_columns: usize,
+}
+
+struct Slot {
+    name: String,
+    view: Box<View>,
+    offset: Vec2,
+    size: Vec2,
+}
+
+// Splits `total` cells in `parts`, and returns the offset and length of
+// the part at `index`.
+fn split(total: usize, parts: usize, index: usize) -> (usize, usize) {
+    let start = total * index / parts;
+    let end = total * (index + 1) / parts;
+    (start, end - start)
+}
+
+new_default!(DashboardLayout);
+
+impl DashboardLayout {
+    /// Creates a new, empty dashboard.
+    ///
+    /// Columns are at least 20 cells wide.
+    pub fn new() -> Self {
+        DashboardLayout {
+            slots: Vec::new(),
+            columns: None,
+            min_width: 20,
+            focus: 0,
+            current_columns: 1,
+        }
+    }
+
+    /// Uses a fixed number of columns.
+    ///
+    /// `0` is treated as `1`.
+    pub fn set_columns(&mut self, columns: usize) {
+        self.columns = Some(max(1, columns));
+    }
+
+    /// Uses a fixed number of columns.
+    ///
+    /// Chainable variant.
+    pub fn columns(self, columns: usize) -> Self {
+        self.with(|s| s.set_columns(columns))
+    }
+
+    /// Chooses the number of columns from the available width, each being
+    /// at least `min_width` cells wide.
+    ///
+    /// This is the default, with a minimum width of 20.
+    pub fn set_min_width(&mut self, min_width: usize) {
+        self.columns = None;
+        self.min_width = max(1, min_width);
+    }
+
+    /// Chooses the number of columns from the available width.
+    ///
+    /// Chainable variant.
+    pub fn min_width(self, min_width: usize) -> Self {
+        self.with(|s| s.set_min_width(min_width))
+    }
+
+    /// Sets the view of the slot called `name`.
+    ///
+    /// If the slot doesn't exist yet, it is added after the others.
+    pub fn set_slot<S, V>(&mut self, name: S, view: V)
+        where S: Into<String>,
+              V: View + 'static
+    {
+        let name = name.into();
+        let view = Box::new(view);
+        match self.slots.iter().position(|slot| slot.name == name) {
+            Some(i) => self.slots[i].view = view,
+            None => {
+                self.slots.push(Slot {
+                                    name: name,
+                                    view: view,
+                                    offset: Vec2::zero(),
+                                    size: Vec2::zero(),
+                                })
+            }
+        }
+    }
+
+    /// Sets the view of the slot called `name`.
+    ///
+    /// Chainable variant.
+    pub fn slot<S, V>(self, name: S, view: V) -> Self
+        where S: Into<String>,
+              V: View + 'static
+    {
+        self.with(|s| s.set_slot(name, view))
+    }
+
+    /// Removes the slot called `name`, and returns its view.
+    pub fn remove_slot(&mut self, name: &str) -> Option<Box<View>> {
+        let i = self.slots.iter().position(|slot| slot.name == name)?;
+        if self.focus > i || self.focus + 1 == self.slots.len() {
+            self.focus = self.focus.saturating_sub(1);
+        }
+        Some(self.slots.remove(i).view)
+    }
+
+    /// Returns the view of the slot called `name`.
+    pub fn get_slot_mut(&mut self, name: &str) -> Option<&mut Box<View>> {
+        self.slots
+            .iter_mut()
+            .find(|slot| slot.name == name)
+            .map(|slot| &mut slot.view)
+    }
+
+    /// Returns the names of the slots, in order.
+    pub fn slot_names(&self) -> Vec<&str> {
+        self.slots.iter().map(|slot| &slot.name[..]).collect()
+    }
+
+    /// Returns the number of slots.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the dashboard has no slot.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    // Computes the size and offset of each slot.
+    fn arrange(&mut self, size: Vec2) {
+        let len = self.slots.len();
+        if len == 0 {
+            return;
+        }
+
+        let columns = match self.columns {
+            Some(columns) => columns,
+            None => max(1, size.x / self.min_width),
+        };
+        let columns = min(columns, len);
+        let rows = (len + columns - 1) / columns;
+        self.current_columns = columns;
+
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            let (row, column) = (i / columns, i % columns);
+            // The last row may have fewer slots.
+            let in_row = min(columns, len - row * columns);
+            let (x, w) = split(size.x, in_row, column);
+            let (y, h) = split(size.y, rows, row);
+            slot.offset = Vec2::new(x, y);
+            slot.size = Vec2::new(w, h);
+        }
+    }
+
+    // Gives the focus to the first child accepting it, going `step` slots
+    // at a time.
+    fn move_focus(&mut self, step: isize, source: Direction)
+                  -> EventResult {
+        let len = self.slots.len() as isize;
+        let mut i = self.focus as isize + step;
+        while i >= 0 && i < len {
+            if self.slots[i as usize].view.take_focus(source) {
+                self.focus = i as usize;
+                return EventResult::Consumed(None);
+            }
+            i += step;
+        }
+        EventResult::Ignored
+    }
+}
+
+impl View for DashboardLayout {
+    fn draw(&self, printer: &Printer) {
+        for (i, slot) in self.slots.iter().enumerate() {
+            let focused = printer.focused && i == self.focus;
+            slot.view
+                .draw(&printer.sub_printer(slot.offset, slot.size, focused));
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        // A dashboard takes all the space it's given.
+        req
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.arrange(size);
+        for slot in &mut self.slots {
+            slot.view.layout(slot.size);
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.slots.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        let columns = self.current_columns as isize;
+        match self.slots[self.focus].view.on_event(event.clone()) {
+            EventResult::Ignored => {
+                match event {
+                    Event::Key(Key::Tab) |
+                    Event::Key(Key::Right) => {
+                        self.move_focus(1, Direction::front())
+                    }
+                    Event::Shift(Key::Tab) |
+                    Event::Key(Key::Left) => {
+                        self.move_focus(-1, Direction::back())
+                    }
+                    Event::Key(Key::Down) => {
+                        self.move_focus(columns, Direction::up())
+                    }
+                    Event::Key(Key::Up) => {
+                        self.move_focus(-columns, Direction::down())
+                    }
+                    _ => EventResult::Ignored,
+                }
+            }
+            res => res.resolve_on(self),
+        }
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        match self.slots
+            .iter_mut()
+            .position(|slot| slot.view.take_focus(source)) {
+            Some(i) => {
+                self.focus = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        for slot in &mut self.slots {
+            slot.view.call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            if slot.view.focus_view(selector).is_ok() {
+                self.focus = i;
+                return Ok(());
+            }
+        }
+
+        Err(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DashboardLayout;
+    use vec::Vec2;
+    use view::View;
+    use views::TextView;
+
+    #[test]
+    fn test_tiles() {
+        let mut dashboard = DashboardLayout::new()
+            .slot("a", TextView::new("a"))
+            .slot("b", TextView::new("b"))
+            .slot("c", TextView::new("c"))
+            .slot("b", TextView::new("new b"));
+        assert_eq!(dashboard.slot_names(), ["a", "b", "c"]);
+
+        // Two columns: c takes the whole last row.
+        dashboard.layout(Vec2::new(41, 10));
+        let tiles: Vec<_> = dashboard.slots
+            .iter()
+            .map(|slot| (slot.offset, slot.size))
+            .collect();
+        assert_eq!(tiles,
+                   vec![(Vec2::new(0, 0), Vec2::new(20, 5)),
+                        (Vec2::new(20, 0), Vec2::new(21, 5)),
+                        (Vec2::new(0, 5), Vec2::new(41, 5))]);
+
+        // Narrower: a single column.
+        dashboard.layout(Vec2::new(30, 9));
+        assert_eq!(dashboard.slots[2].offset, Vec2::new(0, 6));
+
+        assert!(dashboard.remove_slot("a").is_some());
+        assert!(dashboard.remove_slot("a").is_none());
+        assert_eq!(dashboard.len(), 2);
+    }
+}
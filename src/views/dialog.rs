@@ -8,12 +8,15 @@ use direction::Direction;
 use event::*;
 use std::any::Any;
 use std::cmp::max;
-use theme::ColorStyle;
+use std::error::Error;
+use theme::{ColorStyle, Frame};
 
 use unicode_width::UnicodeWidthStr;
+use utils::{TruncateStyle, i18n, truncate};
 use vec::{Vec2, Vec4};
 use view::{Selector, View};
-use views::{Button, DummyView, SizedView, TextView};
+use views::{Button, DummyView, LinearLayout, SizedView, TextContent,
+            TextView};
 
 #[derive(PartialEq)]
 enum Focus {
@@ -42,6 +45,8 @@ pub struct Dialog {
     focus: Focus,
 
     align: Align,
+
+    frame: Option<Frame>,
 }
 
 new_default!(Dialog);
@@ -64,6 +69,7 @@ impl Dialog {
             padding: Vec4::new(1, 1, 0, 0),
             borders: Vec4::new(1, 1, 1, 1),
             align: Align::top_right(),
+            frame: None,
         }
     }
 
@@ -89,8 +95,66 @@ impl Dialog {
     /// Convenient method to create an infobox.
     ///
     /// It will contain the given text and a `Ok` dismiss button.
+    ///
+    /// The button label can be translated with [`i18n::set_translator`].
+    ///
+    /// [`i18n::set_translator`]: ../utils/i18n/fn.set_translator.html
     pub fn info<S: Into<String>>(text: S) -> Self {
-        Dialog::text(text).dismiss_button("Ok")
+        Dialog::text(text).dismiss_button(i18n::translate("Ok"))
+    }
+
+    /// Creates a dialog reporting an error.
+    ///
+    /// It shows the error and its causes, from `Error::source`. The
+    /// `Details` button expands the debug representation of the error,
+    /// which often includes a backtrace, and `Copy details` copies the
+    /// whole report with [`Cursive::copy_to_clipboard`].
+    ///
+    /// Button labels can be translated with [`i18n::set_translator`].
+    ///
+    /// [`Cursive::copy_to_clipboard`]:
+    /// ../struct.Cursive.html#method.copy_to_clipboard
+    /// [`i18n::set_translator`]: ../utils/i18n/fn.set_translator.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::Dialog;
+    /// # use std::fs::File;
+    /// if let Err(err) = File::open("/does/not/exist") {
+    ///     let dialog = Dialog::from_error(&err);
+    /// }
+    /// ```
+    pub fn from_error(error: &Error) -> Self {
+        let mut summary = error.to_string();
+        let mut source = error.source();
+        while let Some(cause) = source {
+            summary.push_str(&format!("\n{}: {}",
+                                      i18n::translate("Caused by"),
+                                      cause));
+            source = cause.source();
+        }
+        let debug = format!("{:?}", error);
+        let report = format!("{}\n\n{}", summary, debug);
+
+        let details = TextContent::new("");
+        let content = LinearLayout::vertical()
+            .child(TextView::new(summary))
+            .child(TextView::new_with_content(details.clone()));
+
+        Dialog::around(content)
+            .title(i18n::translate("Error"))
+            .button(i18n::translate("Details"), move |_| {
+                let shown = !details.get_content().is_empty();
+                if shown {
+                    details.set_content("");
+                } else {
+                    details.set_content(format!("\n{}", debug));
+                }
+            })
+            .button(i18n::translate("Copy details"),
+                    move |s| s.copy_to_clipboard(&report[..]))
+            .dismiss_button(i18n::translate("Ok"))
     }
 
     /// Adds a button to the dialog with the given label and callback.
@@ -146,6 +210,20 @@ impl Dialog {
         self
     }
 
+    /// Sets the frame drawn around the dialog, and around its title.
+    ///
+    /// `None` uses the theme's glyphs.
+    pub fn set_frame(&mut self, frame: Option<Frame>) {
+        self.frame = frame;
+    }
+
+    /// Sets the frame drawn around the dialog, and around its title.
+    ///
+    /// Chainable variant.
+    pub fn frame(self, frame: Frame) -> Self {
+        self.with(|s| s.set_frame(Some(frame)))
+    }
+
     /// Sets the top padding in the dialog (under the title).
     pub fn padding_top(mut self, padding: usize) -> Self {
         self.padding.top = padding;
@@ -228,21 +306,37 @@ impl View for Dialog {
                                        inner_size,
                                        self.focus == Focus::Content));
 
-        printer.print_box(Vec2::new(0, 0), printer.size, false);
-
-        if !self.title.is_empty() {
-            let len = self.title.width();
-            if len + 4 > printer.size.x {
-                return;
+        let (title_left, title_right) = match self.frame {
+            Some(ref frame) => {
+                printer.print_frame(Vec2::new(0, 0),
+                                    printer.size,
+                                    frame,
+                                    false);
+                (frame.title_left.clone(), frame.title_right.clone())
             }
-            let x = (printer.size.x - len) / 2;
+            None => {
+                printer.print_box(Vec2::new(0, 0), printer.size, false);
+                let glyphs = &printer.theme.glyphs;
+                (format!("{} ", glyphs.right_junction),
+                 format!(" {}", glyphs.left_junction))
+            }
+        };
+
+        let (left, right) = (title_left.width(), title_right.width());
+        if !self.title.is_empty() && printer.size.x > left + right {
+            // Shorten the title if it doesn't fit.
+            let title = truncate(&self.title,
+                                 printer.size.x - (left + right),
+                                 TruncateStyle::End);
+            let len = title.width();
+            let x = max(left, (printer.size.x - len) / 2);
             printer.with_high_border(false, |printer| {
-                printer.print((x - 2, 0), "┤ ");
-                printer.print((x + len, 0), " ├");
+                printer.print((x - left, 0), &title_left);
+                printer.print((x + len, 0), &title_right);
             });
 
             printer.with_color(ColorStyle::TitlePrimary,
-                               |p| p.print((x, 0), &self.title));
+                               |p| p.print((x, 0), &title));
         }
 
     }
@@ -281,7 +375,14 @@ impl View for Dialog {
 
         if !self.title.is_empty() {
             // If we have a title, we have to fit it too!
-            inner_size.x = max(inner_size.x, self.title.width() + 6);
+            let decorations = match self.frame {
+                Some(ref frame) => {
+                    frame.title_left.width() + frame.title_right.width()
+                }
+                None => 4,
+            };
+            inner_size.x = max(inner_size.x,
+                               self.title.width() + decorations + 2);
         }
 
         inner_size
@@ -329,7 +430,7 @@ impl View for Dialog {
                             _ => EventResult::Ignored,
                         }
                     }
-                    res => res,
+                    res => res.resolve_on(self),
                 }
             }
             // If we are on a button, we have more choice
@@ -379,7 +480,7 @@ impl View for Dialog {
                             _ => EventResult::Ignored,
                         }
                     }
-                    res => res,
+                    res => res.resolve_on(self),
                 }
             }
         }
@@ -399,6 +500,19 @@ impl View for Dialog {
         }
     }
 
+    fn describe(&self) -> Option<String> {
+        let description = match self.focus {
+            Focus::Content => self.content.describe(),
+            Focus::Button(i) => self.buttons[i].describe(),
+        };
+        match description {
+            Some(description) if !self.title.is_empty() => {
+                Some(format!("{}: {}", self.title, description))
+            }
+            description => description,
+        }
+    }
+
     fn call_on_any<'a>(&mut self, selector: &Selector,
                     callback: Box<FnMut(&mut Any) + 'a>) {
         self.content.call_on_any(selector, callback);
@@ -0,0 +1,596 @@
+use {Printer, With};
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::cmp::{max, min};
+use theme::{BaseColor, Color, ColorStyle};
+use vec::Vec2;
+use view::{Scrollable, View};
+
+/// How a `DiffView` shows the changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffMode {
+    /// Removed and added lines follow each other, like `diff -u`.
+    Unified,
+    /// Old text on the left, new text on the right.
+    SideBySide,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Change {
+    Same,
+    Removed,
+    Added,
+}
+
+struct DiffLine {
+    change: Change,
+    // Line numbers in the old and new texts, starting at 1.
+    old: Option<usize>,
+    new: Option<usize>,
+    text: String,
+}
+
+// One row on screen, made of indices in `lines`.
+//
+// In unified mode, only `left` is used.
+#[derive(Clone, Copy)]
+struct Row {
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+// Returns the changes needed to go from `old` to `new`.
+//
+// Within a block of changes, removed lines come first.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Change> {
+    // Common prefix and suffix don't need the full table.
+    let prefix = old.iter()
+        .zip(new)
+        .take_while(|&(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|&(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // `lcs[i][j]` is the length of the longest common subsequence
+    // between `old_mid[i..]` and `new_mid[j..]`.
+    let (n, m) = (old_mid.len(), new_mid.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = vec![Change::Same; prefix];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            changes.push(Change::Same);
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(Change::Removed);
+            i += 1;
+        } else {
+            changes.push(Change::Added);
+            j += 1;
+        }
+    }
+    changes.extend(vec![Change::Same; suffix]);
+    changes
+}
+
+/// Shows the differences between two texts.
+///
+/// * Arrows, `<PageUp>`, `<PageDown>`, `<Home>` and `<End>` scroll the text.
+/// * `n` and `p` go to the next and previous hunks.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{DiffMode, DiffView};
+/// let view = DiffView::new("a\nb\nc\n", "a\nB\nc\n")
+///     .mode(DiffMode::SideBySide);
+/// assert_eq!(view.hunk_count(), 1);
+/// ```
+pub struct DiffView {
+    lines: Vec<DiffLine>,
+    mode: DiffMode,
+    rows: Vec<Row>,
+    // Index of the first row of each hunk.
+    hunks: Vec<usize>,
+
+    added_color: ColorStyle,
+    removed_color: ColorStyle,
+
+    // First visible row.
+    top: usize,
+    last_size: Vec2,
+}
+
+impl DiffView {
+    /// Creates a new view comparing `old` and `new`.
+    pub fn new(old: &str, new: &str) -> Self {
+        let old_lines: Vec<&str> = old.lines().collect();
+        let new_lines: Vec<&str> = new.lines().collect();
+
+        let mut lines = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        for change in diff_lines(&old_lines, &new_lines) {
+            let (old, new, text) = match change {
+                Change::Same => (Some(i + 1), Some(j + 1), old_lines[i]),
+                Change::Removed => (Some(i + 1), None, old_lines[i]),
+                Change::Added => (None, Some(j + 1), new_lines[j]),
+            };
+            if old.is_some() {
+                i += 1;
+            }
+            if new.is_some() {
+                j += 1;
+            }
+            lines.push(DiffLine {
+                change: change,
+                old: old,
+                new: new,
+                text: text.replace('\t', "    "),
+            });
+        }
+
+        DiffView::from_lines(lines)
+    }
+
+    /// Creates a new view from a diff in the unified format.
+    ///
+    /// File headers are ignored.
+    pub fn from_unified(diff: &str) -> Self {
+        let mut lines = Vec::new();
+        let (mut old, mut new) = (1, 1);
+        // Lines left in the current hunk, on each side.
+        let (mut old_left, mut new_left) = (0, 0);
+        for line in diff.lines() {
+            let in_hunk = old_left > 0 || new_left > 0;
+            if line.starts_with("@@") && !in_hunk {
+                // @@ -old,len +new,len @@
+                let mut parts = line.split_whitespace().skip(1);
+                let (start, len) = parse_range(parts.next());
+                old = start;
+                old_left = len;
+                let (start, len) = parse_range(parts.next());
+                new = start;
+                new_left = len;
+                continue;
+            }
+            // File headers only come between hunks.
+            if line.starts_with('\\') ||
+               !in_hunk && (line.starts_with("---") ||
+                            line.starts_with("+++")) {
+                continue;
+            }
+
+            let (change, text) = match line.chars().next() {
+                Some('+') => (Change::Added, &line[1..]),
+                Some('-') => (Change::Removed, &line[1..]),
+                Some(' ') => (Change::Same, &line[1..]),
+                Some(_) => continue,
+                None => (Change::Same, ""),
+            };
+            let old_line = if change == Change::Added {
+                None
+            } else {
+                old += 1;
+                old_left = old_left.saturating_sub(1);
+                Some(old - 1)
+            };
+            let new_line = if change == Change::Removed {
+                None
+            } else {
+                new += 1;
+                new_left = new_left.saturating_sub(1);
+                Some(new - 1)
+            };
+            lines.push(DiffLine {
+                change: change,
+                old: old_line,
+                new: new_line,
+                text: text.replace('\t', "    "),
+            });
+        }
+
+        DiffView::from_lines(lines)
+    }
+
+    fn from_lines(lines: Vec<DiffLine>) -> Self {
+        DiffView {
+            lines: lines,
+            mode: DiffMode::Unified,
+            rows: Vec::new(),
+            hunks: Vec::new(),
+            added_color: ColorStyle::Custom {
+                front: Color::Dark(BaseColor::Green),
+                back: Color::Dark(BaseColor::White),
+            },
+            removed_color: ColorStyle::Custom {
+                front: Color::Dark(BaseColor::Red),
+                back: Color::Dark(BaseColor::White),
+            },
+            top: 0,
+            last_size: Vec2::zero(),
+        }
+        .with(|s| s.compute_rows())
+    }
+
+    /// Sets the display mode.
+    ///
+    /// Defaults to `DiffMode::Unified`.
+    pub fn set_mode(&mut self, mode: DiffMode) {
+        self.mode = mode;
+        self.compute_rows();
+    }
+
+    /// Sets the display mode.
+    ///
+    /// Chainable variant.
+    pub fn mode(self, mode: DiffMode) -> Self {
+        self.with(|s| s.set_mode(mode))
+    }
+
+    /// Returns the current display mode.
+    pub fn get_mode(&self) -> DiffMode {
+        self.mode
+    }
+
+    /// Sets the color used for added lines.
+    ///
+    /// Defaults to green text.
+    pub fn set_added_color(&mut self, color: ColorStyle) {
+        self.added_color = color;
+    }
+
+    /// Sets the color used for added lines.
+    ///
+    /// Chainable variant.
+    pub fn added_color(self, color: ColorStyle) -> Self {
+        self.with(|s| s.set_added_color(color))
+    }
+
+    /// Sets the color used for removed lines.
+    ///
+    /// Defaults to red text.
+    pub fn set_removed_color(&mut self, color: ColorStyle) {
+        self.removed_color = color;
+    }
+
+    /// Sets the color used for removed lines.
+    ///
+    /// Chainable variant.
+    pub fn removed_color(self, color: ColorStyle) -> Self {
+        self.with(|s| s.set_removed_color(color))
+    }
+
+    /// Returns the number of hunks (blocks of changed lines).
+    pub fn hunk_count(&self) -> usize {
+        self.hunks.len()
+    }
+
+    /// Scrolls to the next hunk, if any.
+    pub fn next_hunk(&mut self) {
+        if let Some(&row) = self.hunks.iter().find(|&&row| row > self.top) {
+            self.top = row;
+            self.fix_top();
+        }
+    }
+
+    /// Scrolls to the previous hunk, if any.
+    pub fn prev_hunk(&mut self) {
+        let top = self.top;
+        if let Some(&row) = self.hunks.iter().rev().find(|&&row| row < top) {
+            self.top = row;
+        }
+    }
+
+    /// Returns the current scroll position.
+    pub fn scroll_offset(&self) -> Vec2 {
+        Vec2::new(0, self.top)
+    }
+
+    /// Sets the scroll position.
+    ///
+    /// Only `offset.y` is used, as this view only scrolls vertically.
+    pub fn set_scroll_offset<O: Into<Vec2>>(&mut self, offset: O) {
+        self.top = offset.into().y;
+        self.fix_top();
+    }
+
+    /// Scrolls to the end of the diff.
+    pub fn scroll_to_bottom(&mut self) {
+        self.top = self.rows.len();
+        self.fix_top();
+    }
+
+    fn compute_rows(&mut self) {
+        self.rows.clear();
+        self.hunks.clear();
+
+        let mut i = 0;
+        while i < self.lines.len() {
+            if self.lines[i].change == Change::Same {
+                self.rows.push(Row {
+                    left: Some(i),
+                    right: Some(i),
+                });
+                i += 1;
+                continue;
+            }
+
+            // Start of a hunk.
+            self.hunks.push(self.rows.len());
+            let start = i;
+            while i < self.lines.len() &&
+                  self.lines[i].change != Change::Same {
+                i += 1;
+            }
+
+            match self.mode {
+                DiffMode::Unified => {
+                    for j in start..i {
+                        self.rows.push(Row {
+                            left: Some(j),
+                            right: None,
+                        });
+                    }
+                }
+                DiffMode::SideBySide => {
+                    let (removed, added): (Vec<usize>, Vec<usize>) =
+                        (start..i).partition(|&j| {
+                            self.lines[j].change == Change::Removed
+                        });
+                    for k in 0..max(removed.len(), added.len()) {
+                        self.rows.push(Row {
+                            left: removed.get(k).cloned(),
+                            right: added.get(k).cloned(),
+                        });
+                    }
+                }
+            }
+        }
+        self.fix_top();
+    }
+
+    fn fix_top(&mut self) {
+        let max_top = self.rows.len().saturating_sub(self.last_size.y);
+        self.top = min(self.top, max_top);
+    }
+
+    fn number_width(&self) -> usize {
+        let max_line = self.lines
+            .iter()
+            .map(|line| max(line.old.unwrap_or(0), line.new.unwrap_or(0)))
+            .max()
+            .unwrap_or(0);
+        max_line.to_string().len()
+    }
+
+    fn color(&self, change: Change) -> ColorStyle {
+        match change {
+            Change::Same => ColorStyle::Primary,
+            Change::Added => self.added_color,
+            Change::Removed => self.removed_color,
+        }
+    }
+
+    // Prints a line in unified mode: both numbers, a sign, and the text.
+    fn draw_unified(&self, printer: &Printer, line: &DiffLine) {
+        let width = self.number_width();
+        printer.with_color(ColorStyle::Secondary, |printer| {
+            printer.print_hline((0, 0), 2 * width + 1, " ");
+            for (i, number) in [line.old, line.new].iter().enumerate() {
+                if let Some(n) = *number {
+                    printer.print_fmt((i * (width + 1), 0),
+                                      format_args!("{:>1$}", n, width));
+                }
+            }
+        });
+
+        let sign = match line.change {
+            Change::Same => " ",
+            Change::Added => "+",
+            Change::Removed => "-",
+        };
+        let printer = printer.offset((2 * width + 2, 0), true);
+        printer.with_color(self.color(line.change), |printer| {
+            printer.print_hline((0, 0), printer.size.x, " ");
+            printer.print((0, 0), sign);
+            printer.print((2, 0), &line.text);
+        });
+    }
+
+    // Prints one side in side-by-side mode.
+    fn draw_side(&self, printer: &Printer, line: Option<&DiffLine>,
+                 old: bool) {
+        let width = self.number_width();
+        let line = match line {
+            Some(line) => line,
+            None => return,
+        };
+        let number = if old { line.old } else { line.new };
+        printer.with_color(ColorStyle::Secondary, |printer| {
+            match number {
+                Some(n) => {
+                    printer.print_fmt((0, 0), format_args!("{:>1$}", n, width))
+                }
+                None => printer.print_hline((0, 0), width, " "),
+            }
+        });
+
+        let printer = printer.offset((width + 1, 0), true);
+        printer.with_color(self.color(line.change), |printer| {
+            printer.print_hline((0, 0), printer.size.x, " ");
+            printer.print((0, 0), &line.text);
+        });
+    }
+}
+
+// Parses the start of a range like `-12,4` or `+3`.
+// Parses `-start,len` or `+start,len` from a hunk header.
+//
+// The length defaults to 1.
+fn parse_range(range: Option<&str>) -> (usize, usize) {
+    let mut parts = range.and_then(|range| range.get(1..))
+        .unwrap_or("")
+        .split(',');
+    let start = parts.next()
+        .and_then(|start| start.parse().ok())
+        .unwrap_or(1);
+    let len = parts.next()
+        .map_or(Some(1), |len| len.parse().ok())
+        .unwrap_or(0);
+    (start, len)
+}
+
+impl Scrollable for DiffView {
+    fn scroll_offset(&self) -> Vec2 {
+        DiffView::scroll_offset(self)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Vec2) {
+        DiffView::set_scroll_offset(self, offset);
+    }
+}
+
+impl View for DiffView {
+    fn draw(&self, printer: &Printer) {
+        let half = printer.size.x / 2;
+        let rows = self.rows.iter().skip(self.top).take(printer.size.y);
+        for (y, row) in rows.enumerate() {
+            let printer =
+                printer.sub_printer((0, y), (printer.size.x, 1), true);
+            match self.mode {
+                DiffMode::Unified => {
+                    if let Some(i) = row.left {
+                        self.draw_unified(&printer, &self.lines[i]);
+                    }
+                }
+                DiffMode::SideBySide => {
+                    let left = printer.sub_printer((0, 0), (half, 1), true);
+                    let right = printer.offset((half + 1, 0), true);
+                    self.draw_side(&left, row.left.map(|i| &self.lines[i]),
+                                   true);
+                    printer.print((half, 0), &printer.theme.glyphs.vertical);
+                    self.draw_side(&right,
+                                   row.right.map(|i| &self.lines[i]),
+                                   false);
+                }
+            }
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let text = self.lines
+            .iter()
+            .map(|line| line.text.len())
+            .max()
+            .unwrap_or(0);
+        let width = self.number_width();
+        let width = match self.mode {
+            DiffMode::Unified => 2 * width + 4 + text,
+            DiffMode::SideBySide => 2 * (width + 1 + text) + 1,
+        };
+        Vec2::new(width, self.rows.len())
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.fix_top();
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let height = max(1, self.last_size.y);
+
+        match event {
+            Event::Key(Key::Up) if self.top > 0 => self.top -= 1,
+            Event::Key(Key::Down) if self.top + height < self.rows.len() => {
+                self.top += 1
+            }
+            Event::Key(Key::PageUp) if self.top > 0 => {
+                self.top -= min(self.top, height)
+            }
+            Event::Key(Key::PageDown) if self.top + height <
+                                         self.rows.len() => {
+                self.top += height;
+                self.fix_top();
+            }
+            Event::Key(Key::Home) => self.top = 0,
+            Event::Key(Key::End) => {
+                self.top = self.rows.len();
+                self.fix_top();
+            }
+            Event::Char('n') => self.next_hunk(),
+            Event::Char('p') => self.prev_hunk(),
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = ["a", "b", "c", "d"];
+        let new = ["a", "x", "c", "d", "e"];
+        assert_eq!(diff_lines(&old, &new),
+                   vec![Change::Same,
+                        Change::Removed,
+                        Change::Added,
+                        Change::Same,
+                        Change::Same,
+                        Change::Added]);
+    }
+
+    #[test]
+    fn test_unified() {
+        let diff = "--- a\n+++ b\n@@ -3,2 +3,2 @@\n a\n-b\n+c\n";
+        let view = DiffView::from_unified(diff);
+        assert_eq!(view.hunk_count(), 1);
+        assert_eq!(view.lines[1].old, Some(4));
+        assert_eq!(view.lines[2].new, Some(4));
+
+        let view = view.mode(DiffMode::SideBySide);
+        assert_eq!(view.rows.len(), 2);
+    }
+
+    #[test]
+    fn test_unified_dashes() {
+        // Content lines can look like file headers.
+        let diff = "--- a\n+++ b\n@@ -1,2 +1,2 @@\n---- foo\n+++ bar\n \
+                    x\n--- c\n+++ d\n@@ -5 +5 @@\n--- e\n+++ f\n";
+        let view = DiffView::from_unified(diff);
+        assert_eq!(view.hunk_count(), 2);
+        let texts: Vec<&str> = view.lines
+            .iter()
+            .map(|line| &line.text[..])
+            .collect();
+        assert_eq!(texts, vec!["--- foo", "++ bar", "x", "-- e", "++ f"]);
+        assert_eq!(view.lines[3].old, Some(5));
+
+        // Headers with multi-byte characters don't panic.
+        DiffView::from_unified("@@ é ü @@\n a\n");
+    }
+}
@@ -0,0 +1,658 @@
+use event::{Event, Key};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Key bindings used by text inputs like `EditView` and `TextArea`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditMode {
+    /// Arrow keys, `<Home>`, `<End>`, `<Backspace>` and `<Del>` only.
+    Default,
+    /// Emacs (and readline) bindings.
+    ///
+    /// * `<Ctrl+a>` / `<Ctrl+e>` go to the start / end of the line.
+    /// * `<Ctrl+b>` / `<Ctrl+f>` / `<Ctrl+p>` / `<Ctrl+n>` move the cursor.
+    /// * `<Alt+b>` / `<Alt+f>` move by words.
+    /// * `<Ctrl+d>` / `<Ctrl+h>` delete a character.
+    /// * `<Ctrl+k>` / `<Ctrl+u>` kill to the end / start of the line.
+    /// * `<Ctrl+w>` / `<Alt+d>` kill the previous / next word.
+    /// * `<Ctrl+y>` yanks the last killed text.
+    Emacs,
+    /// Modal vi bindings.
+    ///
+    /// Views start in insert mode; `<Esc>` goes to normal mode, where
+    /// `<Esc>` is left to the parent views.
+    ///
+    /// Normal mode supports:
+    ///
+    /// * the `h`, `j`, `k`, `l`, `w`, `b`, `e`, `0`, `^` and `$` motions;
+    /// * the `d`, `c` and `y` operators, followed by a motion, by `iw` or
+    ///   `aw`, or repeated to act on the whole line;
+    /// * `i`, `a`, `I`, `A`, `o`, `O`, `s`, `C` and `D`;
+    /// * `x`, `X`, `p` and `P`.
+    Vi,
+}
+
+static GLOBAL_MODE: AtomicUsize = AtomicUsize::new(0);
+
+impl EditMode {
+    /// Sets the mode used by views without a mode of their own.
+    pub fn set_global(mode: EditMode) {
+        let id = match mode {
+            EditMode::Default => 0,
+            EditMode::Emacs => 1,
+            EditMode::Vi => 2,
+        };
+        GLOBAL_MODE.store(id, Ordering::Relaxed);
+    }
+
+    /// Returns the mode used by views without a mode of their own.
+    ///
+    /// Defaults to `EditMode::Default`.
+    pub fn global() -> EditMode {
+        match GLOBAL_MODE.load(Ordering::Relaxed) {
+            1 => EditMode::Emacs,
+            2 => EditMode::Vi,
+            _ => EditMode::Default,
+        }
+    }
+}
+
+/// Text that a `Keymap` can edit.
+pub trait Buffer {
+    /// Returns the whole text.
+    fn content(&self) -> &str;
+
+    /// Returns the cursor position, in bytes.
+    fn cursor(&self) -> usize;
+
+    /// Moves the cursor to the given byte offset.
+    fn set_cursor(&mut self, cursor: usize);
+
+    /// Replaces the text between `start` and `end` with `text`.
+    ///
+    /// The cursor position is set afterwards.
+    fn replace(&mut self, start: usize, end: usize, text: &str);
+
+    /// Returns `true` if the text can contain newlines.
+    fn multiline(&self) -> bool;
+}
+
+/// What a view should do after a `Keymap` saw an event.
+pub enum Outcome {
+    /// Ignore the event.
+    Ignored,
+    /// The text or the cursor may have changed.
+    Consumed,
+    /// Handle this event as usual.
+    Forward(Event),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ViState {
+    Insert,
+    Normal,
+    // An operator is waiting for a motion.
+    Pending(Operator),
+    // An operator is waiting for a text object (after `i` or `a`).
+    Object(Operator, bool),
+}
+
+/// Translates events according to an `EditMode`.
+pub struct Keymap {
+    vi: ViState,
+    // Last killed, deleted or yanked text.
+    register: String,
+}
+
+new_default!(Keymap);
+
+impl Keymap {
+    /// Creates a new keymap, in vi insert mode.
+    pub fn new() -> Self {
+        Keymap {
+            vi: ViState::Insert,
+            register: String::new(),
+        }
+    }
+
+    /// Handles an event for the given buffer.
+    pub fn on_event<B: Buffer>(&mut self, mode: EditMode, event: &Event,
+                               buffer: &mut B)
+                               -> Outcome {
+        match mode {
+            EditMode::Default => Outcome::Forward(event.clone()),
+            EditMode::Emacs => self.on_emacs_event(event, buffer),
+            EditMode::Vi => self.on_vi_event(event, buffer),
+        }
+    }
+
+    fn kill<B: Buffer>(&mut self, buffer: &mut B, start: usize, end: usize) {
+        self.register = buffer.content()[start..end].to_string();
+        buffer.replace(start, end, "");
+        buffer.set_cursor(start);
+    }
+
+    fn paste<B: Buffer>(&self, buffer: &mut B, at: usize) {
+        buffer.replace(at, at, &self.register);
+        buffer.set_cursor(at + self.register.len());
+    }
+
+    fn on_emacs_event<B: Buffer>(&mut self, event: &Event, buffer: &mut B)
+                                 -> Outcome {
+        let cursor = buffer.cursor();
+        let end = {
+            let content = buffer.content();
+            match *event {
+                Event::CtrlChar('a') => line_start(content, cursor),
+                Event::CtrlChar('e') => line_end(content, cursor),
+                Event::AltChar('b') => word_start_back(content, cursor),
+                Event::AltChar('f') => word_end(content, cursor),
+                Event::CtrlChar('k') => {
+                    let end = line_end(content, cursor);
+                    if end == cursor && end < content.len() {
+                        // Kill the newline itself.
+                        end + 1
+                    } else {
+                        end
+                    }
+                }
+                Event::CtrlChar('u') => line_start(content, cursor),
+                Event::CtrlChar('w') |
+                Event::Alt(Key::Backspace) => {
+                    word_start_back(content, cursor)
+                }
+                Event::AltChar('d') => word_end(content, cursor),
+                Event::CtrlChar('y') => cursor,
+                _ => return Outcome::Forward(translate_emacs(event)),
+            }
+        };
+
+        match *event {
+            Event::CtrlChar('k') |
+            Event::AltChar('d') => self.kill(buffer, cursor, end),
+            Event::CtrlChar('u') |
+            Event::CtrlChar('w') |
+            Event::Alt(Key::Backspace) => self.kill(buffer, end, cursor),
+            Event::CtrlChar('y') => self.paste(buffer, cursor),
+            _ => buffer.set_cursor(end),
+        }
+        Outcome::Consumed
+    }
+
+    fn on_vi_event<B: Buffer>(&mut self, event: &Event, buffer: &mut B)
+                              -> Outcome {
+        let state = self.vi;
+        match state {
+            ViState::Insert => {
+                if *event == Event::Key(Key::Esc) {
+                    self.vi = ViState::Normal;
+                    let cursor = buffer.cursor();
+                    let content = buffer.content();
+                    if cursor > line_start(content, cursor) {
+                        let cursor = prev_grapheme(content, cursor);
+                        buffer.set_cursor(cursor);
+                    }
+                    Outcome::Consumed
+                } else {
+                    Outcome::Forward(event.clone())
+                }
+            }
+            ViState::Normal => self.on_vi_normal(event, buffer),
+            ViState::Pending(op) => {
+                self.vi = ViState::Normal;
+                self.on_vi_pending(op, event, buffer)
+            }
+            ViState::Object(op, around) => {
+                self.vi = ViState::Normal;
+                if *event == Event::Char('w') {
+                    let (start, end) = {
+                        let content = buffer.content();
+                        inner_word(content, buffer.cursor(), around)
+                    };
+                    self.apply(op, buffer, start, end);
+                }
+                Outcome::Consumed
+            }
+        }
+    }
+
+    fn on_vi_normal<B: Buffer>(&mut self, event: &Event, buffer: &mut B)
+                               -> Outcome {
+        let c = match *event {
+            Event::Char(c) => c,
+            Event::Key(Key::Esc) => return Outcome::Ignored,
+            // Don't edit the text outside of insert mode.
+            Event::Key(Key::Enter) if buffer.multiline() => {
+                return Outcome::Forward(Event::Key(Key::Down))
+            }
+            Event::Key(Key::Backspace) => {
+                return Outcome::Forward(Event::Key(Key::Left))
+            }
+            _ => return Outcome::Forward(event.clone()),
+        };
+
+        let cursor = buffer.cursor();
+        match c {
+            'h' => return Outcome::Forward(Event::Key(Key::Left)),
+            'l' => return Outcome::Forward(Event::Key(Key::Right)),
+            'j' => return Outcome::Forward(Event::Key(Key::Down)),
+            'k' => return Outcome::Forward(Event::Key(Key::Up)),
+            'x' => {
+                let end = next_grapheme(buffer.content(), cursor);
+                if end == cursor {
+                    return Outcome::Consumed;
+                }
+                self.kill(buffer, cursor, end);
+            }
+            'X' => {
+                if cursor == line_start(buffer.content(), cursor) {
+                    return Outcome::Consumed;
+                }
+                let start = prev_grapheme(buffer.content(), cursor);
+                self.kill(buffer, start, cursor);
+            }
+            'p' => {
+                let at = next_grapheme(buffer.content(), cursor);
+                self.paste(buffer, at);
+                self.to_last_pasted(buffer);
+            }
+            'P' => {
+                self.paste(buffer, cursor);
+                self.to_last_pasted(buffer);
+            }
+            'i' => self.vi = ViState::Insert,
+            'a' => {
+                let next = next_grapheme(buffer.content(), cursor);
+                if cursor < line_end(buffer.content(), cursor) {
+                    buffer.set_cursor(next);
+                }
+                self.vi = ViState::Insert;
+            }
+            'I' => {
+                let start = first_non_blank(buffer.content(), cursor);
+                buffer.set_cursor(start);
+                self.vi = ViState::Insert;
+            }
+            'A' => {
+                let end = line_end(buffer.content(), cursor);
+                buffer.set_cursor(end);
+                self.vi = ViState::Insert;
+            }
+            'o' | 'O' if buffer.multiline() => {
+                let at = if c == 'o' {
+                    line_end(buffer.content(), cursor)
+                } else {
+                    line_start(buffer.content(), cursor)
+                };
+                buffer.replace(at, at, "\n");
+                buffer.set_cursor(if c == 'o' { at + 1 } else { at });
+                self.vi = ViState::Insert;
+            }
+            's' => {
+                let end = next_grapheme(buffer.content(), cursor);
+                self.kill(buffer, cursor, end);
+                self.vi = ViState::Insert;
+            }
+            'D' | 'C' => {
+                let end = line_end(buffer.content(), cursor);
+                self.kill(buffer, cursor, end);
+                if c == 'C' {
+                    self.vi = ViState::Insert;
+                }
+            }
+            'd' => self.vi = ViState::Pending(Operator::Delete),
+            'c' => self.vi = ViState::Pending(Operator::Change),
+            'y' => self.vi = ViState::Pending(Operator::Yank),
+            _ => {
+                let target = match motion(buffer.content(), cursor, c) {
+                    Some(target) => target,
+                    // Unknown commands shouldn't insert anything.
+                    None => return Outcome::Consumed,
+                };
+                buffer.set_cursor(target);
+            }
+        }
+        Outcome::Consumed
+    }
+
+    fn on_vi_pending<B: Buffer>(&mut self, op: Operator, event: &Event,
+                                buffer: &mut B)
+                                -> Outcome {
+        let c = match *event {
+            Event::Char(c) => c,
+            // `<Esc>` or anything else cancels the operator.
+            _ => return Outcome::Consumed,
+        };
+
+        let cursor = buffer.cursor();
+        let (start, end) = {
+            let content = buffer.content();
+            match (op, c) {
+                (_, 'i') | (_, 'a') => {
+                    self.vi = ViState::Object(op, c == 'a');
+                    return Outcome::Consumed;
+                }
+                (Operator::Delete, 'd') |
+                (Operator::Change, 'c') |
+                (Operator::Yank, 'y') => {
+                    whole_line(content, cursor, op == Operator::Delete)
+                }
+                // `cw` acts like `ce`, when on a word.
+                (Operator::Change, 'w') if !is_blank(content, cursor) => {
+                    (cursor, word_end(content, cursor))
+                }
+                (_, 'e') => (cursor, word_end(content, cursor)),
+                _ => {
+                    match motion(content, cursor, c) {
+                        Some(target) if target < cursor => (target, cursor),
+                        Some(target) => (cursor, target),
+                        None => return Outcome::Consumed,
+                    }
+                }
+            }
+        };
+        self.apply(op, buffer, start, end);
+        Outcome::Consumed
+    }
+
+    fn apply<B: Buffer>(&mut self, op: Operator, buffer: &mut B,
+                        start: usize, end: usize) {
+        match op {
+            Operator::Yank => {
+                self.register = buffer.content()[start..end].to_string();
+                buffer.set_cursor(start);
+            }
+            Operator::Delete => self.kill(buffer, start, end),
+            Operator::Change => {
+                self.kill(buffer, start, end);
+                self.vi = ViState::Insert;
+            }
+        }
+    }
+
+    // In normal mode, the cursor stays on the last pasted character.
+    fn to_last_pasted<B: Buffer>(&self, buffer: &mut B) {
+        if !self.register.is_empty() {
+            let cursor = prev_grapheme(buffer.content(), buffer.cursor());
+            buffer.set_cursor(cursor);
+        }
+    }
+}
+
+// Returns the events with a default behaviour for emacs bindings.
+fn translate_emacs(event: &Event) -> Event {
+    match *event {
+        Event::CtrlChar('b') => Event::Key(Key::Left),
+        Event::CtrlChar('f') => Event::Key(Key::Right),
+        Event::CtrlChar('p') => Event::Key(Key::Up),
+        Event::CtrlChar('n') => Event::Key(Key::Down),
+        Event::CtrlChar('d') => Event::Key(Key::Del),
+        Event::CtrlChar('h') => Event::Key(Key::Backspace),
+        _ => event.clone(),
+    }
+}
+
+// Returns the target of a vi motion, if `c` is one.
+fn motion(content: &str, cursor: usize, c: char) -> Option<usize> {
+    Some(match c {
+        '0' => line_start(content, cursor),
+        '^' => first_non_blank(content, cursor),
+        '$' => line_end(content, cursor),
+        'w' => word_start(content, cursor),
+        'b' => word_start_back(content, cursor),
+        'e' => {
+            let end = word_end(content, next_grapheme(content, cursor));
+            prev_grapheme(content, end)
+        }
+        'h' => {
+            if cursor > line_start(content, cursor) {
+                prev_grapheme(content, cursor)
+            } else {
+                cursor
+            }
+        }
+        'l' => {
+            if cursor < line_end(content, cursor) {
+                next_grapheme(content, cursor)
+            } else {
+                cursor
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn prev_grapheme(content: &str, cursor: usize) -> usize {
+    content[..cursor]
+        .graphemes(true)
+        .next_back()
+        .map_or(cursor, |g| cursor - g.len())
+}
+
+fn next_grapheme(content: &str, cursor: usize) -> usize {
+    content[cursor..]
+        .graphemes(true)
+        .next()
+        .map_or(cursor, |g| cursor + g.len())
+}
+
+fn line_start(content: &str, cursor: usize) -> usize {
+    content[..cursor].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(content: &str, cursor: usize) -> usize {
+    content[cursor..].find('\n').map_or(content.len(), |i| cursor + i)
+}
+
+fn first_non_blank(content: &str, cursor: usize) -> usize {
+    let start = line_start(content, cursor);
+    let end = line_end(content, cursor);
+    content[start..end]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(end, |i| start + i)
+}
+
+// Returns the byte range of the line, optionally with its newline.
+fn whole_line(content: &str, cursor: usize, newline: bool)
+              -> (usize, usize) {
+    let start = line_start(content, cursor);
+    let end = line_end(content, cursor);
+    if !newline {
+        (start, end)
+    } else if end < content.len() {
+        (start, end + 1)
+    } else {
+        // Last line: take the previous newline instead.
+        (start.saturating_sub(1), end)
+    }
+}
+
+// Words are runs of alphanumeric characters, or runs of punctuation.
+#[derive(PartialEq, Eq)]
+enum Class {
+    Blank,
+    Word,
+    Punctuation,
+}
+
+fn class(c: char) -> Class {
+    if c.is_whitespace() {
+        Class::Blank
+    } else if c.is_alphanumeric() || c == '_' {
+        Class::Word
+    } else {
+        Class::Punctuation
+    }
+}
+
+fn is_blank(content: &str, cursor: usize) -> bool {
+    content[cursor..].chars().next().map_or(true, char::is_whitespace)
+}
+
+// Start of the next word.
+fn word_start(content: &str, cursor: usize) -> usize {
+    let mut chars = content[cursor..].char_indices().peekable();
+    if let Some(&(_, first)) = chars.peek() {
+        let first = class(first);
+        if first != Class::Blank {
+            while chars.peek().map_or(false, |&(_, c)| class(c) == first) {
+                chars.next();
+            }
+        }
+    }
+    while chars.peek().map_or(false, |&(_, c)| c.is_whitespace()) {
+        chars.next();
+    }
+    chars.peek().map_or(content.len(), |&(i, _)| cursor + i)
+}
+
+// Start of the current or previous word.
+fn word_start_back(content: &str, cursor: usize) -> usize {
+    let mut chars = content[..cursor].char_indices().rev().peekable();
+    while chars.peek().map_or(false, |&(_, c)| c.is_whitespace()) {
+        chars.next();
+    }
+    let mut start = chars.peek().map_or(0, |&(i, _)| i);
+    if let Some(&(_, last)) = chars.peek() {
+        let last = class(last);
+        while let Some(&(i, c)) = chars.peek() {
+            if class(c) != last {
+                break;
+            }
+            start = i;
+            chars.next();
+        }
+    }
+    start
+}
+
+// End of the current or next word (exclusive).
+fn word_end(content: &str, cursor: usize) -> usize {
+    let mut chars = content[cursor..].char_indices().peekable();
+    while chars.peek().map_or(false, |&(_, c)| c.is_whitespace()) {
+        chars.next();
+    }
+    if let Some(&(_, first)) = chars.peek() {
+        let first = class(first);
+        while chars.peek().map_or(false, |&(_, c)| class(c) == first) {
+            chars.next();
+        }
+    }
+    chars.peek().map_or(content.len(), |&(i, _)| cursor + i)
+}
+
+// Byte range of the word under the cursor, for `iw` and `aw`.
+fn inner_word(content: &str, cursor: usize, around: bool) -> (usize, usize) {
+    let current = match content[cursor..].chars().next() {
+        Some(c) => class(c),
+        None => return (cursor, cursor),
+    };
+    let start = content[..cursor]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| class(c) == current)
+        .last()
+        .map_or(cursor, |(i, _)| i);
+    let mut end = content[cursor..]
+        .char_indices()
+        .find(|&(_, c)| class(c) != current)
+        .map_or(content.len(), |(i, _)| cursor + i);
+    if around {
+        end = content[end..]
+            .char_indices()
+            .find(|&(_, c)| !c.is_whitespace() || c == '\n')
+            .map_or(content.len(), |(i, _)| end + i);
+    }
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestBuffer {
+        content: String,
+        cursor: usize,
+    }
+
+    impl Buffer for TestBuffer {
+        fn content(&self) -> &str {
+            &self.content
+        }
+
+        fn cursor(&self) -> usize {
+            self.cursor
+        }
+
+        fn set_cursor(&mut self, cursor: usize) {
+            self.cursor = cursor;
+        }
+
+        fn replace(&mut self, start: usize, end: usize, text: &str) {
+            for _ in self.content.drain(start..end) {}
+            self.content.insert_str(start, text);
+        }
+
+        fn multiline(&self) -> bool {
+            true
+        }
+    }
+
+    fn run(mode: EditMode, content: &str, cursor: usize, keys: &[Event])
+           -> TestBuffer {
+        let mut buffer = TestBuffer {
+            content: content.to_string(),
+            cursor: cursor,
+        };
+        let mut keymap = Keymap::new();
+        for event in keys {
+            keymap.on_event(mode, event, &mut buffer);
+        }
+        buffer
+    }
+
+    fn vi(content: &str, cursor: usize, keys: &str) -> TestBuffer {
+        let mut events = vec![Event::Key(Key::Esc)];
+        events.extend(keys.chars().map(Event::Char));
+        // Leaving insert mode moves the cursor back.
+        run(EditMode::Vi, content, cursor + 1, &events)
+    }
+
+    #[test]
+    fn test_vi() {
+        assert_eq!(vi("foo bar baz", 0, "dw").content, "bar baz");
+        assert_eq!(vi("foo bar baz", 5, "diw").content, "foo  baz");
+        assert_eq!(vi("foo bar baz", 5, "daw").content, "foo baz");
+        assert_eq!(vi("foo bar\nbaz", 1, "dd").content, "baz");
+        assert_eq!(vi("foo bar", 0, "wD").content, "foo ");
+        assert_eq!(vi("foo bar", 0, "ywP").content, "foo foo bar");
+
+        let buffer = vi("foo bar", 4, "ciwbaz");
+        assert_eq!(buffer.content, "foo ");
+        assert_eq!(buffer.cursor, 4);
+    }
+
+    #[test]
+    fn test_emacs() {
+        let buffer = run(EditMode::Emacs,
+                         "foo bar baz",
+                         7,
+                         &[Event::CtrlChar('w'),
+                           Event::CtrlChar('e'),
+                           Event::CtrlChar('y')]);
+        assert_eq!(buffer.content, "foo  bazbar");
+
+        let buffer = run(EditMode::Emacs,
+                         "foo bar",
+                         0,
+                         &[Event::AltChar('f'), Event::CtrlChar('k')]);
+        assert_eq!(buffer.content, "foo");
+    }
+}
@@ -4,14 +4,18 @@ use {Cursive, Printer, With};
 use direction::Direction;
 use event::{Callback, Event, EventResult, Key};
 use std::cell::RefCell;
+use std::mem;
 
 use std::rc::Rc;
 use theme::{ColorStyle, Effect};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
-use utils::simple_suffix;
+use utils::{i18n, simple_suffix};
+use utils::bidi::{self, BaseDirection};
 use vec::Vec2;
 use view::View;
+use views::EditMode;
+use views::edit_mode::{Buffer, Keymap, Outcome};
 
 
 /// Input box where the user can enter and edit text.
@@ -89,6 +93,13 @@ pub struct EditView {
     enabled: bool,
 
     style: ColorStyle,
+
+    /// Base direction of the content.
+    direction: BaseDirection,
+
+    /// Key bindings, or `None` to use the global ones.
+    edit_mode: Option<EditMode>,
+    keymap: Keymap,
 }
 
 new_default!(EditView);
@@ -107,6 +118,9 @@ impl EditView {
             filler: "_".to_string(),
             enabled: true,
             style: ColorStyle::Secondary,
+            direction: BaseDirection::Auto,
+            edit_mode: None,
+            keymap: Keymap::new(),
         }
     }
 
@@ -184,6 +198,37 @@ impl EditView {
         self.with(|s| s.set_style(style))
     }
 
+    /// Sets the base direction of the content.
+    ///
+    /// Right-to-left content is anchored to the right of the view.
+    ///
+    /// Defaults to `BaseDirection::Auto`, which detects the direction from
+    /// the content. Reordering requires the `unicode-bidi` feature.
+    pub fn set_base_direction(&mut self, direction: BaseDirection) {
+        self.direction = direction;
+    }
+
+    /// Sets the base direction of the content.
+    ///
+    /// Chainable variant.
+    pub fn base_direction(self, direction: BaseDirection) -> Self {
+        self.with(|s| s.set_base_direction(direction))
+    }
+
+    /// Sets the key bindings used to edit the content.
+    ///
+    /// Defaults to `EditMode::global()`.
+    pub fn set_edit_mode(&mut self, mode: EditMode) {
+        self.edit_mode = Some(mode);
+    }
+
+    /// Sets the key bindings used to edit the content.
+    ///
+    /// Chainable variant.
+    pub fn edit_mode(self, mode: EditMode) -> Self {
+        self.with(|s| s.set_edit_mode(mode))
+    }
+
     /// Sets a mutable callback to be called whenever the content is modified.
     ///
     /// `callback` will be called with the view
@@ -403,6 +448,30 @@ impl EditView {
 ///
 /// Only works for small `length` (1 or 2).
 /// Best used for single character replacement.
+impl Buffer for EditView {
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor;
+    }
+
+    fn replace(&mut self, start: usize, end: usize, text: &str) {
+        let content = Rc::make_mut(&mut self.content);
+        for _ in content.drain(start..end) {}
+        content.insert_str(start, text);
+    }
+
+    fn multiline(&self) -> bool {
+        false
+    }
+}
+
 fn make_small_stars(length: usize) -> &'static str {
     // TODO: be able to use any character as hidden mode?
     &"****"[..length]
@@ -416,6 +485,31 @@ impl View for EditView {
                 printer.size.x);
 
         let width = self.content.width();
+
+        // Bytes range of the visible content.
+        let visible = if width < self.last_length {
+            // No problem, everything fits.
+            0..self.content.len()
+        } else {
+            let content = &self.content[self.offset..];
+            let display_bytes = content.graphemes(true)
+                .scan(0, |w, g| {
+                    *w += g.width();
+                    if *w > self.last_length { None } else { Some(g) }
+                })
+                .map(|g| g.len())
+                .sum::<usize>();
+            self.offset..self.offset + display_bytes
+        };
+
+        let graphemes =
+            bidi::visual_graphemes(&self.content, visible, self.direction);
+        let width = graphemes.iter().map(|&(_, g)| g.width()).sum::<usize>();
+
+        // Right-to-left text is anchored to the right.
+        let rtl = bidi::is_rtl(&self.content, 0, self.direction);
+        let text_x = if rtl { self.last_length - width } else { 0 };
+
         printer.with_color(self.style, |printer| {
             let effect = if self.enabled {
                 Effect::Reverse
@@ -423,124 +517,138 @@ impl View for EditView {
                 Effect::Simple
             };
             printer.with_effect(effect, |printer| {
+                if self.secret {
+                    printer.print_hline((text_x, 0), width, "*");
+                } else {
+                    let text: String =
+                        graphemes.iter().map(|&(_, g)| g).collect();
+                    printer.print((text_x, 0), &text);
+                }
+
                 if width < self.last_length {
-                    // No problem, everything fits.
-                    if self.secret {
-                        printer.print_hline((0, 0), width, "*");
-                    } else {
-                        printer.print((0, 0), &self.content);
-                    }
-                    let filler_len = (printer.size.x - width) / self.filler.width();
-                    printer.print_hline((width, 0),
+                    let filler_len = (self.last_length - width) / self.filler.width();
+                    let filler_x = if rtl { 0 } else { width };
+                    printer.print_hline((filler_x, 0),
                                         filler_len,
                                         self.filler.as_str());
-                } else {
-                    let content = &self.content[self.offset..];
-                    let display_bytes = content.graphemes(true)
-                        .scan(0, |w, g| {
-                            *w += g.width();
-                            if *w > self.last_length { None } else { Some(g) }
-                        })
-                        .map(|g| g.len())
-                        .fold(0, |a, b| a + b);
-
-                    let content = &content[..display_bytes];
-                    let width = content.width();
-
-                    if self.secret {
-                        printer.print_hline((0, 0), width, "*");
-                    } else {
-                        printer.print((0, 0), content);
-                    }
-
-                    if width < self.last_length {
-                        let filler_len = (self.last_length - width) / self.filler.width();
-                        printer.print_hline((width, 0),
-                                            filler_len,
-                                            self.filler.as_str());
-                    }
                 }
             });
 
             // Now print cursor
             if printer.focused {
-                let c: &str = if self.cursor == self.content.len() {
-                    &self.filler
-                } else {
-                    // Get the char from the string... Is it so hard?
-                    let selected = self.content[self.cursor..]
-                        .graphemes(true)
-                        .next()
-                        .expect(&format!("Found no char at cursor {} in {}",
-                                         self.cursor,
-                                         &self.content));
-                    if self.secret {
-                        make_small_stars(selected.width())
+                let cursor: Option<(usize, &str)> = if self.cursor ==
+                                                       self.content.len() {
+                    let offset = if rtl {
+                        text_x.saturating_sub(1)
                     } else {
-                        selected
+                        width
+                    };
+                    Some((offset, &self.filler))
+                } else {
+                    // Find the cursor among the visible graphemes.
+                    // It may be out of view until the next layout.
+                    let mut offset = text_x;
+                    let mut selected = None;
+                    for &(i, g) in &graphemes {
+                        if i == self.cursor {
+                            selected = Some(g);
+                            break;
+                        }
+                        offset += g.width();
                     }
+                    selected.map(|selected| if self.secret {
+                                     (offset,
+                                      make_small_stars(selected.width()))
+                                 } else {
+                                     (offset, selected)
+                                 })
                 };
-                let offset = self.content[self.offset..self.cursor].width();
-                printer.print((offset, 0), c);
+                if let Some((offset, c)) = cursor {
+                    printer.print((offset, 0), c);
+                }
             }
         });
     }
 
     fn layout(&mut self, size: Vec2) {
-        self.last_length = size.x;
-        // println_stderr!("Promised: {}", size.x);
+        if self.last_length != size.x {
+            self.last_length = size.x;
+            self.keep_cursor_in_view();
+        }
     }
 
     fn take_focus(&mut self, _: Direction) -> bool {
         self.enabled
     }
 
-    fn on_event(&mut self, event: Event) -> EventResult {
+    fn describe(&self) -> Option<String> {
+        if self.secret {
+            Some(format!("{}, {} {}",
+                         i18n::translate("secret edit"),
+                         self.content.chars().count(),
+                         i18n::translate("characters")))
+        } else {
+            Some(format!("{}, {}", i18n::translate("edit"), self.content))
+        }
+    }
 
-        match event {
-            Event::Char(ch) => self.insert(ch),
-            // TODO: handle ctrl-key?
-            Event::Key(Key::Home) => self.cursor = 0,
-            Event::Key(Key::End) => self.cursor = self.content.len(),
-            Event::Key(Key::Left) if self.cursor > 0 => {
-                let len = self.content[..self.cursor]
-                    .graphemes(true)
-                    .last()
-                    .unwrap()
-                    .len();
-                self.cursor -= len;
-            }
-            Event::Key(Key::Right) if self.cursor < self.content.len() => {
-                let len = self.content[self.cursor..]
-                    .graphemes(true)
-                    .next()
-                    .unwrap()
-                    .len();
-                self.cursor += len;
-            }
-            Event::Key(Key::Backspace) if self.cursor > 0 => {
-                let len = self.content[..self.cursor]
-                    .graphemes(true)
-                    .last()
-                    .unwrap()
-                    .len();
-                self.cursor -= len;
-                self.remove(len);
-            }
-            Event::Key(Key::Del) if self.cursor < self.content.len() => {
-                let len = self.content[self.cursor..]
-                    .graphemes(true)
-                    .next()
-                    .unwrap()
-                    .len();
-                self.remove(len);
-            }
-            Event::Key(Key::Enter) if self.on_submit.is_some() => {
-                let cb = self.on_submit.clone().unwrap();
-                let content = self.content.clone();
-                return EventResult::with_cb(move |s| { cb(s, &content); });
-            }
-            _ => return EventResult::Ignored,
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let mode = self.edit_mode.unwrap_or_else(EditMode::global);
+        let mut keymap = mem::replace(&mut self.keymap, Keymap::new());
+        let outcome = keymap.on_event(mode, &event, self);
+        self.keymap = keymap;
+
+        match outcome {
+            Outcome::Ignored => return EventResult::Ignored,
+            Outcome::Consumed => (),
+            Outcome::Forward(event) => match event {
+                Event::Char(ch) => self.insert(ch),
+                Event::Key(Key::Home) => self.cursor = 0,
+                Event::Key(Key::End) => self.cursor = self.content.len(),
+                Event::Key(Key::Left) if self.cursor > 0 => {
+                    let len = self.content[..self.cursor]
+                        .graphemes(true)
+                        .last()
+                        .unwrap()
+                        .len();
+                    self.cursor -= len;
+                }
+                Event::Key(Key::Right) if self.cursor <
+                                          self.content.len() => {
+                    let len = self.content[self.cursor..]
+                        .graphemes(true)
+                        .next()
+                        .unwrap()
+                        .len();
+                    self.cursor += len;
+                }
+                Event::Key(Key::Backspace) if self.cursor > 0 => {
+                    let len = self.content[..self.cursor]
+                        .graphemes(true)
+                        .last()
+                        .unwrap()
+                        .len();
+                    self.cursor -= len;
+                    self.remove(len);
+                }
+                Event::Key(Key::Del) if self.cursor <
+                                        self.content.len() => {
+                    let len = self.content[self.cursor..]
+                        .graphemes(true)
+                        .next()
+                        .unwrap()
+                        .len();
+                    self.remove(len);
+                }
+                Event::Key(Key::Enter) if self.on_submit.is_some() => {
+                    let cb = self.on_submit.clone().unwrap();
+                    let content = self.content.clone();
+                    return EventResult::with_cb(move |s| {
+                        cb(s, &content);
+                    });
+                }
+                _ => return EventResult::Ignored,
+            },
         }
 
         self.keep_cursor_in_view();
@@ -556,3 +664,33 @@ impl View for EditView {
         EventResult::Consumed(cb)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use Printer;
+    use event::{Event, Key};
+    use theme::Theme;
+    use view::View;
+    use super::EditView;
+
+    #[test]
+    fn test_shrink() {
+        let mut view = EditView::new().content("a".repeat(30));
+        view.layout((20, 1).into());
+        view.on_event(Event::Key(Key::End));
+        for _ in 0..5 {
+            view.on_event(Event::Key(Key::Left));
+        }
+        assert_eq!(view.cursor, 25);
+
+        // The cursor stays in view.
+        view.layout((10, 1).into());
+        assert!(view.offset <= view.cursor);
+        assert!(view.cursor < view.offset + 10);
+        let theme = Theme::default();
+        let bounds = Printer::draw_bounds((10, 1), &theme, |printer| {
+            view.draw(printer)
+        });
+        assert!(bounds.is_some());
+    }
+}
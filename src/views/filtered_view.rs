@@ -0,0 +1,152 @@
+use Printer;
+use std::rc::Rc;
+use theme::{Color, DimStyle};
+use view::{View, ViewWrapper};
+
+enum Filter {
+    Map(Rc<Fn(Color) -> Color>),
+    Monochrome,
+    Dim(DimStyle),
+}
+
+// Returns the gray with the same brightness as `color`.
+fn monochrome(color: Color) -> Color {
+    match color.to_rgb() {
+        Some((r, g, b)) => {
+            let luma = 0.299 * f32::from(r) + 0.587 * f32::from(g) +
+                       0.114 * f32::from(b);
+            let luma = luma.round() as u8;
+            Color::Rgb(luma, luma, luma)
+        }
+        None => color,
+    }
+}
+
+/// Wrapper view changing the colors of its content.
+///
+/// Every color the content draws with, from the theme or not, goes
+/// through a filter first. Use it to grey out a disabled pane, or dim an
+/// inactive window.
+///
+/// The filter can be turned off and on again, without rebuilding the
+/// view.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::theme::{DimStyle, Color};
+/// # use cursive::views::{FilteredView, TextView};
+/// let mut pane = FilteredView::dimmed(TextView::new("Inactive"),
+///                                     DimStyle::new(0.5));
+/// // The window is active again.
+/// pane.set_filtering(false);
+///
+/// // Replace red with blue.
+/// let swapped = FilteredView::new(TextView::new("Hello"), |color| {
+///     if color == Color::Rgb(255, 0, 0) {
+///         Color::Rgb(0, 0, 255)
+///     } else {
+///         color
+///     }
+/// });
+/// ```
+pub struct FilteredView<V: View> {
+    view: V,
+    filter: Filter,
+    filtering: bool,
+}
+
+impl<V: View> FilteredView<V> {
+    /// Wraps `view`, drawing each color `c` as `filter(c)`.
+    pub fn new<F>(view: V, filter: F) -> Self
+        where F: Fn(Color) -> Color + 'static
+    {
+        FilteredView::with_filter(view, Filter::Map(Rc::new(filter)))
+    }
+
+    /// Wraps `view`, drawing it in shades of gray.
+    pub fn monochrome(view: V) -> Self {
+        FilteredView::with_filter(view, Filter::Monochrome)
+    }
+
+    /// Wraps `view`, dimming its colors.
+    ///
+    /// Without a color in `style`, colors fade toward the theme's
+    /// background.
+    pub fn dimmed(view: V, style: DimStyle) -> Self {
+        FilteredView::with_filter(view, Filter::Dim(style))
+    }
+
+    fn with_filter(view: V, filter: Filter) -> Self {
+        FilteredView {
+            view: view,
+            filter: filter,
+            filtering: true,
+        }
+    }
+
+    /// Turns the filter on or off.
+    ///
+    /// When off, the content is drawn as usual.
+    pub fn set_filtering(&mut self, filtering: bool) {
+        self.filtering = filtering;
+    }
+
+    /// Returns `true` if the filter is on.
+    pub fn is_filtering(&self) -> bool {
+        self.filtering
+    }
+
+    /// Returns the inner view.
+    pub fn get_inner(&self) -> &V {
+        &self.view
+    }
+
+    /// Returns a mutable reference to the inner view.
+    pub fn get_inner_mut(&mut self) -> &mut V {
+        &mut self.view
+    }
+}
+
+impl<V: View> ViewWrapper for FilteredView<V> {
+    wrap_impl!(self.view: V);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        if !self.filtering {
+            return self.view.draw(printer);
+        }
+
+        let draw = |printer: &Printer| self.view.draw(printer);
+        match self.filter {
+            Filter::Map(ref filter) => {
+                let filter = filter.clone();
+                printer.with_color_filter(move |color| filter(color), draw)
+            }
+            Filter::Monochrome => printer.with_color_filter(monochrome, draw),
+            Filter::Dim(ref style) => {
+                let target = style.color
+                    .unwrap_or(printer.theme.colors.background);
+                let amount = style.amount.max(0.0).min(1.0);
+                printer.with_color_filter(move |color| {
+                                              color.mix(target, amount)
+                                          },
+                                          draw)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::monochrome;
+    use theme::{BaseColor, Color};
+
+    #[test]
+    fn test_monochrome() {
+        assert_eq!(monochrome(Color::Rgb(255, 0, 0)), Color::Rgb(76, 76, 76));
+        assert_eq!(monochrome(Color::Light(BaseColor::White)),
+                   Color::Rgb(255, 255, 255));
+        assert_eq!(monochrome(Color::TerminalDefault),
+                   Color::TerminalDefault);
+    }
+}
@@ -0,0 +1,253 @@
+use Printer;
+use With;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::any::Any;
+use std::cmp::max;
+use vec::Vec2;
+use view::{Selector, View};
+
+/// Arranges its children in rows, wrapping when a row is full.
+///
+/// Children are placed left-to-right with their required size. When the
+/// next child doesn't fit in the remaining width, it starts a new row.
+/// A child wider than the layout gets a row on its own, and is cut.
+///
+/// `<Tab>`, `<Shift+Tab>`, `<Left>` and `<Right>` move the focus between
+/// children.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{Button, FlowLayout};
+/// let tags = FlowLayout::new()
+///     .child(Button::new("rust", |_| ()))
+///     .child(Button::new("terminal", |_| ()))
+///     .child(Button::new("ui", |_| ()));
+/// ```
+pub struct FlowLayout {
+    children: Vec<Child>,
+    // Space between two children on the same row, and between two rows.
+    spacing: Vec2,
+    focus: usize,
+}
+
+struct Child {
+    view: Box<View>,
+    offset: Vec2,
+    size: Vec2,
+}
+
+new_default!(FlowLayout);
+
+impl FlowLayout {
+    /// Creates a new, empty layout.
+    ///
+    /// Children on the same row are separated by one column.
+    pub fn new() -> Self {
+        FlowLayout {
+            children: Vec::new(),
+            spacing: Vec2::new(1, 0),
+            focus: 0,
+        }
+    }
+
+    /// Sets the space between children.
+    ///
+    /// `spacing.x` columns separate children on the same row, and
+    /// `spacing.y` rows separate two rows.
+    pub fn set_spacing<S: Into<Vec2>>(&mut self, spacing: S) {
+        self.spacing = spacing.into();
+    }
+
+    /// Sets the space between children.
+    ///
+    /// Chainable variant.
+    pub fn spacing<S: Into<Vec2>>(self, spacing: S) -> Self {
+        self.with(|s| s.set_spacing(spacing))
+    }
+
+    /// Adds a child to the end of the layout.
+    pub fn add_child<V: View + 'static>(&mut self, view: V) {
+        self.children.push(Child {
+            view: Box::new(view),
+            offset: Vec2::zero(),
+            size: Vec2::zero(),
+        });
+    }
+
+    /// Adds a child to the end of the layout.
+    ///
+    /// Chainable variant.
+    pub fn child<V: View + 'static>(self, view: V) -> Self {
+        self.with(|s| s.add_child(view))
+    }
+
+    /// Removes the child at index `i`.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn remove_child(&mut self, i: usize) -> Box<View> {
+        if self.focus > i || self.focus + 1 == self.children.len() {
+            self.focus = self.focus.saturating_sub(1);
+        }
+        self.children.remove(i).view
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns `true` if this layout has no child.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    // Computes the size and offset of each child for the given
+    // constraint, and returns the total size.
+    fn arrange(&mut self, req: Vec2) -> Vec2 {
+        let mut pos = Vec2::zero();
+        let mut row_height = 0;
+        let mut total = Vec2::zero();
+
+        for child in &mut self.children {
+            child.size = Vec2::min(req, child.view.required_size(req));
+
+            if pos.x > 0 && pos.x + child.size.x > req.x {
+                // Start a new row
+                pos.x = 0;
+                pos.y += row_height + self.spacing.y;
+                row_height = 0;
+            }
+
+            child.offset = pos;
+            row_height = max(row_height, child.size.y);
+            total.x = max(total.x, pos.x + child.size.x);
+            total.y = pos.y + row_height;
+
+            pos.x += child.size.x + self.spacing.x;
+        }
+
+        total
+    }
+
+    // Gives the focus to the next child accepting it, after the current one.
+    fn move_focus(&mut self, source: Direction) -> EventResult {
+        let len = self.children.len();
+        let start = self.focus;
+        let forward = source == Direction::front();
+        for step in 1..len {
+            let i = if forward {
+                (start + step) % len
+            } else {
+                (start + len - step) % len
+            };
+            if self.children[i].view.take_focus(source) {
+                self.focus = i;
+                return EventResult::Consumed(None);
+            }
+        }
+        EventResult::Ignored
+    }
+}
+
+impl View for FlowLayout {
+    fn draw(&self, printer: &Printer) {
+        for (i, child) in self.children.iter().enumerate() {
+            let focused = printer.focused && i == self.focus;
+            child.view
+                .draw(&printer.sub_printer(child.offset, child.size, focused));
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        self.arrange(req)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.arrange(size);
+        for child in &mut self.children {
+            child.view.layout(child.size);
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.children.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        match self.children[self.focus].view.on_event(event.clone()) {
+            EventResult::Ignored => {
+                match event {
+                    Event::Key(Key::Tab) |
+                    Event::Key(Key::Right) => {
+                        self.move_focus(Direction::front())
+                    }
+                    Event::Shift(Key::Tab) |
+                    Event::Key(Key::Left) => {
+                        self.move_focus(Direction::back())
+                    }
+                    _ => EventResult::Ignored,
+                }
+            }
+            res => res.resolve_on(self),
+        }
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        match self.children
+            .iter_mut()
+            .position(|child| child.view.take_focus(source)) {
+            Some(i) => {
+                self.focus = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        for child in &mut self.children {
+            child.view.call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        for (i, child) in self.children.iter_mut().enumerate() {
+            if child.view.focus_view(selector).is_ok() {
+                self.focus = i;
+                return Ok(());
+            }
+        }
+
+        Err(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FlowLayout;
+    use vec::Vec2;
+    use view::View;
+    use views::TextView;
+
+    #[test]
+    fn wrap_rows() {
+        let mut layout = FlowLayout::new()
+            .child(TextView::new("aaa"))
+            .child(TextView::new("bb"))
+            .child(TextView::new("cccc"));
+
+        // Everything fits on a single row.
+        assert_eq!(layout.required_size(Vec2::new(20, 5)), Vec2::new(11, 1));
+
+        // The third child goes to a second row.
+        layout.layout(Vec2::new(8, 5));
+        let offsets: Vec<_> =
+            layout.children.iter().map(|child| child.offset).collect();
+        assert_eq!(offsets,
+                   vec![Vec2::new(0, 0), Vec2::new(4, 0), Vec2::new(0, 1)]);
+        assert_eq!(layout.required_size(Vec2::new(8, 5)), Vec2::new(6, 2));
+    }
+}
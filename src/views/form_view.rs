@@ -0,0 +1,275 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::ser::Error;
+use serde_json::{self, Map, Number, Value};
+use std::marker::PhantomData;
+use view::{Finder, Identifiable, ViewWrapper};
+use views::{Checkbox, EditView, ListView, SelectView};
+
+/// Describes how a struct is edited in a [`FormView`].
+///
+/// Only `Serialize` and `Deserialize` are required: the default methods
+/// are enough for most settings. Override them to change the labels, or
+/// to choose some fields from a list.
+///
+/// [`FormView`]: struct.FormView.html
+pub trait Form: Serialize + DeserializeOwned {
+    /// Returns the label shown next to `field`.
+    ///
+    /// Defaults to the field name, capitalized, with spaces instead of
+    /// underscores.
+    fn label(field: &str) -> String {
+        let label = field.replace('_', " ");
+        let mut chars = label.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => label,
+        }
+    }
+
+    /// Returns the values `field` can take, if it should be chosen from
+    /// a list rather than typed.
+    ///
+    /// Defaults to `None` for every field.
+    fn choices(field: &str) -> Option<Vec<String>> {
+        let _ = field;
+        None
+    }
+}
+
+/// Form editing the fields of a struct.
+///
+/// The struct is serialized to find its fields:
+///
+/// * booleans are edited with a `Checkbox`;
+/// * strings and numbers with an `EditView`, or a popup `SelectView` if
+///   [`Form::choices`] returns some;
+/// * other fields, including nested structs and lists, are not shown, but
+///   kept as they were.
+///
+/// Each field's view has the field name as ID. [`value`] reads the edits
+/// back into a new struct.
+///
+/// Requires the `form` feature.
+///
+/// [`Form::choices`]: trait.Form.html#method.choices
+/// [`value`]: #method.value
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate cursive;
+/// # extern crate serde;
+/// # use serde::{Deserialize, Serialize};
+/// # use cursive::Cursive;
+/// # use cursive::traits::*;
+/// # use cursive::views::{Dialog, Form, FormView};
+/// #[derive(Serialize, Deserialize)]
+/// struct Settings {
+///     user_name: String,
+///     autosave: bool,
+/// }
+///
+/// impl Form for Settings {}
+///
+/// # fn main() {
+/// let mut siv = Cursive::new();
+/// let settings = Settings {
+///     user_name: "bob".to_string(),
+///     autosave: true,
+/// };
+/// let form = FormView::new(&settings).unwrap().with_id("settings");
+/// siv.add_layer(Dialog::around(form).button("Save", |s| {
+///     let settings = s.call_on_id("settings",
+///                                 |form: &mut FormView<Settings>| {
+///                                     form.value()
+///                                 });
+///     if let Some(Ok(settings)) = settings {
+///         s.add_layer(Dialog::info(format!("Saved {}", settings.user_name)));
+///     }
+/// }));
+/// # }
+/// ```
+pub struct FormView<T: Form> {
+    list: ListView,
+    // The struct when the form was built, including fields not shown.
+    fields: Map<String, Value>,
+    _form: PhantomData<T>,
+}
+
+// Parses `text` as a number, or keeps it as a string so deserializing
+// reports the error.
+fn parse_number(text: &str) -> Value {
+    if let Ok(n) = text.parse::<i64>() {
+        Value::from(n)
+    } else if let Ok(n) = text.parse::<u64>() {
+        Value::from(n)
+    } else {
+        match text.parse::<f64>().ok().and_then(Number::from_f64) {
+            Some(n) => Value::Number(n),
+            None => Value::String(text.to_string()),
+        }
+    }
+}
+
+// Returns how a field is shown, or `None` if it is not.
+fn field_text(value: &Value) -> Option<String> {
+    match *value {
+        Value::String(ref s) => Some(s.clone()),
+        Value::Number(ref n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+impl<T: Form> FormView<T> {
+    /// Creates a form editing a copy of `value`.
+    ///
+    /// Fails if `value` does not serialize to a map, like a struct.
+    pub fn new(value: &T) -> Result<Self, serde_json::Error> {
+        let fields = match serde_json::to_value(value)? {
+            Value::Object(fields) => fields,
+            _ => {
+                return Err(Error::custom("expected a struct"));
+            }
+        };
+
+        let mut list = ListView::new();
+        for (name, value) in &fields {
+            let label = T::label(name);
+            if let Value::Bool(checked) = *value {
+                let mut checkbox = Checkbox::new();
+                checkbox.set_checked(checked);
+                list.add_child(&label, checkbox.with_id(name.clone()));
+                continue;
+            }
+
+            let text = match field_text(value) {
+                Some(text) => text,
+                None => continue,
+            };
+            match T::choices(name) {
+                Some(choices) => {
+                    let mut select = SelectView::new().popup();
+                    let selected = choices.iter().position(|c| *c == text);
+                    select.add_all_str(choices);
+                    if let Some(i) = selected {
+                        select.set_selection(i);
+                    }
+                    list.add_child(&label, select.with_id(name.clone()));
+                }
+                None => {
+                    let edit = EditView::new().content(text);
+                    list.add_child(&label, edit.with_id(name.clone()));
+                }
+            }
+        }
+
+        Ok(FormView {
+               list: list,
+               fields: fields,
+               _form: PhantomData,
+           })
+    }
+
+    /// Returns the struct with the current edits.
+    ///
+    /// Fails if a field can't be deserialized, like a number field holding
+    /// letters.
+    pub fn value(&mut self) -> Result<T, serde_json::Error> {
+        let mut fields = self.fields.clone();
+        for (name, value) in &mut fields {
+            let list = &mut self.list;
+            let edited = match *value {
+                Value::Bool(_) => {
+                    list.find_id(name, |c: &mut Checkbox| {
+                            Value::Bool(c.is_checked())
+                        })
+                }
+                Value::String(_) | Value::Number(_) => {
+                    let text = match T::choices(name) {
+                        Some(choices) => {
+                            list.find_id(name,
+                                         |s: &mut SelectView<String>| {
+                                             s.selected_id()
+                                         })
+                                .and_then(|i| i)
+                                .map(|i| choices[i].clone())
+                        }
+                        None => {
+                            list.find_id(name, |e: &mut EditView| {
+                                    e.get_content().to_string()
+                                })
+                        }
+                    };
+                    text.map(|text| if value.is_number() {
+                                 parse_number(&text)
+                             } else {
+                                 Value::String(text)
+                             })
+                }
+                _ => None,
+            };
+            if let Some(edited) = edited {
+                *value = edited;
+            }
+        }
+        serde_json::from_value(Value::Object(fields))
+    }
+}
+
+impl<T: Form + 'static> ViewWrapper for FormView<T> {
+    wrap_impl!(self.list: ListView);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Form, FormView};
+    use serde::{Deserialize, Serialize};
+    use view::Finder;
+    use views::{Checkbox, EditView};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Settings {
+        user_name: String,
+        volume: u8,
+        muted: bool,
+        theme: String,
+        plugins: Vec<String>,
+    }
+
+    impl Form for Settings {
+        fn choices(field: &str) -> Option<Vec<String>> {
+            if field == "theme" {
+                Some(vec!["light".to_string(), "dark".to_string()])
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_form() {
+        let settings = Settings {
+            user_name: "bob".to_string(),
+            volume: 5,
+            muted: false,
+            theme: "dark".to_string(),
+            plugins: vec!["git".to_string()],
+        };
+        assert_eq!(Settings::label("user_name"), "User name");
+
+        let mut form = FormView::new(&settings).unwrap();
+        assert_eq!(form.value().unwrap(), settings);
+
+        form.find_id("volume", |e: &mut EditView| e.set_content("11"));
+        form.find_id("muted", |c: &mut Checkbox| c.check());
+        let edited = form.value().unwrap();
+        assert_eq!(edited.volume, 11);
+        assert!(edited.muted);
+        assert_eq!(edited.theme, "dark");
+        assert_eq!(edited.plugins, settings.plugins);
+
+        form.find_id("volume", |e: &mut EditView| e.set_content("loud"));
+        assert!(form.value().is_err());
+    }
+}
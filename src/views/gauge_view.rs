@@ -0,0 +1,282 @@
+use {Printer, With};
+use align::HAlign;
+use std::cmp::min;
+use std::f64::consts::PI;
+use theme::{BaseColor, Color, ColorStyle};
+use vec::Vec2;
+use view::View;
+
+/// Shape of a `GaugeView`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GaugeStyle {
+    /// A single row, with the value on the right.
+    Horizontal,
+    /// A half-circle dial, with the value below.
+    Radial,
+}
+
+// Radius of a radial gauge, in rows, when enough space is available.
+const RADIUS: usize = 5;
+
+/// Shows a value between a minimum and a maximum, like a dial.
+///
+/// Warning and critical thresholds split the range in zones, each with its
+/// own color.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{GaugeStyle, GaugeView};
+/// let gauge = GaugeView::new()
+///     .range(0.0, 100.0)
+///     .thresholds(70.0, 90.0)
+///     .unit("%")
+///     .style(GaugeStyle::Radial)
+///     .value(42.0);
+/// ```
+pub struct GaugeView {
+    min: f64,
+    max: f64,
+    value: f64,
+    warn: Option<f64>,
+    crit: Option<f64>,
+    unit: String,
+    style: GaugeStyle,
+
+    ok_color: ColorStyle,
+    warn_color: ColorStyle,
+    crit_color: ColorStyle,
+}
+
+fn zone_color(base: BaseColor) -> ColorStyle {
+    ColorStyle::Custom {
+        front: Color::Dark(base),
+        back: Color::Dark(BaseColor::White),
+    }
+}
+
+new_default!(GaugeView);
+
+impl GaugeView {
+    /// Creates a new horizontal gauge, going from `0` to `100`.
+    pub fn new() -> Self {
+        GaugeView {
+            min: 0.0,
+            max: 100.0,
+            value: 0.0,
+            warn: None,
+            crit: None,
+            unit: String::new(),
+            style: GaugeStyle::Horizontal,
+            ok_color: zone_color(BaseColor::Green),
+            warn_color: zone_color(BaseColor::Yellow),
+            crit_color: zone_color(BaseColor::Red),
+        }
+    }
+
+    /// Sets the current value.
+    ///
+    /// Values out of the range are shown at the closest end.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// Sets the current value.
+    ///
+    /// Chainable variant.
+    pub fn value(self, value: f64) -> Self {
+        self.with(|s| s.set_value(value))
+    }
+
+    /// Returns the current value.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Sets the `min` and `max` range for the value.
+    ///
+    /// If `min > max`, swap the two values.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        if min > max {
+            self.min = max;
+            self.max = min;
+        } else {
+            self.min = min;
+            self.max = max;
+        }
+    }
+
+    /// Sets the `min` and `max` range for the value.
+    ///
+    /// Chainable variant.
+    pub fn range(self, min: f64, max: f64) -> Self {
+        self.with(|s| s.set_range(min, max))
+    }
+
+    /// Sets the values starting the warning and critical zones.
+    pub fn set_thresholds(&mut self, warn: f64, crit: f64) {
+        self.warn = Some(warn);
+        self.crit = Some(crit);
+    }
+
+    /// Sets the values starting the warning and critical zones.
+    ///
+    /// Chainable variant.
+    pub fn thresholds(self, warn: f64, crit: f64) -> Self {
+        self.with(|s| s.set_thresholds(warn, crit))
+    }
+
+    /// Sets the colors used for the ok, warning and critical zones.
+    ///
+    /// Defaults to green, yellow and red.
+    pub fn set_colors(&mut self, ok: ColorStyle, warn: ColorStyle,
+                      crit: ColorStyle) {
+        self.ok_color = ok;
+        self.warn_color = warn;
+        self.crit_color = crit;
+    }
+
+    /// Sets the colors used for the ok, warning and critical zones.
+    ///
+    /// Chainable variant.
+    pub fn colors(self, ok: ColorStyle, warn: ColorStyle, crit: ColorStyle)
+                  -> Self {
+        self.with(|s| s.set_colors(ok, warn, crit))
+    }
+
+    /// Sets the unit printed after the value.
+    pub fn set_unit<S: Into<String>>(&mut self, unit: S) {
+        self.unit = unit.into();
+    }
+
+    /// Sets the unit printed after the value.
+    ///
+    /// Chainable variant.
+    pub fn unit<S: Into<String>>(self, unit: S) -> Self {
+        self.with(|s| s.set_unit(unit))
+    }
+
+    /// Sets the shape of the gauge.
+    ///
+    /// Defaults to `GaugeStyle::Horizontal`.
+    pub fn set_style(&mut self, style: GaugeStyle) {
+        self.style = style;
+    }
+
+    /// Sets the shape of the gauge.
+    ///
+    /// Chainable variant.
+    pub fn style(self, style: GaugeStyle) -> Self {
+        self.with(|s| s.set_style(style))
+    }
+
+    // Position of `value` in the range, between 0 and 1.
+    fn fraction(&self, value: f64) -> f64 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        ((value - self.min) / (self.max - self.min)).max(0.0).min(1.0)
+    }
+
+    fn color_at(&self, value: f64) -> ColorStyle {
+        if self.crit.map_or(false, |crit| value >= crit) {
+            self.crit_color
+        } else if self.warn.map_or(false, |warn| value >= warn) {
+            self.warn_color
+        } else {
+            self.ok_color
+        }
+    }
+
+    fn readout(&self) -> String {
+        if self.unit.is_empty() {
+            format!("{:.1}", self.value)
+        } else {
+            format!("{:.1} {}", self.value, self.unit)
+        }
+    }
+
+    fn draw_horizontal(&self, printer: &Printer) {
+        let readout = self.readout();
+        let length = printer.size.x.saturating_sub(readout.len() + 1);
+        let filled = (self.fraction(self.value) * length as f64).round();
+
+        for x in 0..length {
+            // Value at the middle of this cell.
+            let fraction = (x as f64 + 0.5) / length as f64;
+            let value = self.min + fraction * (self.max - self.min);
+            if (x as f64) < filled {
+                printer.with_color(self.color_at(value), |printer| {
+                    printer.print((x, 0), "█");
+                });
+            } else {
+                printer.with_color(ColorStyle::Secondary, |printer| {
+                    printer.print((x, 0), "░");
+                });
+            }
+        }
+
+        printer.with_color(self.color_at(self.value), |printer| {
+            printer.print((length + 1, 0), &readout);
+        });
+    }
+
+    fn draw_radial(&self, printer: &Printer) {
+        // Keep a row for the readout.
+        // Cells are about twice as high as they are wide.
+        let radius = min(printer.size.y.saturating_sub(1),
+                         printer.size.x.saturating_sub(1) / 4);
+        if radius == 0 {
+            return;
+        }
+        let center = printer.size.x as f64 / 2.0;
+        let value = self.fraction(self.value);
+
+        for y in 0..radius {
+            for x in 0..printer.size.x {
+                let dx = (x as f64 + 0.5 - center) / 2.0;
+                let dy = (radius - y) as f64 - 0.5;
+                let distance = (dx * dx + dy * dy).sqrt();
+                let radius = radius as f64;
+                if distance > radius || distance <= radius - 1.0 {
+                    continue;
+                }
+
+                // From 0 on the left to 1 on the right.
+                let fraction = 1.0 - dy.atan2(dx) / PI;
+                if fraction <= value {
+                    let value = self.min + fraction * (self.max - self.min);
+                    printer.with_color(self.color_at(value), |printer| {
+                        printer.print((x, y), "█");
+                    });
+                } else {
+                    printer.with_color(ColorStyle::Secondary, |printer| {
+                        printer.print((x, y), "░");
+                    });
+                }
+            }
+        }
+
+        let readout = self.readout();
+        let offset = HAlign::Center.get_offset(readout.len(), printer.size.x);
+        printer.with_color(self.color_at(self.value), |printer| {
+            printer.print((offset, radius), &readout);
+        });
+    }
+}
+
+impl View for GaugeView {
+    fn draw(&self, printer: &Printer) {
+        match self.style {
+            GaugeStyle::Horizontal => self.draw_horizontal(printer),
+            GaugeStyle::Radial => self.draw_radial(printer),
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        match self.style {
+            GaugeStyle::Horizontal => Vec2::new(req.x, 1),
+            GaugeStyle::Radial => Vec2::new(4 * RADIUS + 1, RADIUS + 1),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use {Cursive, Printer, With};
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::rc::Rc;
+use theme::{BaseColor, Color, ColorStyle};
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::View;
+
+/// Colors used by a `HeatmapView`, from the lowest to the highest value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeatmapPalette {
+    /// Shades of green, like contribution calendars.
+    Greens,
+    /// From blue to red, through yellow.
+    Heat,
+    /// Shades of grey.
+    Grays,
+    /// Any list of colors.
+    Custom(Vec<Color>),
+}
+
+impl HeatmapPalette {
+    fn colors(&self) -> Vec<Color> {
+        match *self {
+            HeatmapPalette::Greens => {
+                vec![Color::RgbLowRes(4, 5, 4),
+                     Color::RgbLowRes(2, 4, 2),
+                     Color::RgbLowRes(0, 3, 0),
+                     Color::RgbLowRes(0, 2, 0),
+                     Color::RgbLowRes(0, 1, 0)]
+            }
+            HeatmapPalette::Heat => {
+                vec![Color::RgbLowRes(0, 0, 4),
+                     Color::RgbLowRes(0, 3, 5),
+                     Color::RgbLowRes(5, 5, 0),
+                     Color::RgbLowRes(5, 2, 0),
+                     Color::RgbLowRes(4, 0, 0)]
+            }
+            HeatmapPalette::Grays => {
+                (0..6).rev().map(|i| Color::RgbLowRes(i, i, i)).collect()
+            }
+            HeatmapPalette::Custom(ref colors) => colors.clone(),
+        }
+    }
+}
+
+/// Shows a grid of values as colored cells.
+///
+/// The arrow keys move the selection between cells.
+///
+/// Values that are `NaN` are shown as empty cells.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{HeatmapPalette, HeatmapView};
+/// let heatmap = HeatmapView::new(vec![vec![0.0, 1.0, 4.0],
+///                                     vec![2.0, 0.0, 3.0]])
+///     .palette(HeatmapPalette::Heat)
+///     .y_labels(vec!["Mon", "Tue"])
+///     .on_select(|s, x, y, value| {
+///         // Show the value somewhere
+///     });
+/// ```
+pub struct HeatmapView {
+    // Values, row by row.
+    values: Vec<Vec<f64>>,
+    palette: Vec<Color>,
+    x_labels: Vec<String>,
+    y_labels: Vec<String>,
+    focus: Vec2,
+    on_select: Option<Rc<Fn(&mut Cursive, usize, usize, f64)>>,
+}
+
+impl HeatmapView {
+    /// Creates a new heatmap with the given rows of values.
+    pub fn new(values: Vec<Vec<f64>>) -> Self {
+        HeatmapView {
+            values: values,
+            palette: HeatmapPalette::Greens.colors(),
+            x_labels: Vec::new(),
+            y_labels: Vec::new(),
+            focus: Vec2::zero(),
+            on_select: None,
+        }
+    }
+
+    /// Replaces all values.
+    pub fn set_values(&mut self, values: Vec<Vec<f64>>) {
+        self.values = values;
+        self.focus = Vec2::zero();
+    }
+
+    /// Sets the value of a single cell.
+    ///
+    /// Does nothing if the cell doesn't exist.
+    pub fn set_value(&mut self, x: usize, y: usize, value: f64) {
+        if let Some(row) = self.values.get_mut(y) {
+            if let Some(cell) = row.get_mut(x) {
+                *cell = value;
+            }
+        }
+    }
+
+    /// Returns the value of a cell, if it exists.
+    pub fn get_value(&self, x: usize, y: usize) -> Option<f64> {
+        self.values.get(y).and_then(|row| row.get(x)).cloned()
+    }
+
+    /// Returns the coordinates of the selected cell.
+    pub fn selection(&self) -> (usize, usize) {
+        (self.focus.x, self.focus.y)
+    }
+
+    /// Sets the colors to use.
+    ///
+    /// Defaults to `HeatmapPalette::Greens`.
+    pub fn set_palette(&mut self, palette: HeatmapPalette) {
+        self.palette = palette.colors();
+    }
+
+    /// Sets the colors to use.
+    ///
+    /// Chainable variant.
+    pub fn palette(self, palette: HeatmapPalette) -> Self {
+        self.with(|s| s.set_palette(palette))
+    }
+
+    /// Sets the labels printed above each column.
+    ///
+    /// Labels are skipped when they would overlap the previous one, and
+    /// can be empty.
+    pub fn set_x_labels<S, I>(&mut self, labels: I)
+        where S: Into<String>,
+              I: IntoIterator<Item = S>
+    {
+        self.x_labels = labels.into_iter().map(Into::into).collect();
+    }
+
+    /// Sets the labels printed above each column.
+    ///
+    /// Chainable variant.
+    pub fn x_labels<S, I>(self, labels: I) -> Self
+        where S: Into<String>,
+              I: IntoIterator<Item = S>
+    {
+        self.with(|s| s.set_x_labels(labels))
+    }
+
+    /// Sets the labels printed left of each row.
+    pub fn set_y_labels<S, I>(&mut self, labels: I)
+        where S: Into<String>,
+              I: IntoIterator<Item = S>
+    {
+        self.y_labels = labels.into_iter().map(Into::into).collect();
+    }
+
+    /// Sets the labels printed left of each row.
+    ///
+    /// Chainable variant.
+    pub fn y_labels<S, I>(self, labels: I) -> Self
+        where S: Into<String>,
+              I: IntoIterator<Item = S>
+    {
+        self.with(|s| s.set_y_labels(labels))
+    }
+
+    /// Sets a callback to be used when the selection changes.
+    ///
+    /// It will be given the coordinates and the value of the cell.
+    pub fn set_on_select<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, usize, usize, f64) + 'static
+    {
+        self.on_select = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when the selection changes.
+    ///
+    /// Chainable variant.
+    pub fn on_select<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive, usize, usize, f64) + 'static
+    {
+        self.with(|s| s.set_on_select(cb))
+    }
+
+    fn columns(&self) -> usize {
+        self.values.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    fn label_width(&self) -> usize {
+        let width = self.y_labels
+            .iter()
+            .map(|label| label.width())
+            .max()
+            .unwrap_or(0);
+        if width > 0 { width + 1 } else { 0 }
+    }
+
+    fn header_height(&self) -> usize {
+        if self.x_labels.is_empty() { 0 } else { 1 }
+    }
+
+    // Returns the palette color for `value`.
+    fn color(&self, value: f64, (min, max): (f64, f64)) -> Option<Color> {
+        if value.is_nan() || self.palette.is_empty() {
+            return None;
+        }
+        let last = self.palette.len() - 1;
+        let i = if max > min {
+            ((value - min) / (max - min) * last as f64).round() as usize
+        } else {
+            last
+        };
+        Some(self.palette[i.min(last)])
+    }
+
+    fn range(&self) -> (f64, f64) {
+        let values = self.values.iter().flat_map(|row| row).cloned();
+        values.filter(|v| !v.is_nan())
+            .fold((::std::f64::INFINITY, ::std::f64::NEG_INFINITY),
+                  |(min, max), v| (min.min(v), max.max(v)))
+    }
+
+    fn move_focus(&mut self, x: isize, y: isize) -> EventResult {
+        let new_y = self.focus.y as isize + y;
+        if new_y < 0 || new_y as usize >= self.values.len() {
+            return EventResult::Ignored;
+        }
+        let new_x = self.focus.x as isize + x;
+        let row = &self.values[new_y as usize];
+        if new_x < 0 || new_x as usize >= row.len() {
+            return EventResult::Ignored;
+        }
+
+        self.focus = Vec2::new(new_x as usize, new_y as usize);
+        let (x, y) = (self.focus.x, self.focus.y);
+        let value = self.values[y][x];
+        match self.on_select.clone() {
+            Some(cb) => EventResult::with_cb(move |s| cb(s, x, y, value)),
+            None => EventResult::Consumed(None),
+        }
+    }
+}
+
+impl View for HeatmapView {
+    fn draw(&self, printer: &Printer) {
+        let left = self.label_width();
+        let top = self.header_height();
+        let range = self.range();
+
+        // Column labels, skipping overlapping ones.
+        let mut next_free = 0;
+        for (i, label) in self.x_labels.iter().enumerate() {
+            let x = left + 2 * i;
+            if label.is_empty() || x < next_free {
+                continue;
+            }
+            printer.with_color(ColorStyle::Secondary,
+                               |printer| printer.print((x, 0), label));
+            next_free = x + label.width() + 1;
+        }
+
+        for (y, row) in self.values.iter().enumerate() {
+            if let Some(label) = self.y_labels.get(y) {
+                printer.with_color(ColorStyle::Secondary, |printer| {
+                    printer.print((0, top + y), label)
+                });
+            }
+
+            for (x, &value) in row.iter().enumerate() {
+                let back = match self.color(value, range) {
+                    Some(color) => color,
+                    None => printer.theme.colors.view,
+                };
+                let color = ColorStyle::Custom {
+                    front: Color::Dark(BaseColor::Black),
+                    back: back,
+                };
+                let selected = printer.focused && self.focus == (x, y).into();
+                let text = if selected { "[]" } else { "  " };
+                printer.with_color(color, |printer| {
+                    printer.print((left + 2 * x, top + y), text);
+                });
+            }
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new(self.label_width() + 2 * self.columns(),
+                  self.header_height() + self.values.len())
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Left) => self.move_focus(-1, 0),
+            Event::Key(Key::Right) => self.move_focus(1, 0),
+            Event::Key(Key::Up) => self.move_focus(0, -1),
+            Event::Key(Key::Down) => self.move_focus(0, 1),
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        !self.values.is_empty()
+    }
+}
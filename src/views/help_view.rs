@@ -0,0 +1,341 @@
+use Printer;
+use With;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use menu::{MenuItem, MenuTree};
+use std::cmp::{max, min};
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use utils::{fuzzy, i18n};
+use vec::Vec2;
+use view::{ScrollBase, View};
+
+/// A key binding or action shown in a `HelpView`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HelpEntry {
+    /// Group this entry is shown in.
+    pub category: String,
+    /// Key combination, like `Ctrl-S`. Can be empty.
+    pub key: String,
+    /// What the key does.
+    pub description: String,
+}
+
+/// Lists key bindings and actions, grouped by category.
+///
+/// Typing searches the list; `<Backspace>` removes the last searched
+/// character.
+///
+/// [`Cursive::help_view`] builds one from the registered global callbacks
+/// and the menubar, so the help screen can't get out of date.
+///
+/// [`Cursive::help_view`]: ../struct.Cursive.html#method.help_view
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::Cursive;
+/// # use cursive::views::Dialog;
+/// let mut siv = Cursive::new();
+///
+/// siv.add_global_callback('q', |s| s.quit());
+/// siv.describe_global_callback('q', "General", "Quit the application");
+///
+/// siv.add_global_callback('?', |s| {
+///     let help = s.help_view();
+///     s.add_layer(Dialog::around(help).title("Help").dismiss_button("Ok"));
+/// });
+/// ```
+pub struct HelpView {
+    entries: Vec<HelpEntry>,
+    query: String,
+    // What is shown after filtering.
+    lines: Vec<Line>,
+    scrollbase: ScrollBase,
+}
+
+enum Line {
+    // Index of the first entry in this category.
+    Category(usize),
+    Entry(usize),
+}
+
+// The search line and the delimiter below it.
+const HEADER: usize = 2;
+
+new_default!(HelpView);
+
+impl HelpView {
+    /// Creates a new, empty help view.
+    pub fn new() -> Self {
+        HelpView {
+            entries: Vec::new(),
+            query: String::new(),
+            lines: Vec::new(),
+            scrollbase: ScrollBase::new(),
+        }
+    }
+
+    /// Adds an entry.
+    ///
+    /// Entries are shown grouped by category, in the order categories
+    /// first appear.
+    pub fn add_entry<C, K, D>(&mut self, category: C, key: K, description: D)
+        where C: Into<String>,
+              K: Into<String>,
+              D: Into<String>
+    {
+        self.entries.push(HelpEntry {
+            category: category.into(),
+            key: key.into(),
+            description: description.into(),
+        });
+        self.update_lines();
+    }
+
+    /// Adds an entry.
+    ///
+    /// Chainable variant.
+    pub fn entry<C, K, D>(self, category: C, key: K, description: D) -> Self
+        where C: Into<String>,
+              K: Into<String>,
+              D: Into<String>
+    {
+        self.with(|s| s.add_entry(category, key, description))
+    }
+
+    /// Adds an entry for each action in `menu`, in the given category.
+    ///
+    /// Actions in sub-menus are described with their path, like
+    /// `Recent > Clear`.
+    pub fn add_menu<S>(&mut self, category: S, menu: &MenuTree)
+        where S: Into<String>
+    {
+        let category = category.into();
+        self.add_menu_items(&category, "", menu);
+    }
+
+    fn add_menu_items(&mut self, category: &str, prefix: &str,
+                      menu: &MenuTree) {
+        for item in &menu.children {
+            match *item {
+                MenuItem::Leaf(ref label, _) => {
+                    let description = format!("{}{}", prefix, label);
+                    self.add_entry(category, "", description);
+                }
+                MenuItem::Subtree(ref label, ref tree) => {
+                    let prefix = format!("{}{} > ", prefix, label);
+                    self.add_menu_items(category, &prefix, tree);
+                }
+                MenuItem::Delimiter => (),
+            }
+        }
+    }
+
+    /// Returns all entries, including those hidden by the search.
+    pub fn entries(&self) -> &[HelpEntry] {
+        &self.entries
+    }
+
+    /// Sets the search query.
+    ///
+    /// Only entries whose key or description fuzzy-match the query are
+    /// shown.
+    pub fn set_query<S: Into<String>>(&mut self, query: S) {
+        self.query = query.into();
+        self.update_lines();
+    }
+
+    /// Returns the current search query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the number of entries matching the search.
+    pub fn visible_len(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| match **line {
+                Line::Entry(_) => true,
+                Line::Category(_) => false,
+            })
+            .count()
+    }
+
+    fn matches(&self, entry: &HelpEntry) -> bool {
+        fuzzy::find(&self.query, &entry.description).is_some() ||
+        fuzzy::find(&self.query, &entry.key).is_some()
+    }
+
+    // Groups the matching entries by category.
+    fn update_lines(&mut self) {
+        let mut lines = Vec::new();
+        let mut done: Vec<&str> = Vec::new();
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            if done.contains(&&entry.category[..]) {
+                continue;
+            }
+            done.push(&entry.category);
+
+            let matching: Vec<_> = (i..self.entries.len())
+                .filter(|&j| {
+                    let other = &self.entries[j];
+                    other.category == entry.category && self.matches(other)
+                })
+                .map(Line::Entry)
+                .collect();
+            if !matching.is_empty() {
+                lines.push(Line::Category(i));
+                lines.extend(matching);
+            }
+        }
+
+        self.lines = lines;
+        self.scrollbase.scroll_top();
+    }
+
+    fn key_width(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| entry.key.width())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl View for HelpView {
+    fn draw(&self, printer: &Printer) {
+        if printer.size.y <= HEADER {
+            return;
+        }
+
+        let label = i18n::translate("Search:");
+        printer.with_color(ColorStyle::Secondary,
+                           |printer| printer.print((0, 0), &label));
+        printer.print((label.width() + 1, 0), &self.query);
+        printer.print_hline((0, 1),
+                            printer.size.x,
+                            &printer.theme.glyphs.horizontal);
+
+        if self.lines.is_empty() {
+            let text = i18n::translate("No match");
+            printer.with_color(ColorStyle::Secondary, |printer| {
+                printer.print((0, HEADER), &text)
+            });
+            return;
+        }
+
+        let key_width = self.key_width();
+        let size = printer.size - (0, HEADER);
+        let printer = printer.sub_printer((0, HEADER), size, true);
+        self.scrollbase.draw(&printer, |printer, i| match self.lines[i] {
+            Line::Category(j) => {
+                printer.with_color(ColorStyle::TitlePrimary, |printer| {
+                    printer.print((0, 0), &self.entries[j].category)
+                });
+            }
+            Line::Entry(j) => {
+                let entry = &self.entries[j];
+                printer.with_color(ColorStyle::Tertiary, |printer| {
+                    printer.print((2, 0), &entry.key)
+                });
+                printer.print((key_width + 4, 0), &entry.description);
+            }
+        });
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let key_width = self.key_width();
+        let w = self.entries
+            .iter()
+            .map(|entry| {
+                max(entry.category.width(),
+                    key_width + 4 + entry.description.width())
+            })
+            .max()
+            .unwrap_or(0);
+        let h = HEADER + max(self.lines.len(), 1);
+
+        // Keep room for the scrollbar.
+        let w = if h > req.y { w + 2 } else { w };
+        let search = i18n::translate("Search:").width() + 1;
+        Vec2::new(max(w, search + self.query.width()), min(h, req.y))
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.scrollbase
+            .set_heights(size.y.saturating_sub(HEADER), self.lines.len());
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Char(c) => {
+                self.query.push(c);
+                self.update_lines();
+            }
+            Event::Key(Key::Backspace) if !self.query.is_empty() => {
+                self.query.pop();
+                self.update_lines();
+            }
+            Event::Key(Key::Up) if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(1)
+            }
+            Event::Key(Key::Down) if self.scrollbase.can_scroll_down() => {
+                self.scrollbase.scroll_down(1)
+            }
+            Event::Key(Key::PageUp) if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(10)
+            }
+            Event::Key(Key::PageDown) if self.scrollbase
+                .can_scroll_down() => self.scrollbase.scroll_down(10),
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HelpView;
+    use event::{Event, Key};
+    use menu::MenuTree;
+
+    #[test]
+    fn search() {
+        let mut view = HelpView::new()
+            .entry("General", "q", "Quit")
+            .entry("Edit", "Ctrl-C", "Copy")
+            .entry("General", "?", "Show help");
+        view.add_menu("File",
+                      &MenuTree::new()
+                           .leaf("Open", |_| ())
+                           .delimiter()
+                           .subtree("Recent",
+                                    MenuTree::new().leaf("Clear", |_| ())));
+        assert_eq!(view.entries()[4].description, "Recent > Clear");
+        assert_eq!(view.visible_len(), 5);
+        // Categories are grouped: 3 headers and 5 entries.
+        assert_eq!(view.lines.len(), 8);
+
+        view.set_query("clear");
+        assert_eq!(view.visible_len(), 1);
+        assert_eq!(view.lines.len(), 2);
+
+        view.set_query("ctrl");
+        assert_eq!(view.visible_len(), 1);
+    }
+
+    #[test]
+    fn key_names() {
+        assert_eq!(Event::CtrlChar('p').to_string(), "Ctrl-P");
+        assert_eq!(Event::Shift(Key::Tab).to_string(), "Shift-Tab");
+        assert_eq!(Event::Char(' ').to_string(), "Space");
+        assert_eq!(Event::Key(Key::Del).to_string(), "Delete");
+    }
+}
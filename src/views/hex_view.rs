@@ -0,0 +1,322 @@
+use {Printer, With};
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::cell::RefCell;
+use std::cmp::min;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use theme::ColorStyle;
+use vec::Vec2;
+use view::View;
+
+/// Provides the data shown by a `HexView`.
+///
+/// Only the visible part is read, so implementations can page through
+/// sources bigger than memory.
+pub trait HexData {
+    /// Returns the total number of bytes.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there is no data at all.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads bytes starting at `offset` into `buf`.
+    ///
+    /// Returns the number of bytes read.
+    fn read(&self, offset: usize, buf: &mut [u8]) -> usize;
+
+    /// Replaces the byte at `offset`.
+    ///
+    /// By default, the data is read-only.
+    fn write(&mut self, offset: usize, value: u8) -> io::Result<()> {
+        let _ = (offset, value);
+        Err(io::Error::new(io::ErrorKind::PermissionDenied, "read-only data"))
+    }
+}
+
+impl HexData for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn read(&self, offset: usize, buf: &mut [u8]) -> usize {
+        if offset >= Vec::len(self) {
+            return 0;
+        }
+        let data = &self[offset..];
+        let n = min(data.len(), buf.len());
+        buf[..n].copy_from_slice(&data[..n]);
+        n
+    }
+
+    fn write(&mut self, offset: usize, value: u8) -> io::Result<()> {
+        self[offset] = value;
+        Ok(())
+    }
+}
+
+/// Data read directly from a file, without loading it in memory.
+pub struct FileData {
+    file: RefCell<File>,
+    len: usize,
+}
+
+impl FileData {
+    /// Wraps the given file.
+    ///
+    /// The file needs to be opened for writing to edit it.
+    pub fn new(file: File) -> io::Result<Self> {
+        let len = file.metadata()?.len() as usize;
+        Ok(FileData {
+            file: RefCell::new(file),
+            len: len,
+        })
+    }
+}
+
+impl HexData for FileData {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn read(&self, offset: usize, buf: &mut [u8]) -> usize {
+        let mut file = self.file.borrow_mut();
+        if file.seek(SeekFrom::Start(offset as u64)).is_err() {
+            return 0;
+        }
+
+        // `read` may return less than asked, so keep reading.
+        let mut n = 0;
+        while n < buf.len() {
+            match file.read(&mut buf[n..]) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => n += read,
+            }
+        }
+        n
+    }
+
+    fn write(&mut self, offset: usize, value: u8) -> io::Result<()> {
+        let file = self.file.get_mut();
+        file.seek(SeekFrom::Start(offset as u64))?;
+        file.write_all(&[value])
+    }
+}
+
+// Bytes shown on each row.
+const BYTES_PER_ROW: usize = 16;
+
+// Cells taken by each part of a row.
+const OFFSET_WIDTH: usize = 10;
+const HEX_WIDTH: usize = 3 * BYTES_PER_ROW + 1;
+
+/// Shows binary data as offset, hexadecimal and ASCII columns.
+///
+/// Use the arrow keys to move around. In edit mode, typing hexadecimal
+/// digits replaces the byte under the cursor.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::HexView;
+/// let view = HexView::new(vec![0xde, 0xad, 0xbe, 0xef]).editable(true);
+/// ```
+pub struct HexView {
+    data: Box<HexData>,
+    // Byte under the cursor.
+    cursor: usize,
+    // `true` if the high half of the byte was already typed.
+    low_nibble: bool,
+    // First visible row.
+    top: usize,
+    height: usize,
+    editable: bool,
+}
+
+impl HexView {
+    /// Creates a new view around the given data.
+    pub fn new<D: HexData + 'static>(data: D) -> Self {
+        HexView {
+            data: Box::new(data),
+            cursor: 0,
+            low_nibble: false,
+            top: 0,
+            height: 0,
+            editable: false,
+        }
+    }
+
+    /// Enables or disables edit mode.
+    ///
+    /// Defaults to `false`.
+    pub fn set_editable(&mut self, editable: bool) {
+        self.editable = editable;
+        self.low_nibble = false;
+    }
+
+    /// Enables or disables edit mode.
+    ///
+    /// Chainable variant.
+    pub fn editable(self, editable: bool) -> Self {
+        self.with(|s| s.set_editable(editable))
+    }
+
+    /// Returns the offset of the byte under the cursor.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor to the given offset.
+    pub fn set_cursor(&mut self, offset: usize) {
+        self.cursor = min(offset, self.data.len().saturating_sub(1));
+        self.low_nibble = false;
+        self.scroll_to_cursor();
+    }
+
+    /// Returns a reference to the data.
+    pub fn get_data(&self) -> &HexData {
+        &*self.data
+    }
+
+    fn rows(&self) -> usize {
+        (self.data.len() + BYTES_PER_ROW - 1) / BYTES_PER_ROW
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let row = self.cursor / BYTES_PER_ROW;
+        if row < self.top {
+            self.top = row;
+        } else if self.height > 0 && row >= self.top + self.height {
+            self.top = row + 1 - self.height;
+        }
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let max = self.data.len().saturating_sub(1) as isize;
+        let target = self.cursor as isize + delta;
+        let target = if target < 0 {
+            0
+        } else if target > max {
+            max
+        } else {
+            target
+        };
+        self.set_cursor(target as usize);
+    }
+
+    fn edit(&mut self, digit: u8) {
+        let mut byte = [0];
+        if self.data.read(self.cursor, &mut byte) == 0 {
+            return;
+        }
+
+        let value = if self.low_nibble {
+            (byte[0] & 0xf0) | digit
+        } else {
+            (byte[0] & 0x0f) | (digit << 4)
+        };
+
+        if self.data.write(self.cursor, value).is_err() {
+            return;
+        }
+
+        if self.low_nibble {
+            self.move_by(1);
+        } else {
+            self.low_nibble = true;
+        }
+    }
+
+    fn draw_row(&self, printer: &Printer, row: usize) {
+        let offset = row * BYTES_PER_ROW;
+        let mut buf = [0; BYTES_PER_ROW];
+        let n = self.data.read(offset, &mut buf);
+
+        printer.with_color(ColorStyle::Secondary, |printer| {
+            printer.print_fmt((0, 0), format_args!("{:08x}", offset));
+        });
+
+        for (i, &byte) in buf[..n].iter().enumerate() {
+            let x = OFFSET_WIDTH + 3 * i + i / 8;
+            let ascii = if (0x20..0x7f).contains(&byte) {
+                byte as char
+            } else {
+                '.'
+            };
+
+            if offset + i == self.cursor {
+                let style = if printer.focused {
+                    ColorStyle::Highlight
+                } else {
+                    ColorStyle::HighlightInactive
+                };
+                printer.with_color(style, |printer| {
+                    printer.print_fmt((x, 0), format_args!("{:02x}", byte));
+                    printer.print_fmt((OFFSET_WIDTH + HEX_WIDTH + 1 + i, 0),
+                                      format_args!("{}", ascii));
+                });
+            } else {
+                printer.print_fmt((x, 0), format_args!("{:02x}", byte));
+                printer.print_fmt((OFFSET_WIDTH + HEX_WIDTH + 1 + i, 0),
+                                  format_args!("{}", ascii));
+            }
+        }
+    }
+}
+
+impl View for HexView {
+    fn draw(&self, printer: &Printer) {
+        let rows = self.top..min(self.top + self.height, self.rows());
+        for (y, row) in rows.enumerate() {
+            let printer = printer.sub_printer((0, y),
+                                              (printer.size.x, 1),
+                                              printer.focused);
+            self.draw_row(&printer, row);
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let width = OFFSET_WIDTH + HEX_WIDTH + 1 + BYTES_PER_ROW;
+        Vec2::new(width, min(self.rows(), req.y))
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.height = size.y;
+        self.scroll_to_cursor();
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let row = BYTES_PER_ROW as isize;
+        let page = row * self.height as isize;
+
+        match event {
+            Event::Key(Key::Left) => self.move_by(-1),
+            Event::Key(Key::Right) => self.move_by(1),
+            Event::Key(Key::Up) => self.move_by(-row),
+            Event::Key(Key::Down) => self.move_by(row),
+            Event::Key(Key::PageUp) => self.move_by(-page),
+            Event::Key(Key::PageDown) => self.move_by(page),
+            Event::Key(Key::Home) => {
+                let start = self.cursor - self.cursor % BYTES_PER_ROW;
+                self.set_cursor(start);
+            }
+            Event::Key(Key::End) => {
+                let start = self.cursor - self.cursor % BYTES_PER_ROW;
+                self.set_cursor(start + BYTES_PER_ROW - 1);
+            }
+            Event::Char(c) if self.editable && c.is_digit(16) => {
+                let digit = c.to_digit(16).unwrap() as u8;
+                self.edit(digit);
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        !self.data.is_empty()
+    }
+}
@@ -1,8 +1,9 @@
+use event::{Event, EventResult};
 use owning_ref::{RcRef, OwningHandle};
 use std::any::Any;
 
 use std::cell::{RefCell, RefMut};
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use view::{Selector, View, ViewWrapper};
 
 /// Wrapper around a view to provide interior mutability.
@@ -18,6 +19,73 @@ pub struct IdView<V: View> {
 /// [`RefMut`]: https://doc.rust-lang.org/std/cell/struct.RefMut.html
 pub type ViewRef<V> = OwningHandle<RcRef<RefCell<V>>, RefMut<'static, V>>;
 
+/// Handle to a view wrapped in an [`IdView`], that doesn't keep it alive.
+///
+/// It can be stored in callbacks and used later, without looking for the
+/// view again. Once the view is removed from the tree, the handle still
+/// works, but finds nothing.
+///
+/// [`IdView`]: struct.IdView.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::Cursive;
+/// # use cursive::traits::*;
+/// # use cursive::views::TextView;
+/// let mut siv = Cursive::new();
+/// siv.add_layer(TextView::new("0").with_id("counter"));
+///
+/// let counter = siv.find_handle::<TextView>("counter").unwrap();
+/// siv.add_global_callback('+', move |_| {
+///     counter.with_view_mut(|view| view.set_content("1"));
+/// });
+/// ```
+pub struct ViewHandle<V: View> {
+    view: Weak<RefCell<V>>,
+}
+
+impl<V: View> Clone for ViewHandle<V> {
+    fn clone(&self) -> Self {
+        ViewHandle { view: self.view.clone() }
+    }
+}
+
+impl<V: View> ViewHandle<V> {
+    /// Runs a closure on the view, if it still exists.
+    ///
+    /// Returns `None` if the view was dropped, or if it is already
+    /// borrowed (for instance from inside one of its own callbacks).
+    pub fn with_view_mut<F, R>(&self, f: F) -> Option<R>
+        where F: FnOnce(&mut V) -> R
+    {
+        let view = match self.view.upgrade() {
+            Some(view) => view,
+            None => return None,
+        };
+        let result = view.try_borrow_mut().ok().map(|mut v| f(&mut *v));
+        result
+    }
+
+    /// Gets mutable access to the view, if it still exists.
+    ///
+    /// Like [`IdView::get_mut`], this panics if the view is already
+    /// borrowed.
+    ///
+    /// [`IdView::get_mut`]: struct.IdView.html#method.get_mut
+    pub fn get_mut(&self) -> Option<ViewRef<V>> {
+        self.view.upgrade().map(|view| {
+            OwningHandle::new(RcRef::new(view),
+                              |x| unsafe { x.as_ref() }.unwrap().borrow_mut())
+        })
+    }
+
+    /// Returns `true` if the view still exists.
+    pub fn is_alive(&self) -> bool {
+        self.view.upgrade().is_some()
+    }
+}
+
 impl<V: View> IdView<V> {
     /// Wraps `view` in a new `IdView`.
     pub fn new<S: Into<String>>(id: S, view: V) -> Self {
@@ -39,6 +107,11 @@ impl<V: View> IdView<V> {
         OwningHandle::new(cell_ref,
                           |x| unsafe { x.as_ref() }.unwrap().borrow_mut())
     }
+
+    /// Returns a handle to the inner view, that doesn't keep it alive.
+    pub fn handle(&self) -> ViewHandle<V> {
+        ViewHandle { view: Rc::downgrade(&self.view) }
+    }
 }
 
 impl<T: View + 'static> ViewWrapper for IdView<T> {
@@ -62,6 +135,11 @@ impl<T: View + 'static> ViewWrapper for IdView<T> {
             .map(|mut v| f(&mut *v))
     }
 
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        self.with_view_mut(|v| v.on_event(event).resolve_on(v))
+            .unwrap_or(EventResult::Ignored)
+    }
+
     fn wrap_call_on_any<'a>(&mut self, selector: &Selector,
                          mut callback: Box<for<'b> FnMut(&'b mut Any) + 'a>) {
         match selector {
@@ -77,3 +155,87 @@ impl<T: View + 'static> ViewWrapper for IdView<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::IdView;
+    use event::{Event, EventResult};
+    use view::{SizeConstraint, View};
+    use views::{BoxView, Canvas, LinearLayout, ListView, StackView,
+                TextView};
+
+    #[test]
+    fn handle() {
+        let view = IdView::new("text", TextView::new("abc"));
+        let handle = view.handle();
+        assert_eq!(handle.with_view_mut(|v| v.get_content().to_string()),
+                   Some("abc".to_string()));
+
+        {
+            let _borrowed = handle.get_mut();
+            assert_eq!(handle.with_view_mut(|_| ()), None);
+        }
+
+        drop(view);
+        assert!(!handle.is_alive());
+        assert_eq!(handle.with_view_mut(|_| ()), None);
+    }
+
+    #[test]
+    fn ancestor() {
+        let closer = Canvas::new(()).with_on_event(|event, _| match event {
+            Event::Char('q') => {
+                EventResult::with_ancestor(|stack: &mut StackView| {
+                    stack.pop_layer();
+                    EventResult::Consumed(None)
+                })
+            }
+            Event::Char('r') => {
+                EventResult::with_ancestor(|text: &mut TextView| {
+                    text.set_content("renamed");
+                    EventResult::Consumed(None)
+                })
+            }
+            _ => EventResult::Ignored,
+        });
+        let layout = LinearLayout::vertical().child(closer);
+        let layout = IdView::new("layout", layout);
+
+        let mut stack = StackView::new();
+        stack.add_layer(TextView::new("bottom"));
+        stack.add_layer(layout);
+
+        // No `TextView` contains the canvas.
+        assert!(stack.on_event(Event::Char('r')).is_consumed());
+        assert_eq!(stack.len(), 2);
+
+        assert!(stack.on_event(Event::Char('q')).is_consumed());
+        assert_eq!(stack.len(), 1);
+    }
+
+    #[test]
+    fn ancestor_wrappers() {
+        let canvas = Canvas::new(()).with_on_event(|event, _| match event {
+            Event::Char('l') => {
+                EventResult::with_ancestor(|_: &mut ListView| {
+                    EventResult::Ignored
+                })
+            }
+            Event::Char('w') => {
+                EventResult::with_ancestor(|view: &mut BoxView<ListView>| {
+                    view.set_width(SizeConstraint::Fixed(3));
+                    EventResult::Consumed(None)
+                })
+            }
+            _ => EventResult::Ignored,
+        });
+        let list = ListView::new().child("canvas", canvas);
+        let mut view = BoxView::with_full_width(list);
+
+        // The list gets the result and ignores the event.
+        assert!(!view.on_event(Event::Char('l')).is_consumed());
+
+        assert!(view.on_event(Event::Char('w')).is_consumed());
+        assert_eq!(view.get_width(), SizeConstraint::Fixed(3));
+    }
+}
@@ -0,0 +1,312 @@
+use {Printer, With};
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use image::imageops::{self, FilterType};
+use std::cmp::min;
+use std::env;
+use std::io::Cursor;
+use theme::{Color, ColorStyle};
+use vec::Vec2;
+use view::View;
+
+/// How an image is sent to the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// Colored half-block characters, two pixels per cell.
+    ///
+    /// Works everywhere, but with a low resolution.
+    HalfBlocks,
+    /// The sixel graphics format, supported by xterm, mlterm or foot.
+    Sixel,
+    /// Inline images from iTerm2.
+    Iterm2,
+}
+
+impl ImageProtocol {
+    /// Guesses the best protocol from the environment.
+    pub fn detect() -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        let program = env::var("TERM_PROGRAM").unwrap_or_default();
+        if program == "iTerm.app" {
+            ImageProtocol::Iterm2
+        } else if term.contains("sixel") || term.starts_with("mlterm") ||
+                  term.starts_with("foot") {
+            ImageProtocol::Sixel
+        } else {
+            ImageProtocol::HalfBlocks
+        }
+    }
+}
+
+/// How an image is resized to fit the view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageFit {
+    /// Use as much space as possible, keeping the aspect ratio.
+    Contain,
+    /// Use all the available space, ignoring the aspect ratio.
+    Stretch,
+    /// Scale by a fixed ratio.
+    ///
+    /// With a ratio of `1.0`, each pixel takes half a cell.
+    Scale(f32),
+}
+
+/// Shows an image.
+///
+/// Sixel and iTerm2 images need a backend writing directly to the
+/// terminal; otherwise, half-block characters are used.
+///
+/// Requires the `image` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate image;
+/// # extern crate cursive;
+/// # use cursive::views::{ImageFit, ImageView};
+/// # fn main() {
+/// let image = image::open("logo.png").unwrap();
+/// let view = ImageView::new(image).fit(ImageFit::Contain);
+/// # }
+/// ```
+pub struct ImageView {
+    image: RgbaImage,
+    fit: ImageFit,
+    protocol: ImageProtocol,
+
+    // Image resized for the last layout, and its size in cells.
+    resized: Option<RgbaImage>,
+    cells: Vec2,
+    // Escape sequence for the resized image, if not using half-blocks.
+    encoded: Option<Vec<u8>>,
+}
+
+impl ImageView {
+    /// Creates a new view around the given image.
+    ///
+    /// The protocol is detected from the environment.
+    pub fn new(image: DynamicImage) -> Self {
+        ImageView {
+            image: image.to_rgba8(),
+            fit: ImageFit::Contain,
+            protocol: ImageProtocol::detect(),
+            resized: None,
+            cells: Vec2::zero(),
+            encoded: None,
+        }
+    }
+
+    /// Sets how the image is resized.
+    ///
+    /// Defaults to `ImageFit::Contain`.
+    pub fn set_fit(&mut self, fit: ImageFit) {
+        self.fit = fit;
+        self.resized = None;
+    }
+
+    /// Sets how the image is resized.
+    ///
+    /// Chainable variant.
+    pub fn fit(self, fit: ImageFit) -> Self {
+        self.with(|s| s.set_fit(fit))
+    }
+
+    /// Sets the protocol used to show the image.
+    pub fn set_protocol(&mut self, protocol: ImageProtocol) {
+        self.protocol = protocol;
+        self.resized = None;
+    }
+
+    /// Sets the protocol used to show the image.
+    ///
+    /// Chainable variant.
+    pub fn protocol(self, protocol: ImageProtocol) -> Self {
+        self.with(|s| s.set_protocol(protocol))
+    }
+
+    // Returns the size of the image in cells, to fit in `available`.
+    fn cells_for(&self, available: Vec2) -> Vec2 {
+        // Each cell is two pixels high.
+        let (width, height) = (self.image.width() as f32,
+                               self.image.height() as f32 / 2.0);
+        let (max_x, max_y) = (available.x as f32, available.y as f32);
+        let (x, y) = match self.fit {
+            ImageFit::Stretch => (max_x, max_y),
+            ImageFit::Contain => {
+                let ratio = (max_x / width).min(max_y / height);
+                (width * ratio, height * ratio)
+            }
+            ImageFit::Scale(ratio) => {
+                ((width * ratio).min(max_x), (height * ratio).min(max_y))
+            }
+        };
+        Vec2::new(x.round().max(1.0) as usize, y.round().max(1.0) as usize)
+    }
+}
+
+// Returns the index of the closest color in the 6x6x6 cube.
+fn sixel_color(pixel: &[u8]) -> usize {
+    let level = |c: u8| c as usize * 6 / 256;
+    36 * level(pixel[0]) + 6 * level(pixel[1]) + level(pixel[2])
+}
+
+fn encode_sixel(image: &RgbaImage) -> Vec<u8> {
+    let mut out = b"\x1bPq".to_vec();
+    for i in 0..216 {
+        let (r, g, b) = (i / 36, (i / 6) % 6, i % 6);
+        out.extend(format!("#{};2;{};{};{}", i, r * 20, g * 20, b * 20)
+                       .into_bytes());
+    }
+
+    let (width, height) = image.dimensions();
+    let mut band = 0;
+    while band < height {
+        let rows = min(6, height - band);
+        // Colors used in this band.
+        let mut colors: Vec<usize> = (0..width)
+            .flat_map(|x| (0..rows).map(move |dy| (x, band + dy)))
+            .map(|(x, y)| sixel_color(&image.get_pixel(x, y).0))
+            .collect();
+        colors.sort();
+        colors.dedup();
+
+        for color in colors {
+            out.extend(format!("#{}", color).into_bytes());
+            for x in 0..width {
+                let mut bits = 0;
+                for dy in 0..rows {
+                    let pixel = image.get_pixel(x, band + dy);
+                    if pixel.0[3] >= 128 && sixel_color(&pixel.0) == color {
+                        bits |= 1 << dy;
+                    }
+                }
+                out.push(63 + bits);
+            }
+            // Go back to the start of the band.
+            out.push(b'$');
+        }
+        // Next band.
+        out.push(b'-');
+        band += 6;
+    }
+    out.extend(b"\x1b\\");
+    out
+}
+
+fn base64(data: &[u8]) -> String {
+    const TABLE: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                                   abcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let n = chunk.iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - 6 * i)) & 0x3f;
+                out.push(TABLE[index as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn encode_iterm2(image: &RgbaImage, cells: Vec2) -> Option<Vec<u8>> {
+    let mut png = Cursor::new(Vec::new());
+    if image.write_to(&mut png, ImageFormat::Png).is_err() {
+        return None;
+    }
+    let header = format!("\x1b]1337;File=inline=1;width={};height={};\
+                          preserveAspectRatio=0:",
+                         cells.x,
+                         cells.y);
+    let mut out = header.into_bytes();
+    out.extend(base64(png.get_ref()).into_bytes());
+    out.push(0x07);
+    Some(out)
+}
+
+fn convert_color(pixel: &[u8], background: Color) -> Color {
+    if pixel[3] < 128 {
+        background
+    } else {
+        Color::Rgb(pixel[0], pixel[1], pixel[2])
+    }
+}
+
+impl View for ImageView {
+    fn draw(&self, printer: &Printer) {
+        if let Some(ref encoded) = self.encoded {
+            if printer.print_raw((0, 0), encoded) {
+                return;
+            }
+        }
+
+        let image = match self.resized {
+            Some(ref image) => image,
+            None => return,
+        };
+        let background = printer.theme.colors.view;
+        for y in 0..self.cells.y {
+            for x in 0..self.cells.x {
+                let top = image.get_pixel(x as u32, 2 * y as u32);
+                let bottom = image.get_pixel(x as u32, 2 * y as u32 + 1);
+                let color = ColorStyle::Custom {
+                    front: convert_color(&top.0, background),
+                    back: convert_color(&bottom.0, background),
+                };
+                printer.with_color(color, |printer| {
+                    printer.print((x, y), "▀");
+                });
+            }
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        self.cells_for(req)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        let cells = self.cells_for(size);
+        if self.resized.is_some() && cells == self.cells {
+            return;
+        }
+
+        self.cells = cells;
+        self.encoded = match self.protocol {
+            ImageProtocol::HalfBlocks => None,
+            ImageProtocol::Sixel => {
+                // We don't know the size of a cell in pixels.
+                // Let's assume 8x16, with a height multiple of 6.
+                let height = (cells.y as u32 * 16 + 5) / 6 * 6;
+                let resized = imageops::resize(&self.image,
+                                               cells.x as u32 * 8,
+                                               height,
+                                               FilterType::Triangle);
+                Some(encode_sixel(&resized))
+            }
+            // The terminal scales the image itself.
+            ImageProtocol::Iterm2 => encode_iterm2(&self.image, cells),
+        };
+
+        // Keep a half-block version even with other protocols, in case the
+        // backend doesn't support raw output.
+        self.resized = Some(imageops::resize(&self.image,
+                                             cells.x as u32,
+                                             cells.y as u32 * 2,
+                                             FilterType::Triangle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+    }
+}
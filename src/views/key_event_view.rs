@@ -9,6 +9,9 @@ use view::{View, ViewWrapper};
 ///
 /// If the event doesn't have a corresponding callback, it will stay ignored.
 ///
+/// Callbacks registered with `register_pre` run before the child sees the
+/// event, and take precedence over it.
+///
 /// # Examples
 ///
 /// ```
@@ -21,6 +24,7 @@ use view::{View, ViewWrapper};
 pub struct KeyEventView<T: View> {
     content: T,
     callbacks: HashMap<Event, Callback>,
+    pre_callbacks: HashMap<Event, Callback>,
 }
 
 impl<T: View> KeyEventView<T> {
@@ -29,6 +33,7 @@ impl<T: View> KeyEventView<T> {
         KeyEventView {
             content: view,
             callbacks: HashMap::new(),
+            pre_callbacks: HashMap::new(),
         }
     }
 
@@ -40,12 +45,27 @@ impl<T: View> KeyEventView<T> {
 
         self
     }
+
+    /// Registers a callback when the given key is pressed.
+    ///
+    /// The child will not see this key.
+    pub fn register_pre<F, E: Into<Event>>(mut self, event: E, cb: F) -> Self
+        where F: Fn(&mut Cursive) + 'static
+    {
+        self.pre_callbacks.insert(event.into(), Callback::from_fn(cb));
+
+        self
+    }
 }
 
 impl<T: View> ViewWrapper for KeyEventView<T> {
     wrap_impl!(self.content: T);
 
     fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        if let Some(cb) = self.pre_callbacks.get(&event) {
+            return EventResult::Consumed(Some(cb.clone()));
+        }
+
         match self.content.on_event(event.clone()) {
             EventResult::Ignored => {
                 match self.callbacks.get(&event) {
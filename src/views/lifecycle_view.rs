@@ -0,0 +1,138 @@
+use With;
+use vec::Vec2;
+use view::{View, ViewWrapper};
+
+/// Wrapper view running callbacks when its content enters or leaves the
+/// view tree.
+///
+/// * The `on_attach` callback runs on the first layout, when the view is
+///   about to be shown for the first time.
+/// * The `on_detach` callback runs when the view is dropped, for instance
+///   after its layer was removed with `Cursive::pop_layer`. It only runs if
+///   the view was attached.
+///
+/// This gives views a chance to start or stop timers, subscribe to data
+/// sources, or release resources at a predictable time.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{LifecycleView, TextView};
+/// let view = LifecycleView::new(TextView::new("Clock"))
+///     .on_attach(|_| println!("Starting the timer"))
+///     .on_detach(|_| println!("Stopping the timer"));
+/// ```
+pub struct LifecycleView<T: View> {
+    view: T,
+    attached: bool,
+    on_attach: Option<Box<Fn(&mut T)>>,
+    on_detach: Option<Box<Fn(&mut T)>>,
+}
+
+impl<T: View> LifecycleView<T> {
+    /// Wraps the given view.
+    pub fn new(view: T) -> Self {
+        LifecycleView {
+            view: view,
+            attached: false,
+            on_attach: None,
+            on_detach: None,
+        }
+    }
+
+    /// Sets a callback to run when the view is first laid out.
+    pub fn set_on_attach<F>(&mut self, cb: F)
+        where F: Fn(&mut T) + 'static
+    {
+        self.on_attach = Some(Box::new(cb));
+    }
+
+    /// Sets a callback to run when the view is first laid out.
+    ///
+    /// Chainable variant.
+    pub fn on_attach<F>(self, cb: F) -> Self
+        where F: Fn(&mut T) + 'static
+    {
+        self.with(|s| s.set_on_attach(cb))
+    }
+
+    /// Sets a callback to run when the view is dropped.
+    pub fn set_on_detach<F>(&mut self, cb: F)
+        where F: Fn(&mut T) + 'static
+    {
+        self.on_detach = Some(Box::new(cb));
+    }
+
+    /// Sets a callback to run when the view is dropped.
+    ///
+    /// Chainable variant.
+    pub fn on_detach<F>(self, cb: F) -> Self
+        where F: Fn(&mut T) + 'static
+    {
+        self.with(|s| s.set_on_detach(cb))
+    }
+
+    /// Returns `true` if the view was attached to the view tree.
+    pub fn is_attached(&self) -> bool {
+        self.attached
+    }
+}
+
+impl<T: View> ViewWrapper for LifecycleView<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        if !self.attached {
+            self.attached = true;
+            if let Some(ref cb) = self.on_attach {
+                cb(&mut self.view);
+            }
+        }
+        self.view.layout(size);
+    }
+}
+
+impl<T: View> Drop for LifecycleView<T> {
+    fn drop(&mut self) {
+        if !self.attached {
+            return;
+        }
+        if let Some(ref cb) = self.on_detach {
+            cb(&mut self.view);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LifecycleView;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use view::View;
+    use views::DummyView;
+
+    #[test]
+    fn attach_detach() {
+        let events = Rc::new(Cell::new((0, 0)));
+
+        let attach = events.clone();
+        let detach = events.clone();
+        let mut view = LifecycleView::new(DummyView)
+            .on_attach(move |_| attach.set((attach.get().0 + 1, 0)))
+            .on_detach(move |_| detach.set((1, detach.get().1 + 1)));
+        assert!(!view.is_attached());
+
+        view.layout((10, 10).into());
+        view.layout((10, 10).into());
+        assert_eq!(events.get(), (1, 0));
+
+        drop(view);
+        assert_eq!(events.get(), (1, 1));
+
+        // Never attached, so never detached.
+        let detach = events.clone();
+        drop(LifecycleView::new(DummyView)
+                 .on_detach(move |_| detach.set((0, 0))));
+        assert_eq!(events.get(), (1, 1));
+    }
+}
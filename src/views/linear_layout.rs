@@ -6,6 +6,7 @@ use event::{Event, EventResult, Key};
 
 use std::any::Any;
 use std::cmp::min;
+use std::rc::Rc;
 use vec::Vec2;
 use view::{Selector, SizeCache};
 use view::View;
@@ -17,6 +18,9 @@ pub struct LinearLayout {
     focus: usize,
 
     cache: Option<XY<SizeCache>>,
+
+    // Sees events before the focused child.
+    on_capture: Option<Rc<Fn(&mut Event) -> EventResult>>,
 }
 
 struct Child {
@@ -56,9 +60,44 @@ impl LinearLayout {
             orientation: orientation,
             focus: 0,
             cache: None,
+            on_capture: None,
         }
     }
 
+    /// Sets a callback to see events before the focused child.
+    ///
+    /// See [capturing events](../event/index.html#capturing-events).
+    pub fn set_on_capture<F>(&mut self, cb: F)
+        where F: Fn(&mut Event) -> EventResult + 'static
+    {
+        self.on_capture = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to see events before the focused child.
+    ///
+    /// Chainable variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::event::{Event, EventResult};
+    /// # use cursive::views::{EditView, LinearLayout};
+    /// // `<Ctrl+s>` saves, even when an `EditView` is focused.
+    /// let layout = LinearLayout::vertical()
+    ///     .child(EditView::new())
+    ///     .on_capture(|event| match *event {
+    ///         Event::CtrlChar('s') => EventResult::with_cb(|s| {
+    ///             // Save something
+    ///         }),
+    ///         _ => EventResult::Ignored,
+    ///     });
+    /// ```
+    pub fn on_capture<F>(self, cb: F) -> Self
+        where F: Fn(&mut Event) -> EventResult + 'static
+    {
+        self.with(|s| s.set_on_capture(cb))
+    }
+
     /// Modifies the weight of the last child added.
     ///
     /// It is an error to call this before adding a child (and it will panic).
@@ -352,7 +391,18 @@ impl View for LinearLayout {
         true
     }
 
-    fn on_event(&mut self, event: Event) -> EventResult {
+    fn describe(&self) -> Option<String> {
+        self.children.get(self.focus)?.view.describe()
+    }
+
+    fn on_event(&mut self, mut event: Event) -> EventResult {
+        if let Some(cb) = self.on_capture.clone() {
+            let result = cb(&mut event);
+            if result.is_consumed() {
+                return result;
+            }
+        }
+
         match self.children[self.focus].view.on_event(event.clone()) {
             EventResult::Ignored => {
                 match event {
@@ -391,7 +441,7 @@ impl View for LinearLayout {
                     _ => EventResult::Ignored,
                 }
             }
-            res => res,
+            res => res.resolve_on(self),
         }
     }
 
@@ -9,7 +9,7 @@ use std::rc::Rc;
 
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
-use view::ScrollBase;
+use view::{ScrollBase, Scrollable, ScrollbarVisibility};
 use view::Selector;
 use view::View;
 
@@ -41,6 +41,11 @@ pub struct ListView {
     focus: usize,
     // This callback is called when the selection is changed.
     on_select: Option<Rc<Fn(&mut Cursive, &String)>>,
+    reorderable: bool,
+    // Called with the old and new positions of a moved child.
+    on_reorder: Option<Rc<Fn(&mut Cursive, usize, usize)>>,
+    // Sees events before the focused child.
+    on_capture: Option<Rc<Fn(&mut Event) -> EventResult>>,
 }
 
 new_default!(ListView);
@@ -53,6 +58,9 @@ impl ListView {
             scrollbase: ScrollBase::new(),
             focus: 0,
             on_select: None,
+            reorderable: false,
+            on_reorder: None,
+            on_capture: None,
         }
     }
 
@@ -103,6 +111,107 @@ impl ListView {
         self.with(|s| s.set_on_select(cb))
     }
 
+    /// Returns the current scroll position.
+    pub fn scroll_offset(&self) -> Vec2 {
+        self.scrollbase.scroll_offset()
+    }
+
+    /// Sets the scroll position.
+    ///
+    /// Only `offset.y` is used, as this view only scrolls vertically.
+    pub fn set_scroll_offset<O: Into<Vec2>>(&mut self, offset: O) {
+        self.scrollbase.set_scroll_offset(offset.into());
+    }
+
+    /// Scrolls to the last child.
+    ///
+    /// The focus doesn't move: moving it later scrolls back to it.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scrollbase.scroll_bottom();
+    }
+
+    /// Sets when to show the scrollbar.
+    ///
+    /// Defaults to `ScrollbarVisibility::Auto`.
+    pub fn set_scrollbar_visibility(&mut self,
+                                    visibility: ScrollbarVisibility) {
+        self.scrollbase.visibility = visibility;
+    }
+
+    /// Sets when to show the scrollbar.
+    ///
+    /// Chainable variant.
+    pub fn scrollbar_visibility(self, visibility: ScrollbarVisibility)
+                                -> Self {
+        self.with(|s| s.set_scrollbar_visibility(visibility))
+    }
+
+    /// Allows the user to move the focused child with `<Ctrl+Up>` and
+    /// `<Ctrl+Down>`.
+    pub fn set_reorderable(&mut self, reorderable: bool) {
+        self.reorderable = reorderable;
+    }
+
+    /// Allows the user to move the focused child with `<Ctrl+Up>` and
+    /// `<Ctrl+Down>`.
+    ///
+    /// Chainable variant.
+    pub fn reorderable(self) -> Self {
+        self.with(|s| s.set_reorderable(true))
+    }
+
+    /// Sets a callback to be used when the user moves a child.
+    ///
+    /// It will be given the old and the new position of the child.
+    pub fn set_on_reorder<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, usize, usize) + 'static
+    {
+        self.on_reorder = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when the user moves a child.
+    ///
+    /// Chainable variant.
+    pub fn on_reorder<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive, usize, usize) + 'static
+    {
+        self.with(|s| s.set_on_reorder(cb))
+    }
+
+    /// Sets a callback to see events before the focused child.
+    ///
+    /// See [capturing events](../event/index.html#capturing-events).
+    pub fn set_on_capture<F>(&mut self, cb: F)
+        where F: Fn(&mut Event) -> EventResult + 'static
+    {
+        self.on_capture = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to see events before the focused child.
+    ///
+    /// Chainable variant.
+    pub fn on_capture<F>(self, cb: F) -> Self
+        where F: Fn(&mut Event) -> EventResult + 'static
+    {
+        self.with(|s| s.set_on_capture(cb))
+    }
+
+    /// Moves a child (or a delimiter) from position `old` to `new`.
+    ///
+    /// The focus follows the child it was on.
+    pub fn move_child(&mut self, old: usize, new: usize) {
+        let child = self.children.remove(old);
+        self.children.insert(new, child);
+
+        if self.focus == old {
+            self.focus = new;
+        } else if old < self.focus && self.focus <= new {
+            self.focus -= 1;
+        } else if new <= self.focus && self.focus < old {
+            self.focus += 1;
+        }
+    }
+
     /// Returns the index of the currently focused item.
     ///
     /// Panics if the list is empty.
@@ -160,6 +269,18 @@ impl ListView {
             Callback::from_fn(move |s| cb(s, &focused_string))
         }))
     }
+
+    // Moves the focused child to `new`, as asked by the user.
+    fn reorder(&mut self, new: usize) -> EventResult {
+        let old = self.focus;
+        self.move_child(old, new);
+        self.scrollbase.scroll_to(new);
+
+        match self.on_reorder.clone() {
+            Some(cb) => EventResult::with_cb(move |s| cb(s, old, new)),
+            None => EventResult::Consumed(None),
+        }
+    }
 }
 
 fn try_focus((i, child): (usize, &mut Child), source: direction::Direction)
@@ -177,6 +298,16 @@ fn try_focus((i, child): (usize, &mut Child), source: direction::Direction)
     }
 }
 
+impl Scrollable for ListView {
+    fn scroll_offset(&self) -> Vec2 {
+        ListView::scroll_offset(self)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Vec2) {
+        ListView::set_scroll_offset(self, offset);
+    }
+}
+
 impl View for ListView {
     fn draw(&self, printer: &Printer) {
         if self.children.is_empty() {
@@ -216,10 +347,12 @@ impl View for ListView {
             .max()
             .unwrap_or(0);
 
-        if self.children.len() > req.y {
-            Vec2::new(label_width + 1 + view_size + 2, req.y)
+        let scrolling = self.children.len() > req.y;
+        let h = if scrolling { req.y } else { self.children.len() };
+        if self.scrollbase.visibility.shown(scrolling) {
+            Vec2::new(label_width + 1 + view_size + 2, h)
         } else {
-            Vec2::new(label_width + 1 + view_size, self.children.len())
+            Vec2::new(label_width + 1 + view_size, h)
         }
     }
 
@@ -235,7 +368,11 @@ impl View for ListView {
             .unwrap_or(0);
 
         let spacing = 1;
-        let scrollbar_width = if self.children.len() > size.y { 2 } else { 0 };
+        let scrollbar_width = if self.scrollbase.shows_scrollbar() {
+            2
+        } else {
+            0
+        };
 
         let available = if label_width + spacing + scrollbar_width > size.x {
             // We have no space for the kids! :(
@@ -251,19 +388,36 @@ impl View for ListView {
         }
     }
 
-    fn on_event(&mut self, event: Event) -> EventResult {
+    fn on_event(&mut self, mut event: Event) -> EventResult {
         if self.children.is_empty() {
             return EventResult::Ignored;
         }
 
+        if let Some(cb) = self.on_capture.clone() {
+            let result = cb(&mut event);
+            if result.is_consumed() {
+                return result;
+            }
+        }
+
         if let Child::Row(_, ref mut view) = self.children[self.focus] {
             let result = view.on_event(event.clone());
             if result.is_consumed() {
-                return result;
+                return result.resolve_on(self);
             }
         }
 
         match event {
+            Event::Ctrl(Key::Up) if self.reorderable && self.focus > 0 => {
+                let focus = self.focus;
+                self.reorder(focus - 1)
+            }
+            Event::Ctrl(Key::Down) if self.reorderable &&
+                                      self.focus + 1 <
+                                      self.children.len() => {
+                let focus = self.focus;
+                self.reorder(focus + 1)
+            }
             Event::Key(Key::Up) if self.focus > 0 => {
                 self.move_focus(1, direction::Direction::down())
             }
@@ -313,6 +467,20 @@ impl View for ListView {
         true
     }
 
+    fn describe(&self) -> Option<String> {
+        match *self.children.get(self.focus)? {
+            Child::Row(ref label, ref view) => {
+                match view.describe() {
+                    Some(description) if !label.is_empty() => {
+                        Some(format!("{}: {}", label, description))
+                    }
+                    description => description,
+                }
+            }
+            Child::Delimiter => None,
+        }
+    }
+
     fn call_on_any<'a>(&mut self, selector: &Selector,
                     mut callback: Box<FnMut(&mut Any) + 'a>) {
         for view in self.children
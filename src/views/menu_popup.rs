@@ -10,6 +10,7 @@ use std::cmp::min;
 use std::rc::Rc;
 
 use unicode_width::UnicodeWidthStr;
+use utils::i18n;
 use vec::Vec2;
 use view::{Position, ScrollBase, View};
 use views::KeyEventView;
@@ -275,6 +276,18 @@ impl View for MenuPopup {
 
     fn layout(&mut self, size: Vec2) {
         self.scrollbase
-            .set_heights(size.y - 2, self.menu.children.len());
+            .set_heights(size.y.saturating_sub(2), self.menu.children.len());
+        // Make sure the focused item is visible.
+        self.scrollbase.scroll_to(self.focus);
+    }
+
+    fn describe(&self) -> Option<String> {
+        match *self.menu.children.get(self.focus)? {
+            MenuItem::Leaf(ref label, _) => Some(label.clone()),
+            MenuItem::Subtree(ref label, _) => {
+                Some(format!("{} {}", label, i18n::translate("submenu")))
+            }
+            MenuItem::Delimiter => None,
+        }
     }
 }
@@ -8,6 +8,7 @@ use std::rc::Rc;
 
 use theme::ColorStyle;
 use unicode_width::UnicodeWidthStr;
+use utils::i18n;
 use vec::Vec2;
 use view::{Position, View};
 use views::{KeyEventView, MenuPopup};
@@ -113,6 +114,13 @@ impl Menubar {
             .map(|&mut (_, ref mut tree)| Rc::make_mut(tree))
     }
 
+    /// Returns the label and menu at the given position.
+    ///
+    /// Returns `None` if `i >= self.len()`.
+    pub fn get(&self, i: usize) -> Option<(&str, &MenuTree)> {
+        self.menus.get(i).map(|&(ref label, ref tree)| (&label[..], &**tree))
+    }
+
     /// Looks for an item with the given label.
     pub fn find_subtree(&mut self, label: &str) -> Option<&mut MenuTree> {
         // Look for the menu with the correct label,
@@ -194,7 +202,7 @@ impl View for Menubar {
             let selected = (self.state != State::Inactive) &&
                            (i == self.focus);
             printer.with_selection(selected, |printer| {
-                printer.print((offset, 0), &format!(" {} ", title));
+                printer.print_fmt((offset, 0), format_args!(" {} ", title));
                 offset += title.width() + 2;
             });
         }
@@ -247,6 +255,14 @@ impl View for Menubar {
         true
     }
 
+    fn describe(&self) -> Option<String> {
+        self.menus
+            .get(self.focus)
+            .map(|&(ref label, _)| {
+                     format!("{} {}", label, i18n::translate("menu"))
+                 })
+    }
+
     fn required_size(&mut self, _: Vec2) -> Vec2 {
         // TODO: scroll the options if the screen is too small?
 
@@ -35,54 +35,130 @@ macro_rules! impl_enabled {
     }
 }
 
+mod absolute_layout;
+mod aligned_view;
+mod banner_view;
 mod box_view;
+mod breadcrumb_view;
 mod button;
+mod cached_view;
 mod canvas;
+mod chat_view;
 mod checkbox;
+#[cfg(feature = "clock")]
+mod clock_view;
+#[cfg(feature = "syntect")]
+mod code_view;
+mod command_palette;
+mod dashboard_layout;
 mod dialog;
+mod diff_view;
 mod dummy;
+mod edit_mode;
 mod edit_view;
+mod filtered_view;
+mod flow_layout;
+#[cfg(feature = "form")]
+mod form_view;
+mod gauge_view;
+mod heatmap_view;
+mod help_view;
+mod hex_view;
 mod id_view;
+#[cfg(feature = "image")]
+mod image_view;
 mod key_event_view;
 mod layer;
+mod lifecycle_view;
 mod linear_layout;
 mod list_view;
 mod menubar;
 mod menu_popup;
+mod padded_view;
+mod pager_view;
 mod panel;
 mod progress_bar;
+#[cfg(feature = "qrcode")]
+mod qr_code_view;
 mod radio;
+mod responsive_view;
+mod scroll_sync;
 mod select_view;
 mod slider_view;
 mod shadow_view;
 mod sized_view;
 mod stack_view;
+mod table_view;
+mod task_list_view;
 mod text_area;
+#[cfg(feature = "terminal-view")]
+mod terminal_view;
 mod text_view;
+mod themed_view;
+mod timer_view;
 mod tracked_view;
+mod window_view;
 
+pub use self::absolute_layout::AbsoluteLayout;
+pub use self::aligned_view::AlignedView;
+pub use self::banner_view::{BannerFont, BannerView, FontError};
 pub use self::box_view::BoxView;
+pub use self::breadcrumb_view::BreadcrumbView;
 pub use self::button::Button;
+pub use self::cached_view::CachedView;
 pub use self::canvas::Canvas;
+pub use self::chat_view::{ChatView, Message};
 pub use self::checkbox::Checkbox;
+#[cfg(feature = "clock")]
+pub use self::clock_view::ClockView;
+#[cfg(feature = "syntect")]
+pub use self::code_view::CodeView;
+pub use self::command_palette::CommandPalette;
+pub use self::dashboard_layout::DashboardLayout;
 pub use self::dialog::Dialog;
+pub use self::diff_view::{DiffMode, DiffView};
 pub use self::dummy::DummyView;
+pub use self::edit_mode::EditMode;
 pub use self::edit_view::EditView;
-pub use self::id_view::{IdView, ViewRef};
+pub use self::filtered_view::FilteredView;
+pub use self::flow_layout::FlowLayout;
+#[cfg(feature = "form")]
+pub use self::form_view::{Form, FormView};
+pub use self::gauge_view::{GaugeStyle, GaugeView};
+pub use self::heatmap_view::{HeatmapPalette, HeatmapView};
+pub use self::help_view::{HelpEntry, HelpView};
+pub use self::hex_view::{FileData, HexData, HexView};
+pub use self::id_view::{IdView, ViewHandle, ViewRef};
+#[cfg(feature = "image")]
+pub use self::image_view::{ImageFit, ImageProtocol, ImageView};
 pub use self::key_event_view::KeyEventView;
 pub use self::layer::Layer;
+pub use self::lifecycle_view::LifecycleView;
 pub use self::linear_layout::LinearLayout;
 pub use self::list_view::ListView;
 pub use self::menu_popup::MenuPopup;
 pub use self::menubar::Menubar;
+pub use self::padded_view::PaddedView;
+pub use self::pager_view::PagerView;
 pub use self::panel::Panel;
-pub use self::progress_bar::{Counter, ProgressBar};
+pub use self::progress_bar::{Counter, Progress, ProgressBar};
+#[cfg(feature = "qrcode")]
+pub use self::qr_code_view::QrCodeView;
 pub use self::radio::{RadioGroup, RadioButton};
+pub use self::responsive_view::ResponsiveView;
+pub use self::scroll_sync::ScrollSync;
 pub use self::select_view::SelectView;
 pub use self::shadow_view::ShadowView;
 pub use self::sized_view::SizedView;
 pub use self::slider_view::SliderView;
 pub use self::stack_view::StackView;
+pub use self::table_view::TableView;
+pub use self::task_list_view::{Completed, TaskHandle, TaskListView};
 pub use self::text_area::TextArea;
-pub use self::text_view::TextView;
+#[cfg(feature = "terminal-view")]
+pub use self::terminal_view::TerminalView;
+pub use self::text_view::{TextContent, TextContentRef, TextView};
+pub use self::themed_view::ThemedView;
+pub use self::timer_view::{CountdownView, StopwatchView};
 pub use self::tracked_view::TrackedView;
+pub use self::window_view::{WindowManager, WindowView};
@@ -0,0 +1,75 @@
+use Printer;
+use vec::{Vec2, Vec4};
+use view::{View, ViewWrapper};
+
+/// Adds padding to another view.
+///
+/// The margins are empty space around the child: they are part of this
+/// view's size, but the child never draws there.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{PaddedView, TextView};
+/// // Left and right margins of 2, top and bottom margins of 1.
+/// let view = PaddedView::new((2, 2, 1, 1), TextView::new("Padded text"));
+/// ```
+pub struct PaddedView<V: View> {
+    view: V,
+    margins: Vec4,
+}
+
+impl<V: View> PaddedView<V> {
+    /// Wraps `view` in a new `PaddedView` with the given margins.
+    ///
+    /// Margins are given as `(left, right, top, bottom)`.
+    pub fn new<M: Into<Vec4>>(margins: M, view: V) -> Self {
+        PaddedView {
+            view: view,
+            margins: margins.into(),
+        }
+    }
+
+    /// Sets the margins for this view.
+    pub fn set_margins<M: Into<Vec4>>(&mut self, margins: M) {
+        self.margins = margins.into();
+    }
+}
+
+impl<V: View> ViewWrapper for PaddedView<V> {
+    wrap_impl!(self.view: V);
+
+    fn wrap_required_size(&mut self, req: Vec2) -> Vec2 {
+        let margins = self.margins.combined();
+        self.view.required_size(req - margins.or_min(req)) + margins
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        let margins = self.margins.combined().or_min(size);
+        self.view.layout(size - margins);
+    }
+
+    fn wrap_draw(&self, printer: &Printer) {
+        let top_left = self.margins.top_left();
+        let bot_right = self.margins.bot_right();
+        let size = printer.size - (top_left + bot_right).or_min(printer.size);
+        self.view.draw(&printer.sub_printer(top_left, size, true));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PaddedView;
+    use vec::Vec2;
+    use view::View;
+    use views::TextView;
+
+    #[test]
+    fn margins() {
+        let mut view = PaddedView::new((2, 1, 1, 0), TextView::new("abc"));
+
+        assert_eq!(Vec2::new(6, 2), view.required_size(Vec2::new(10, 10)));
+        // Not enough room: the margins are kept, the content shrinks.
+        assert_eq!(Vec2::new(4, 4), view.required_size(Vec2::new(4, 10)));
+    }
+}
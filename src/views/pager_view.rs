@@ -0,0 +1,384 @@
+use Printer;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::cmp::{max, min};
+use std::io::{BufRead, BufReader, Read};
+use theme::{ColorStyle, Effect};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use utils::i18n;
+use vec::Vec2;
+use view::{ScrollBase, Scrollable, View};
+
+// Number of lines to read at once.
+const CHUNK: usize = 256;
+
+/// Read-only view for large texts, similar to `less`.
+///
+/// Lines are read from the source only when needed.
+///
+/// * Arrows, `<PageUp>`, `<PageDown>`, `<Home>` and `<End>` scroll the text.
+/// * `/` starts a search; `n` and `N` go to the next and previous matches.
+///
+/// The last row shows the position in the text, or the search prompt.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::views::PagerView;
+/// # use std::fs::File;
+/// let file = File::open("/var/log/syslog").unwrap();
+/// let pager = PagerView::new(file);
+/// ```
+pub struct PagerView {
+    source: Option<Box<BufRead>>,
+    lines: Vec<String>,
+
+    // First visible line.
+    top: usize,
+    // Line we're smoothly scrolling to.
+    target: Option<usize>,
+    // Number of cells hidden on the left.
+    left: usize,
+
+    // Pattern from the last search.
+    pattern: Option<String>,
+    // Search being entered, if any.
+    input: Option<String>,
+    // Message to show in the status line.
+    message: Option<String>,
+
+    last_size: Vec2,
+}
+
+impl PagerView {
+    /// Creates a new pager reading from the given source.
+    pub fn new<R: Read + 'static>(source: R) -> Self {
+        PagerView {
+            source: Some(Box::new(BufReader::new(source))),
+            lines: Vec::new(),
+            top: 0,
+            target: None,
+            left: 0,
+            pattern: None,
+            input: None,
+            message: None,
+            last_size: Vec2::zero(),
+        }
+    }
+
+    /// Returns the number of lines read so far.
+    ///
+    /// Also returns `true` if the entire source was read.
+    pub fn lines_read(&self) -> (usize, bool) {
+        (self.lines.len(), self.source.is_none())
+    }
+
+    /// Returns the current scroll position.
+    pub fn scroll_offset(&self) -> Vec2 {
+        Vec2::new(0, self.top)
+    }
+
+    /// Sets the scroll position, reading the source as needed.
+    ///
+    /// Only `offset.y` is used, as this view only scrolls vertically.
+    pub fn set_scroll_offset<O: Into<Vec2>>(&mut self, offset: O) {
+        let top = self.clamp_top(offset.into().y);
+        self.top = top;
+        self.target = None;
+    }
+
+    /// Reads the entire source, and scrolls to its end.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_bottom();
+    }
+
+    // Reads from the source until we have at least `n` lines.
+    fn load_until(&mut self, n: usize) {
+        while self.lines.len() < n {
+            let mut buffer = Vec::new();
+            let result = match self.source {
+                Some(ref mut source) => {
+                    source.read_until(b'\n', &mut buffer)
+                }
+                None => return,
+            };
+
+            match result {
+                Ok(0) => self.source = None,
+                Ok(_) => {
+                    if buffer.ends_with(b"\n") {
+                        buffer.pop();
+                    }
+                    if buffer.ends_with(b"\r") {
+                        buffer.pop();
+                    }
+                    let line = String::from_utf8_lossy(&buffer)
+                        .replace('\t', "    ");
+                    self.lines.push(line);
+                }
+                Err(e) => {
+                    let error = i18n::translate("Error");
+                    self.message = Some(format!("{}: {}", error, e));
+                    self.source = None;
+                }
+            }
+        }
+    }
+
+    // Number of rows available for text.
+    fn text_height(&self) -> usize {
+        self.last_size.y.saturating_sub(1)
+    }
+
+    // Loads enough lines, and returns `top` if the view can scroll there,
+    // or the last valid position.
+    fn clamp_top(&mut self, top: usize) -> usize {
+        let height = self.text_height();
+        self.load_until(top + height + CHUNK);
+        let max_top = self.lines.len().saturating_sub(height);
+        min(top, max_top)
+    }
+
+    fn scroll_down(&mut self, n: usize) {
+        let start = self.target.unwrap_or(self.top);
+        let top = max(start, self.clamp_top(start + n));
+        self.scroll_smoothly(top);
+    }
+
+    fn scroll_up(&mut self, n: usize) {
+        let start = self.target.unwrap_or(self.top);
+        self.scroll_smoothly(start - min(start, n));
+    }
+
+    fn scroll_bottom(&mut self) {
+        self.load_until(usize::max_value());
+        let height = self.text_height();
+        self.top = self.lines.len().saturating_sub(height);
+        self.target = None;
+    }
+
+    // Scrolls to the given line, smoothly if enabled.
+    fn scroll_smoothly(&mut self, top: usize) {
+        let distance = max(top, self.top) - min(top, self.top);
+        if distance > 1 && ScrollBase::smooth_scrolling() {
+            self.target = Some(top);
+            self.animate();
+        } else {
+            self.top = top;
+            self.target = None;
+        }
+    }
+
+    // Moves one frame further in a smooth scrolling animation.
+    fn animate(&mut self) {
+        if let Some(target) = self.target {
+            // Cover a third of the remaining distance, at least one line.
+            if target > self.top {
+                self.top += max(1, (target - self.top) / 3);
+            } else {
+                self.top -= max(1, (self.top - target) / 3);
+            }
+
+            if self.top == target {
+                self.target = None;
+            }
+        }
+    }
+
+    // Moves to the next (or previous) line matching the last search.
+    fn find(&mut self, forward: bool) {
+        let pattern = match self.pattern {
+            Some(ref pattern) => pattern.clone(),
+            None => return,
+        };
+
+        let found = if forward {
+            let mut i = self.top + 1;
+            loop {
+                self.load_until(i + CHUNK);
+                if i >= self.lines.len() {
+                    break None;
+                }
+                if self.lines[i].contains(&pattern) {
+                    break Some(i);
+                }
+                i += 1;
+            }
+        } else {
+            (0..self.top).rev().find(|&i| self.lines[i].contains(&pattern))
+        };
+
+        match found {
+            Some(i) => {
+                self.top = i;
+                self.target = None;
+            }
+            None => {
+                self.message = Some(i18n::translate("Pattern not found"))
+            }
+        }
+    }
+
+    fn status(&self) -> String {
+        if let Some(ref input) = self.input {
+            return format!("/{}", input);
+        }
+        if let Some(ref message) = self.message {
+            return message.clone();
+        }
+
+        let bottom = min(self.top + self.text_height(), self.lines.len());
+        if self.source.is_none() {
+            let percent = if self.lines.is_empty() {
+                100
+            } else {
+                bottom * 100 / self.lines.len()
+            };
+            format!("lines {}-{}/{} {}%",
+                    self.top + 1,
+                    bottom,
+                    self.lines.len(),
+                    percent)
+        } else {
+            format!("lines {}-{}/{}+", self.top + 1, bottom, self.lines.len())
+        }
+    }
+
+    fn draw_line(&self, printer: &Printer, line: &str) {
+        let matches: Vec<(usize, usize)> = match self.pattern {
+            Some(ref pattern) if !pattern.is_empty() => {
+                line.match_indices(pattern.as_str())
+                    .map(|(i, m)| (i, i + m.len()))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+
+        let mut x = 0;
+        for (i, g) in line.grapheme_indices(true) {
+            let width = g.width();
+            if x >= self.left {
+                let pos = (x - self.left, 0);
+                if matches.iter().any(|&(start, end)| start <= i && i < end) {
+                    printer.with_color(ColorStyle::Highlight,
+                                       |printer| printer.print(pos, g));
+                } else {
+                    printer.print(pos, g);
+                }
+            }
+            x += width;
+            if x >= self.left + printer.size.x {
+                break;
+            }
+        }
+    }
+
+    fn on_input_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Char(c) => {
+                if let Some(ref mut input) = self.input {
+                    input.push(c);
+                }
+            }
+            Event::Key(Key::Backspace) => {
+                if let Some(ref mut input) = self.input {
+                    input.pop();
+                }
+            }
+            Event::Key(Key::Esc) => self.input = None,
+            Event::Key(Key::Enter) => {
+                let input = self.input.take().unwrap_or_else(String::new);
+                // An empty search repeats the last one.
+                if !input.is_empty() {
+                    self.pattern = Some(input);
+                }
+                self.find(true);
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
+}
+
+impl Scrollable for PagerView {
+    fn scroll_offset(&self) -> Vec2 {
+        PagerView::scroll_offset(self)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Vec2) {
+        PagerView::set_scroll_offset(self, offset);
+    }
+}
+
+impl View for PagerView {
+    fn draw(&self, printer: &Printer) {
+        let height = self.text_height();
+        let lines = self.lines.iter().skip(self.top).take(height);
+        for (y, line) in lines.enumerate() {
+            let printer =
+                printer.sub_printer((0, y), (printer.size.x, 1), true);
+            self.draw_line(&printer, line);
+        }
+
+        if printer.size.y > 0 {
+            let y = printer.size.y - 1;
+            printer.with_effect(Effect::Reverse, |printer| {
+                printer.print((0, y), &self.status());
+            });
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        // Take all the space we can get.
+        req
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.load_until(self.top + size.y + CHUNK);
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if event == Event::Refresh {
+            // Smooth scrolling moves a bit further on each tick.
+            self.animate();
+            return EventResult::Ignored;
+        }
+
+        if self.input.is_some() {
+            return self.on_input_event(event);
+        }
+
+        self.message = None;
+        let height = self.text_height();
+        let half_width = max(1, self.last_size.x / 2);
+
+        match event {
+            Event::Key(Key::Down) |
+            Event::Char('j') => self.scroll_down(1),
+            Event::Key(Key::Up) |
+            Event::Char('k') => self.scroll_up(1),
+            Event::Key(Key::PageDown) |
+            Event::Char(' ') => self.scroll_down(max(1, height)),
+            Event::Key(Key::PageUp) |
+            Event::Char('b') => self.scroll_up(max(1, height)),
+            Event::Key(Key::Home) |
+            Event::Char('g') => self.set_scroll_offset((0, 0)),
+            Event::Key(Key::End) |
+            Event::Char('G') => self.scroll_bottom(),
+            Event::Key(Key::Right) => self.left += half_width,
+            Event::Key(Key::Left) => self.left -= min(self.left, half_width),
+            Event::Char('/') => self.input = Some(String::new()),
+            Event::Char('n') => self.find(true),
+            Event::Char('N') => self.find(false),
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
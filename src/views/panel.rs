@@ -1,16 +1,41 @@
 use Printer;
+use With;
+use theme::Frame;
 use vec::Vec2;
 use view::{View, ViewWrapper};
 
 /// Draws a border around a wrapped view.
+///
+/// The border uses the theme's glyphs, unless the panel is given a
+/// [`Frame`].
+///
+/// [`Frame`]: ../theme/struct.Frame.html
 pub struct Panel<V: View> {
     view: V,
+    frame: Option<Frame>,
 }
 
 impl<V: View> Panel<V> {
     /// Creates a new panel around the given view.
     pub fn new(view: V) -> Self {
-        Panel { view: view }
+        Panel {
+            view: view,
+            frame: None,
+        }
+    }
+
+    /// Sets the frame drawn around the view.
+    ///
+    /// `None` uses the theme's glyphs.
+    pub fn set_frame(&mut self, frame: Option<Frame>) {
+        self.frame = frame;
+    }
+
+    /// Sets the frame drawn around the view.
+    ///
+    /// Chainable variant.
+    pub fn frame(self, frame: Frame) -> Self {
+        self.with(|s| s.set_frame(Some(frame)))
     }
 }
 
@@ -30,7 +55,12 @@ impl<V: View> ViewWrapper for Panel<V> {
     }
 
     fn wrap_draw(&self, printer: &Printer) {
-        printer.print_box((0, 0), printer.size, true);
+        match self.frame {
+            Some(ref frame) => {
+                printer.print_frame((0, 0), printer.size, frame, true)
+            }
+            None => printer.print_box((0, 0), printer.size, true),
+        }
         self.view.draw(&printer.sub_printer((1, 1),
                                             printer.size - (2, 2),
                                             true));
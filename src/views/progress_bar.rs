@@ -1,13 +1,18 @@
 
 
 use Printer;
+use With;
 use align::HAlign;
+use std::cell::Cell;
 use std::cmp;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use std::thread;
+use std::time::{Duration, Instant};
 use theme::{ColorStyle, Effect};
+#[cfg(feature = "format")]
+use utils::format;
 use view::View;
 
 // pub type CbPromise = Option<Box<Fn(&mut Cursive) + Send>>;
@@ -38,6 +43,55 @@ impl Counter {
     }
 }
 
+/// State of a `ProgressBar`, given to its label maker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// Current value of the main counter.
+    pub value: usize,
+    /// Minimum value.
+    pub min: usize,
+    /// Maximum value.
+    pub max: usize,
+    /// Values of the segments stacked after the main one.
+    pub segments: Vec<usize>,
+    /// Time since the bar was first drawn (or since the last task started).
+    pub elapsed: Duration,
+    // Value when the time started.
+    start_value: usize,
+}
+
+impl Progress {
+    /// Returns the progression of the main counter, from 0 to 100.
+    pub fn percent(&self) -> usize {
+        if self.value < self.min {
+            0
+        } else {
+            cmp::min(100, 101 * (self.value - self.min) /
+                          (1 + self.max - self.min))
+        }
+    }
+
+    /// Estimates the time left until the main counter reaches `max`.
+    ///
+    /// Returns `None` if there was no progress yet.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.value <= self.start_value {
+            return None;
+        }
+
+        let left = self.max.saturating_sub(self.value) as f64;
+        let rate = (self.value - self.start_value) as f64 /
+                   seconds(self.elapsed);
+        let left = left / rate;
+        Some(Duration::new(left as u64,
+                           (left.fract() * 1_000_000_000.0) as u32))
+    }
+}
+
+fn seconds(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) * 1e-9
+}
+
 /// Animated bar showing a progress value.
 ///
 /// This bar has an internal counter, and adapts the length of the displayed
@@ -47,10 +101,14 @@ impl Counter {
 /// It also prints a customizable text in the center of the bar, which
 /// defaults to the progression percentage.
 ///
+/// More segments can be stacked after the main one, each with its own
+/// counter and color. This shows a breakdown, like used and cached memory.
+///
 /// # Example
 ///
 /// ```
-/// # use cursive::views::ProgressBar;
+/// # use cursive::theme::ColorStyle;
+/// # use cursive::views::{Counter, ProgressBar};
 /// let bar = ProgressBar::new()
 ///                       .with_task(|counter| {
 ///                           // This closure is called in parallel.
@@ -60,16 +118,28 @@ impl Counter {
 ///                               counter.tick(1);
 ///                           }
 ///                       });
+///
+/// let memory = ProgressBar::new()
+///     .max(1024)
+///     .with_value(Counter::new(300))
+///     .segment(Counter::new(200), ColorStyle::Secondary)
+///     .with_progress_label(|p| {
+///         format!("{} used, {} cached", p.value, p.segments[0])
+///     });
 /// ```
 pub struct ProgressBar {
     min: usize,
     max: usize,
     value: Counter,
+    segments: Vec<(Counter, ColorStyle)>,
+    // When the time started, and the value at that time.
+    start: Cell<Option<(Instant, usize)>>,
     // TODO: use a Promise instead?
-    label_maker: Box<Fn(usize, (usize, usize)) -> String>,
+    label_maker: Box<Fn(&Progress) -> String>,
 }
 
 
+#[cfg(not(feature = "format"))]
 fn make_percentage(value: usize, (min, max): (usize, usize)) -> String {
     if value < min {
         // ?? Negative progress?
@@ -81,6 +151,17 @@ fn make_percentage(value: usize, (min, max): (usize, usize)) -> String {
     }
 }
 
+// Follows the current locale.
+#[cfg(feature = "format")]
+fn make_percentage(value: usize, (min, max): (usize, usize)) -> String {
+    let percent = if value < min {
+        -((101 * (min - value) / (1 + max - min)) as f64)
+    } else {
+        (101 * (value - min) / (1 + max - min)) as f64
+    };
+    format::percentage(percent / 100.0, 0)
+}
+
 new_default!(ProgressBar);
 
 impl ProgressBar {
@@ -96,7 +177,10 @@ impl ProgressBar {
             min: 0,
             max: 100,
             value: Counter::new(0),
-            label_maker: Box::new(make_percentage),
+            segments: Vec::new(),
+            start: Cell::new(None),
+            label_maker: Box::new(|p| make_percentage(p.value,
+                                                       (p.min, p.max))),
         }
     }
 
@@ -106,9 +190,24 @@ impl ProgressBar {
     /// by directly modifying the value pointed to by `value`.
     pub fn with_value(mut self, value: Counter) -> Self {
         self.value = value;
+        self.start.set(None);
         self
     }
 
+    /// Stacks a segment after the existing ones.
+    ///
+    /// It shows the value of `counter`, drawn with `color`.
+    pub fn add_segment(&mut self, counter: Counter, color: ColorStyle) {
+        self.segments.push((counter, color));
+    }
+
+    /// Stacks a segment after the existing ones.
+    ///
+    /// Chainable variant.
+    pub fn segment(self, counter: Counter, color: ColorStyle) -> Self {
+        self.with(|s| s.add_segment(counter, color))
+    }
+
     /// Starts a function in a separate thread, and monitor the progress.
     ///
     /// `f` will be given a `Counter` to increment the bar's progress.
@@ -117,6 +216,7 @@ impl ProgressBar {
     /// to advance the progress in multiple sessions.
     pub fn start<F: FnOnce(Counter) + Send + 'static>(&mut self, f: F) {
         let counter: Counter = self.value.clone();
+        self.start.set(None);
 
         thread::spawn(move || { f(counter); });
     }
@@ -135,7 +235,8 @@ impl ProgressBar {
     /// The given function will be called with `(value, (min, max))`.
     /// Its output will be used as the label to print inside the progress bar.
     ///
-    /// The default one shows a percentage progress:
+    /// The default one shows a percentage progress (formatted with the
+    /// current locale when the `format` feature is enabled):
     ///
     /// ```
     /// fn make_percentage(value: usize, (min, max): (usize, usize)) -> String {
@@ -144,12 +245,59 @@ impl ProgressBar {
     /// }
     /// ```
     pub fn with_label<F: Fn(usize, (usize, usize)) -> String + 'static>
-        (mut self, label_maker: F)
+        (self, label_maker: F)
          -> Self {
+        self.with_progress_label(move |p| label_maker(p.value, (p.min, p.max)))
+    }
+
+    /// Sets the label generator, given the full state of the bar.
+    ///
+    /// This gives access to the segments and the estimated time left:
+    ///
+    /// ```
+    /// # use cursive::views::ProgressBar;
+    /// let bar = ProgressBar::new().with_progress_label(|p| match p.eta() {
+    ///     Some(eta) => format!("{}% ({}s left)", p.percent(), eta.as_secs()),
+    ///     None => format!("{}%", p.percent()),
+    /// });
+    /// ```
+    pub fn with_progress_label<F>(mut self, label_maker: F) -> Self
+        where F: Fn(&Progress) -> String + 'static
+    {
         self.label_maker = Box::new(label_maker);
         self
     }
 
+    /// Returns the current state of the bar.
+    pub fn progress(&self) -> Progress {
+        let value = self.value.get();
+        let (elapsed, start_value) = match self.start.get() {
+            Some((instant, start_value)) => (instant.elapsed(), start_value),
+            None => (Duration::new(0, 0), value),
+        };
+
+        Progress {
+            value: value,
+            min: self.min,
+            max: self.max,
+            segments: self.segments
+                .iter()
+                .map(|&(ref counter, _)| counter.get())
+                .collect(),
+            elapsed: elapsed,
+            start_value: start_value,
+        }
+    }
+
+    // Returns the length of the bar up to `value`.
+    fn length(&self, value: usize, available: usize) -> usize {
+        if value < self.min {
+            0
+        } else {
+            ((1 + available) * (value - self.min)) / (1 + self.max - self.min)
+        }
+    }
+
     /// Sets the minimum value.
     ///
     /// When `value` equals `min`, the bar is at the minimum level.
@@ -195,30 +343,69 @@ impl ProgressBar {
 
 impl View for ProgressBar {
     fn draw(&self, printer: &Printer) {
+        if self.start.get().is_none() {
+            self.start.set(Some((Instant::now(), self.value.get())));
+        }
+
         // Now, the bar itself...
         let available = printer.size.x;
 
-        let value = self.value.get();
+        let progress = self.progress();
 
+        // Each segment ends where the sum of the previous values does.
         // If we're under the minimum, don't draw anything.
         // If we're over the maximum, we'll try to draw more, but the printer
         // will crop us anyway, so it's not a big deal.
-        let length = if value < self.min {
-            0
-        } else {
-            ((1 + available) * (value - self.min)) / (1 + self.max - self.min)
-        };
+        let mut ends = vec![(progress.value, ColorStyle::Highlight)];
+        for (&(_, color), value) in self.segments
+            .iter()
+            .zip(&progress.segments) {
+            let total = ends[ends.len() - 1].0 + value;
+            ends.push((total, color));
+        }
 
-        let label = (self.label_maker)(value, (self.min, self.max));
+        let label = (self.label_maker)(&progress);
         let offset = HAlign::Center.get_offset(label.len(), printer.size.x);
 
         printer.with_color(ColorStyle::Highlight, |printer| {
             printer.with_effect(Effect::Reverse, |printer| {
                 printer.print((offset, 0), &label);
             });
-            let printer = &printer.sub_printer((0, 0), (length, 1), true);
-            printer.print_hline((0, 0), length, " ");
-            printer.print((offset, 0), &label);
         });
+
+        // Later segments are drawn first, then covered by earlier ones.
+        for &(total, color) in ends.iter().rev() {
+            let length = self.length(total, available);
+            printer.with_color(color, |printer| {
+                let printer = &printer.sub_printer((0, 0), (length, 1), true);
+                printer.print_hline((0, 0), length, " ");
+                printer.print((offset, 0), &label);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Counter, ProgressBar};
+    use std::time::Duration;
+    use theme::ColorStyle;
+
+    #[test]
+    fn test_progress() {
+        let bar = ProgressBar::new()
+            .range(0, 40)
+            .with_value(Counter::new(10))
+            .segment(Counter::new(5), ColorStyle::Secondary);
+
+        let mut progress = bar.progress();
+        assert_eq!(progress.percent(), 24);
+        assert_eq!(progress.segments, vec![5]);
+        assert_eq!(progress.eta(), None);
+
+        // 10 more in 2 seconds: 20 left take 4 seconds.
+        progress.value = 20;
+        progress.elapsed = Duration::from_secs(2);
+        assert_eq!(progress.eta(), Some(Duration::from_secs(4)));
     }
 }
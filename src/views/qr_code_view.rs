@@ -0,0 +1,83 @@
+use Printer;
+use qrcode::{self, QrCode};
+use qrcode::types::QrError;
+use theme::{BaseColor, Color, ColorStyle};
+use vec::Vec2;
+use view::View;
+
+// Empty modules around the code, required by most readers.
+const QUIET_ZONE: usize = 4;
+
+/// Shows some data as a QR code.
+///
+/// Each cell holds two modules of the code, using half-block characters.
+/// The code is always printed dark on light, to be readable.
+///
+/// Requires the `qrcode` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::QrCodeView;
+/// let view = QrCodeView::new("https://github.com/gyscos/Cursive").unwrap();
+/// ```
+pub struct QrCodeView {
+    // Dark modules, including the quiet zone.
+    modules: Vec<Vec<bool>>,
+}
+
+impl QrCodeView {
+    /// Creates a new view encoding `data`.
+    ///
+    /// Fails if `data` is too long to fit in a QR code.
+    pub fn new<D: AsRef<[u8]>>(data: D) -> Result<Self, QrError> {
+        let code = QrCode::new(data)?;
+        let width = code.width();
+        let size = width + 2 * QUIET_ZONE;
+
+        let mut modules = vec![vec![false; size]; size];
+        for y in 0..width {
+            for x in 0..width {
+                modules[y + QUIET_ZONE][x + QUIET_ZONE] =
+                    code[(x, y)] == qrcode::Color::Dark;
+            }
+        }
+
+        Ok(QrCodeView { modules: modules })
+    }
+
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules.get(y).map_or(false, |row| row[x])
+    }
+}
+
+impl View for QrCodeView {
+    fn draw(&self, printer: &Printer) {
+        let color = ColorStyle::Custom {
+            front: Color::Dark(BaseColor::Black),
+            back: Color::Light(BaseColor::White),
+        };
+
+        printer.with_color(color, |printer| {
+            let size = self.modules.len();
+            for y in 0..(size + 1) / 2 {
+                for x in 0..size {
+                    let top = self.is_dark(x, 2 * y);
+                    let bottom = self.is_dark(x, 2 * y + 1);
+                    let c = match (top, bottom) {
+                        (true, true) => "█",
+                        (true, false) => "▀",
+                        (false, true) => "▄",
+                        (false, false) => " ",
+                    };
+                    printer.print((x, y), c);
+                }
+            }
+        });
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let size = self.modules.len();
+        Vec2::new(size, (size + 1) / 2)
+    }
+}
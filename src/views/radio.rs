@@ -5,6 +5,7 @@ use event::{Event, EventResult, Key};
 use std::cell::RefCell;
 use std::rc::Rc;
 use theme::ColorStyle;
+use utils::i18n;
 use vec::Vec2;
 use view::View;
 
@@ -151,6 +152,18 @@ impl<T> View for RadioButton<T> {
         self.enabled
     }
 
+    fn describe(&self) -> Option<String> {
+        let state = if self.is_selected() {
+            "selected"
+        } else {
+            "not selected"
+        };
+        Some(format!("{} {}, {}",
+                     self.label,
+                     i18n::translate("radio button"),
+                     i18n::translate(state)))
+    }
+
     fn draw(&self, printer: &Printer) {
         if self.enabled {
             printer.with_selection(printer.focused,
@@ -0,0 +1,175 @@
+use Printer;
+use With;
+use direction::Direction;
+use event::{Event, EventResult};
+use std::any::Any;
+use vec::Vec2;
+use view::{Selector, View};
+
+/// Picks one of several layouts depending on the available size.
+///
+/// Each alternative has a minimum size. The last added alternative that
+/// fits in the available space is used; the first one is the fallback
+/// when nothing else fits. The choice is made again on each layout, so
+/// the view changes when the terminal is resized.
+///
+/// All alternatives are kept alive, and id lookups reach all of them (the
+/// active one first). Only the active one is drawn and receives events.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{LinearLayout, ResponsiveView, TextView};
+/// let view = ResponsiveView::new(LinearLayout::vertical()
+///                                    .child(TextView::new("Menu"))
+///                                    .child(TextView::new("Content")))
+///     .alternative((120, 0),
+///                  LinearLayout::horizontal()
+///                      .child(TextView::new("Menu"))
+///                      .child(TextView::new("Content")));
+/// ```
+pub struct ResponsiveView {
+    children: Vec<Child>,
+    current: usize,
+    focused: bool,
+}
+
+struct Child {
+    view: Box<View>,
+    min_size: Vec2,
+}
+
+impl ResponsiveView {
+    /// Creates a new `ResponsiveView` with the given fallback layout.
+    pub fn new<V: View + 'static>(view: V) -> Self {
+        ResponsiveView {
+            children: vec![Child {
+                               view: Box::new(view),
+                               min_size: Vec2::zero(),
+                           }],
+            current: 0,
+            focused: false,
+        }
+    }
+
+    /// Adds a layout to use when at least `min_size` is available.
+    pub fn add_alternative<S, V>(&mut self, min_size: S, view: V)
+        where S: Into<Vec2>,
+              V: View + 'static
+    {
+        self.children.push(Child {
+            view: Box::new(view),
+            min_size: min_size.into(),
+        });
+    }
+
+    /// Adds a layout to use when at least `min_size` is available.
+    ///
+    /// Chainable variant.
+    pub fn alternative<S, V>(self, min_size: S, view: V) -> Self
+        where S: Into<Vec2>,
+              V: View + 'static
+    {
+        self.with(|s| s.add_alternative(min_size, view))
+    }
+
+    /// Returns the index of the layout currently in use.
+    ///
+    /// The fallback layout has index 0, and alternatives follow in the
+    /// order they were added.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    // Returns the index of the layout to use for the given size.
+    fn select(&self, size: Vec2) -> usize {
+        self.children
+            .iter()
+            .rposition(|child| child.min_size.fits_in(size))
+            .unwrap_or(0)
+    }
+}
+
+impl View for ResponsiveView {
+    fn draw(&self, printer: &Printer) {
+        self.children[self.current].view.draw(printer);
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let i = self.select(req);
+        self.children[i].view.required_size(req)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        let i = self.select(size);
+        if i != self.current {
+            self.current = i;
+            if self.focused {
+                self.focused = self.children[i]
+                    .view
+                    .take_focus(Direction::none());
+            }
+        }
+        self.children[i].view.layout(size);
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        self.children[self.current]
+            .view
+            .on_event(event)
+            .resolve_on(self)
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        self.focused = self.children[self.current].view.take_focus(source);
+        self.focused
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        let current = self.current;
+        self.children[current]
+            .view
+            .call_on_any(selector, Box::new(|any| callback(any)));
+        for (i, child) in self.children.iter_mut().enumerate() {
+            if i != current {
+                child.view
+                    .call_on_any(selector, Box::new(|any| callback(any)));
+            }
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        let result = self.children[self.current].view.focus_view(selector);
+        if result.is_ok() {
+            self.focused = true;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponsiveView;
+    use vec::Vec2;
+    use view::View;
+    use views::{DummyView, TextView};
+
+    #[test]
+    fn select_layout() {
+        let mut view = ResponsiveView::new(DummyView)
+            .alternative((40, 0), TextView::new("wide"))
+            .alternative((80, 20), TextView::new("large"));
+
+        view.layout(Vec2::new(30, 30));
+        assert_eq!(view.current(), 0);
+
+        view.layout(Vec2::new(100, 10));
+        assert_eq!(view.current(), 1);
+
+        view.layout(Vec2::new(100, 30));
+        assert_eq!(view.current(), 2);
+
+        assert_eq!(view.required_size(Vec2::new(50, 5)), Vec2::new(4, 1));
+    }
+}
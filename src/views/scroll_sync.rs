@@ -0,0 +1,150 @@
+use XY;
+use With;
+use event::{Event, EventResult};
+use utils::Binding;
+use vec::Vec2;
+use view::{Scrollable, View, ViewWrapper};
+
+/// Wrapper view keeping its content scrolled with other views.
+///
+/// Views sharing the same `Binding` scroll together: when one of them
+/// scrolls, the others follow on the next layout. This suits side-by-side
+/// diff panes, or a text with a separate gutter.
+///
+/// Each axis can be linked separately. Views only following vertically
+/// still share their horizontal position, but ignore it.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::utils::Binding;
+/// # use cursive::vec::Vec2;
+/// # use cursive::views::{LinearLayout, ScrollSync, TextView};
+/// let offset = Binding::new(Vec2::zero());
+/// let panes = LinearLayout::horizontal()
+///     .child(ScrollSync::new(offset.clone(), TextView::new("old")))
+///     .child(ScrollSync::new(offset.clone(), TextView::new("new")));
+/// ```
+pub struct ScrollSync<T: View + Scrollable> {
+    view: T,
+    offset: Binding<Vec2>,
+    linked: XY<bool>,
+    // Generation of the shared offset we last saw.
+    generation: usize,
+    // Our offset after the last sync.
+    last: Vec2,
+}
+
+// Takes the linked axes from `linked_from`, and the others from `rest`.
+fn merge(linked: XY<bool>, linked_from: Vec2, rest: Vec2) -> Vec2 {
+    Vec2::new(if linked.x { linked_from.x } else { rest.x },
+              if linked.y { linked_from.y } else { rest.y })
+}
+
+impl<T: View + Scrollable> ScrollSync<T> {
+    /// Wraps `view`, scrolling it with the shared `offset`.
+    ///
+    /// Both axes are linked.
+    pub fn new(offset: Binding<Vec2>, view: T) -> Self {
+        ScrollSync {
+            last: view.scroll_offset(),
+            view: view,
+            // The first layout catches up with the shared offset.
+            generation: offset.generation().wrapping_sub(1),
+            offset: offset,
+            linked: XY::new(true, true),
+        }
+    }
+
+    /// Sets which axes follow the shared offset.
+    pub fn set_linked(&mut self, linked: XY<bool>) {
+        self.linked = linked;
+    }
+
+    /// Sets which axes follow the shared offset.
+    ///
+    /// Chainable variant.
+    pub fn linked(self, linked: XY<bool>) -> Self {
+        self.with(|s| s.set_linked(linked))
+    }
+
+    /// Returns the inner view.
+    pub fn get_inner(&self) -> &T {
+        &self.view
+    }
+
+    /// Returns a mutable reference to the inner view.
+    pub fn get_inner_mut(&mut self) -> &mut T {
+        &mut self.view
+    }
+
+    // Shares our offset if we scrolled, or follows the shared one.
+    fn sync(&mut self) {
+        let offset = self.view.scroll_offset();
+        if offset != self.last {
+            let linked = self.linked;
+            self.offset.update(|shared| {
+                *shared = merge(linked, offset, *shared);
+            });
+        } else if self.offset.generation() != self.generation {
+            let target = merge(self.linked, self.offset.get(), offset);
+            self.view.set_scroll_offset(target);
+        }
+        self.generation = self.offset.generation();
+        self.last = self.view.scroll_offset();
+    }
+}
+
+impl<T: View + Scrollable> ViewWrapper for ScrollSync<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let result = self.view.on_event(event);
+        self.sync();
+        result
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.view.layout(size);
+        self.sync();
+    }
+
+    fn wrap_needs_relayout(&self) -> bool {
+        self.offset.generation() != self.generation ||
+        self.view.needs_relayout()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScrollSync;
+    use XY;
+    use event::{Event, Key};
+    use utils::Binding;
+    use vec::Vec2;
+    use view::View;
+    use views::TextView;
+
+    #[test]
+    fn test_sync() {
+        let offset = Binding::new(Vec2::zero());
+        let mut left = ScrollSync::new(offset.clone(),
+                                       TextView::new("1\n2\n3\n4\n5"));
+        let mut right = ScrollSync::new(offset.clone(),
+                                        TextView::new("a\nb\nc\nd\ne"));
+        left.layout(Vec2::new(5, 2));
+        right.layout(Vec2::new(5, 2));
+
+        left.on_event(Event::Key(Key::Down));
+        assert_eq!(offset.get(), Vec2::new(0, 1));
+        assert!(right.needs_relayout());
+        right.layout(Vec2::new(5, 2));
+        assert_eq!(right.get_inner().scroll_offset(), Vec2::new(0, 1));
+
+        // Only following horizontally: the vertical offset is ignored.
+        let mut other = ScrollSync::new(offset.clone(), TextView::new("x\ny"))
+            .linked(XY::new(true, false));
+        other.layout(Vec2::new(5, 1));
+        assert_eq!(other.get_inner().scroll_offset(), Vec2::new(0, 0));
+    }
+}
@@ -10,11 +10,13 @@ use std::borrow::Borrow;
 use std::cell::Cell;
 use std::cmp::min;
 use std::rc::Rc;
-use theme::ColorStyle;
+use theme::{ColorStyle, Style};
 
 use unicode_width::UnicodeWidthStr;
+use utils::{TruncateStyle, fuzzy, i18n, truncate};
 use vec::Vec2;
-use view::{Position, ScrollBase, View};
+use view::{Boxable, Position, ScrollBase, Scrollable, ScrollbarVisibility,
+           View};
 use views::MenuPopup;
 
 /// View to select an item among a list.
@@ -58,13 +60,25 @@ pub struct SelectView<T = String> {
     on_submit: Option<Rc<Fn(&mut Cursive, &T)>>,
     // This callback is called when the selection is changed.
     on_select: Option<Rc<Fn(&mut Cursive, &T)>>,
+    // `true` if Ctrl+Up/Down can move the selected item.
+    reorderable: bool,
+    // Called with the old and new positions of a moved item.
+    on_reorder: Option<Rc<Fn(&mut Cursive, usize, usize)>>,
     align: Align,
     // `true` if we show a one-line view, with popup on selection.
     popup: bool,
+    // Maximum height of the popup, including borders.
+    popup_max_height: Option<usize>,
+    // Called when the popup is dismissed without selection.
+    on_cancel: Option<Callback>,
     // We need the last offset to place the popup window
     // We "cache" it during the draw, so we need interior mutability.
     last_offset: Cell<Vec2>,
     last_size: Vec2,
+    // Only items matching this are shown.
+    filter: String,
+    // Ids of the items currently shown, in order.
+    rows: Vec<usize>,
 }
 
 impl<T: 'static> SelectView<T> {
@@ -77,10 +91,16 @@ impl<T: 'static> SelectView<T> {
             scrollbase: ScrollBase::new(),
             on_select: None,
             on_submit: None,
+            reorderable: false,
+            on_reorder: None,
             align: Align::top_left(),
             popup: false,
+            popup_max_height: None,
+            on_cancel: None,
             last_offset: Cell::new(Vec2::zero()),
             last_size: Vec2::zero(),
+            filter: String::new(),
+            rows: Vec::new(),
         }
     }
 
@@ -96,6 +116,76 @@ impl<T: 'static> SelectView<T> {
         self.popup = popup;
     }
 
+    /// Sets the maximum height of the popup, including its borders.
+    ///
+    /// If there are more items, the popup will scroll.
+    ///
+    /// The popup is also limited by the space available on screen.
+    pub fn set_popup_max_height(&mut self, max_height: Option<usize>) {
+        self.popup_max_height = max_height;
+    }
+
+    /// Sets the maximum height of the popup, including its borders.
+    ///
+    /// Chainable variant.
+    pub fn popup_max_height(self, max_height: usize) -> Self {
+        self.with(|s| s.set_popup_max_height(Some(max_height)))
+    }
+
+    /// Returns the current scroll position.
+    pub fn scroll_offset(&self) -> Vec2 {
+        self.scrollbase.scroll_offset()
+    }
+
+    /// Sets the scroll position.
+    ///
+    /// Only `offset.y` is used, as this view only scrolls vertically.
+    pub fn set_scroll_offset<O: Into<Vec2>>(&mut self, offset: O) {
+        self.scrollbase.set_scroll_offset(offset.into());
+    }
+
+    /// Scrolls to the last item.
+    ///
+    /// The focus doesn't move: moving it later scrolls back to it.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scrollbase.scroll_bottom();
+    }
+
+    /// Sets when to show the scrollbar.
+    ///
+    /// Defaults to `ScrollbarVisibility::Auto`.
+    pub fn set_scrollbar_visibility(&mut self,
+                                    visibility: ScrollbarVisibility) {
+        self.scrollbase.visibility = visibility;
+    }
+
+    /// Sets when to show the scrollbar.
+    ///
+    /// Chainable variant.
+    pub fn scrollbar_visibility(self, visibility: ScrollbarVisibility)
+                                -> Self {
+        self.with(|s| s.set_scrollbar_visibility(visibility))
+    }
+
+    /// Sets a callback to be used when the popup is dismissed.
+    ///
+    /// This happens when the user closes the popup without selecting
+    /// any item (with `<Esc>`).
+    pub fn set_on_cancel<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive) + 'static
+    {
+        self.on_cancel = Some(Callback::from_fn(cb));
+    }
+
+    /// Sets a callback to be used when the popup is dismissed.
+    ///
+    /// Chainable variant.
+    pub fn on_cancel<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive) + 'static
+    {
+        self.with(|s| s.set_on_cancel(cb))
+    }
+
     /// Disables this view.
     ///
     /// A disabled view cannot be selected.
@@ -141,6 +231,38 @@ impl<T: 'static> SelectView<T> {
         self.with(|s| s.set_on_select(cb))
     }
 
+    /// Allows the user to move the selected item with `<Ctrl+Up>` and
+    /// `<Ctrl+Down>`.
+    pub fn set_reorderable(&mut self, reorderable: bool) {
+        self.reorderable = reorderable;
+    }
+
+    /// Allows the user to move the selected item with `<Ctrl+Up>` and
+    /// `<Ctrl+Down>`.
+    ///
+    /// Chainable variant.
+    pub fn reorderable(self) -> Self {
+        self.with(|s| s.set_reorderable(true))
+    }
+
+    /// Sets a callback to be used when the user moves an item.
+    ///
+    /// It will be given the old and the new position of the item.
+    pub fn set_on_reorder<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, usize, usize) + 'static
+    {
+        self.on_reorder = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when the user moves an item.
+    ///
+    /// Chainable variant.
+    pub fn on_reorder<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive, usize, usize) + 'static
+    {
+        self.with(|s| s.set_on_reorder(cb))
+    }
+
     /// Sets a callback to be used when `<Enter>` is pressed.
     ///
     /// The item currently selected will be given to the callback.
@@ -188,6 +310,76 @@ impl<T: 'static> SelectView<T> {
         self
     }
 
+    /// Only shows items whose label fuzzy-matches `filter`.
+    ///
+    /// Matched characters are highlighted, and hidden items can't be
+    /// selected. Items keep their ids and their order. An empty filter
+    /// shows all items.
+    ///
+    /// See [`fuzzy::find`] for the matching rules.
+    ///
+    /// [`fuzzy::find`]: ../utils/fuzzy/fn.find.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::{EditView, SelectView};
+    /// let filter = EditView::new().on_edit(|s, text, _| {
+    ///     s.call_on_id("list",
+    ///                  |view: &mut SelectView| view.set_filter(text));
+    /// });
+    /// ```
+    pub fn set_filter<S: Into<String>>(&mut self, filter: S) {
+        self.filter = filter.into();
+        self.update_filter();
+
+        if !self.items.is_empty() && !self.is_selectable(self.focus()) {
+            self.focus_down(1);
+            self.focus_up(0);
+        }
+        let row = self.row(self.focus());
+        self.scrollbase.scroll_to(row);
+    }
+
+    /// Only shows items whose label fuzzy-matches `filter`.
+    ///
+    /// Chainable variant.
+    pub fn filter<S: Into<String>>(self, filter: S) -> Self {
+        self.with(|s| s.set_filter(filter))
+    }
+
+    /// Returns the number of items matching the current filter.
+    pub fn visible_len(&self) -> usize {
+        self.rows.len()
+    }
+
+    // Matches all items against the filter again.
+    fn update_filter(&mut self) {
+        self.rows.clear();
+        for (i, item) in self.items.iter_mut().enumerate() {
+            item.filter_match = fuzzy::find(&self.filter, &item.label);
+            if item.filter_match.is_some() {
+                self.rows.push(i);
+            }
+        }
+        if !self.popup {
+            self.scrollbase.set_heights(self.last_size.y, self.rows.len());
+        }
+    }
+
+    // Returns the row where the given item is shown.
+    //
+    // For a hidden item, returns the row of the next shown one.
+    fn row(&self, id: usize) -> usize {
+        self.rows.binary_search(&id).unwrap_or_else(|row| row)
+    }
+
+    // Returns `true` if the given item can be selected.
+    fn is_selectable(&self, id: usize) -> bool {
+        let item = &self.items[id];
+        item.enabled && item.filter_match.is_some()
+    }
+
     /// Returns the value of the currently selected item.
     ///
     /// Panics if the list is empty.
@@ -198,23 +390,80 @@ impl<T: 'static> SelectView<T> {
     /// Removes all items from this view.
     pub fn clear(&mut self) {
         self.items.clear();
+        self.rows.clear();
         self.focus.set(0);
     }
 
     /// Adds a item to the list, with given label and value.
     pub fn add_item<S: Into<String>>(&mut self, label: S, value: T) {
-        self.items.push(Item::new(label.into(), value));
+        let mut item = Item::new(label.into(), value);
+        item.filter_match = fuzzy::find(&self.filter, &item.label);
+        if item.filter_match.is_some() {
+            self.rows.push(self.items.len());
+        }
+        self.items.push(item);
+    }
+
+    /// Sets the style used to print the item at the given position.
+    ///
+    /// The color is only used when the item is not selected.
+    pub fn set_item_style<S: Into<Style>>(&mut self, id: usize, style: S) {
+        self.items[id].style = Some(style.into());
+    }
+
+    /// Sets a secondary label for the item at the given position.
+    ///
+    /// It will be printed right-aligned, after the main label.
+    /// This can be used to show a shortcut or a size, for instance.
+    pub fn set_item_secondary<S: Into<String>>(&mut self, id: usize,
+                                               label: S) {
+        self.items[id].secondary = Some(label.into());
+    }
+
+    /// Enables or disables the item at the given position.
+    ///
+    /// Disabled items are skipped when moving the selection.
+    pub fn set_item_enabled(&mut self, id: usize, enabled: bool) {
+        self.items[id].enabled = enabled;
+        if !enabled && self.focus() == id {
+            self.focus_down(1);
+            self.focus_up(0);
+        }
+    }
+
+    /// Returns `true` if the item at the given position is enabled.
+    pub fn is_item_enabled(&self, id: usize) -> bool {
+        self.items[id].enabled
     }
 
     /// Removes an item from the list.
     pub fn remove_item(&mut self, id: usize) {
         self.items.remove(id);
+        self.update_filter();
         let focus = self.focus();
         if focus >= id && focus > 0 {
             self.focus.set(focus - 1);
         }
     }
 
+    /// Moves an item from position `old` to position `new`.
+    ///
+    /// The selection follows the item it was on.
+    pub fn move_item(&mut self, old: usize, new: usize) {
+        let item = self.items.remove(old);
+        self.items.insert(new, item);
+        self.update_filter();
+
+        let focus = self.focus();
+        if focus == old {
+            self.focus.set(new);
+        } else if old < focus && focus <= new {
+            self.focus.set(focus - 1);
+        } else if new <= focus && focus < old {
+            self.focus.set(focus + 1);
+        }
+    }
+
     /// Chainable variant of add_item
     pub fn item<S: Into<String>>(self, label: S, value: T) -> Self {
         self.with(|s| s.add_item(label, value))
@@ -241,12 +490,40 @@ impl<T: 'static> SelectView<T> {
     }
 
     fn draw_item(&self, printer: &Printer, i: usize) {
-        let l = self.items[i].label.width();
-        let x = self.align.h.get_offset(l, printer.size.x);
-        printer.print_hline((0, 0), x, " ");
-        printer.print((x, 0), &self.items[i].label);
-        if l < printer.size.x {
-            printer.print_hline((x + l, 0), printer.size.x - l - x, " ");
+        let item = &self.items[i];
+
+        // Keep some room for the secondary label, if any.
+        let secondary = item.secondary.as_ref().map_or("", |s| s.as_str());
+        let secondary_width = secondary.width();
+        let available = if secondary.is_empty() {
+            printer.size.x
+        } else {
+            printer.size.x.saturating_sub(secondary_width + 1)
+        };
+
+        let label = truncate(&item.label, available, TruncateStyle::End);
+        let l = label.width();
+        let x = self.align.h.get_offset(l, available);
+        printer.print_hline((0, 0), printer.size.x, " ");
+
+        let style = match item.style {
+            // Don't override the selection color.
+            Some(style) if i == self.focus() => Style::from(style.effect),
+            Some(style) => style,
+            None => Style::none(),
+        };
+        printer.with_style(style, |printer| match item.filter_match {
+            // Highlight matches, unless the label was truncated.
+            Some(ref m) if i != self.focus() && label == item.label => {
+                let label =
+                    fuzzy::highlight(&item.label, m, ColorStyle::TitlePrimary);
+                printer.print_styled((x, 0), &label);
+            }
+            _ => printer.print((x, 0), &label),
+        });
+
+        if !secondary.is_empty() && secondary_width < printer.size.x {
+            printer.print((printer.size.x - secondary_width, 0), secondary);
         }
     }
 
@@ -279,18 +556,52 @@ impl<T: 'static> SelectView<T> {
     pub fn set_selection(&mut self, i: usize) {
         // TODO: Check if `i > self.len()` ?
         self.focus.set(i);
-        self.scrollbase.scroll_to(i);
+        let row = self.row(i);
+        self.scrollbase.scroll_to(row);
+    }
+
+    // Moves the selected item to `new`, as asked by the user.
+    fn reorder(&mut self, new: usize) -> EventResult {
+        let old = self.focus();
+        self.move_item(old, new);
+        let row = self.row(new);
+        self.scrollbase.scroll_to(row);
+
+        match self.on_reorder.clone() {
+            Some(cb) => EventResult::with_cb(move |s| cb(s, old, new)),
+            None => EventResult::Consumed(None),
+        }
     }
 
+    // Moves the focus up by `n` items, skipping disabled ones.
     fn focus_up(&mut self, n: usize) {
         let focus = self.focus();
-        let n = min(focus, n);
-        self.focus.set(focus - n);
+        let target = focus - min(focus, n);
+
+        // Look further up if needed, or settle for a closer item.
+        let found = (0..target + 1)
+            .rev()
+            .find(|&i| self.is_selectable(i))
+            .or_else(|| (target..focus).find(|&i| self.is_selectable(i)));
+        if let Some(i) = found {
+            self.focus.set(i);
+        }
     }
 
+    // Moves the focus down by `n` items, skipping disabled ones.
     fn focus_down(&mut self, n: usize) {
-        let focus = min(self.focus() + n, self.items.len() - 1);
-        self.focus.set(focus);
+        let focus = self.focus();
+        let target = min(focus + n, self.items.len() - 1);
+
+        // Look further down if needed, or settle for a closer item.
+        let found = (target..self.items.len())
+            .find(|&i| self.is_selectable(i))
+            .or_else(|| {
+                (focus + 1..target).rev().find(|&i| self.is_selectable(i))
+            });
+        if let Some(i) = found {
+            self.focus.set(i);
+        }
     }
 }
 
@@ -335,6 +646,16 @@ impl SelectView<String> {
     }
 }
 
+impl<T: 'static> Scrollable for SelectView<T> {
+    fn scroll_offset(&self) -> Vec2 {
+        SelectView::scroll_offset(self)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Vec2) {
+        SelectView::set_scroll_offset(self, offset);
+    }
+}
+
 impl<T: 'static> View for SelectView<T> {
     fn draw(&self, printer: &Printer) {
         self.last_offset.set(printer.offset);
@@ -360,23 +681,28 @@ impl<T: 'static> View for SelectView<T> {
                 printer.print((0, 0), "<");
                 printer.print((x - 1, 0), ">");
 
-                let label = &self.items[self.focus()].label;
+                // Keep the text between the borders.
+                let label = truncate(&self.items[self.focus()].label,
+                                     x.saturating_sub(2),
+                                     TruncateStyle::End);
 
                 // And center the text?
-                let offset = HAlign::Center.get_offset(label.len(), x);
+                let offset = HAlign::Center.get_offset(label.width(), x);
 
-                printer.print((offset, 0), label);
+                printer.print((offset, 0), &label);
             });
         } else {
 
-            let h = self.items.len();
+            let h = self.rows.len();
             let offset = self.align.v.get_offset(h, printer.size.y);
             let printer =
                 &printer.sub_printer(Vec2::new(0, offset), printer.size, true);
 
-            self.scrollbase.draw(printer, |printer, i| {
+            self.scrollbase.draw(printer, |printer, row| {
+                let i = self.rows[row];
                 printer.with_selection(i == self.focus(), |printer| {
-                    if i != self.focus() && !self.enabled {
+                    if i != self.focus() &&
+                       !(self.enabled && self.items[i].enabled) {
                         printer.with_color(ColorStyle::Secondary, |printer| {
                             self.draw_item(printer, i)
                         });
@@ -394,15 +720,20 @@ impl<T: 'static> View for SelectView<T> {
         // we'll still return our longest item.
         let w = self.items
             .iter()
-            .map(|item| item.label.width())
+            .map(|item| {
+                let secondary = item.secondary
+                    .as_ref()
+                    .map_or(0, |s| s.width() + 1);
+                item.label.width() + secondary
+            })
             .max()
             .unwrap_or(1);
         if self.popup {
             Vec2::new(w + 2, 1)
         } else {
-            let h = self.items.len();
+            let h = self.rows.len();
 
-            let scrolling = req.y < h;
+            let scrolling = self.scrollbase.visibility.shown(req.y < h);
 
             // Add 2 spaces for the scrollbar if we need
             let w = if scrolling { w + 2 } else { w };
@@ -419,7 +750,15 @@ impl<T: 'static> View for SelectView<T> {
                     // Build a shallow menu tree to mimick the items array.
                     // TODO: cache it?
                     let mut tree = MenuTree::new();
+                    // Disabled items are not shown in the popup.
+                    let mut tree_focus = 0;
                     for (i, item) in self.items.iter().enumerate() {
+                        if !self.is_selectable(i) {
+                            continue;
+                        }
+                        if i < self.focus() {
+                            tree_focus += 1;
+                        }
                         let focus = self.focus.clone();
                         let on_submit = self.on_submit.as_ref().cloned();
                         let value = item.value.clone();
@@ -438,42 +777,65 @@ impl<T: 'static> View for SelectView<T> {
                     // This is the offset for the label text.
                     // We'll want to show the popup so that the text matches.
                     // It'll be soo cool.
-                    let item_length = self.items[focus].label.len();
+                    let item_length = self.items[focus].label.width();
                     let text_offset = if self.last_size.x >= item_length {
                         (self.last_size.x - item_length) / 2
                     } else {
                         // We were too small to show the entire item last time.
                         0
                     };
-                    // The total offset for the window is:
-                    // * the last absolute offset at which we drew this view
-                    // * shifted to the top of the focus (so the line matches)
-                    // * shifted to the right of the text offset
-                    // * shifted top-left of the border+padding of the popup
-                    let offset = self.last_offset.get() - (0, focus) +
-                                 (text_offset, 0) -
-                                 (2, 1);
+                    // Shift to the right of the text offset,
+                    // and to the left of the border+padding of the popup.
+                    let last_offset = self.last_offset.get();
+                    let x = (last_offset.x + text_offset) as isize - 2;
+                    let max_height = self.popup_max_height;
+                    let on_cancel = self.on_cancel.clone();
+
                     // And now, we can return the callback.
                     EventResult::with_cb(move |s| {
                         // The callback will want to work with a fresh Rc
                         let tree = tree.clone();
+
+                        let (y, height) = popup_placement(s.screen_size().y,
+                                                          last_offset.y,
+                                                          tree.len() + 2,
+                                                          max_height,
+                                                          tree_focus);
+
                         // We'll relativise the absolute position,
                         // So that we are locked to the parent view.
                         // A nice effect is that window resizes will keep both
                         // layers together.
                         let current_offset = s.screen().offset();
-                        let offset = XY::<isize>::from(offset) -
-                                     current_offset;
+                        let offset = XY::new(x, y as isize) - current_offset;
+
+                        let mut popup = MenuPopup::new(tree).focus(tree_focus);
+                        if let Some(ref on_cancel) = on_cancel {
+                            let on_cancel = on_cancel.clone();
+                            popup = popup.on_dismiss(move |s| on_cancel(s));
+                        }
+
                         // And finally, put the view in view!
                         s.screen_mut()
                             .add_layer_at(Position::parent(offset),
-                                          MenuPopup::new(tree).focus(focus));
+                                          popup.max_height(height));
                     })
                 }
                 _ => EventResult::Ignored,
             }
         } else {
             match event {
+                Event::Ctrl(Key::Up) if self.reorderable &&
+                                        self.focus() > 0 => {
+                    let focus = self.focus();
+                    return self.reorder(focus - 1);
+                }
+                Event::Ctrl(Key::Down) if self.reorderable &&
+                                          self.focus() + 1 <
+                                          self.items.len() => {
+                    let focus = self.focus();
+                    return self.reorder(focus + 1);
+                }
                 Event::Key(Key::Up) if self.focus() > 0 => self.focus_up(1),
                 Event::Key(Key::Down) if self.focus() + 1 <
                                          self.items.len() => {
@@ -481,8 +843,14 @@ impl<T: 'static> View for SelectView<T> {
                 }
                 Event::Key(Key::PageUp) => self.focus_up(10),
                 Event::Key(Key::PageDown) => self.focus_down(10),
-                Event::Key(Key::Home) => self.focus.set(0),
-                Event::Key(Key::End) => self.focus.set(self.items.len() - 1),
+                Event::Key(Key::Home) => {
+                    let focus = self.focus();
+                    self.focus_up(focus)
+                }
+                Event::Key(Key::End) => {
+                    let len = self.items.len();
+                    self.focus_down(len)
+                }
                 Event::Key(Key::Enter) if self.on_submit.is_some() => {
                     let cb = self.on_submit.clone().unwrap();
                     let v = self.selection();
@@ -497,19 +865,23 @@ impl<T: 'static> View for SelectView<T> {
                     // Cycle back to the beginning of
                     // the list when we reach the end.
                     // This is achieved by chaining twice the iterator
+                    let len = self.items.len();
                     let iter = self.items.iter().chain(self.items.iter());
                     if let Some((i, _)) = iter.enumerate()
                         .skip(self.focus() + 1)
-                        .find(|&(_, item)| item.label.starts_with(c)) {
+                        .find(|&(i, item)| {
+                            self.is_selectable(i % len) &&
+                            item.label.starts_with(c)
+                        }) {
                         // Apply modulo in case we have a hit
                         // from the chained iterator
-                        self.focus.set(i % self.items.len());
+                        self.focus.set(i % len);
                     }
                 }
                 _ => return EventResult::Ignored,
             }
-            let focus = self.focus();
-            self.scrollbase.scroll_to(focus);
+            let row = self.row(self.focus());
+            self.scrollbase.scroll_to(row);
 
             EventResult::Consumed(self.on_select.clone().map(|cb| {
                 let v = self.selection();
@@ -519,21 +891,65 @@ impl<T: 'static> View for SelectView<T> {
     }
 
     fn take_focus(&mut self, _: Direction) -> bool {
-        self.enabled && !self.items.is_empty()
+        self.enabled && (0..self.items.len()).any(|i| self.is_selectable(i))
+    }
+
+    fn describe(&self) -> Option<String> {
+        let item = self.items.get(self.focus())?;
+        Some(format!("{}, {} {} {}",
+                     item.label,
+                     self.focus() + 1,
+                     i18n::translate("of"),
+                     self.items.len()))
     }
 
     fn layout(&mut self, size: Vec2) {
         self.last_size = size;
 
         if !self.popup {
-            self.scrollbase.set_heights(size.y, self.items.len());
+            self.scrollbase.set_heights(size.y, self.rows.len());
         }
     }
 }
 
+// Finds where to show a popup of `height` rows, for a view at row `view_y`.
+//
+// Returns the row of the top of the popup, and its (possibly reduced) height.
+//
+// Ideally, the focused item in the popup is printed right over the view.
+// If there's not enough space, place the popup below or above the view.
+fn popup_placement(screen_height: usize, view_y: usize, height: usize,
+                   max_height: Option<usize>, focus: usize)
+                   -> (usize, usize) {
+    let height = max_height.map_or(height, |max| min(height, max));
+
+    // Number of rows from the top of the popup to the focused item.
+    let focus_row = min(focus + 1, height.saturating_sub(2));
+
+    let space_below = screen_height.saturating_sub(view_y + 1);
+    let space_above = view_y;
+
+    if view_y >= focus_row && view_y - focus_row + height <= screen_height {
+        (view_y - focus_row, height)
+    } else if height <= space_below {
+        (view_y + 1, height)
+    } else if height <= space_above {
+        (view_y - height, height)
+    } else if space_below >= space_above {
+        (view_y + 1, space_below)
+    } else {
+        (0, space_above)
+    }
+}
+
 struct Item<T> {
     label: String,
     value: Rc<T>,
+    secondary: Option<String>,
+    style: Option<Style>,
+    enabled: bool,
+    // `None` if the item is hidden by the filter.
+    filter_match: Option<fuzzy::Match>,
 }
 
 impl<T> Item<T> {
@@ -541,6 +957,86 @@ impl<T> Item<T> {
         Item {
             label: label,
             value: Rc::new(value),
+            secondary: None,
+            style: None,
+            enabled: true,
+            filter_match: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SelectView;
+    use super::popup_placement;
+    use direction::Direction;
+    use event::{Event, Key};
+    use view::View;
+    use views::{Dialog, LinearLayout, TextView};
+
+    #[test]
+    fn test_move_item() {
+        let mut view = SelectView::new().with_all_str(vec!["a", "b", "c"]);
+        view.set_selection(0);
+        view.move_item(0, 2);
+        assert_eq!(*view.selection(), "a");
+        assert_eq!(view.selected_id(), Some(2));
+
+        view.move_item(0, 1);
+        assert_eq!(view.selected_id(), Some(2));
+        view.move_item(2, 0);
+        assert_eq!(view.selected_id(), Some(0));
+        assert_eq!(*view.selection(), "a");
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut view = SelectView::new()
+            .with_all_str(vec!["apple", "banana", "cherry", "grape"]);
+        view.set_selection(1);
+
+        view.set_filter("ap");
+        assert_eq!(view.visible_len(), 2);
+        assert_eq!(*view.selection(), "grape");
+
+        view.add_item_str("papaya");
+        view.remove_item(0);
+        assert_eq!(view.visible_len(), 2);
+
+        view.set_filter("");
+        assert_eq!(view.visible_len(), 4);
+        assert_eq!(*view.selection(), "grape");
+    }
+
+    #[test]
+    fn test_popup_placement() {
+        // Enough room: the focused item is right over the view.
+        assert_eq!(popup_placement(20, 10, 6, None, 2), (7, 6));
+        // Near the top: go below.
+        assert_eq!(popup_placement(20, 1, 6, None, 3), (2, 6));
+        // Near the bottom: go above.
+        assert_eq!(popup_placement(20, 18, 12, None, 0), (6, 12));
+        // Not enough room anywhere: take the biggest side.
+        assert_eq!(popup_placement(10, 3, 12, None, 5), (4, 6));
+        // Capped height.
+        assert_eq!(popup_placement(20, 10, 12, Some(5), 0), (9, 5));
+    }
+
+    #[test]
+    fn test_describe() {
+        let select = SelectView::new().with_all_str(vec!["Red", "Blue"]);
+        let mut dialog = Dialog::around(LinearLayout::vertical()
+                                            .child(TextView::new("Color?"))
+                                            .child(select))
+            .title("Theme")
+            .button("Ok", |_| ());
+        dialog.take_focus(Direction::front());
+        assert_eq!(dialog.describe(), Some("Theme: Red, 1 of 2".to_string()));
+
+        dialog.on_event(Event::Key(Key::Down));
+        assert_eq!(dialog.describe(), Some("Theme: Blue, 2 of 2".to_string()));
+
+        dialog.on_event(Event::Key(Key::Tab));
+        assert_eq!(dialog.describe(), Some("Theme: Ok button".to_string()));
+    }
+}
@@ -5,6 +5,7 @@ use direction::{Direction, Orientation};
 use event::{Callback, Event, EventResult, Key};
 use std::rc::Rc;
 use theme::ColorStyle;
+use utils::i18n;
 use vec::Vec2;
 use view::View;
 
@@ -153,4 +154,12 @@ impl View for SliderView {
     fn take_focus(&mut self, _: Direction) -> bool {
         true
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{}, {} {} {}",
+                     i18n::translate("slider"),
+                     self.value + 1,
+                     i18n::translate("of"),
+                     self.max_value))
+    }
 }
@@ -1,27 +1,49 @@
 use Printer;
 
 use ::With;
-use direction::Direction;
+use direction::{Absolute, Direction};
 use event::{Event, EventResult};
 use std::any::Any;
-use theme::ColorStyle;
+use std::rc::Rc;
+use theme::{ColorStyle, DimStyle};
 use vec::Vec2;
-use view::{Offset, Position, Selector, View};
+use view::{Offset, Position, Rect, Selector, View};
 use views::{Layer, ShadowView};
 
 /// Simple stack of views.
 /// Only the top-most view is active and can receive input.
+///
+/// Layers can be marked as always on top, like a FPS counter or a
+/// notification area: they stay above the other layers, but never receive
+/// input, and `pop_layer` leaves them alone.
+///
+/// Modal layers dim the layers below them, so they stand out.
 pub struct StackView {
     layers: Vec<Child>,
     last_size: Vec2,
+
+    // Sees events before the top layer.
+    on_capture: Option<Rc<Fn(&mut Event) -> EventResult>>,
 }
 
 enum Placement {
     Floating(Position),
     Fullscreen,
+    // Next to an area, on the preferred side if possible.
+    Anchored(Rect, Absolute),
 }
 
 impl Placement {
+    // Shrinks `size` to the room left for the layer.
+    fn fit(&self, size: Vec2, available: Vec2) -> Vec2 {
+        match *self {
+            Placement::Anchored(ref anchor, side) => {
+                anchor.place_beside(size, available, side).1
+            }
+            _ => size,
+        }
+    }
+
     pub fn compute_offset<S, A, P>(&self, size: S, available: A, parent: P)
                                    -> Vec2
         where S: Into<Vec2>,
@@ -33,10 +55,21 @@ impl Placement {
                 position.compute_offset(size, available, parent)
             }
             Placement::Fullscreen => Vec2::zero(),
+            Placement::Anchored(ref anchor, side) => {
+                anchor.place_beside(size, available, side).0
+            }
         }
     }
 }
 
+// How a layer dims the layers below it.
+enum Dim {
+    None,
+    // Uses the style of the theme.
+    Theme,
+    Custom(DimStyle),
+}
+
 struct Child {
     view: Box<View>,
     size: Vec2,
@@ -46,6 +79,11 @@ struct Child {
     // So we want to call `take_focus` right after the first call
     // to `layout`; this flag remembers when we've done that.
     virgin: bool,
+
+    // Always-on-top layers are kept at the end of the stack.
+    always_on_top: bool,
+
+    dim: Dim,
 }
 
 new_default!(StackView);
@@ -56,20 +94,41 @@ impl StackView {
         StackView {
             layers: Vec::new(),
             last_size: Vec2::zero(),
+            on_capture: None,
         }
     }
 
+    /// Sets a callback to see events before the top layer.
+    ///
+    /// See [capturing events](../event/index.html#capturing-events).
+    pub fn set_on_capture<F>(&mut self, cb: F)
+        where F: Fn(&mut Event) -> EventResult + 'static
+    {
+        self.on_capture = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to see events before the top layer.
+    ///
+    /// Chainable variant.
+    pub fn on_capture<F>(self, cb: F) -> Self
+        where F: Fn(&mut Event) -> EventResult + 'static
+    {
+        self.with(|s| s.set_on_capture(cb))
+    }
+
     /// Adds a new full-screen layer on top of the stack.
     ///
     /// Fullscreen layers have no shadow.
     pub fn add_fullscreen_layer<T>(&mut self, view: T)
         where T: 'static + View
     {
-        self.layers.push(Child {
+        self.insert_layer(Child {
             view: Box::new(Layer::new(view)),
             size: Vec2::zero(),
             placement: Placement::Fullscreen,
             virgin: true,
+            always_on_top: false,
+            dim: Dim::None,
         });
     }
 
@@ -102,7 +161,7 @@ impl StackView {
     pub fn add_layer_at<T>(&mut self, position: Position, view: T)
         where T: 'static + View
     {
-        self.layers.push(Child {
+        self.insert_layer(Child {
             // Skip padding for absolute/parent-placed views
             view: Box::new(ShadowView::new(Layer::new(view))
                 .top_padding(position.y == Offset::Center)
@@ -110,6 +169,8 @@ impl StackView {
             size: Vec2::new(0, 0),
             placement: Placement::Floating(position),
             virgin: true,
+            always_on_top: false,
+            dim: Dim::None,
         });
     }
 
@@ -122,33 +183,294 @@ impl StackView {
         self.with(|s| s.add_layer_at(position, view))
     }
 
+    /// Adds a popup on top of the stack, next to `anchor`.
+    ///
+    /// The popup goes on the `preferred` side of `anchor`, unless it only
+    /// fits on the opposite side. See [`Rect::place_beside`].
+    ///
+    /// [`Rect::place_beside`]: ../view/struct.Rect.html#method.place_beside
+    pub fn add_anchored_layer<T>(&mut self, anchor: Rect, view: T,
+                                 preferred: Absolute)
+        where T: 'static + View
+    {
+        self.insert_layer(Child {
+            view: Box::new(ShadowView::new(Layer::new(view))
+                .top_padding(false)
+                .left_padding(false)),
+            size: Vec2::zero(),
+            placement: Placement::Anchored(anchor, preferred),
+            virgin: true,
+            always_on_top: false,
+            dim: Dim::None,
+        });
+    }
+
+    /// Adds a modal view on top of the stack, in the center of the screen.
+    ///
+    /// Layers below are dimmed, as set by the `dim` style of the theme.
+    pub fn add_modal_layer<T>(&mut self, view: T)
+        where T: 'static + View
+    {
+        self.add_layer(view);
+        let i = self.active_len() - 1;
+        self.layers[i].dim = Dim::Theme;
+    }
+
+    /// Adds a modal view on top of the stack, in the center of the screen.
+    ///
+    /// Chainable variant.
+    pub fn modal_layer<T>(self, view: T) -> Self
+        where T: 'static + View
+    {
+        self.with(|s| s.add_modal_layer(view))
+    }
+
+    /// Sets how the layer at index `i` dims the layers below it.
+    ///
+    /// This overrides the style of the theme; `None` stops dimming.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn set_layer_dim(&mut self, i: usize, dim: Option<DimStyle>) {
+        self.layers[i].dim = match dim {
+            Some(dim) => Dim::Custom(dim),
+            None => Dim::None,
+        };
+    }
+
+    /// Adds a view always on top of the other layers.
+    ///
+    /// It never receives input, and stays until removed with
+    /// `remove_layer`.
+    pub fn add_overlay<T>(&mut self, position: Position, view: T)
+        where T: 'static + View
+    {
+        self.add_layer_at(position, view);
+        let i = self.active_len() - 1;
+        self.set_always_on_top(i, true);
+    }
+
+    /// Adds a view always on top of the other layers.
+    ///
+    /// Chainable variant.
+    pub fn overlay<T>(self, position: Position, view: T) -> Self
+        where T: 'static + View
+    {
+        self.with(|s| s.add_overlay(position, view))
+    }
+
+    // Adds a regular layer below the always-on-top ones.
+    fn insert_layer(&mut self, child: Child) {
+        let i = self.active_len();
+        self.layers.insert(i, child);
+    }
+
+    // Number of layers which are not always on top.
+    fn active_len(&self) -> usize {
+        self.layers
+            .iter()
+            .position(|layer| layer.always_on_top)
+            .unwrap_or_else(|| self.layers.len())
+    }
+
     /// Remove the top-most layer.
+    ///
+    /// Always-on-top layers are not removed.
     pub fn pop_layer(&mut self) {
-        self.layers.pop();
+        let i = self.active_len();
+        if i > 0 {
+            self.layers.remove(i - 1);
+        }
+    }
+
+    /// Removes the layer at index `i`, counting from the bottom.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn remove_layer(&mut self, i: usize) {
+        self.layers.remove(i);
+    }
+
+    /// Returns the index of the layer containing the view with the given
+    /// ID, counting from the bottom.
+    pub fn find_layer_from_id(&mut self, id: &str) -> Option<usize> {
+        let selector = Selector::Id(id);
+        self.layers.iter_mut().position(|layer| {
+            let mut found = false;
+            layer.view.call_on_any(&selector, Box::new(|_| found = true));
+            found
+        })
+    }
+
+    /// Moves the layer at index `i` one step up.
+    ///
+    /// Layers never go above always-on-top layers, unless they are
+    /// themselves always on top.
+    pub fn raise_layer(&mut self, i: usize) {
+        if i + 1 < self.layers.len() &&
+           self.layers[i].always_on_top == self.layers[i + 1].always_on_top {
+            self.layers.swap(i, i + 1);
+        }
+    }
+
+    /// Moves the layer at index `i` one step down.
+    ///
+    /// Always-on-top layers never go below regular layers.
+    pub fn lower_layer(&mut self, i: usize) {
+        if i > 0 &&
+           self.layers[i].always_on_top == self.layers[i - 1].always_on_top {
+            self.layers.swap(i, i - 1);
+        }
+    }
+
+    /// Moves the layer at index `i` on top of the others, and returns its
+    /// new index.
+    ///
+    /// A regular layer becomes the active one, below always-on-top layers.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn move_to_front(&mut self, i: usize) -> usize {
+        let layer = self.layers.remove(i);
+        let i = if layer.always_on_top {
+            self.layers.len()
+        } else {
+            self.active_len()
+        };
+        self.layers.insert(i, layer);
+        i
+    }
+
+    /// Moves the layer at index `i` below the others, and returns its new
+    /// index.
+    ///
+    /// Always-on-top layers stay above regular layers.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn move_to_back(&mut self, i: usize) -> usize {
+        let layer = self.layers.remove(i);
+        let i = if layer.always_on_top {
+            self.active_len()
+        } else {
+            0
+        };
+        self.layers.insert(i, layer);
+        i
+    }
+
+    /// Marks the layer at index `i` as always on top, or not, and returns
+    /// its new index.
+    ///
+    /// The layer is moved on top of the layers it now belongs with.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn set_always_on_top(&mut self, i: usize, always_on_top: bool)
+                             -> usize {
+        self.layers[i].always_on_top = always_on_top;
+        if always_on_top {
+            let layer = self.layers.remove(i);
+            self.layers.push(layer);
+            self.layers.len() - 1
+        } else {
+            self.move_to_front(i)
+        }
+    }
+
+    /// Returns `true` if the layer at index `i` is always on top.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn is_always_on_top(&self, i: usize) -> bool {
+        self.layers[i].always_on_top
     }
 
     /// Computes the offset of the current top view.
+    ///
+    /// Always-on-top layers are ignored.
     pub fn offset(&self) -> Vec2 {
-        let mut previous = Vec2::zero();
-        for layer in &self.layers {
-            let offset = layer.placement
-                .compute_offset(layer.size, self.last_size, previous);
-            previous = offset;
+        let i = self.active_len();
+        if i == 0 {
+            return Vec2::zero();
         }
-        previous
+        self.layer_offsets()[i - 1]
     }
 
     /// Returns the size for each layer in this view.
     pub fn layer_sizes(&self) -> Vec<Vec2> {
         self.layers.iter().map(|layer| layer.size).collect()
     }
+
+    /// Returns the offset of each layer in this view.
+    ///
+    /// Like sizes, offsets are only known after a layout.
+    pub fn layer_offsets(&self) -> Vec<Vec2> {
+        let mut previous = Vec2::zero();
+        self.layers
+            .iter()
+            .map(|layer| {
+                previous = layer.placement
+                    .compute_offset(layer.size, self.last_size, previous);
+                previous
+            })
+            .collect()
+    }
+
+    /// Returns the index of the layer receiving events, if any.
+    ///
+    /// This is the top-most layer which is not always on top.
+    pub fn focused_layer(&self) -> Option<usize> {
+        self.active_len().checked_sub(1)
+    }
+
+    /// Returns the number of layers, including the bottom one.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if there is no layer.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Returns the offset of the layer at index `i`, counting from the
+    /// bottom.
+    pub fn layer_offset(&self, i: usize) -> Option<Vec2> {
+        self.layer_offsets().get(i).cloned()
+    }
+
+    /// Returns the size of the layer at index `i`, counting from the bottom.
+    pub fn layer_size(&self, i: usize) -> Option<Vec2> {
+        self.layers.get(i).map(|layer| layer.size)
+    }
+
+    /// Moves the layer at index `i`, counting from the bottom.
+    ///
+    /// The position is computed again on each layout, so a layer placed
+    /// with `Position::top_right()` for instance stays in this corner when
+    /// the terminal is resized.
+    ///
+    /// A fullscreen layer becomes a floating one.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn reposition_layer(&mut self, i: usize, position: Position) {
+        self.layers[i].placement = Placement::Floating(position);
+    }
 }
 
 impl View for StackView {
     fn draw(&self, printer: &Printer) {
-        let last = self.layers.len();
+        let last = self.active_len();
         let mut previous = Vec2::zero();
         printer.with_color(ColorStyle::Primary, |printer| {
+            // Only the top-most modal layer dims the others.
+            let dim = self.layers[..last]
+                .iter()
+                .rposition(|layer| match layer.dim {
+                    Dim::None => false,
+                    _ => true,
+                })
+                .map(|i| match self.layers[i].dim {
+                    Dim::Custom(ref style) => (i, style),
+                    _ => (i, &printer.theme.dim),
+                })
+                .filter(|&(_, style)| style.is_visible());
+
             for (i, v) in self.layers.iter().enumerate() {
                 // Place the view
                 // Center the view
@@ -156,17 +478,37 @@ impl View for StackView {
                     .compute_offset(v.size, printer.size, previous);
 
                 previous = offset;
-                v.view
-                    .draw(&printer.sub_printer(offset, v.size, i + 1 == last));
+                let draw = |printer: &Printer| {
+                    v.view.draw(&printer.sub_printer(offset,
+                                                     v.size,
+                                                     i + 1 == last))
+                };
+                match dim {
+                    Some((modal, style)) if i < modal => {
+                        printer.with_palette(|palette| {
+                            *palette = palette.dimmed(style);
+                        }, draw)
+                    }
+                    _ => draw(printer),
+                }
             }
         });
     }
 
-    fn on_event(&mut self, event: Event) -> EventResult {
-        match self.layers.last_mut() {
-            None => EventResult::Ignored,
-            Some(v) => v.view.on_event(event),
+    fn on_event(&mut self, mut event: Event) -> EventResult {
+        if let Some(cb) = self.on_capture.clone() {
+            let result = cb(&mut event);
+            if result.is_consumed() {
+                return result;
+            }
         }
+
+        let last = self.active_len();
+        let result = match last {
+            0 => EventResult::Ignored,
+            _ => self.layers[last - 1].view.on_event(event),
+        };
+        result.resolve_on(self)
     }
 
     fn layout(&mut self, size: Vec2) {
@@ -178,6 +520,7 @@ impl View for StackView {
         for layer in &mut self.layers {
             // Give each guy what he asks for, within the budget constraints.
             let size = Vec2::min(size, layer.view.required_size(size));
+            let size = layer.placement.fit(size, self.last_size);
             layer.size = size;
             layer.view.layout(layer.size);
 
@@ -202,12 +545,18 @@ impl View for StackView {
     }
 
     fn take_focus(&mut self, source: Direction) -> bool {
-        match self.layers.last_mut() {
-            None => false,
-            Some(mut v) => v.view.take_focus(source),
+        let last = self.active_len();
+        match last {
+            0 => false,
+            _ => self.layers[last - 1].view.take_focus(source),
         }
     }
 
+    fn describe(&self) -> Option<String> {
+        let last = self.active_len();
+        self.layers[..last].last()?.view.describe()
+    }
+
     fn call_on_any<'a>(&mut self, selector: &Selector,
                     mut callback: Box<FnMut(&mut Any) + 'a>) {
         for layer in &mut self.layers {
@@ -225,3 +574,36 @@ impl View for StackView {
         Err(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::StackView;
+    use view::{Position, View};
+    use views::TextView;
+
+    #[test]
+    fn test_always_on_top() {
+        let mut stack = StackView::new()
+            .layer(TextView::new("a"))
+            .overlay(Position::center(), TextView::new("fps"))
+            .layer(TextView::new("b"));
+        assert!(stack.is_always_on_top(2));
+        assert_eq!(stack.describe(), Some("b".to_string()));
+        assert_eq!(stack.focused_layer(), Some(1));
+
+        // Overlays stay on top.
+        stack.raise_layer(1);
+        assert!(stack.is_always_on_top(2));
+        assert_eq!(stack.move_to_front(0), 1);
+        assert_eq!(stack.describe(), Some("a".to_string()));
+
+        stack.pop_layer();
+        stack.pop_layer();
+        stack.pop_layer();
+        assert_eq!(stack.len(), 1);
+        assert_eq!(stack.focused_layer(), None);
+        assert_eq!(stack.set_always_on_top(0, false), 0);
+        stack.pop_layer();
+        assert!(stack.is_empty());
+    }
+}
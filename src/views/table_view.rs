@@ -0,0 +1,843 @@
+use {Cursive, Printer, With};
+use align::HAlign;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::borrow::Cow;
+use std::cmp::{max, min};
+#[cfg(feature = "csv")]
+use std::io::{self, BufRead, BufReader, Read};
+#[cfg(feature = "csv")]
+use std::mem;
+use std::rc::Rc;
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+#[cfg(feature = "format")]
+use utils::format;
+use utils::i18n;
+use vec::Vec2;
+use view::{ScrollBase, ScrollableDataSource, View};
+use views::{EditView, SelectView};
+
+struct Column {
+    title: String,
+    // Values offered when editing, if the column isn't free text.
+    choices: Option<Vec<String>>,
+    align: HAlign,
+    // Turns a cell into the text shown, if it isn't shown as is.
+    formatter: Option<Rc<Fn(&str) -> String>>,
+}
+
+impl Column {
+    fn new(title: String, choices: Option<Vec<String>>) -> Self {
+        Column {
+            title: title,
+            choices: choices,
+            align: HAlign::Left,
+            formatter: None,
+        }
+    }
+
+    // Returns the text shown for `cell`.
+    fn show<'a>(&self, cell: &'a str) -> Cow<'a, str> {
+        match self.formatter {
+            Some(ref formatter) => Cow::Owned(formatter(cell)),
+            None => Cow::Borrowed(cell),
+        }
+    }
+}
+
+enum Editor {
+    Text(EditView),
+    Choice(SelectView<String>),
+}
+
+impl Editor {
+    fn value(&self) -> String {
+        match *self {
+            Editor::Text(ref view) => view.get_content().to_string(),
+            Editor::Choice(ref view) => view.selection().to_string(),
+        }
+    }
+
+    fn view(&self) -> &View {
+        match *self {
+            Editor::Text(ref view) => view,
+            Editor::Choice(ref view) => view,
+        }
+    }
+
+    fn view_mut(&mut self) -> &mut View {
+        match *self {
+            Editor::Text(ref mut view) => view,
+            Editor::Choice(ref mut view) => view,
+        }
+    }
+}
+
+// The titles and the delimiter below them.
+const HEADER: usize = 2;
+
+// Blank, delimiter, blank.
+const SEPARATOR: usize = 3;
+
+/// Rows of text, shown in columns.
+///
+/// The arrow keys move between cells, and the selected cell is
+/// highlighted.
+///
+/// # Editing
+///
+/// Once made editable, `<Enter>` on a cell opens an editor in place: an
+/// `EditView`, or a `SelectView` for columns with a fixed set of choices.
+/// `<Enter>` again keeps the new value, and runs the `on_cell_edit`
+/// callback; `<Esc>` cancels.
+///
+/// # Data sources
+///
+/// Rows can also come from a `ScrollableDataSource`: only the visible rows
+/// are fetched, and column widths only depend on them. Edits then only
+/// change the fetched rows, so `on_cell_edit` should save them to the
+/// source.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::TableView;
+/// let table = TableView::new()
+///     .column("Name")
+///     .choice_column("Role", vec!["admin", "user"])
+///     .row(vec!["alice", "admin"])
+///     .row(vec!["bob", "user"])
+///     .editable()
+///     .on_cell_edit(|_, row, column, value| {
+///         // Save the change somewhere.
+///     });
+/// ```
+pub struct TableView {
+    columns: Vec<Column>,
+    // All rows, or only those fetched from the source.
+    rows: Vec<Vec<String>>,
+    // Index of the first row of `rows`.
+    first_row: usize,
+    source: Option<Box<ScrollableDataSource<Row = Vec<String>>>>,
+    // The rows must be fetched again.
+    stale: bool,
+    // Selected cell: (row, column).
+    focus: (usize, usize),
+    editable: bool,
+    editor: Option<Editor>,
+    on_cell_edit: Option<Rc<Fn(&mut Cursive, usize, usize, &str)>>,
+    // Width of each column.
+    widths: Vec<usize>,
+    scrollbase: ScrollBase,
+}
+
+new_default!(TableView);
+
+impl TableView {
+    /// Creates a new, empty table.
+    pub fn new() -> Self {
+        TableView {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            first_row: 0,
+            source: None,
+            stale: false,
+            focus: (0, 0),
+            editable: false,
+            editor: None,
+            on_cell_edit: None,
+            widths: Vec::new(),
+            scrollbase: ScrollBase::new(),
+        }
+    }
+
+    /// Adds a column, edited as free text.
+    pub fn add_column<S: Into<String>>(&mut self, title: S) {
+        self.columns.push(Column::new(title.into(), None));
+    }
+
+    /// Adds a column, edited as free text.
+    ///
+    /// Chainable variant.
+    pub fn column<S: Into<String>>(self, title: S) -> Self {
+        self.with(|s| s.add_column(title))
+    }
+
+    /// Adds a column, edited by picking one of `choices`.
+    pub fn add_choice_column<S, I, C>(&mut self, title: S, choices: I)
+        where S: Into<String>,
+              I: IntoIterator<Item = C>,
+              C: Into<String>
+    {
+        let choices: Vec<String> = choices.into_iter()
+            .map(Into::into)
+            .collect();
+        self.columns.push(Column::new(title.into(), Some(choices)));
+    }
+
+    /// Adds a column, edited by picking one of `choices`.
+    ///
+    /// Chainable variant.
+    pub fn choice_column<S, I, C>(self, title: S, choices: I) -> Self
+        where S: Into<String>,
+              I: IntoIterator<Item = C>,
+              C: Into<String>
+    {
+        self.with(|s| s.add_choice_column(title, choices))
+    }
+
+    /// Sets the alignment of a column, title included.
+    ///
+    /// Does nothing if `column` doesn't exist.
+    pub fn set_column_align(&mut self, column: usize, align: HAlign) {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.align = align;
+        }
+    }
+
+    /// Sets the alignment of a column, title included.
+    ///
+    /// Chainable variant.
+    pub fn column_align(self, column: usize, align: HAlign) -> Self {
+        self.with(|s| s.set_column_align(column, align))
+    }
+
+    /// Sets how the cells of a column are shown.
+    ///
+    /// Cells keep their value: `cell`, the editors and `on_cell_edit`
+    /// still see it. Does nothing if `column` doesn't exist.
+    pub fn set_column_formatter<F>(&mut self, column: usize, formatter: F)
+        where F: Fn(&str) -> String + 'static
+    {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.formatter = Some(Rc::new(formatter));
+        }
+    }
+
+    /// Sets how the cells of a column are shown.
+    ///
+    /// Chainable variant.
+    pub fn column_formatter<F>(self, column: usize, formatter: F) -> Self
+        where F: Fn(&str) -> String + 'static
+    {
+        self.with(|s| s.set_column_formatter(column, formatter))
+    }
+
+    /// Shows the numbers of a column with the current locale.
+    ///
+    /// Cells that are not numbers are shown as is.
+    ///
+    /// Only available with the `format` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::TableView;
+    /// let table = TableView::new()
+    ///     .column("Size")
+    ///     .number_column(0, 2)
+    ///     .row(vec!["1234.5"]);
+    /// ```
+    #[cfg(feature = "format")]
+    pub fn set_number_column(&mut self, column: usize, decimals: usize) {
+        self.set_column_formatter(column, move |cell| {
+            match cell.trim().parse::<f64>() {
+                Ok(value) => format::number(value, decimals),
+                Err(_) => cell.to_string(),
+            }
+        });
+    }
+
+    /// Shows the numbers of a column with the current locale.
+    ///
+    /// Chainable variant.
+    #[cfg(feature = "format")]
+    pub fn number_column(self, column: usize, decimals: usize) -> Self {
+        self.with(|s| s.set_number_column(column, decimals))
+    }
+
+    /// Reads a table from CSV data.
+    ///
+    /// The first record gives the column titles. Fields are separated by
+    /// `delimiter`, like `','`, or `'\t'` for TSV, and can be quoted with
+    /// `"`.
+    ///
+    /// All of `reader` is read and kept in memory before this returns.
+    /// Large data should rather be shown through a `ScrollableDataSource`.
+    ///
+    /// Columns where every value is a number are aligned to the right.
+    ///
+    /// Only available with the `csv` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::TableView;
+    /// let data = "name,size\nfoo.txt,120\n\"bar, baz.txt\",4\n";
+    /// let table = TableView::from_csv(data.as_bytes(), ',').unwrap();
+    /// assert_eq!(table.cell(1, 0), Some("bar, baz.txt"));
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn from_csv<R: Read>(reader: R, delimiter: char)
+                             -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let mut table = TableView::new();
+
+        if let Some(titles) = read_record(&mut reader, delimiter)? {
+            for title in titles {
+                table.add_column(title);
+            }
+        }
+        while let Some(record) = read_record(&mut reader, delimiter)? {
+            // Skip blank lines.
+            if record.len() == 1 && record[0].is_empty() {
+                continue;
+            }
+            table.rows.push(record);
+        }
+
+        for i in 0..table.columns.len() {
+            let mut cells = table.rows
+                .iter()
+                .filter_map(|cells| cells.get(i))
+                .filter(|cell| !cell.is_empty())
+                .peekable();
+            let numeric = cells.peek().is_some() &&
+                          cells.all(|cell| cell.trim().parse::<f64>().is_ok());
+            if numeric {
+                table.set_column_align(i, HAlign::Right);
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Adds a row, with one cell per column.
+    pub fn add_row<I, S>(&mut self, cells: I)
+        where I: IntoIterator<Item = S>,
+              S: Into<String>
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Adds a row, with one cell per column.
+    ///
+    /// Chainable variant.
+    pub fn row<I, S>(self, cells: I) -> Self
+        where I: IntoIterator<Item = S>,
+              S: Into<String>
+    {
+        self.with(|s| s.add_row(cells))
+    }
+
+    /// Shows the rows of `source`, fetched as they become visible.
+    ///
+    /// This replaces the rows added so far.
+    pub fn set_source<S>(&mut self, source: S)
+        where S: ScrollableDataSource<Row = Vec<String>> + 'static
+    {
+        self.clear();
+        self.source = Some(Box::new(source));
+        self.stale = true;
+    }
+
+    /// Shows the rows of `source`, fetched as they become visible.
+    ///
+    /// Chainable variant.
+    pub fn source<S>(self, source: S) -> Self
+        where S: ScrollableDataSource<Row = Vec<String>> + 'static
+    {
+        self.with(|s| s.set_source(source))
+    }
+
+    /// Fetches the visible rows from the source again on the next layout.
+    ///
+    /// Call this when the content of the source changed.
+    pub fn refresh(&mut self) {
+        self.stale = true;
+    }
+
+    /// Removes all rows, and the source if any. Columns are kept.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.first_row = 0;
+        self.source = None;
+        self.editor = None;
+        self.focus = (0, 0);
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        match self.source {
+            Some(ref source) => source.len(),
+            None => self.rows.len(),
+        }
+    }
+
+    /// Returns `true` if the table has no row.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the content of a cell, if it exists.
+    ///
+    /// With a source, only the visible rows are available.
+    pub fn cell(&self, row: usize, column: usize) -> Option<&str> {
+        row.checked_sub(self.first_row)
+            .and_then(|row| self.rows.get(row))
+            .and_then(|cells| cells.get(column))
+            .map(|cell| &cell[..])
+    }
+
+    /// Sets the content of a cell.
+    ///
+    /// Does nothing if `row` doesn't exist, or isn't visible with a source.
+    pub fn set_cell<S: Into<String>>(&mut self, row: usize, column: usize,
+                                     value: S) {
+        let row = match row.checked_sub(self.first_row) {
+            Some(row) => row,
+            None => return,
+        };
+        if let Some(cells) = self.rows.get_mut(row) {
+            if cells.len() <= column {
+                cells.resize(column + 1, String::new());
+            }
+            cells[column] = value.into();
+        }
+    }
+
+    /// Returns the selected cell, as `(row, column)`.
+    ///
+    /// Returns `None` if the table is empty.
+    pub fn selected_cell(&self) -> Option<(usize, usize)> {
+        if self.is_empty() || self.columns.is_empty() {
+            None
+        } else {
+            Some(self.focus)
+        }
+    }
+
+    /// Selects a cell. Out of range values are clamped.
+    pub fn set_selected_cell(&mut self, row: usize, column: usize) {
+        self.editor = None;
+        self.focus = (min(row, self.len().saturating_sub(1)),
+                      min(column, self.columns.len().saturating_sub(1)));
+        self.scrollbase.scroll_to(self.focus.0);
+    }
+
+    /// Sets whether cells can be edited.
+    pub fn set_editable(&mut self, editable: bool) {
+        self.editable = editable;
+        if !editable {
+            self.editor = None;
+        }
+    }
+
+    /// Lets cells be edited.
+    ///
+    /// Chainable variant.
+    pub fn editable(self) -> Self {
+        self.with(|s| s.set_editable(true))
+    }
+
+    /// Returns `true` if a cell is being edited.
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    /// Sets a callback to be used when a cell is edited.
+    ///
+    /// It is given the row and column of the cell, and its new content.
+    pub fn set_on_cell_edit<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, usize, usize, &str) + 'static
+    {
+        self.on_cell_edit = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when a cell is edited.
+    ///
+    /// Chainable variant.
+    pub fn on_cell_edit<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive, usize, usize, &str) + 'static
+    {
+        self.with(|s| s.set_on_cell_edit(cb))
+    }
+
+    fn start_editing(&mut self) {
+        let (row, column) = self.focus;
+        let value = self.cell(row, column).unwrap_or("").to_string();
+
+        let editor = match self.columns[column].choices {
+            Some(ref choices) if !choices.is_empty() => {
+                let mut select = SelectView::new()
+                    .with_all_str(choices.clone());
+                if let Some(i) = choices.iter().position(|c| *c == value) {
+                    select.set_selection(i);
+                }
+                Editor::Choice(select)
+            }
+            _ => Editor::Text(EditView::new().content(value)),
+        };
+        self.editor = Some(editor);
+        self.layout_editor();
+    }
+
+    fn commit(&mut self) -> EventResult {
+        let value = match self.editor.take() {
+            Some(editor) => editor.value(),
+            None => return EventResult::Ignored,
+        };
+        let (row, column) = self.focus;
+        self.set_cell(row, column, value.clone());
+
+        match self.on_cell_edit.clone() {
+            Some(cb) => {
+                EventResult::with_cb(move |s| cb(s, row, column, &value))
+            }
+            None => EventResult::Consumed(None),
+        }
+    }
+
+    // Returns the size of the editor, which may cover the following rows.
+    fn editor_size(&self) -> Vec2 {
+        let width = self.widths.get(self.focus.1).cloned().unwrap_or(0);
+        let width = max(1, width);
+        let height = match self.editor {
+            Some(Editor::Choice(ref select)) => {
+                let below = (self.scrollbase.start_line +
+                             self.scrollbase.view_height)
+                    .saturating_sub(self.focus.0);
+                max(1, min(select.len(), below))
+            }
+            _ => 1,
+        };
+        Vec2::new(width, height)
+    }
+
+    fn layout_editor(&mut self) {
+        let size = self.editor_size();
+        if let Some(ref mut editor) = self.editor {
+            editor.view_mut().layout(size);
+        }
+    }
+
+    fn column_x(&self, column: usize) -> usize {
+        self.widths[..column].iter().sum::<usize>() + SEPARATOR * column
+    }
+
+    // Fetches the visible rows from the source, if needed.
+    fn fetch_rows(&mut self) {
+        let source = match self.source {
+            Some(ref mut source) => source,
+            None => return,
+        };
+
+        let len = source.len();
+        let start = min(self.scrollbase.start_line, len);
+        let end = min(len, start + self.scrollbase.view_height);
+        if self.stale || start != self.first_row ||
+           end - start != self.rows.len() {
+            self.rows = source.get_rows(start..end);
+            self.first_row = start;
+            self.stale = false;
+        }
+    }
+
+    fn compute_widths(&mut self) {
+        self.widths = self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.rows
+                    .iter()
+                    .filter_map(|cells| cells.get(i))
+                    .map(|cell| column.show(cell).width())
+                    .fold(column.title.width(), max)
+            })
+            .collect();
+    }
+
+    // Returns the text shown for a cell.
+    fn shown_cell(&self, row: usize, column: usize) -> Cow<str> {
+        self.columns[column].show(self.cell(row, column).unwrap_or(""))
+    }
+
+    fn cell_offset(&self, row: usize, column: usize) -> usize {
+        let cell = self.shown_cell(row, column);
+        let x = self.column_x(column);
+        x + self.columns[column].align.get_offset(cell.width(),
+                                                  self.widths[column])
+    }
+
+    fn move_focus(&mut self, key: Key) -> EventResult {
+        let (row, column) = self.focus;
+        let last_row = self.len().saturating_sub(1);
+        let last_column = self.columns.len().saturating_sub(1);
+        self.focus = match key {
+            Key::Up if row > 0 => (row - 1, column),
+            Key::Down if row < last_row => (row + 1, column),
+            Key::Left if column > 0 => (row, column - 1),
+            Key::Right if column < last_column => (row, column + 1),
+            Key::PageUp if row > 0 => (row.saturating_sub(10), column),
+            Key::PageDown if row < last_row => {
+                (min(row + 10, last_row), column)
+            }
+            Key::Home => (0, column),
+            Key::End => (last_row, column),
+            _ => return EventResult::Ignored,
+        };
+        self.scrollbase.scroll_to(self.focus.0);
+        EventResult::Consumed(None)
+    }
+}
+
+// Reads a CSV record, which may span several lines if a field is quoted.
+//
+// Returns `None` at the end of the input.
+#[cfg(feature = "csv")]
+fn read_record<B: BufRead>(reader: &mut B, delimiter: char)
+                           -> io::Result<Option<Vec<String>>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    loop {
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if quoted {
+                if c != '"' {
+                    field.push(c);
+                } else if chars.peek() == Some(&'"') {
+                    // Escaped quote.
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            } else if c == '"' {
+                quoted = true;
+            } else if c == delimiter {
+                fields.push(mem::replace(&mut field, String::new()));
+            } else if c != '\n' && c != '\r' {
+                field.push(c);
+            }
+        }
+
+        // The record goes on with the next line, unless the quote is never
+        // closed.
+        line.clear();
+        if !quoted || reader.read_line(&mut line)? == 0 {
+            break;
+        }
+    }
+    fields.push(field);
+
+    Ok(Some(fields))
+}
+
+impl View for TableView {
+    fn draw(&self, printer: &Printer) {
+        for (i, (column, &width)) in
+            self.columns.iter().zip(&self.widths).enumerate() {
+            let x = self.column_x(i) +
+                    column.align.get_offset(column.title.width(), width);
+            printer.with_color(ColorStyle::TitlePrimary, |printer| {
+                printer.print((x, 0), &column.title)
+            });
+        }
+        printer.print_hline((0, 1),
+                            printer.size.x,
+                            &printer.theme.glyphs.horizontal);
+
+        if printer.size.y <= HEADER {
+            return;
+        }
+
+        let size = printer.size - (0, HEADER);
+        let rows = printer.sub_printer((0, HEADER), size, true);
+        self.scrollbase.draw(&rows, |printer, i| {
+            for (j, width) in self.widths.iter().enumerate() {
+                let x = self.column_x(j);
+                if j > 0 {
+                    printer.print((x - 2, 0), &printer.theme.glyphs.vertical);
+                }
+
+                let cell = self.shown_cell(i, j);
+                if (i, j) != self.focus {
+                    printer.print((self.cell_offset(i, j), 0), &cell);
+                    continue;
+                }
+                let color = if printer.focused {
+                    ColorStyle::Highlight
+                } else {
+                    ColorStyle::HighlightInactive
+                };
+                printer.with_color(color, |printer| {
+                    printer.print_hline((x, 0), *width, " ");
+                    printer.print((self.cell_offset(i, j), 0), &cell);
+                });
+            }
+        });
+
+        // The editor is drawn on top, and may cover the following rows.
+        if let Some(ref editor) = self.editor {
+            let y = self.focus.0 - self.scrollbase.start_line;
+            let offset = (self.column_x(self.focus.1), y);
+            let printer = rows.sub_printer(offset, self.editor_size(), true);
+            editor.view().draw(&printer);
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        self.compute_widths();
+        let w = self.widths.iter().sum::<usize>() +
+                SEPARATOR * self.widths.len().saturating_sub(1);
+        let h = HEADER + self.len();
+
+        // Keep room for the scrollbar.
+        let w = if h > req.y { w + 2 } else { w };
+        Vec2::new(w, min(h, req.y))
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        let len = self.len();
+        self.scrollbase.set_heights(size.y.saturating_sub(HEADER), len);
+        self.fetch_rows();
+        self.compute_widths();
+        self.layout_editor();
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.editor.is_some() {
+            return match event {
+                Event::Key(Key::Enter) => self.commit(),
+                Event::Key(Key::Esc) => {
+                    self.editor = None;
+                    EventResult::Consumed(None)
+                }
+                event => {
+                    let editor = self.editor.as_mut().unwrap();
+                    editor.view_mut().on_event(event)
+                }
+            };
+        }
+
+        match event {
+            Event::Key(Key::Enter) if self.editable &&
+                                      self.selected_cell().is_some() => {
+                self.start_editing();
+                EventResult::Consumed(None)
+            }
+            Event::Key(key) => self.move_focus(key),
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.selected_cell().is_some()
+    }
+
+    fn describe(&self) -> Option<String> {
+        let (row, column) = self.selected_cell()?;
+        Some(format!("{}, {}, {} {} {}",
+                     self.columns[column].title,
+                     self.cell(row, column).unwrap_or(""),
+                     row + 1,
+                     i18n::translate("of"),
+                     self.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "csv")]
+    use align::HAlign;
+    use super::TableView;
+    use event::{Event, EventResult, Key};
+    use vec::Vec2;
+    use view::View;
+
+    #[test]
+    fn test_edit() {
+        let mut table = TableView::new()
+            .column("Name")
+            .choice_column("Role", vec!["admin", "user"])
+            .row(vec!["alice", "admin"])
+            .row(vec!["bob", "user"])
+            .editable();
+        table.layout(Vec2::new(20, 10));
+
+        table.on_event(Event::Key(Key::Down));
+        table.on_event(Event::Key(Key::Enter));
+        assert!(table.is_editing());
+        table.on_event(Event::Char('!'));
+        table.on_event(Event::Key(Key::Enter));
+        assert_eq!(table.cell(1, 0), Some("bob!"));
+
+        // Cancelling keeps the old value.
+        table.on_event(Event::Key(Key::Enter));
+        table.on_event(Event::Key(Key::Backspace));
+        table.on_event(Event::Key(Key::Esc));
+        assert!(!table.is_editing());
+        assert_eq!(table.cell(1, 0), Some("bob!"));
+
+        // Choices start on the current value.
+        table.on_event(Event::Key(Key::Right));
+        table.on_event(Event::Key(Key::Enter));
+        table.on_event(Event::Key(Key::Up));
+        match table.on_event(Event::Key(Key::Enter)) {
+            EventResult::Consumed(None) => (),
+            _ => panic!("no callback was set"),
+        }
+        assert_eq!(table.cell(1, 1), Some("admin"));
+        assert_eq!(table.describe().unwrap(), "Role, admin, 2 of 2");
+    }
+
+    #[test]
+    fn test_source() {
+        let rows: Vec<Vec<String>> = (0..100)
+            .map(|i| vec![i.to_string()])
+            .collect();
+        let mut table = TableView::new().column("N").source(rows);
+        assert_eq!(table.len(), 100);
+
+        // Only the visible rows are fetched.
+        table.layout(Vec2::new(10, 5));
+        assert_eq!(table.rows.len(), 3);
+        table.on_event(Event::Key(Key::End));
+        table.layout(Vec2::new(10, 5));
+        assert_eq!(table.first_row, 97);
+        assert_eq!(table.cell(99, 0), Some("99"));
+        assert_eq!(table.cell(0, 0), None);
+    }
+
+    #[test]
+    fn test_column_formatter() {
+        let mut table = TableView::new()
+            .column("N")
+            .column_formatter(0, |cell| format!("[{}]", cell))
+            .row(vec!["12"]);
+        table.required_size(Vec2::new(10, 5));
+        assert_eq!(table.widths, vec![4]);
+        // The value itself doesn't change.
+        assert_eq!(table.cell(0, 0), Some("12"));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_from_csv() {
+        let data = "name\tsize\n\"multi\nline\"\t1.5\n\n\"a \"\"b\"\"\"\t\n";
+        let table = TableView::from_csv(data.as_bytes(), '\t').unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.cell(0, 0), Some("multi\nline"));
+        assert_eq!(table.cell(1, 0), Some("a \"b\""));
+        assert_eq!(table.cell(1, 1), Some(""));
+        assert_eq!(table.columns[0].align, HAlign::Left);
+        assert_eq!(table.columns[1].align, HAlign::Right);
+    }
+}
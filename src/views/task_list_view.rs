@@ -0,0 +1,319 @@
+use {Printer, With};
+use std::cmp::{max, min};
+use std::sync::{Arc, Mutex};
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::View;
+
+// Width of the progress bars, when enough space is available.
+const BAR_WIDTH: usize = 20;
+
+const SPINNER: [&'static str; 4] = ["|", "/", "-", "\\"];
+
+/// What a `TaskListView` does with finished tasks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Completed {
+    /// Finished tasks stay where they are.
+    Keep,
+    /// Finished tasks are moved after the running ones.
+    Archive,
+    /// Finished tasks are removed from the list.
+    Remove,
+}
+
+struct Task {
+    // (done, total), if known.
+    progress: Option<(usize, usize)>,
+    status: String,
+    ticks: usize,
+    finished: bool,
+    failed: bool,
+}
+
+/// Handle to update a task of a `TaskListView`.
+///
+/// It can be cloned and sent to other threads.
+#[derive(Clone)]
+pub struct TaskHandle {
+    task: Arc<Mutex<Task>>,
+}
+
+impl TaskHandle {
+    fn update<F: FnOnce(&mut Task)>(&self, f: F) {
+        // A panicking thread shouldn't prevent others from reporting.
+        match self.task.lock() {
+            Ok(mut task) => f(&mut task),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+
+    /// Sets the progress: `done` units out of `total`.
+    pub fn set_progress(&self, done: usize, total: usize) {
+        self.update(|task| task.progress = Some((done, total)));
+    }
+
+    /// Advances a task with an unknown total, animating its spinner.
+    pub fn tick(&self) {
+        self.update(|task| task.ticks = task.ticks.wrapping_add(1));
+    }
+
+    /// Sets the text shown after the progress.
+    pub fn set_status<S: Into<String>>(&self, status: S) {
+        let status = status.into();
+        self.update(|task| task.status = status);
+    }
+
+    /// Marks the task as successfully finished.
+    pub fn finish(&self) {
+        self.update(|task| {
+            task.finished = true;
+            if let Some((_, total)) = task.progress {
+                task.progress = Some((total, total));
+            }
+        });
+    }
+
+    /// Marks the task as failed, showing `reason` as its status.
+    pub fn fail<S: Into<String>>(&self, reason: S) {
+        let reason = reason.into();
+        self.update(|task| {
+            task.finished = true;
+            task.failed = true;
+            task.status = reason;
+        });
+    }
+
+    /// Returns `true` if the task is finished, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        let mut finished = false;
+        self.update(|task| finished = task.finished);
+        finished
+    }
+}
+
+/// Shows many named tasks, each with its own progress and status.
+///
+/// Each task is updated through a `TaskHandle`, which can be sent to the
+/// thread doing the work. Tasks with a known total show a bar, others a
+/// spinner.
+///
+/// The view only shows changes when it is redrawn, so the application
+/// should set a refresh rate with `Cursive::set_fps`.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{Completed, TaskListView};
+/// # use std::thread;
+/// let mut tasks = TaskListView::new().completed(Completed::Archive);
+///
+/// for name in &["a.txt", "b.txt"] {
+///     let handle = tasks.add_task(*name);
+///     thread::spawn(move || {
+///         for i in 0..100 {
+///             handle.set_progress(i, 100);
+///         }
+///         handle.finish();
+///     });
+/// }
+/// ```
+pub struct TaskListView {
+    tasks: Vec<(String, Arc<Mutex<Task>>)>,
+    completed: Completed,
+}
+
+new_default!(TaskListView);
+
+impl TaskListView {
+    /// Creates a new, empty list.
+    ///
+    /// Finished tasks are kept where they are.
+    pub fn new() -> Self {
+        TaskListView {
+            tasks: Vec::new(),
+            completed: Completed::Keep,
+        }
+    }
+
+    /// Sets what to do with finished tasks.
+    pub fn set_completed(&mut self, completed: Completed) {
+        self.completed = completed;
+    }
+
+    /// Sets what to do with finished tasks.
+    ///
+    /// Chainable variant.
+    pub fn completed(self, completed: Completed) -> Self {
+        self.with(|s| s.set_completed(completed))
+    }
+
+    /// Adds a task, and returns a handle to update it.
+    pub fn add_task<S: Into<String>>(&mut self, name: S) -> TaskHandle {
+        let task = Arc::new(Mutex::new(Task {
+                                            progress: None,
+                                            status: String::new(),
+                                            ticks: 0,
+                                            finished: false,
+                                            failed: false,
+                                        }));
+        self.tasks.push((name.into(), task.clone()));
+        TaskHandle { task: task }
+    }
+
+    /// Returns the number of tasks in the list.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if the list has no task.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Returns the number of tasks not finished yet.
+    pub fn running(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|&&(_, ref task)| !task_state(task, |t| t.finished))
+            .count()
+    }
+
+    /// Removes all finished tasks.
+    pub fn clear_finished(&mut self) {
+        self.tasks.retain(|&(_, ref task)| !task_state(task, |t| t.finished));
+    }
+
+    fn arrange(&mut self) {
+        match self.completed {
+            Completed::Keep => (),
+            Completed::Remove => self.clear_finished(),
+            Completed::Archive => {
+                // Stable: tasks keep their order in each group.
+                self.tasks
+                    .sort_by_key(|&(_, ref task)| {
+                                     task_state(task, |t| t.finished)
+                                 });
+            }
+        }
+    }
+
+    fn name_width(&self) -> usize {
+        self.tasks
+            .iter()
+            .map(|&(ref name, _)| name.width())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn task_state<T, F: FnOnce(&Task) -> T>(task: &Mutex<Task>, f: F) -> T {
+    match task.lock() {
+        Ok(task) => f(&task),
+        Err(poisoned) => f(&poisoned.into_inner()),
+    }
+}
+
+// Returns the readout of a task: its percentage, or a spinner.
+fn readout(task: &Task) -> String {
+    match task.progress {
+        Some((done, total)) if total > 0 => {
+            format!("{:3}%", min(100, 100 * done / total))
+        }
+        _ if task.finished => "done".to_string(),
+        _ => SPINNER[task.ticks % SPINNER.len()].to_string(),
+    }
+}
+
+impl View for TaskListView {
+    fn draw(&self, printer: &Printer) {
+        let name_width = self.name_width();
+        let bar_width = min(BAR_WIDTH,
+                            printer.size.x.saturating_sub(name_width + 8));
+
+        for (y, &(ref name, ref task)) in self.tasks.iter().enumerate() {
+            task_state(task, |task| {
+                let color = if task.finished && !task.failed {
+                    ColorStyle::Secondary
+                } else {
+                    ColorStyle::Primary
+                };
+                printer.with_color(color, |printer| {
+                    printer.print((0, y), name);
+                });
+
+                let mut x = name_width + 2;
+                if let Some((done, total)) = task.progress {
+                    let filled = if total == 0 {
+                        0
+                    } else {
+                        min(bar_width, bar_width * done / total)
+                    };
+                    printer.with_color(ColorStyle::Highlight, |printer| {
+                        printer.print_hline((x, y), filled, "█");
+                    });
+                    printer.with_color(ColorStyle::Secondary, |printer| {
+                        printer.print_hline((x + filled, y),
+                                            bar_width - filled,
+                                            "░");
+                    });
+                    x += bar_width + 1;
+                }
+                printer.print((x, y), &readout(task));
+                x += 5;
+
+                let color = if task.failed {
+                    ColorStyle::Tertiary
+                } else {
+                    ColorStyle::Secondary
+                };
+                printer.with_color(color, |printer| {
+                    printer.print((x, y), &task.status);
+                });
+            });
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let status = self.tasks
+            .iter()
+            .map(|&(_, ref task)| task_state(task, |t| t.status.width()))
+            .max()
+            .unwrap_or(0);
+        let w = self.name_width() + 2 + BAR_WIDTH + 1 + 5 + status;
+        Vec2::new(w, max(1, self.tasks.len()))
+    }
+
+    fn layout(&mut self, _: Vec2) {
+        self.arrange();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Completed, TaskListView};
+    use vec::Vec2;
+    use view::View;
+
+    #[test]
+    fn test_completed() {
+        let mut view = TaskListView::new().completed(Completed::Archive);
+        let a = view.add_task("a");
+        view.add_task("b");
+        a.set_progress(3, 4);
+        a.finish();
+        assert_eq!(view.running(), 1);
+
+        view.layout(Vec2::new(40, 2));
+        let names: Vec<_> = view.tasks
+            .iter()
+            .map(|&(ref name, _)| name.clone())
+            .collect();
+        assert_eq!(names, ["b", "a"]);
+        assert!(a.is_finished());
+
+        view.set_completed(Completed::Remove);
+        view.layout(Vec2::new(40, 2));
+        assert_eq!(view.len(), 1);
+    }
+}
@@ -0,0 +1,592 @@
+use Printer;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use libc;
+use std::cmp::min;
+use std::ffi::CStr;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use theme::{BaseColor, Color, ColorStyle};
+use vec::Vec2;
+use view::View;
+use vte;
+
+const BASE_COLORS: [BaseColor; 8] = [BaseColor::Black,
+                                     BaseColor::Red,
+                                     BaseColor::Green,
+                                     BaseColor::Yellow,
+                                     BaseColor::Blue,
+                                     BaseColor::Magenta,
+                                     BaseColor::Cyan,
+                                     BaseColor::White];
+
+// A character on the screen, with its attributes.
+#[derive(Clone, Copy)]
+struct Cell {
+    c: char,
+    front: Color,
+    back: Color,
+    reverse: bool,
+}
+
+impl Cell {
+    fn blank() -> Self {
+        Cell {
+            c: ' ',
+            front: Color::Dark(BaseColor::White),
+            back: Color::Dark(BaseColor::Black),
+            reverse: false,
+        }
+    }
+
+    fn color(&self) -> ColorStyle {
+        if self.reverse {
+            ColorStyle::Custom {
+                front: self.back,
+                back: self.front,
+            }
+        } else {
+            ColorStyle::Custom {
+                front: self.front,
+                back: self.back,
+            }
+        }
+    }
+}
+
+// Grid of cells, updated by the VT100 parser.
+struct Screen {
+    rows: Vec<Vec<Cell>>,
+    size: Vec2,
+    cursor: Vec2,
+    show_cursor: bool,
+    // Attributes for the next printed characters.
+    pen: Cell,
+}
+
+impl Screen {
+    fn new() -> Self {
+        Screen {
+            rows: Vec::new(),
+            size: Vec2::zero(),
+            cursor: Vec2::zero(),
+            show_cursor: true,
+            pen: Cell::blank(),
+        }
+    }
+
+    fn resize(&mut self, size: Vec2) {
+        for row in &mut self.rows {
+            row.resize(size.x, Cell::blank());
+        }
+        // Drop lines from the top, to keep the cursor line visible.
+        while self.rows.len() > size.y {
+            self.rows.remove(0);
+            self.cursor.y = self.cursor.y.saturating_sub(1);
+        }
+        while self.rows.len() < size.y {
+            self.rows.push(vec![Cell::blank(); size.x]);
+        }
+        self.size = size;
+        self.cursor.x = min(self.cursor.x, size.x.saturating_sub(1));
+        self.cursor.y = min(self.cursor.y, size.y.saturating_sub(1));
+    }
+
+    fn line_feed(&mut self) {
+        if self.cursor.y + 1 < self.size.y {
+            self.cursor.y += 1;
+        } else if self.size.y > 0 {
+            self.rows.remove(0);
+            self.rows.push(vec![Cell::blank(); self.size.x]);
+        }
+    }
+
+    // Clears cells `start..end` of the given row.
+    fn clear(&mut self, y: usize, start: usize, end: usize) {
+        if let Some(row) = self.rows.get_mut(y) {
+            let end = min(end, row.len());
+            for cell in &mut row[min(start, end)..end] {
+                *cell = Cell::blank();
+            }
+        }
+    }
+
+    fn set_graphics(&mut self, params: &[u16]) {
+        if params.is_empty() {
+            self.pen = Cell::blank();
+        }
+
+        let mut params = params.iter().cloned();
+        while let Some(p) = params.next() {
+            match p {
+                0 => self.pen = Cell::blank(),
+                7 => self.pen.reverse = true,
+                27 => self.pen.reverse = false,
+                30...37 => {
+                    self.pen.front = Color::Dark(BASE_COLORS[p as usize - 30])
+                }
+                39 => self.pen.front = Cell::blank().front,
+                40...47 => {
+                    self.pen.back = Color::Dark(BASE_COLORS[p as usize - 40])
+                }
+                49 => self.pen.back = Cell::blank().back,
+                90...97 => {
+                    self.pen.front = Color::Light(BASE_COLORS[p as usize - 90])
+                }
+                100...107 => {
+                    let color = BASE_COLORS[p as usize - 100];
+                    self.pen.back = Color::Light(color);
+                }
+                38 | 48 => {
+                    let color = match params.next() {
+                        Some(5) => params.next().map(indexed_color),
+                        Some(2) => {
+                            let r = params.next().unwrap_or(0) as u8;
+                            let g = params.next().unwrap_or(0) as u8;
+                            let b = params.next().unwrap_or(0) as u8;
+                            Some(Color::Rgb(r, g, b))
+                        }
+                        _ => None,
+                    };
+                    if let Some(color) = color {
+                        if p == 38 {
+                            self.pen.front = color;
+                        } else {
+                            self.pen.back = color;
+                        }
+                    }
+                }
+                // Other attributes are not supported.
+                _ => (),
+            }
+        }
+    }
+}
+
+// Converts a color from the 256-colors palette.
+fn indexed_color(i: u16) -> Color {
+    match i {
+        0...7 => Color::Dark(BASE_COLORS[i as usize]),
+        8...15 => Color::Light(BASE_COLORS[i as usize - 8]),
+        16...231 => {
+            let i = i as u8 - 16;
+            Color::RgbLowRes(i / 36, (i / 6) % 6, i % 6)
+        }
+        _ => {
+            let grey = (8 + 10 * (min(i, 255) - 232)) as u8;
+            Color::Rgb(grey, grey, grey)
+        }
+    }
+}
+
+// Returns the `i`-th parameter, replacing `0` and missing ones.
+fn param(params: &[u16], i: usize, default: usize) -> usize {
+    match params.get(i) {
+        Some(&0) | None => default,
+        Some(&p) => p as usize,
+    }
+}
+
+impl vte::Perform for Screen {
+    fn print(&mut self, c: char) {
+        if self.size.x == 0 || self.size.y == 0 {
+            return;
+        }
+        if self.cursor.x >= self.size.x {
+            self.cursor.x = 0;
+            self.line_feed();
+        }
+        let mut cell = self.pen;
+        cell.c = c;
+        self.rows[self.cursor.y][self.cursor.x] = cell;
+        self.cursor.x += 1;
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' | 0x0b | 0x0c => self.line_feed(),
+            b'\r' => self.cursor.x = 0,
+            0x08 => self.cursor.x = self.cursor.x.saturating_sub(1),
+            b'\t' => {
+                let next = (self.cursor.x / 8 + 1) * 8;
+                self.cursor.x = min(next, self.size.x.saturating_sub(1));
+            }
+            _ => (),
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &vte::Params, intermediates: &[u8],
+                    _: bool, action: char) {
+        let params: Vec<u16> = params.iter().map(|p| p[0]).collect();
+        let max_x = self.size.x.saturating_sub(1);
+        let max_y = self.size.y.saturating_sub(1);
+
+        if intermediates == b"?" {
+            // Private modes: we only know about the cursor visibility.
+            if params.contains(&25) {
+                match action {
+                    'h' => self.show_cursor = true,
+                    'l' => self.show_cursor = false,
+                    _ => (),
+                }
+            }
+            return;
+        }
+
+        let (x, y) = (self.cursor.x, self.cursor.y);
+        match action {
+            'A' => self.cursor.y = y.saturating_sub(param(&params, 0, 1)),
+            'B' => self.cursor.y = min(y + param(&params, 0, 1), max_y),
+            'C' => self.cursor.x = min(x + param(&params, 0, 1), max_x),
+            'D' => self.cursor.x = x.saturating_sub(param(&params, 0, 1)),
+            'G' => self.cursor.x = min(param(&params, 0, 1) - 1, max_x),
+            'd' => self.cursor.y = min(param(&params, 0, 1) - 1, max_y),
+            'H' | 'f' => {
+                self.cursor.y = min(param(&params, 0, 1) - 1, max_y);
+                self.cursor.x = min(param(&params, 1, 1) - 1, max_x);
+            }
+            'J' => {
+                let (width, height) = (self.size.x, self.size.y);
+                match params.get(0).cloned().unwrap_or(0) {
+                    0 => {
+                        self.clear(y, x, width);
+                        for row in y + 1..height {
+                            self.clear(row, 0, width);
+                        }
+                    }
+                    1 => {
+                        self.clear(y, 0, x + 1);
+                        for row in 0..y {
+                            self.clear(row, 0, width);
+                        }
+                    }
+                    _ => {
+                        for row in 0..height {
+                            self.clear(row, 0, width);
+                        }
+                    }
+                }
+            }
+            'K' => {
+                let width = self.size.x;
+                match params.get(0).cloned().unwrap_or(0) {
+                    0 => self.clear(y, x, width),
+                    1 => self.clear(y, 0, x + 1),
+                    _ => self.clear(y, 0, width),
+                }
+            }
+            'P' => {
+                // Delete characters, shifting the rest of the line.
+                if let Some(row) = self.rows.get_mut(y) {
+                    let n = min(param(&params, 0, 1), row.len() - x);
+                    for _ in 0..n {
+                        row.remove(x);
+                        row.push(Cell::blank());
+                    }
+                }
+            }
+            '@' => {
+                // Insert blank characters.
+                if let Some(row) = self.rows.get_mut(y) {
+                    let n = min(param(&params, 0, 1), row.len() - x);
+                    for _ in 0..n {
+                        row.pop();
+                        row.insert(x, Cell::blank());
+                    }
+                }
+            }
+            'L' => {
+                for _ in 0..min(param(&params, 0, 1), self.size.y - y) {
+                    self.rows.pop();
+                    self.rows.insert(y, vec![Cell::blank(); self.size.x]);
+                }
+            }
+            'M' => {
+                for _ in 0..min(param(&params, 0, 1), self.size.y - y) {
+                    self.rows.remove(y);
+                    self.rows.push(vec![Cell::blank(); self.size.x]);
+                }
+            }
+            'm' => self.set_graphics(&params),
+            _ => (),
+        }
+    }
+
+    fn esc_dispatch(&mut self, _: &[u8], _: bool, byte: u8) {
+        if byte == b'c' {
+            // Full reset.
+            let size = self.size;
+            *self = Screen::new();
+            self.resize(size);
+        }
+    }
+}
+
+/// Runs a command in a pseudo-terminal, and shows its output.
+///
+/// When focused, key presses are forwarded to the command.
+///
+/// Only a subset of VT100 is supported; `TERM` is set to `vt100` for the
+/// command.
+///
+/// The view only updates when cursive redraws, so you may want to call
+/// [`Cursive::set_fps`].
+///
+/// Requires the `terminal-view` feature, and only works on unix.
+///
+/// [`Cursive::set_fps`]: ../struct.Cursive.html#method.set_fps
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::views::TerminalView;
+/// # use std::process::Command;
+/// let view = TerminalView::spawn(Command::new("bash")).unwrap();
+/// ```
+pub struct TerminalView {
+    master: File,
+    child: Child,
+    status: Option<ExitStatus>,
+    output: mpsc::Receiver<Vec<u8>>,
+    parser: vte::Parser,
+    screen: Screen,
+}
+
+impl TerminalView {
+    /// Spawns the given command in a new pseudo-terminal.
+    pub fn spawn(mut command: Command) -> io::Result<Self> {
+        let master = open_master()?;
+        let slave = open_slave(&master)?;
+
+        command.env("TERM", "vt100")
+            .stdin(Stdio::from(slave.try_clone()?))
+            .stdout(Stdio::from(slave.try_clone()?))
+            .stderr(Stdio::from(slave));
+        unsafe {
+            command.pre_exec(|| {
+                // Make the pseudo-terminal the controlling terminal.
+                if libc::setsid() < 0 ||
+                   libc::ioctl(0, libc::TIOCSCTTY as _, 0) < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let child = command.spawn()?;
+
+        // Reading blocks, so do it in a separate thread.
+        let (sender, receiver) = mpsc::channel();
+        let mut reader = master.try_clone()?;
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            loop {
+                match reader.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        if sender.send(buffer[..n].to_vec()).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(TerminalView {
+            master: master,
+            child: child,
+            status: None,
+            output: receiver,
+            parser: vte::Parser::new(),
+            screen: Screen::new(),
+        })
+    }
+
+    /// Returns the exit status of the command, if it already exited.
+    pub fn exit_status(&mut self) -> Option<ExitStatus> {
+        if self.status.is_none() {
+            self.status = self.child.try_wait().ok().and_then(|s| s);
+        }
+        self.status
+    }
+
+    /// Sends raw bytes to the command, as if they were typed.
+    pub fn send(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.master.write_all(bytes)
+    }
+
+    fn set_window_size(&self, size: Vec2) {
+        let size = libc::winsize {
+            ws_row: size.y as u16,
+            ws_col: size.x as u16,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        unsafe {
+            libc::ioctl(self.master.as_raw_fd(),
+                        libc::TIOCSWINSZ as _,
+                        &size);
+        }
+    }
+}
+
+fn open_master() -> io::Result<File> {
+    unsafe {
+        let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // Closes `fd` if anything fails.
+        let master = File::from_raw_fd(fd);
+        // Other commands must not inherit it, or the terminal stays open
+        // after this one exits.
+        if libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) < 0 ||
+           libc::grantpt(fd) != 0 || libc::unlockpt(fd) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(master)
+    }
+}
+
+fn open_slave(master: &File) -> io::Result<File> {
+    let name = unsafe {
+        let name = libc::ptsname(master.as_raw_fd());
+        if name.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    };
+    OpenOptions::new().read(true).write(true).open(name)
+}
+
+// Returns the bytes a terminal would send for this event.
+fn event_bytes(event: &Event) -> Option<Vec<u8>> {
+    let bytes: &[u8] = match *event {
+        Event::Char(c) => return Some(c.to_string().into_bytes()),
+        Event::CtrlChar(c) => return Some(vec![(c as u8) & 0x1f]),
+        Event::AltChar(c) => {
+            return Some(format!("\x1b{}", c).into_bytes());
+        }
+        Event::Key(Key::Enter) => b"\r",
+        Event::Key(Key::Tab) => b"\t",
+        Event::Key(Key::Backspace) => b"\x7f",
+        Event::Key(Key::Esc) => b"\x1b",
+        Event::Key(Key::Up) => b"\x1b[A",
+        Event::Key(Key::Down) => b"\x1b[B",
+        Event::Key(Key::Right) => b"\x1b[C",
+        Event::Key(Key::Left) => b"\x1b[D",
+        Event::Key(Key::Home) => b"\x1b[H",
+        Event::Key(Key::End) => b"\x1b[F",
+        Event::Key(Key::Ins) => b"\x1b[2~",
+        Event::Key(Key::Del) => b"\x1b[3~",
+        Event::Key(Key::PageUp) => b"\x1b[5~",
+        Event::Key(Key::PageDown) => b"\x1b[6~",
+        _ => return None,
+    };
+    Some(bytes.to_vec())
+}
+
+impl View for TerminalView {
+    fn draw(&self, printer: &Printer) {
+        for (y, row) in self.screen.rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let mut color = cell.color();
+                if printer.focused && self.screen.show_cursor &&
+                   self.status.is_none() &&
+                   (x, y) == (self.screen.cursor.x, self.screen.cursor.y) {
+                    color = ColorStyle::Highlight;
+                }
+                printer.with_color(color, |printer| {
+                    printer.print((x, y), &cell.c.to_string());
+                });
+            }
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        // Take all the space we can get.
+        req
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        if size != self.screen.size {
+            self.screen.resize(size);
+            self.set_window_size(size);
+        }
+
+        // Layout happens on each step, so this is where we catch up.
+        while let Ok(bytes) = self.output.try_recv() {
+            for byte in bytes {
+                self.parser.advance(&mut self.screen, byte);
+            }
+        }
+        self.exit_status();
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.status.is_some() {
+            return EventResult::Ignored;
+        }
+
+        match event_bytes(&event) {
+            Some(bytes) => {
+                // The command may have exited in the meantime.
+                let _ = self.send(&bytes);
+                EventResult::Consumed(None)
+            }
+            None => EventResult::Ignored,
+        }
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
+
+impl Drop for TerminalView {
+    fn drop(&mut self) {
+        if self.exit_status().is_none() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(screen: &mut Screen, input: &str) {
+        let mut parser = vte::Parser::new();
+        for &byte in input.as_bytes() {
+            parser.advance(screen, byte);
+        }
+    }
+
+    fn row(screen: &Screen, y: usize) -> String {
+        screen.rows[y].iter().map(|cell| cell.c).collect()
+    }
+
+    #[test]
+    fn test_screen() {
+        let mut screen = Screen::new();
+        screen.resize(Vec2::new(4, 2));
+
+        feed(&mut screen, "abcdef");
+        assert_eq!(row(&screen, 0), "abcd");
+        assert_eq!(row(&screen, 1), "ef  ");
+
+        // Scrolls up on the last line.
+        feed(&mut screen, "\r\nxy");
+        assert_eq!(row(&screen, 0), "ef  ");
+        assert_eq!(row(&screen, 1), "xy  ");
+
+        feed(&mut screen, "\x1b[1;2H\x1b[K\x1b[2;1H\x1b[P");
+        assert_eq!(row(&screen, 0), "e   ");
+        assert_eq!(row(&screen, 1), "y   ");
+    }
+}
@@ -4,12 +4,15 @@ use {Printer, With, XY};
 use direction::Direction;
 use event::{Event, EventResult, Key};
 use odds::vec::VecExt;
+use std::mem;
 use theme::{ColorStyle, Effect};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
-use utils::{LinesIterator, Row, prefix};
+use utils::{LinesIterator, Row, i18n, prefix, rewrap};
 use vec::Vec2;
-use view::{ScrollBase, SizeCache, View};
+use view::{ScrollBase, Scrollable, SizeCache, View};
+use views::EditMode;
+use views::edit_mode::{Buffer, Keymap, Outcome};
 
 /// Multi-lines text editor.
 ///
@@ -33,6 +36,10 @@ pub struct TextArea {
 
     /// Byte offset of the currently selected grapheme.
     cursor: usize,
+
+    /// Key bindings, or `None` to use the global ones.
+    edit_mode: Option<EditMode>,
+    keymap: Keymap,
 }
 
 fn make_rows(text: &str, width: usize) -> Vec<Row> {
@@ -53,6 +60,8 @@ impl TextArea {
             scrollbase: ScrollBase::new().right_padding(0),
             last_size: None,
             cursor: 0,
+            edit_mode: None,
+            keymap: Keymap::new(),
         }
     }
 
@@ -80,6 +89,39 @@ impl TextArea {
         self.with(|s| s.set_content(content))
     }
 
+    /// Sets the key bindings used to edit the content.
+    ///
+    /// Defaults to `EditMode::global()`.
+    pub fn set_edit_mode(&mut self, mode: EditMode) {
+        self.edit_mode = Some(mode);
+    }
+
+    /// Sets the key bindings used to edit the content.
+    ///
+    /// Chainable variant.
+    pub fn edit_mode(self, mode: EditMode) -> Self {
+        self.with(|s| s.set_edit_mode(mode))
+    }
+
+    /// Returns the current scroll position.
+    pub fn scroll_offset(&self) -> Vec2 {
+        self.scrollbase.scroll_offset()
+    }
+
+    /// Sets the scroll position.
+    ///
+    /// Only `offset.y` is used, as this view only scrolls vertically.
+    pub fn set_scroll_offset<O: Into<Vec2>>(&mut self, offset: O) {
+        self.scrollbase.set_scroll_offset(offset.into());
+    }
+
+    /// Scrolls to the end of the content.
+    ///
+    /// The cursor doesn't move: moving it later scrolls back to it.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scrollbase.scroll_bottom();
+    }
+
     /// Finds the row containing the grapheme at the given offset
     fn row_at(&self, offset: usize) -> usize {
         // println_stderr!("Offset: {}", offset);
@@ -192,6 +234,7 @@ impl TextArea {
                 start: self.content.len(),
                 end: self.content.len(),
                 width: 0,
+                hyphenated: false,
             });
         }
     }
@@ -362,6 +405,64 @@ impl TextArea {
     }
 }
 
+impl Buffer for TextArea {
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor;
+    }
+
+    fn replace(&mut self, start: usize, end: usize, text: &str) {
+        for _ in self.content.drain(start..end) {}
+        self.content.insert_str(start, text);
+
+        let size = match self.last_size {
+            Some(size) => size.map(|s| s.value),
+            // We'll get a layout command soon.
+            None => return,
+        };
+
+        // Only the lines touched by the edit need to be wrapped again.
+        let scrollable = self.rows.len() > size.y;
+        let available = if scrollable { size.x - 1 } else { size.x };
+        rewrap(&mut self.rows,
+               &self.content,
+               start,
+               end,
+               start + text.len(),
+               |text| make_rows(text, available));
+        self.fix_ghost_row();
+
+        if (self.rows.len() > size.y) != scrollable {
+            // The scrollbar changes the available width: start over.
+            self.invalidate();
+            self.compute_rows(size);
+            return;
+        }
+        self.scrollbase.set_heights(size.y, self.rows.len());
+    }
+
+    fn multiline(&self) -> bool {
+        true
+    }
+}
+
+impl Scrollable for TextArea {
+    fn scroll_offset(&self) -> Vec2 {
+        TextArea::scroll_offset(self)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Vec2) {
+        TextArea::set_scroll_offset(self, offset);
+    }
+}
+
 impl View for TextArea {
     fn required_size(&mut self, constraint: Vec2) -> Vec2 {
         self.compute_rows(constraint);
@@ -419,37 +520,56 @@ impl View for TextArea {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        match event {
-            Event::Char(ch) => self.insert(ch),
-            Event::Key(Key::Enter) => self.insert('\n'),
-            Event::Key(Key::Backspace) if self.cursor > 0 => self.backspace(),
-            Event::Key(Key::Del) if self.cursor < self.content.len() => {
-                self.delete()
-            }
-
-            Event::Key(Key::End) => {
-                let row = self.selected_row();
-                self.cursor = self.rows[row].end;
-                if row + 1 < self.rows.len() &&
-                   self.cursor == self.rows[row + 1].start {
-                    self.move_left();
+        let mode = self.edit_mode.unwrap_or_else(EditMode::global);
+        let mut keymap = mem::replace(&mut self.keymap, Keymap::new());
+        let outcome = keymap.on_event(mode, &event, self);
+        self.keymap = keymap;
+
+        match outcome {
+            Outcome::Ignored => return EventResult::Ignored,
+            Outcome::Consumed => (),
+            Outcome::Forward(event) => match event {
+                Event::Char(ch) => self.insert(ch),
+                Event::Key(Key::Enter) => self.insert('\n'),
+                Event::Key(Key::Backspace) if self.cursor > 0 => {
+                    self.backspace()
                 }
-            }
-            Event::Ctrl(Key::Home) => self.cursor = 0,
-            Event::Ctrl(Key::End) => self.cursor = self.content.len(),
-            Event::Key(Key::Home) => {
-                self.cursor = self.rows[self.selected_row()].start
-            }
-            Event::Key(Key::Up) if self.selected_row() > 0 => self.move_up(),
-            Event::Key(Key::Down) if self.selected_row() + 1 <
-                                     self.rows.len() => self.move_down(),
-            Event::Key(Key::PageUp) => self.page_up(),
-            Event::Key(Key::PageDown) => self.page_down(),
-            Event::Key(Key::Left) if self.cursor > 0 => self.move_left(),
-            Event::Key(Key::Right) if self.cursor < self.content.len() => {
-                self.move_right()
-            }
-            _ => return EventResult::Ignored,
+                Event::Key(Key::Del) if self.cursor <
+                                        self.content.len() => self.delete(),
+
+                Event::Key(Key::End) => {
+                    let row = self.selected_row();
+                    self.cursor = self.rows[row].end;
+                    if row + 1 < self.rows.len() &&
+                       self.cursor == self.rows[row + 1].start {
+                        self.move_left();
+                    }
+                }
+                Event::Ctrl(Key::Home) => self.cursor = 0,
+                Event::Ctrl(Key::End) => {
+                    self.cursor = self.content.len()
+                }
+                Event::Key(Key::Home) => {
+                    self.cursor = self.rows[self.selected_row()].start
+                }
+                Event::Key(Key::Up) if self.selected_row() > 0 => {
+                    self.move_up()
+                }
+                Event::Key(Key::Down) if self.selected_row() + 1 <
+                                         self.rows.len() => {
+                    self.move_down()
+                }
+                Event::Key(Key::PageUp) => self.page_up(),
+                Event::Key(Key::PageDown) => self.page_down(),
+                Event::Key(Key::Left) if self.cursor > 0 => {
+                    self.move_left()
+                }
+                Event::Key(Key::Right) if self.cursor <
+                                          self.content.len() => {
+                    self.move_right()
+                }
+                _ => return EventResult::Ignored,
+            },
         }
 
         // println_stderr!("Rows: {:?}", self.rows);
@@ -463,7 +583,40 @@ impl View for TextArea {
         self.enabled
     }
 
+    fn describe(&self) -> Option<String> {
+        // The line with the cursor.
+        let start = self.content[..self.cursor]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let end = self.content[self.cursor..]
+            .find('\n')
+            .map_or(self.content.len(), |i| self.cursor + i);
+        Some(format!("{}, {}",
+                     i18n::translate("text area"),
+                     &self.content[start..end]))
+    }
+
     fn layout(&mut self, size: Vec2) {
         self.compute_rows(size);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TextArea;
+    use views::edit_mode::Buffer;
+    use view::View;
+
+    #[test]
+    fn test_replace() {
+        let mut area = TextArea::new().content("abc def\nghi\njkl mno");
+        area.layout((6, 10).into());
+
+        area.replace(5, 9, "x yz\n\n");
+        assert_eq!(area.get_content(), "abc dx yz\n\nhi\njkl mno");
+
+        let mut fresh = TextArea::new().content(area.get_content());
+        fresh.layout((6, 10).into());
+        assert_eq!(area.rows, fresh.rows);
+    }
+}
@@ -1,31 +1,388 @@
+use Cursive;
 use Printer;
 use With;
 use XY;
 use align::*;
 use direction::Direction;
 use event::*;
+use owning_ref::{ArcRef, OwningHandle};
 
+use std::borrow::Cow;
+use std::cmp::{Ordering, max, min};
+use std::fmt;
+use std::ops::{Deref, Range};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use theme::{ColorStyle, Style};
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
-use utils::{LinesIterator, Row};
+use utils::{Binding, Row, Span, StyledString, WordWrap, WrapStrategy,
+            rewrap};
+use utils::bidi::{self, BaseDirection, RowOrder};
 use vec::Vec2;
-use view::{SizeCache, View, ScrollBase, ScrollStrategy};
+use view::{SizeCache, View, ScrollBase, ScrollStrategy, Scrollable,
+           ScrollbarVisibility};
+
+/// Provides access to the content of a `TextView`.
+///
+/// Cloning this object will still point to the same content, so it can be
+/// used to update a `TextView` from anywhere, including other threads.
+///
+/// Changes will show up on the next event cycle. If you update the content
+/// from another thread, call [`Cursive::set_fps`] to regularly check for
+/// changes.
+///
+/// [`Cursive::set_fps`]: ../struct.Cursive.html#method.set_fps
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::{TextContent, TextView};
+/// let content = TextContent::new("content");
+/// let view = TextView::new_with_content(content.clone());
+///
+/// // Later, possibly in a different thread
+/// content.set_content("new content");
+/// assert_eq!(view.get_content(), "new content");
+/// ```
+#[derive(Clone)]
+pub struct TextContent {
+    content: Arc<Mutex<TextContentInner>>,
+}
+
+struct TextContentInner {
+    content: String,
+    spans: Vec<Span>,
+
+    // Number of newlines in the content.
+    newlines: usize,
+    // If set, only keep this many lines.
+    max_lines: Option<usize>,
+
+    // Incremented every time the content is replaced.
+    generation: usize,
+    // Number of bytes dropped from the beginning since the last replacement.
+    dropped: usize,
+    // What the last replacement changed, unless lines were dropped since.
+    edit: Option<Edit>,
+}
+
+// Part of the content changed by a replacement.
+#[derive(Clone, Copy)]
+struct Edit {
+    // State of the content before, to tell which rows this applies to.
+    dropped: usize,
+    end: usize,
+    ends_with_newline: bool,
+    // The bytes replaced, and the new content length.
+    start: usize,
+    old_end: usize,
+    new_end: usize,
+    len: usize,
+}
+
+// Returns the range to replace to turn `old` into `new`,
+// as `(start, old_end, new_end)`.
+fn changed_range(old: &str, new: &str) -> (usize, usize, usize) {
+    let prefix = old.bytes()
+        .zip(new.bytes())
+        .take_while(|&(a, b)| a == b)
+        .count();
+    let suffix = old.bytes()
+        .rev()
+        .zip(new.bytes().rev())
+        .take(min(old.len(), new.len()) - prefix)
+        .take_while(|&(a, b)| a == b)
+        .count();
+    (prefix, old.len() - suffix, new.len() - suffix)
+}
+
+/// A reference to the text in a `TextContent`.
+///
+/// The content is locked as long as this reference is alive,
+/// so don't keep it around.
+pub struct TextContentRef {
+    handle: OwningHandle<ArcRef<Mutex<TextContentInner>>,
+                         MutexGuard<'static, TextContentInner>>,
+}
+
+impl Deref for TextContentRef {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.handle.content
+    }
+}
+
+impl AsRef<str> for TextContentRef {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<'a> PartialEq<&'a str> for TextContentRef {
+    fn eq(&self, other: &&'a str) -> bool {
+        &**self == *other
+    }
+}
+
+impl PartialEq<str> for TextContentRef {
+    fn eq(&self, other: &str) -> bool {
+        &**self == other
+    }
+}
+
+impl fmt::Debug for TextContentRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl fmt::Display for TextContentRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl From<TextContentRef> for String {
+    fn from(content: TextContentRef) -> Self {
+        content.to_string()
+    }
+}
+
+impl TextContent {
+    /// Creates a new text content around the given text.
+    pub fn new<S: Into<String>>(content: S) -> Self {
+        let content = content.into();
+        TextContent {
+            content: Arc::new(Mutex::new(TextContentInner {
+                newlines: content.matches('\n').count(),
+                content: content,
+                spans: Vec::new(),
+                max_lines: None,
+                generation: 0,
+                dropped: 0,
+                edit: None,
+            })),
+        }
+    }
+
+    fn lock<'a>(&'a self) -> MutexGuard<'a, TextContentInner> {
+        self.content.lock().unwrap()
+    }
+
+    /// Replaces the text.
+    pub fn set_content<S: Into<String>>(&self, content: S) {
+        self.lock().set_content(content.into());
+    }
+
+    /// Appends (possibly styled) text to the end.
+    pub fn append<S: Into<StyledString>>(&self, content: S) {
+        self.lock().append(content.into());
+    }
+
+    /// Sets the maximum number of lines to keep.
+    ///
+    /// When more lines are added, the oldest ones are dropped.
+    ///
+    /// Defaults to `None`, which keeps everything.
+    pub fn set_max_lines(&self, max_lines: Option<usize>) {
+        let mut inner = self.lock();
+        inner.max_lines = max_lines;
+        inner.trim_lines();
+    }
+
+    /// Returns a reference to the current text.
+    ///
+    /// The content will be locked while this reference is alive.
+    pub fn get_content(&self) -> TextContentRef {
+        let arc_ref = ArcRef::new(self.content.clone());
+        TextContentRef {
+            handle: OwningHandle::new(arc_ref, |mutex| {
+                unsafe { mutex.as_ref() }.unwrap().lock().unwrap()
+            }),
+        }
+    }
+}
+
+impl TextContentInner {
+    fn set_content(&mut self, content: String) {
+        let (start, old_end, new_end) = changed_range(&self.content, &content);
+        self.edit = Some(Edit {
+                             dropped: self.dropped,
+                             end: self.dropped + self.content.len(),
+                             ends_with_newline: self.content.ends_with('\n'),
+                             start: start,
+                             old_end: old_end,
+                             new_end: new_end,
+                             len: content.len(),
+                         });
+
+        self.newlines = content.matches('\n').count();
+        self.content = content;
+        self.spans.clear();
+        self.generation += 1;
+        self.dropped = 0;
+        self.trim_lines();
+    }
+
+    fn append(&mut self, content: StyledString) {
+        let (source, spans) = content.into_parts();
+        let offset = self.content.len();
+
+        self.content.push_str(&source);
+        self.newlines += source.matches('\n').count();
+        self.spans.extend(spans.into_iter().map(|mut span| {
+            span.shift(offset);
+            span
+        }));
+        self.trim_lines();
+    }
+
+    // Drops the oldest lines if we have more than `max_lines`.
+    fn trim_lines(&mut self) {
+        let max_lines = match self.max_lines {
+            Some(max_lines) => max_lines,
+            None => return,
+        };
+
+        let lines = if self.content.is_empty() ||
+                       self.content.ends_with('\n') {
+            self.newlines
+        } else {
+            self.newlines + 1
+        };
+
+        if lines <= max_lines {
+            return;
+        }
+
+        let excess = lines - max_lines;
+        // Cut right after the last dropped line.
+        let cut = self.content
+            .match_indices('\n')
+            .nth(excess - 1)
+            .map_or(self.content.len(), |(i, _)| i + 1);
+
+        self.content.drain(..cut);
+        self.newlines = self.content.matches('\n').count();
+        self.dropped += cut;
+        self.edit = None;
+
+        self.spans.retain(|span| span.end > cut);
+        for span in &mut self.spans {
+            span.rev_shift(cut);
+        }
+    }
+
+    // Returns the span at the given offset in the content, if any.
+    fn span_at(&self, offset: usize) -> Option<&Span> {
+        self.spans
+            .binary_search_by(|span| if span.end <= offset {
+                Ordering::Less
+            } else if span.start > offset {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            })
+            .ok()
+            .map(|i| &self.spans[i])
+    }
+}
 
 /// A simple view showing a fixed text
 pub struct TextView {
-    content: String,
+    content: TextContent,
     rows: Vec<Row>,
+    // Display order of each row, computed along with `rows`.
+    orders: Vec<RowOrder>,
+    // Width used to compute `rows`, if they are up to date.
+    rows_width: Option<usize>,
+
+    // State of the content when `rows` were computed.
+    generation: usize,
+    dropped: usize,
+    // End of the content, including dropped bytes.
+    end: usize,
 
     align: Align,
+    direction: BaseDirection,
+    wrap: Box<WrapStrategy>,
 
     // If `false`, disable scrolling.
     scrollable: bool,
 
     // ScrollBase make many scrolling-related things easier
     scrollbase: ScrollBase,
-    scroll_strategy: ScrollStrategy,
     last_size: Option<XY<SizeCache>>,
     width: Option<usize>,
+
+    // Value to show, and the generation we last saw.
+    binding: Option<(Binding<String>, usize)>,
+
+    // Occurrences of a pattern to highlight, if any.
+    search: Option<Search>,
+
+    // Called when a link is activated.
+    on_link: Option<Rc<Fn(&mut Cursive, &str)>>,
+    // Start of the focused link, including dropped bytes.
+    focused_link: Option<usize>,
+
+    // If `true`, text can be selected with the keyboard.
+    selectable: bool,
+    // Anchor and cursor of the selection, including dropped bytes.
+    selection: Option<(usize, usize)>,
+    // Called with the selected text when it's copied.
+    on_copy: Option<Rc<Fn(&mut Cursive, &str)>>,
+
+    // Tells which rows stay pinned at the top when scrolled past.
+    sticky: Option<Box<Fn(&str) -> bool>>,
+}
+
+struct Search {
+    pattern: String,
+    style: Style,
+    // Byte ranges of the occurrences, including dropped bytes.
+    matches: Vec<(usize, usize)>,
+    // Index of the emphasized occurrence, if any.
+    current: Option<usize>,
+}
+
+impl Search {
+    // Finds the occurrences again, after the content changed.
+    fn update(&mut self, content: &str, dropped: usize) {
+        let current = self.current.map(|i| self.matches[i].0);
+
+        self.matches.clear();
+        if !self.pattern.is_empty() {
+            self.matches.extend(content.match_indices(&self.pattern[..])
+                .map(|(i, m)| (dropped + i, dropped + i + m.len())));
+        }
+
+        // Keep emphasizing the same occurrence, if it's still there.
+        self.current = current.and_then(|start| {
+            self.matches.iter().position(|&(s, _)| s == start)
+        });
+    }
+
+    // Returns the style at the given offset, including dropped bytes.
+    fn style_at(&self, offset: usize) -> Option<Style> {
+        self.matches
+            .binary_search_by(|&(start, end)| if end <= offset {
+                Ordering::Less
+            } else if start > offset {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            })
+            .ok()
+            .map(|i| if Some(i) == self.current {
+                Style::from(ColorStyle::Highlight)
+            } else {
+                self.style
+            })
+    }
 }
 
 // If the last character is a newline, strip it.
@@ -37,22 +394,89 @@ fn strip_last_newline(content: &str) -> &str {
     }
 }
 
+// Updates the rows after a replacement in `content`.
+//
+// Returns `false` if the rows need to be computed again instead.
+fn apply_edit(rows: &mut Vec<Row>, wrap: &WrapStrategy, content: &str,
+              edit: Edit, width: usize)
+              -> bool {
+    // The rows ignore the last newline.
+    let content = &content[..edit.len];
+    let stripped = strip_last_newline(content);
+    let old_len = edit.end - edit.dropped;
+    let old_stripped = if edit.ends_with_newline {
+        old_len - 1
+    } else {
+        old_len
+    };
+    if content.ends_with('\n') != edit.ends_with_newline ||
+       edit.new_end > stripped.len() ||
+       edit.old_end > old_stripped {
+        return false;
+    }
+
+    rewrap(rows,
+           stripped,
+           edit.start,
+           edit.old_end,
+           edit.new_end,
+           |text| wrap.wrap(text, width));
+    true
+}
+
 impl TextView {
     /// Creates a new TextView with the given content.
     pub fn new<S: Into<String>>(content: S) -> Self {
-        let content = content.into();
+        TextView::new_with_content(TextContent::new(content))
+    }
+
+    /// Creates a new TextView using the given shared content.
+    ///
+    /// Updates made through `content` will show up in this view.
+    pub fn new_with_content(content: TextContent) -> Self {
         TextView {
             content: content,
             rows: Vec::new(),
+            orders: Vec::new(),
+            rows_width: None,
+            generation: 0,
+            dropped: 0,
+            end: 0,
             scrollable: true,
             scrollbase: ScrollBase::new(),
-            scroll_strategy: ScrollStrategy::KeepRow,
             align: Align::top_left(),
+            direction: BaseDirection::Auto,
+            wrap: Box::new(WordWrap),
             last_size: None,
             width: None,
+            binding: None,
+            search: None,
+            on_link: None,
+            focused_link: None,
+            selectable: false,
+            selection: None,
+            on_copy: None,
+            sticky: None,
         }
     }
 
+    /// Binds this view to a shared value.
+    ///
+    /// The content is replaced by the value now, and every time it
+    /// changes.
+    pub fn set_binding(&mut self, binding: Binding<String>) {
+        let generation = binding.generation();
+        self.content.set_content(binding.get());
+        self.binding = Some((binding, generation));
+    }
+
+    /// Binds this view to a shared value.
+    ///
+    /// Chainable variant.
+    pub fn binding(self, binding: Binding<String>) -> Self {
+        self.with(|s| s.set_binding(binding))
+    }
+
     /// Creates a new empty `TextView`.
     pub fn empty() -> Self {
         TextView::new("")
@@ -76,6 +500,22 @@ impl TextView {
         self.with(|s| s.set_scrollable(scrollable))
     }
 
+    /// Sets when to show the scrollbar.
+    ///
+    /// Defaults to `ScrollbarVisibility::Auto`.
+    pub fn set_scrollbar_visibility(&mut self,
+                                    visibility: ScrollbarVisibility) {
+        self.scrollbase.visibility = visibility;
+    }
+
+    /// Sets when to show the scrollbar.
+    ///
+    /// Chainable variant.
+    pub fn scrollbar_visibility(self, visibility: ScrollbarVisibility)
+                                -> Self {
+        self.with(|s| s.set_scrollbar_visibility(visibility))
+    }
+
     /// Sets the horizontal alignment for this view.
     pub fn h_align(mut self, h: HAlign) -> Self {
         self.align.h = h;
@@ -97,6 +537,40 @@ impl TextView {
         self
     }
 
+    /// Sets the base direction of the text.
+    ///
+    /// Right-to-left paragraphs are reordered for display, and their
+    /// horizontal alignment is mirrored.
+    ///
+    /// Defaults to `BaseDirection::Auto`, which detects the direction of
+    /// each paragraph. Reordering requires the `unicode-bidi` feature.
+    pub fn set_base_direction(&mut self, direction: BaseDirection) {
+        self.direction = direction;
+        self.last_size = None;
+    }
+
+    /// Sets the base direction of the text.
+    ///
+    /// Chainable variant.
+    pub fn base_direction(self, direction: BaseDirection) -> Self {
+        self.with(|s| s.set_base_direction(direction))
+    }
+
+    /// Sets the strategy used to split the content into rows.
+    ///
+    /// Defaults to `WordWrap`.
+    pub fn set_wrap<W: WrapStrategy + 'static>(&mut self, wrap: W) {
+        self.wrap = Box::new(wrap);
+        self.invalidate();
+    }
+
+    /// Sets the strategy used to split the content into rows.
+    ///
+    /// Chainable variant.
+    pub fn wrap<W: WrapStrategy + 'static>(self, wrap: W) -> Self {
+        self.with(|s| s.set_wrap(wrap))
+    }
+
     /// Center the text horizontally and vertically inside the view.
     pub fn center(mut self) -> Self {
         self.align = Align::center();
@@ -112,20 +586,157 @@ impl TextView {
 
     /// Replace the text in this view.
     pub fn set_content<S: Into<String>>(&mut self, content: S) {
-        let content = content.into();
-        self.content = content;
-        self.invalidate();
+        self.content.set_content(content);
     }
 
     /// Append content to the end of a TextView.
     pub fn append_content(&mut self, content: &str) {
-        self.content.push_str(content);
-        self.invalidate();
+        self.append(content);
+    }
+
+    /// Append (possibly styled) content to the end of a TextView.
+    ///
+    /// Only the last line is wrapped again, so this stays cheap even with
+    /// a large content. This makes it suitable to stream logs for instance.
+    ///
+    /// See also `set_max_lines` to limit memory usage.
+    pub fn append<S: Into<StyledString>>(&mut self, content: S) {
+        self.content.append(content);
+    }
+
+    /// Sets the maximum number of lines to keep.
+    ///
+    /// When more lines are added, the oldest ones are dropped.
+    /// The scroll position stays on the same content.
+    ///
+    /// Defaults to `None`, which keeps everything.
+    pub fn set_max_lines(&mut self, max_lines: Option<usize>) {
+        self.content.set_max_lines(max_lines);
+    }
+
+    /// Sets the maximum number of lines to keep.
+    ///
+    /// Chainable variant.
+    pub fn max_lines(self, max_lines: usize) -> Self {
+        self.with(|s| s.set_max_lines(Some(max_lines)))
     }
 
     /// Returns the current text in this view.
-    pub fn get_content(&self) -> &str {
-        &self.content
+    ///
+    /// The content is locked while the returned reference is alive.
+    /// It derefs to `&str`, and compares with strings like one.
+    pub fn get_content(&self) -> TextContentRef {
+        self.content.get_content()
+    }
+
+    /// Returns a shared handle to the content of this view.
+    ///
+    /// It can be used to update the view from anywhere.
+    pub fn get_shared_content(&self) -> TextContent {
+        self.content.clone()
+    }
+
+    // Returns `true` if the content changed since the rows were computed.
+    fn content_changed(&self) -> bool {
+        if let Some((ref binding, seen)) = self.binding {
+            if binding.generation() != seen {
+                return true;
+            }
+        }
+
+        let content = self.content.lock();
+        content.generation != self.generation ||
+        content.dropped != self.dropped ||
+        content.dropped + content.content.len() != self.end
+    }
+
+    // Updates the rows after the content changed.
+    //
+    // Appended text only needs its last line to be wrapped again,
+    // and dropped lines just remove some rows.
+    fn sync_content(&mut self) {
+        if let Some((ref binding, ref mut seen)) = self.binding {
+            let generation = binding.generation();
+            if generation != *seen {
+                *seen = generation;
+                self.content.set_content(binding.get());
+            }
+        }
+
+        if !self.content_changed() {
+            return;
+        }
+
+        // The size may have changed.
+        self.last_size = None;
+
+        let content = self.content.lock();
+        if content.generation != self.generation {
+            // A single replacement only needs the lines it touched
+            // to be wrapped again.
+            let edit = content.edit
+                .filter(|edit| {
+                            content.generation == self.generation + 1 &&
+                            edit.dropped == self.dropped &&
+                            edit.end == self.end
+                        });
+            match (edit, self.rows_width) {
+                (Some(edit), Some(width)) if apply_edit(&mut self.rows,
+                                                       &*self.wrap,
+                                                       &content.content,
+                                                       edit,
+                                                       width) => {
+                    self.dropped = 0;
+                    self.end = edit.len;
+                }
+                // Everything changed.
+                _ => self.rows_width = None,
+            }
+        }
+        if content.dropped > self.end {
+            self.rows_width = None;
+        }
+
+        if let Some(width) = self.rows_width {
+            // Remove rows for dropped lines.
+            let cut = content.dropped - self.dropped;
+            let dropped = self.rows
+                .iter()
+                .position(|row| row.start >= cut)
+                .unwrap_or(self.rows.len());
+            self.rows.drain(..dropped);
+            for row in &mut self.rows {
+                row.rev_shift(cut);
+            }
+
+            // Keep showing the same content.
+            let start_line = self.scrollbase.start_line;
+            self.scrollbase.start_line = start_line.saturating_sub(dropped);
+
+            // The last line may have been continued,
+            // so it needs to be wrapped again.
+            let old_content = &content.content[..self.end - content.dropped];
+            let tail = strip_last_newline(old_content)
+                .rfind('\n')
+                .map_or(0, |i| i + 1);
+            let keep = self.rows
+                .iter()
+                .position(|row| row.start >= tail)
+                .unwrap_or(self.rows.len());
+            self.rows.truncate(keep);
+
+            let rows = self.wrap
+                .wrap(strip_last_newline(&content.content[tail..]), width);
+            self.rows.extend(rows.into_iter().map(|row| row.shifted(tail)));
+        }
+
+        self.generation = content.generation;
+        self.dropped = content.dropped;
+        self.end = content.dropped + content.content.len();
+
+        if let Some(ref mut search) = self.search {
+            search.update(&content.content, content.dropped);
+        }
     }
 
     fn is_cache_valid(&self, size: Vec2) -> bool {
@@ -141,10 +752,10 @@ impl TextView {
     /// when the size of the view or the content change.
     ///
     /// It is reset to `ScrollStrategy::KeepRow` whenever the user scrolls
-    /// manually.
+    /// manually, except for `ScrollStrategy::StickToBottom` when the user
+    /// stays at the bottom.
     pub fn set_scroll_strategy(&mut self, strategy: ScrollStrategy) {
-        self.scroll_strategy = strategy;
-        self.adjust_scroll();
+        self.scrollbase.set_strategy(strategy);
     }
 
     /// Defines the way scrolling is adjusted on content or size change.
@@ -154,18 +765,406 @@ impl TextView {
         self.with(|s| s.set_scroll_strategy(strategy))
     }
 
-    // Apply the scrolling strategy to the current scroll position.
-    //
-    // Called when computing rows and when applying a new strategy.
-    fn adjust_scroll(&mut self) {
-        match self.scroll_strategy {
-            ScrollStrategy::StickToTop => self.scrollbase.scroll_top(),
-            ScrollStrategy::StickToBottom => self.scrollbase.scroll_bottom(),
-            ScrollStrategy::KeepRow => (),
+    /// Returns the current scroll position.
+    pub fn scroll_offset(&self) -> Vec2 {
+        self.scrollbase.scroll_offset()
+    }
+
+    /// Sets the scroll position.
+    ///
+    /// Only `offset.y` is used, as this view only scrolls vertically.
+    pub fn set_scroll_offset<O: Into<Vec2>>(&mut self, offset: O) {
+        self.scrollbase.set_scroll_offset(offset.into());
+    }
+
+    /// Scrolls to the end of the text.
+    ///
+    /// This uses the rows from the last layout. To also follow content
+    /// added later, use `ScrollStrategy::StickToBottom`.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scrollbase.scroll_bottom();
+    }
+
+    /// Highlights all occurrences of `pattern` in the given style.
+    ///
+    /// Matching is exact and case-sensitive. Occurrences are found again
+    /// when the content changes. Use `next_match` and `prev_match` to
+    /// scroll to each occurrence in turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::TextView;
+    /// # use cursive::theme::ColorStyle;
+    /// let mut view = TextView::new("To be, or not to be");
+    /// view.highlight_matches("be", ColorStyle::TitlePrimary);
+    /// assert_eq!(view.match_count(), 2);
+    /// ```
+    pub fn highlight_matches<S: Into<Style>>(&mut self, pattern: &str,
+                                             style: S) {
+        let mut search = Search {
+            pattern: pattern.to_string(),
+            style: style.into(),
+            matches: Vec::new(),
+            current: None,
         };
+        {
+            let content = self.content.lock();
+            search.update(&content.content, content.dropped);
+        }
+        self.search = Some(search);
+    }
+
+    /// Removes the highlighting set by `highlight_matches`.
+    pub fn clear_matches(&mut self) {
+        self.search = None;
+    }
+
+    /// Returns the number of occurrences currently highlighted.
+    pub fn match_count(&self) -> usize {
+        self.search.as_ref().map_or(0, |search| search.matches.len())
+    }
+
+    /// Emphasizes the next occurrence, and scrolls to it.
+    ///
+    /// Goes back to the first occurrence after the last one.
+    ///
+    /// Returns `false` if there is no occurrence.
+    pub fn next_match(&mut self) -> bool {
+        self.move_match(true)
+    }
+
+    /// Emphasizes the previous occurrence, and scrolls to it.
+    ///
+    /// Goes back to the last occurrence before the first one.
+    ///
+    /// Returns `false` if there is no occurrence.
+    pub fn prev_match(&mut self) -> bool {
+        self.move_match(false)
+    }
+
+    fn move_match(&mut self, forward: bool) -> bool {
+        let start = match self.search {
+            Some(ref mut search) if !search.matches.is_empty() => {
+                let len = search.matches.len();
+                let i = match (search.current, forward) {
+                    (Some(i), true) => (i + 1) % len,
+                    (Some(i), false) => (i + len - 1) % len,
+                    (None, true) => 0,
+                    (None, false) => len - 1,
+                };
+                search.current = Some(i);
+                search.matches[i].0
+            }
+            _ => return false,
+        };
+
+        self.scroll_to_offset(start);
+        true
+    }
+
+    // Scrolls to the row showing the given offset, including dropped bytes.
+    fn scroll_to_offset(&mut self, offset: usize) {
+        // Without rows, we can't know where it is yet.
+        if self.rows_width.is_none() {
+            return;
+        }
+
+        let offset = offset.saturating_sub(self.dropped);
+        let row = self.rows
+            .iter()
+            .rposition(|row| row.start <= offset)
+            .unwrap_or(0);
+        self.scrollbase.scroll_to(row);
+        self.scrollbase.user_scrolled();
+    }
+
+    /// Sets a callback to run when a link is activated.
+    ///
+    /// Links are added with `StyledString::append_link`. When the view
+    /// has links, `<Tab>` and `<Shift+Tab>` move between them and `<Enter>`
+    /// activates the focused one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::TextView;
+    /// # use cursive::theme::ColorStyle;
+    /// # use cursive::utils::StyledString;
+    /// let mut text = StyledString::plain("Read the ");
+    /// text.append_link("manual", "help:manual", ColorStyle::Tertiary);
+    ///
+    /// let mut view = TextView::empty().on_link(|s, url| {
+    ///     if url == "help:manual" {
+    ///         s.quit();
+    ///     }
+    /// });
+    /// view.append(text);
+    /// ```
+    pub fn set_on_link<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, &str) + 'static
+    {
+        self.on_link = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to run when a link is activated.
+    ///
+    /// Chainable variant.
+    pub fn on_link<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive, &str) + 'static
+    {
+        self.with(|s| s.set_on_link(cb))
+    }
+
+    /// Keeps rows matching `is_sticky` pinned at the top when scrolled
+    /// past, like section titles or date separators in a log.
+    ///
+    /// `is_sticky` is given the text of each row. The last sticky row
+    /// above the view then replaces its first row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::TextView;
+    /// let log = TextView::new("-- Monday --\nstarted\n-- Tuesday --\nok")
+    ///     .sticky_rows(|row| row.starts_with("--"));
+    /// ```
+    pub fn set_sticky_rows<F>(&mut self, is_sticky: F)
+        where F: Fn(&str) -> bool + 'static
+    {
+        self.sticky = Some(Box::new(is_sticky));
+    }
+
+    /// Keeps rows matching `is_sticky` pinned at the top when scrolled
+    /// past.
+    ///
+    /// Chainable variant.
+    pub fn sticky_rows<F>(self, is_sticky: F) -> Self
+        where F: Fn(&str) -> bool + 'static
+    {
+        self.with(|s| s.set_sticky_rows(is_sticky))
+    }
+
+    /// Lets the user select text with the keyboard.
+    ///
+    /// `<Shift>` with the arrow keys, `<Home>` or `<End>` selects text,
+    /// starting at the top of the view. `<Ctrl+C>` copies it, and `<Esc>`
+    /// clears the selection.
+    ///
+    /// The copied text goes to the `on_copy` callback if there is one, and
+    /// to the terminal clipboard otherwise, with
+    /// [`Cursive::copy_to_clipboard`].
+    ///
+    /// [`Cursive::copy_to_clipboard`]:
+    /// ../struct.Cursive.html#method.copy_to_clipboard
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.selectable = selectable;
+        if !selectable {
+            self.selection = None;
+        }
+    }
+
+    /// Lets the user select text with the keyboard.
+    ///
+    /// Chainable variant.
+    pub fn selectable(self, selectable: bool) -> Self {
+        self.with(|s| s.set_selectable(selectable))
+    }
+
+    /// Sets a callback to run with the selected text when it's copied.
+    ///
+    /// It replaces the terminal clipboard, for applications having their
+    /// own.
+    pub fn set_on_copy<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive, &str) + 'static
+    {
+        self.on_copy = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to run with the selected text when it's copied.
+    ///
+    /// Chainable variant.
+    pub fn on_copy<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive, &str) + 'static
+    {
+        self.with(|s| s.set_on_copy(cb))
+    }
+
+    /// Selects bytes in `range` of the current content.
+    ///
+    /// The selection is clamped to the content.
+    pub fn set_selection(&mut self, range: Range<usize>) {
+        let content = self.content.lock();
+        let len = content.content.len();
+        let start = content.dropped + min(range.start, len);
+        let end = content.dropped + min(range.end, len);
+        self.selection = Some((start, end));
+    }
+
+    /// Returns the selected bytes of the current content, if any.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let (anchor, cursor) = self.selection?;
+        let content = self.content.lock();
+        let len = content.content.len();
+        let clamp = |offset: usize| {
+            min(offset.saturating_sub(content.dropped), len)
+        };
+        let (start, end) = (clamp(min(anchor, cursor)),
+                            clamp(max(anchor, cursor)));
+        if start == end { None } else { Some(start..end) }
+    }
+
+    /// Returns the selected text, if any.
+    pub fn selected_text(&self) -> Option<String> {
+        let range = self.selection()?;
+        Some(self.content.lock().content[range].to_string())
+    }
+
+    /// Clears the selection.
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
+    }
+
+    // Copies the selected text, to `on_copy` or the terminal clipboard.
+    fn copy_selection(&mut self) -> EventResult {
+        let text = match self.selected_text() {
+            Some(text) => text,
+            None => return EventResult::Ignored,
+        };
+
+        match self.on_copy.clone() {
+            Some(cb) => EventResult::with_cb(move |s| cb(s, &text)),
+            None => {
+                EventResult::with_cb(move |s| s.copy_to_clipboard(&text[..]))
+            }
+        }
+    }
+
+    // Moves the cursor of the selection, starting one if needed.
+    fn move_selection(&mut self, key: Key) -> EventResult {
+        let (anchor, cursor) = {
+            let content = self.content.lock();
+            if content.generation != self.generation || self.rows.is_empty() {
+                // We need a layout first.
+                return EventResult::Ignored;
+            }
+
+            // Rows start at `self.dropped`, text at `content.dropped`.
+            let text = strip_last_newline(&content.content);
+            let base = content.dropped;
+            let row_start = |i: usize| {
+                max(self.dropped + self.rows[i].start, base)
+            };
+            let row_end = |i: usize| {
+                max(self.dropped + self.rows[i].end, base)
+            };
+
+            let (anchor, cursor) = match self.selection {
+                Some(selection) => selection,
+                None => {
+                    let start = row_start(self.scrollbase.start_line);
+                    (start, start)
+                }
+            };
+            let cursor = min(max(cursor, base), base + text.len());
+            let row = (0..self.rows.len())
+                .rev()
+                .find(|&i| row_start(i) <= cursor)
+                .unwrap_or(0);
+            let i = cursor - base;
+
+            let moved = match key {
+                Key::Left => {
+                    let previous = text[..i].graphemes(true).next_back();
+                    cursor - previous.map_or(0, str::len)
+                }
+                Key::Right => {
+                    let next = text[i..].graphemes(true).next();
+                    cursor + next.map_or(0, str::len)
+                }
+                Key::Home => row_start(row),
+                Key::End => row_end(row),
+                Key::Up | Key::Down => {
+                    let target = match key {
+                        Key::Up if row > 0 => row - 1,
+                        Key::Down if row + 1 < self.rows.len() => row + 1,
+                        _ => row,
+                    };
+                    // Keep the same column, as far as the row allows.
+                    let column = text[row_start(row) - base..i].width();
+                    let mut offset = row_start(target);
+                    let mut width = 0;
+                    let graphemes =
+                        text[offset - base..row_end(target) - base]
+                            .graphemes(true);
+                    for g in graphemes {
+                        width += g.width();
+                        if width > column {
+                            break;
+                        }
+                        offset += g.len();
+                    }
+                    offset
+                }
+                _ => return EventResult::Ignored,
+            };
+            (anchor, moved)
+        };
+
+        self.selection = Some((anchor, cursor));
+        self.scroll_to_offset(cursor);
+        EventResult::Consumed(None)
+    }
+
+    /// Returns the target of the focused link, if any.
+    pub fn focused_link(&self) -> Option<String> {
+        let focused = self.focused_link?;
+
+        let content = self.content.lock();
+        content.spans
+            .iter()
+            .find(|span| content.dropped + span.start == focused)
+            .and_then(|span| span.link.clone())
+    }
+
+    // Returns the start of each link, including dropped bytes.
+    fn link_starts(&self) -> Vec<usize> {
+        let content = self.content.lock();
+        content.spans
+            .iter()
+            .filter(|span| span.link.is_some())
+            .map(|span| content.dropped + span.start)
+            .collect()
+    }
+
+    // Focuses the next or previous link.
+    fn move_link(&mut self, forward: bool) -> EventResult {
+        let links = self.link_starts();
+        let next = match (self.focused_link, forward) {
+            (Some(current), true) => {
+                links.iter().find(|&&start| start > current)
+            }
+            (Some(current), false) => {
+                links.iter().rev().find(|&&start| start < current)
+            }
+            (None, true) => links.first(),
+            (None, false) => links.last(),
+        };
+
+        match next.cloned() {
+            Some(start) => {
+                self.focused_link = Some(start);
+                self.scroll_to_offset(start);
+                EventResult::Consumed(None)
+            }
+            // Let the parent move the focus.
+            None => EventResult::Ignored,
+        }
     }
 
     fn compute_rows(&mut self, size: Vec2) {
+        self.sync_content();
+
         if self.is_cache_valid(size) {
             return;
         }
@@ -173,22 +1172,30 @@ impl TextView {
         // Completely bust the cache
         // Just in case we fail, we don't want to leave a bad cache.
         self.last_size = None;
+        self.orders.clear();
 
         if size.x == 0 {
             // Nothing we can do at this point.
             return;
         }
 
-        // First attempt: naively hope that we won't need a scrollbar_width
-        // (This means we try to use the entire available width for text).
-        self.rows = LinesIterator::new(strip_last_newline(&self.content),
-                                       size.x)
-            .collect();
+        let visibility = self.scrollbase.visibility;
+
+        // Rows computed with a scrollbar are still good if we need one.
+        let keep_rows = self.scrollable && size.x >= 2 &&
+                        self.rows_width == Some(size.x - 2) &&
+                        visibility.shown(self.rows.len() > size.y);
+
+        if !keep_rows {
+            // First attempt: naively hope that we won't need a scrollbar_width
+            // (This means we try to use the entire available width for text).
+            self.wrap_rows(size.x);
+        }
 
         // Width taken by the scrollbar. Without a scrollbar, it's 0.
         let mut scrollbar_width = 0;
 
-        if self.scrollable && self.rows.len() > size.y {
+        if self.scrollable && visibility.shown(self.rows.len() > size.y) {
             // We take 1 column for the bar itself + 1 spacing column
             scrollbar_width = 2;
 
@@ -199,9 +1206,10 @@ impl TextView {
 
             // If we're too high, include a scrollbar_width
             let available = size.x - scrollbar_width;
-            self.rows = LinesIterator::new(&self.content, available).collect();
+            self.wrap_rows(available);
 
-            if self.rows.is_empty() && !self.content.is_empty() {
+            let is_empty = self.content.lock().content.is_empty();
+            if self.rows.is_empty() && !is_empty {
                 // We have some content, we we didn't find any row for it?
                 // This probably means we couldn't even make a single row
                 // (for instance we only have 1 column and we have a wide character).
@@ -209,6 +1217,10 @@ impl TextView {
             }
         }
 
+        self.orders = bidi::row_orders(&self.content.lock().content,
+                                       &self.rows,
+                                       self.direction);
+
         // Desired width, including the scrollbar_width.
         self.width = self.rows
             .iter()
@@ -228,36 +1240,205 @@ impl TextView {
         // Build a fresh cache.
         self.last_size = Some(SizeCache::build(my_size, size));
 
-        // Adjust scrolling, in case we're sticking to the bottom for instance.
+        // This also adjusts scrolling, in case we're sticking to the bottom.
         self.scrollbase.set_heights(size.y, self.rows.len());
-        self.adjust_scroll();
+    }
+
+    // Splits the content into rows of `width` cells, unless it's already done.
+    fn wrap_rows(&mut self, width: usize) {
+        if self.rows_width == Some(width) {
+            return;
+        }
+
+        let content = self.content.lock();
+        self.rows = self.wrap.wrap(strip_last_newline(&content.content), width);
+        self.rows_width = Some(width);
     }
 
     // Invalidates the cache, so next call will recompute everything.
     fn invalidate(&mut self) {
         self.last_size = None;
+        self.rows_width = None;
     }
 }
 
 
+impl Scrollable for TextView {
+    fn scroll_offset(&self) -> Vec2 {
+        TextView::scroll_offset(self)
+    }
+
+    fn set_scroll_offset(&mut self, offset: Vec2) {
+        TextView::set_scroll_offset(self, offset);
+    }
+}
+
 impl View for TextView {
     fn draw(&self, printer: &Printer) {
+        let content = self.content.lock();
+        // The content may have been replaced since the layout.
+        // Until the next layout catches up, old rows show what still fits.
+        let stale = content.generation != self.generation;
+
+        // Lines may have been dropped since the layout.
+        let cut = if stale { 0 } else { content.dropped - self.dropped };
+
+        // Returns row `i`, relative to the current content.
+        let row_at = |i: usize| {
+            let mut row = self.rows[i];
+            if row.start < cut {
+                return None;
+            }
+            row.rev_shift(cut);
+            if stale {
+                let text = &content.content;
+                row.end = min(row.end, text.len());
+                if row.start > row.end || !text.is_char_boundary(row.start) ||
+                   !text.is_char_boundary(row.end) {
+                    return None;
+                }
+            }
+            Some(row)
+        };
 
         let h = self.rows.len();
         let offset = self.align.v.get_offset(h, printer.size.y);
         let printer =
             &printer.sub_printer(Vec2::new(0, offset), printer.size, true);
 
-        self.scrollbase.draw(printer, |printer, i| {
-            let row = &self.rows[i];
-            let text = &self.content[row.start..row.end];
-            let l = text.width();
-            let x = self.align.h.get_offset(l, printer.size.x);
-            printer.print((x, 0), text);
+        let is_sticky = |i: usize| match (self.sticky.as_ref(), row_at(i)) {
+            (Some(sticky), Some(row)) => {
+                sticky(&content.content[row.start..row.end])
+            }
+            _ => false,
+        };
+
+        self.scrollbase.draw_sticky(printer, is_sticky, |printer, i| {
+            let row = match row_at(i) {
+                Some(row) => row,
+                None => return,
+            };
+            let row = &row;
+
+            let text = &content.content;
+            let marker = self.wrap.marker(text, row).unwrap_or("");
+            let order = match self.orders.get(i).filter(|_| !stale) {
+                Some(order) => Cow::Borrowed(order),
+                // The layout didn't get that far, or the content changed.
+                None => {
+                    let rtl = bidi::is_rtl(text, row.start, self.direction);
+                    let visual = bidi::visual_graphemes(text,
+                                                        row.start..row.end,
+                                                        self.direction)
+                        .into_iter()
+                        .map(|(offset, g)| {
+                                 let start = offset - row.start;
+                                 start..start + g.len()
+                             })
+                        .collect();
+                    Cow::Owned(RowOrder {
+                                   rtl: rtl,
+                                   visual: Some(visual),
+                               })
+                }
+            };
+            let rtl = order.rtl;
+            let get_offset = |l| if rtl {
+                self.align.h.mirrored().get_offset(l, printer.size.x)
+            } else {
+                self.align.h.get_offset(l, printer.size.x)
+            };
+
+            let search = self.search
+                .as_ref()
+                .filter(|search| !search.matches.is_empty());
+
+            let selection = self.selection
+                .map(|(a, b)| (min(a, b), max(a, b)))
+                .filter(|&(start, end)| start < end);
+
+            if content.spans.is_empty() && search.is_none() &&
+               selection.is_none() {
+                let text = order.text(text, row);
+                let x = get_offset(text.width() + marker.width());
+                printer.print((x, 0), &text);
+                printer.print((x + text.width(), 0), marker);
+            } else {
+                let graphemes = order.graphemes(text, row);
+                let l: usize = graphemes.iter().map(|&(_, g)| g.width()).sum();
+                let mut x = get_offset(l + marker.width());
+                for &(offset, g) in &graphemes {
+                    let span = content.span_at(offset);
+                    let link = span.and_then(|span| span.link.as_ref());
+                    let start = span.map(|span| content.dropped + span.start);
+                    let focused = printer.focused && link.is_some() &&
+                                  start == self.focused_link;
+
+                    let offset = content.dropped + offset;
+                    let selected = selection.map_or(false, |(start, end)| {
+                        start <= offset && offset < end
+                    });
+
+                    let style = if focused || selected {
+                        Some(Style::from(ColorStyle::Highlight))
+                    } else {
+                        search.and_then(|search| {
+                                search.style_at(offset)
+                            })
+                            .or_else(|| span.map(|span| span.style))
+                    };
+
+                    let print = |printer: &Printer| match link {
+                        Some(url) => printer.print_link((x, 0), g, url),
+                        None => printer.print((x, 0), g),
+                    };
+                    match style {
+                        Some(style) => printer.with_style(style, print),
+                        None => print(printer),
+                    }
+                    x += g.width();
+                }
+                printer.print((x, 0), marker);
+            }
         });
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if event == Event::Refresh {
+            // Smooth scrolling moves a bit further on each tick.
+            self.scrollbase.animate();
+            return EventResult::Ignored;
+        }
+
+        if self.selectable {
+            match event {
+                Event::Shift(key) => {
+                    let result = self.move_selection(key);
+                    if result.is_consumed() {
+                        return result;
+                    }
+                }
+                Event::CtrlChar('c') => return self.copy_selection(),
+                Event::Key(Key::Esc) if self.selection.is_some() => {
+                    self.selection = None;
+                    return EventResult::Consumed(None);
+                }
+                _ => (),
+            }
+        }
+
+        match event {
+            Event::Key(Key::Tab) => return self.move_link(true),
+            Event::Shift(Key::Tab) => return self.move_link(false),
+            Event::Key(Key::Enter) => {
+                if let (Some(cb), Some(url)) = (self.on_link.clone(),
+                                                self.focused_link()) {
+                    return EventResult::with_cb(move |s| cb(s, &url));
+                }
+            }
+            _ => (),
+        }
+
         if !self.scrollbase.scrollable() {
             return EventResult::Ignored;
         }
@@ -276,12 +1457,12 @@ impl View for TextView {
         }
 
         // We just scrolled manually, so reset the scroll strategy.
-        self.scroll_strategy = ScrollStrategy::KeepRow;
+        self.scrollbase.user_scrolled();
         EventResult::Consumed(None)
     }
 
     fn needs_relayout(&self) -> bool {
-        self.last_size.is_none()
+        self.last_size.is_none() || self.content_changed()
     }
 
     fn required_size(&mut self, size: Vec2) -> Vec2 {
@@ -297,8 +1478,28 @@ impl View for TextView {
         ideal
     }
 
-    fn take_focus(&mut self, _: Direction) -> bool {
-        self.scrollbase.scrollable()
+    fn take_focus(&mut self, source: Direction) -> bool {
+        let links = self.link_starts();
+        if !links.is_empty() {
+            self.focused_link = if source == Direction::back() {
+                links.last().cloned()
+            } else {
+                links.first().cloned()
+            };
+            return true;
+        }
+
+        self.selectable || self.scrollbase.scrollable()
+    }
+
+    fn describe(&self) -> Option<String> {
+        let content = self.get_content();
+        let content = content.trim();
+        if content.is_empty() {
+            None
+        } else {
+            Some(content.to_string())
+        }
     }
 
     fn layout(&mut self, size: Vec2) {
@@ -307,3 +1508,212 @@ impl View for TextView {
         self.scrollbase.set_heights(size.y, self.rows.len());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use Printer;
+    use super::{TextContent, TextView};
+    use direction::Direction;
+    use event::{Event, EventResult, Key};
+    use theme::{ColorStyle, Theme};
+    use utils::{Binding, StyledString};
+    use vec::Vec2;
+    use view::{Rect, ScrollStrategy, View};
+
+    fn row_texts(view: &TextView) -> Vec<String> {
+        let content = view.get_content();
+        view.rows
+            .iter()
+            .map(|row| content[row.start..row.end].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_append() {
+        let mut view = TextView::new("abc def\n\nghi");
+        view.layout((4, 10).into());
+
+        view.append(" jkl\nmno");
+        view.append(StyledString::styled("pqr\n", ColorStyle::Highlight));
+        view.layout((4, 10).into());
+
+        let mut fresh = TextView::new(view.get_content());
+        fresh.layout((4, 10).into());
+        assert_eq!(row_texts(&view), row_texts(&fresh));
+        assert_eq!(view.content.lock().spans[0].start,
+                   "abc def\n\nghi jkl\nmno".len());
+    }
+
+    #[test]
+    fn test_edit() {
+        let mut view = TextView::new("abc def\n\nghi jkl\nmno\n");
+        view.layout((4, 10).into());
+
+        let edits = ["abc xhi jkl\nmno\n",
+                     "abc xhi jkl\n\nmno pq\n",
+                     "abc xhi jkl\n\nmno pq\nr",
+                     "ab"];
+        for edit in &edits {
+            view.set_content(*edit);
+            view.layout((4, 10).into());
+
+            let mut fresh = TextView::new(*edit);
+            fresh.layout((4, 10).into());
+            assert_eq!(row_texts(&view), row_texts(&fresh));
+        }
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let mut view = TextView::new("a\nb\nc").max_lines(2);
+        assert_eq!(view.get_content(), "b\nc");
+
+        view.layout((10, 10).into());
+        view.append("\nd\n");
+        view.layout((10, 10).into());
+        assert_eq!(view.get_content(), "c\nd\n");
+        assert_eq!(row_texts(&view), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn test_shared_content() {
+        let content = TextContent::new("abc");
+        let mut view = TextView::new_with_content(content.clone());
+        view.layout((10, 10).into());
+        assert!(!view.needs_relayout());
+
+        content.append(" def");
+        assert!(view.needs_relayout());
+        view.layout((10, 10).into());
+        assert_eq!(row_texts(&view), vec!["abc def"]);
+
+        content.set_content("ghi");
+        view.layout((10, 10).into());
+        assert_eq!(row_texts(&view), vec!["ghi"]);
+    }
+
+    #[test]
+    fn test_draw_stale() {
+        let content = TextContent::new("abc\ndef");
+        let mut view = TextView::new_with_content(content.clone());
+        view.layout((10, 10).into());
+
+        // Old rows keep drawing until the next layout.
+        content.set_content("中\nxyz");
+        let theme = Theme::default();
+        let bounds = Printer::draw_bounds((10, 10), &theme, |printer| {
+            view.draw(printer)
+        });
+        assert_eq!(bounds, Some(Rect::new((0, 0), (3, 2))));
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut view = TextView::new("abc def\nghi").selectable(true);
+        view.layout((10, 10).into());
+
+        view.on_event(Event::Shift(Key::Right));
+        view.on_event(Event::Shift(Key::Right));
+        assert_eq!(view.selected_text(), Some("ab".to_string()));
+        view.on_event(Event::Shift(Key::Down));
+        assert_eq!(view.selected_text(), Some("abc def\ngh".to_string()));
+        view.on_event(Event::Shift(Key::Home));
+        assert_eq!(view.selection(), Some(0..8));
+
+        assert!(view.on_event(Event::CtrlChar('c')).is_consumed());
+
+        view.on_event(Event::Key(Key::Esc));
+        assert_eq!(view.selection(), None);
+    }
+
+    #[test]
+    fn test_stick_to_bottom() {
+        let mut view = TextView::new("1\n2\n3\n4")
+            .scroll_strategy(ScrollStrategy::StickToBottom);
+        view.layout((10, 2).into());
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 2));
+
+        // Still at the bottom: we keep following new content.
+        view.on_event(Event::Key(Key::End));
+        view.append("\n5");
+        view.layout((10, 2).into());
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 3));
+
+        view.on_event(Event::Key(Key::Up));
+        view.append("\n6");
+        view.layout((10, 2).into());
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 2));
+
+        view.set_scroll_offset((0, 10));
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 4));
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut view = TextView::new("a\nfoo\nb\nc\nfoo\nd");
+        view.highlight_matches("foo", ColorStyle::Highlight);
+        assert_eq!(view.match_count(), 2);
+        view.layout((10, 2).into());
+
+        // Already visible.
+        assert!(view.next_match());
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 0));
+        assert!(view.next_match());
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 3));
+        assert!(view.next_match());
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 1));
+        assert!(view.prev_match());
+        assert_eq!(view.scroll_offset(), Vec2::new(0, 3));
+
+        // Occurrences follow the content.
+        view.append("\nfoo");
+        view.layout((10, 2).into());
+        assert_eq!(view.match_count(), 3);
+
+        view.clear_matches();
+        assert!(!view.next_match());
+    }
+
+    #[test]
+    fn test_links() {
+        let mut text = StyledString::plain("See ");
+        text.append_link("a", "url:a", ColorStyle::Tertiary);
+        text.append_plain(" and ");
+        text.append_link("b", "url:b", ColorStyle::Tertiary);
+
+        let mut view = TextView::empty().on_link(|_, _| ());
+        view.append(text);
+        view.layout((20, 5).into());
+
+        assert!(view.take_focus(Direction::back()));
+        assert_eq!(view.focused_link(), Some("url:b".to_string()));
+        view.on_event(Event::Shift(Key::Tab));
+        assert_eq!(view.focused_link(), Some("url:a".to_string()));
+
+        match view.on_event(Event::Key(Key::Enter)) {
+            EventResult::Consumed(Some(_)) => (),
+            _ => panic!("The link should be activated."),
+        }
+
+        // The focus leaves after the last link.
+        view.on_event(Event::Key(Key::Tab));
+        match view.on_event(Event::Key(Key::Tab)) {
+            EventResult::Ignored => (),
+            _ => panic!("The focus should move on."),
+        }
+    }
+
+    #[test]
+    fn test_binding() {
+        let binding = Binding::new("abc".to_string());
+        let mut view = TextView::empty().binding(binding.clone());
+        view.layout((10, 10).into());
+        assert_eq!(row_texts(&view), vec!["abc"]);
+        assert!(!view.needs_relayout());
+
+        binding.set("def".to_string());
+        assert!(view.needs_relayout());
+        view.layout((10, 10).into());
+        assert_eq!(row_texts(&view), vec!["def"]);
+    }
+}
@@ -0,0 +1,55 @@
+use Printer;
+use theme::Palette;
+use view::{View, ViewWrapper};
+
+/// Wrapper view changing the palette for its content.
+///
+/// Views inside use the same roles as usual, like `ColorStyle::Primary`
+/// or `ColorStyle::Highlight`, but these get their colors from the
+/// modified palette. Changes cascade: a `ThemedView` inside another one
+/// starts from the palette of its parent.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::theme::{BaseColor, Color};
+/// # use cursive::views::{Dialog, TextView, ThemedView};
+/// let warning = ThemedView::new(|palette| {
+///     palette.view = Color::Dark(BaseColor::Red);
+///     palette.primary = Color::Light(BaseColor::White);
+/// }, Dialog::around(TextView::new("Disk almost full!")));
+/// ```
+pub struct ThemedView<T: View> {
+    view: T,
+    change: Box<Fn(&mut Palette)>,
+}
+
+impl<T: View> ThemedView<T> {
+    /// Wraps the given view.
+    ///
+    /// `change` is called with the palette of the parent on each draw.
+    pub fn new<F>(change: F, view: T) -> Self
+        where F: Fn(&mut Palette) + 'static
+    {
+        ThemedView {
+            view: view,
+            change: Box::new(change),
+        }
+    }
+
+    /// Replaces the palette change.
+    pub fn set_change<F>(&mut self, change: F)
+        where F: Fn(&mut Palette) + 'static
+    {
+        self.change = Box::new(change);
+    }
+}
+
+impl<T: View> ViewWrapper for ThemedView<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        printer.with_palette(|palette| (self.change)(palette),
+                             |printer| self.view.draw(printer));
+    }
+}
@@ -0,0 +1,347 @@
+use {Cursive, Printer, With};
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key};
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::View;
+
+// Measures the time spent running, across pauses.
+#[derive(Clone, Copy)]
+struct Clock {
+    // Time accumulated before the last start.
+    elapsed: Duration,
+    // When the clock was last started, if it's running.
+    started: Option<Instant>,
+}
+
+impl Clock {
+    fn new() -> Self {
+        Clock {
+            elapsed: Duration::new(0, 0),
+            started: None,
+        }
+    }
+
+    fn elapsed_at(&self, now: Instant) -> Duration {
+        match self.started {
+            Some(started) => self.elapsed + now.duration_since(started),
+            None => self.elapsed,
+        }
+    }
+
+    fn start_at(&mut self, now: Instant) {
+        if self.started.is_none() {
+            self.started = Some(now);
+        }
+    }
+
+    fn pause_at(&mut self, now: Instant) {
+        self.elapsed = self.elapsed_at(now);
+        self.started = None;
+    }
+
+    fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+}
+
+// Formats `duration` as `mm:ss`, or `h:mm:ss` past an hour.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes) = (seconds / 3600, seconds / 60 % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds % 60)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds % 60)
+    }
+}
+
+// Handles the keys shared by timers: `<Space>` to start or pause, and
+// `<Backspace>` to reset.
+//
+// Returns `None` if the event was ignored, and `Some(true)` if the timer
+// should be reset.
+fn timer_event(clock: &mut Clock, event: &Event) -> Option<bool> {
+    let now = Instant::now();
+    match *event {
+        Event::Char(' ') if clock.is_running() => clock.pause_at(now),
+        Event::Char(' ') => clock.start_at(now),
+        Event::Key(Key::Backspace) => return Some(true),
+        _ => return None,
+    }
+    Some(false)
+}
+
+/// Measures the time since it was started.
+///
+/// The time is read from the system clock when drawing: call
+/// [`Cursive::set_fps`] so it keeps updating.
+///
+/// While focused, `<Space>` starts or pauses it, and `<Backspace>` resets
+/// it.
+///
+/// [`Cursive::set_fps`]: ../struct.Cursive.html#method.set_fps
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::StopwatchView;
+/// let mut stopwatch = StopwatchView::new();
+/// stopwatch.start();
+/// assert!(stopwatch.is_running());
+/// ```
+pub struct StopwatchView {
+    clock: Clock,
+    format: Box<Fn(Duration) -> String>,
+}
+
+new_default!(StopwatchView);
+
+impl StopwatchView {
+    /// Creates a new stopwatch, paused at zero.
+    ///
+    /// The time is shown as `mm:ss`, or `h:mm:ss` past an hour.
+    pub fn new() -> Self {
+        StopwatchView {
+            clock: Clock::new(),
+            format: Box::new(format_duration),
+        }
+    }
+
+    /// Sets the function formatting the time shown.
+    pub fn set_format<F>(&mut self, format: F)
+        where F: Fn(Duration) -> String + 'static
+    {
+        self.format = Box::new(format);
+    }
+
+    /// Sets the function formatting the time shown.
+    ///
+    /// Chainable variant.
+    pub fn format<F>(self, format: F) -> Self
+        where F: Fn(Duration) -> String + 'static
+    {
+        self.with(|s| s.set_format(format))
+    }
+
+    /// Starts measuring time, or resumes after a pause.
+    pub fn start(&mut self) {
+        self.clock.start_at(Instant::now());
+    }
+
+    /// Stops measuring time, keeping the time measured so far.
+    pub fn pause(&mut self) {
+        self.clock.pause_at(Instant::now());
+    }
+
+    /// Goes back to zero, and pauses.
+    pub fn reset(&mut self) {
+        self.clock = Clock::new();
+    }
+
+    /// Returns `true` if the stopwatch is measuring time.
+    pub fn is_running(&self) -> bool {
+        self.clock.is_running()
+    }
+
+    /// Returns the time measured so far.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed_at(Instant::now())
+    }
+}
+
+impl View for StopwatchView {
+    fn draw(&self, printer: &Printer) {
+        printer.print((0, 0), &(self.format)(self.elapsed()));
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new((self.format)(self.elapsed()).width(), 1)
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match timer_event(&mut self.clock, &event) {
+            Some(true) => self.reset(),
+            Some(false) => (),
+            None => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
+
+/// Counts down from a duration.
+///
+/// Like [`StopwatchView`], the time is read from the system clock when
+/// drawing, and the same keys control it while focused.
+///
+/// When the time is up, the countdown pauses and runs its `on_finish`
+/// callback. This is noticed on the next `Event::Refresh` reaching the
+/// view, so [`Cursive::set_fps`] must be called, and the countdown must
+/// be focused.
+///
+/// [`StopwatchView`]: struct.StopwatchView.html
+/// [`Cursive::set_fps`]: ../struct.Cursive.html#method.set_fps
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{CountdownView, Dialog};
+/// # use std::time::Duration;
+/// let countdown = CountdownView::new(Duration::from_secs(60))
+///     .on_finish(|s| s.add_layer(Dialog::info("Time's up!")));
+/// assert_eq!(countdown.remaining(), Duration::from_secs(60));
+/// ```
+pub struct CountdownView {
+    clock: Clock,
+    duration: Duration,
+    format: Box<Fn(Duration) -> String>,
+    on_finish: Option<Callback>,
+    // Set once the callback ran, until the next reset.
+    finished: bool,
+}
+
+impl CountdownView {
+    /// Creates a new countdown from `duration`, paused.
+    pub fn new(duration: Duration) -> Self {
+        CountdownView {
+            clock: Clock::new(),
+            duration: duration,
+            format: Box::new(format_duration),
+            on_finish: None,
+            finished: false,
+        }
+    }
+
+    /// Sets the duration to count down from, and resets the countdown.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+        self.reset();
+    }
+
+    /// Sets the function formatting the time shown.
+    pub fn set_format<F>(&mut self, format: F)
+        where F: Fn(Duration) -> String + 'static
+    {
+        self.format = Box::new(format);
+    }
+
+    /// Sets the function formatting the time shown.
+    ///
+    /// Chainable variant.
+    pub fn format<F>(self, format: F) -> Self
+        where F: Fn(Duration) -> String + 'static
+    {
+        self.with(|s| s.set_format(format))
+    }
+
+    /// Sets a callback to run when the time is up.
+    pub fn set_on_finish<F>(&mut self, cb: F)
+        where F: Fn(&mut Cursive) + 'static
+    {
+        self.on_finish = Some(Callback::from_fn(cb));
+    }
+
+    /// Sets a callback to run when the time is up.
+    ///
+    /// Chainable variant.
+    pub fn on_finish<F>(self, cb: F) -> Self
+        where F: Fn(&mut Cursive) + 'static
+    {
+        self.with(|s| s.set_on_finish(cb))
+    }
+
+    /// Starts counting down, or resumes after a pause.
+    pub fn start(&mut self) {
+        self.clock.start_at(Instant::now());
+    }
+
+    /// Stops counting down, keeping the remaining time.
+    pub fn pause(&mut self) {
+        self.clock.pause_at(Instant::now());
+    }
+
+    /// Goes back to the full duration, and pauses.
+    pub fn reset(&mut self) {
+        self.clock = Clock::new();
+        self.finished = false;
+    }
+
+    /// Returns `true` if the countdown is running.
+    pub fn is_running(&self) -> bool {
+        self.clock.is_running()
+    }
+
+    /// Returns the time left.
+    pub fn remaining(&self) -> Duration {
+        let elapsed = self.clock.elapsed_at(Instant::now());
+        self.duration.checked_sub(elapsed).unwrap_or_default()
+    }
+
+    /// Returns `true` if the time is up.
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == Duration::new(0, 0)
+    }
+}
+
+impl View for CountdownView {
+    fn draw(&self, printer: &Printer) {
+        printer.print((0, 0), &(self.format)(self.remaining()));
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new((self.format)(self.remaining()).width(), 1)
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if event == Event::Refresh {
+            if self.finished || !self.is_finished() {
+                return EventResult::Ignored;
+            }
+            self.finished = true;
+            self.pause();
+            return EventResult::Consumed(self.on_finish.clone());
+        }
+
+        match timer_event(&mut self.clock, &event) {
+            Some(true) => self.reset(),
+            Some(false) => (),
+            None => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clock, format_duration};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_clock() {
+        let secs = Duration::from_secs;
+        let t0 = Instant::now();
+        let mut clock = Clock::new();
+        assert_eq!(clock.elapsed_at(t0 + secs(5)), secs(0));
+
+        clock.start_at(t0);
+        assert_eq!(clock.elapsed_at(t0 + secs(2)), secs(2));
+        clock.pause_at(t0 + secs(3));
+        assert_eq!(clock.elapsed_at(t0 + secs(10)), secs(3));
+
+        // Resuming adds to the time measured before the pause.
+        clock.start_at(t0 + secs(10));
+        assert_eq!(clock.elapsed_at(t0 + secs(11)), secs(4));
+
+        assert_eq!(format_duration(secs(65)), "01:05");
+        assert_eq!(format_duration(secs(3725)), "1:02:05");
+    }
+}
@@ -30,7 +30,9 @@ impl<T: View> TrackedView<T> {
     }
 
     /// Wraps itself in a `IdView` for easy retrieval.
-    pub fn with_id(self, id: &str) -> IdView<Self> {
+    pub fn with_id(self, id: &str) -> IdView<Self>
+        where T: 'static
+    {
         IdView::new(id, self)
     }
 }
@@ -0,0 +1,506 @@
+use {Cursive, Printer, With};
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key};
+use std::any::Any;
+use std::cmp::max;
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use utils::{TruncateStyle, i18n, truncate};
+use vec::Vec2;
+use view::{Selector, View};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    Move,
+    Resize,
+}
+
+// Smallest size a window can be resized to, borders included.
+const MIN_SIZE: (usize, usize) = (8, 3);
+
+const CLOSE_BUTTON: &'static str = "[x]";
+
+/// Floating window around a view, with a title bar and a close button.
+///
+/// Windows are meant to be shown in a `WindowManager`, which places them
+/// and keeps track of the focused one. The keyboard handles the window
+/// itself:
+///
+/// * `<Alt+F7>` starts moving the window with the arrow keys.
+/// * `<Alt+F8>` starts resizing the window with the arrow keys.
+/// * `<Enter>` or `<Esc>` stop moving or resizing.
+/// * `<Alt+F4>` closes the window.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{TextView, WindowView};
+/// let window = WindowView::new("Notes", TextView::new("Hello"))
+///     .offset((10, 4))
+///     .size((30, 8))
+///     .on_close(|s| s.quit());
+/// ```
+pub struct WindowView<T: View> {
+    view: T,
+    title: String,
+    offset: Vec2,
+    // Size chosen by the user; the required size is used otherwise.
+    size: Option<Vec2>,
+    last_size: Vec2,
+    mode: Mode,
+    closed: bool,
+    on_close: Option<Callback>,
+}
+
+impl<T: View> WindowView<T> {
+    /// Wraps `view` in a new window.
+    pub fn new<S: Into<String>>(title: S, view: T) -> Self {
+        WindowView {
+            view: view,
+            title: title.into(),
+            offset: Vec2::zero(),
+            size: None,
+            last_size: Vec2::zero(),
+            mode: Mode::Normal,
+            closed: false,
+            on_close: None,
+        }
+    }
+
+    /// Sets the title of the window.
+    pub fn set_title<S: Into<String>>(&mut self, title: S) {
+        self.title = title.into();
+    }
+
+    /// Sets the title of the window.
+    ///
+    /// Chainable variant.
+    pub fn title<S: Into<String>>(self, title: S) -> Self {
+        self.with(|s| s.set_title(title))
+    }
+
+    /// Returns the title of the window.
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Sets the position of the top-left corner of the window.
+    pub fn set_offset<V: Into<Vec2>>(&mut self, offset: V) {
+        self.offset = offset.into();
+    }
+
+    /// Sets the position of the top-left corner of the window.
+    ///
+    /// Chainable variant.
+    pub fn offset<V: Into<Vec2>>(self, offset: V) -> Self {
+        self.with(|s| s.set_offset(offset))
+    }
+
+    /// Returns the position of the top-left corner of the window.
+    pub fn get_offset(&self) -> Vec2 {
+        self.offset
+    }
+
+    /// Sets the size of the window, borders included.
+    ///
+    /// Without it, the window takes the size its content requires.
+    pub fn set_size<V: Into<Vec2>>(&mut self, size: V) {
+        self.size = Some(Vec2::max(size.into(), MIN_SIZE));
+    }
+
+    /// Sets the size of the window, borders included.
+    ///
+    /// Chainable variant.
+    pub fn size<V: Into<Vec2>>(self, size: V) -> Self {
+        self.with(|s| s.set_size(size))
+    }
+
+    /// Returns the size of the window, from the last layout.
+    pub fn get_size(&self) -> Vec2 {
+        self.last_size
+    }
+
+    /// Sets a callback to run when the window is closed.
+    pub fn set_on_close<F: Fn(&mut Cursive) + 'static>(&mut self, cb: F) {
+        self.on_close = Some(Callback::from_fn(cb));
+    }
+
+    /// Sets a callback to run when the window is closed.
+    ///
+    /// Chainable variant.
+    pub fn on_close<F: Fn(&mut Cursive) + 'static>(self, cb: F) -> Self {
+        self.with(|s| s.set_on_close(cb))
+    }
+
+    /// Closes the window.
+    ///
+    /// A `WindowManager` removes closed windows after each event.
+    pub fn close(&mut self) -> EventResult {
+        self.closed = true;
+        self.mode = Mode::Normal;
+        EventResult::Consumed(self.on_close.clone())
+    }
+
+    /// Returns `true` if the window was closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Returns the inner view.
+    pub fn get_inner(&self) -> &T {
+        &self.view
+    }
+
+    /// Returns a mutable reference to the inner view.
+    pub fn get_inner_mut(&mut self) -> &mut T {
+        &mut self.view
+    }
+
+    // Moves or resizes the window with the arrow keys.
+    fn handle_mode(&mut self, event: Event) -> EventResult {
+        let (dx, dy): (isize, isize) = match event {
+            Event::Key(Key::Enter) |
+            Event::Key(Key::Esc) => {
+                self.mode = Mode::Normal;
+                return EventResult::Consumed(None);
+            }
+            Event::Key(Key::Left) => (-1, 0),
+            Event::Key(Key::Right) => (1, 0),
+            Event::Key(Key::Up) => (0, -1),
+            Event::Key(Key::Down) => (0, 1),
+            // Other keys are ignored until the window is in place.
+            _ => return EventResult::Consumed(None),
+        };
+
+        fn shift(value: usize, delta: isize) -> usize {
+            if delta < 0 {
+                value.saturating_sub(1)
+            } else {
+                value + delta as usize
+            }
+        }
+
+        if self.mode == Mode::Move {
+            self.offset = Vec2::new(shift(self.offset.x, dx),
+                                    shift(self.offset.y, dy));
+        } else {
+            let size = self.size.unwrap_or(self.last_size);
+            self.set_size((shift(size.x, dx), shift(size.y, dy)));
+        }
+        EventResult::Consumed(None)
+    }
+}
+
+impl<T: View + 'static> View for WindowView<T> {
+    fn draw(&self, printer: &Printer) {
+        if printer.size.x < 2 || printer.size.y < 2 {
+            return;
+        }
+
+        // Windows may overlap: hide what's below.
+        for y in 0..printer.size.y {
+            printer.print_hline((0, y), printer.size.x, " ");
+        }
+
+        let inner = printer.size - (2, 2);
+        self.view.draw(&printer.sub_printer((1, 1), inner, true));
+
+        printer.print_box((0, 0), printer.size, false);
+
+        let close = printer.size.x.saturating_sub(CLOSE_BUTTON.len() + 1);
+        if close > 0 {
+            printer.print((close, 0), CLOSE_BUTTON);
+        }
+
+        // Keep room for the close button and the borders of the title.
+        let room = close.saturating_sub(5);
+        if !self.title.is_empty() && room > 0 {
+            let title = truncate(&self.title, room, TruncateStyle::End);
+            let len = title.width();
+            let x = 2 + (room - len) / 2;
+            let glyphs = &printer.theme.glyphs;
+            printer.with_high_border(false, |printer| {
+                printer.print((x - 2, 0), &glyphs.right_junction);
+                printer.print((x + len + 1, 0), &glyphs.left_junction);
+            });
+
+            let color = if printer.focused {
+                ColorStyle::TitlePrimary
+            } else {
+                ColorStyle::TitleSecondary
+            };
+            printer.with_color(color, |p| p.print((x, 0), &title));
+        }
+
+        let mode = match self.mode {
+            Mode::Normal => return,
+            Mode::Move => i18n::translate("move"),
+            Mode::Resize => i18n::translate("resize"),
+        };
+        printer.with_color(ColorStyle::Highlight, |printer| {
+            printer.print((2, printer.size.y - 1), &mode);
+        });
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let size = match self.size {
+            Some(size) => size,
+            None => {
+                let inner = req.map(|v| v.saturating_sub(2));
+                let title = self.title.width() + CLOSE_BUTTON.len() + 6;
+                let size = self.view.required_size(inner) + (2, 2);
+                Vec2::new(max(size.x, title), size.y)
+            }
+        };
+        Vec2::min(size, req)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.view.layout(size.map(|v| v.saturating_sub(2)));
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.mode != Mode::Normal {
+            return self.handle_mode(event);
+        }
+
+        match event {
+            Event::Alt(Key::F7) => self.mode = Mode::Move,
+            Event::Alt(Key::F8) => self.mode = Mode::Resize,
+            Event::Alt(Key::F4) => return self.close(),
+            event => return self.view.on_event(event).resolve_on(self),
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        self.view.take_focus(source)
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       callback: Box<FnMut(&mut Any) + 'a>) {
+        self.view.call_on_any(selector, callback);
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        self.view.focus_view(selector)
+    }
+
+    fn describe(&self) -> Option<String> {
+        match self.view.describe() {
+            Some(description) => {
+                Some(format!("{}: {}", self.title, description))
+            }
+            None => Some(self.title.clone()),
+        }
+    }
+}
+
+/// Floating windows, one of them focused.
+///
+/// The focused window is drawn on top of the others. `<Ctrl+F6>` focuses
+/// the next window. Windows are removed once closed.
+///
+/// The manager takes all the space it's given, so it is usually a
+/// fullscreen layer.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{TextView, WindowManager, WindowView};
+/// let mut windows = WindowManager::new();
+/// windows.add_window(WindowView::new("Notes", TextView::new("Hello")));
+/// windows.add_window(WindowView::new("Todo", TextView::new("- Write")));
+/// assert_eq!(windows.titles(), ["Notes", "Todo"]);
+/// ```
+pub struct WindowManager {
+    // Back to front: the last window is focused.
+    windows: Vec<WindowView<Box<View>>>,
+}
+
+new_default!(WindowManager);
+
+impl WindowManager {
+    /// Creates a new manager, without windows.
+    pub fn new() -> Self {
+        WindowManager { windows: Vec::new() }
+    }
+
+    /// Adds a window on top of the others, and focuses it.
+    ///
+    /// Windows without an offset are placed in cascade.
+    pub fn add_window<V: View + 'static>(&mut self, window: WindowView<V>) {
+        let n = self.windows.len();
+        let offset = if window.offset == Vec2::zero() {
+            Vec2::new(2 * n, n)
+        } else {
+            window.offset
+        };
+        self.windows.push(WindowView {
+                              view: Box::new(window.view),
+                              title: window.title,
+                              offset: offset,
+                              size: window.size,
+                              last_size: window.last_size,
+                              mode: window.mode,
+                              closed: window.closed,
+                              on_close: window.on_close,
+                          });
+    }
+
+    /// Adds a window on top of the others, and focuses it.
+    ///
+    /// Chainable variant.
+    pub fn window<V: View + 'static>(self, window: WindowView<V>) -> Self {
+        self.with(|s| s.add_window(window))
+    }
+
+    /// Returns the number of windows.
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Returns `true` if there is no window.
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Returns the titles of the windows, from back to front.
+    pub fn titles(&self) -> Vec<&str> {
+        self.windows.iter().map(|window| window.get_title()).collect()
+    }
+
+    /// Returns the focused window, if any.
+    pub fn focused_window(&mut self) -> Option<&mut WindowView<Box<View>>> {
+        self.windows.last_mut()
+    }
+
+    /// Brings the window at index `i` (from back to front) on top, and
+    /// focuses it.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn focus_window(&mut self, i: usize) {
+        let window = self.windows.remove(i);
+        self.windows.push(window);
+    }
+
+    /// Focuses the next window, sending the focused one to the back.
+    pub fn cycle_focus(&mut self) {
+        if let Some(window) = self.windows.pop() {
+            self.windows.insert(0, window);
+        }
+    }
+
+    /// Removes the window at index `i` (from back to front).
+    ///
+    /// Its `on_close` callback is not run.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn remove_window(&mut self, i: usize) -> WindowView<Box<View>> {
+        self.windows.remove(i)
+    }
+}
+
+impl View for WindowManager {
+    fn draw(&self, printer: &Printer) {
+        let top = self.windows.len().saturating_sub(1);
+        for (i, window) in self.windows.iter().enumerate() {
+            let focused = printer.focused && i == top;
+            let printer = printer.sub_printer(window.offset,
+                                              window.last_size,
+                                              focused);
+            window.draw(&printer);
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        // Windows can be anywhere.
+        req
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        for window in &mut self.windows {
+            let window_size = window.required_size(size);
+            // Keep windows on screen.
+            let room = size.zip_map(window_size, |a, b| a.saturating_sub(b));
+            window.offset = Vec2::min(window.offset, room);
+            window.layout(window_size);
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if event == Event::Ctrl(Key::F6) && self.windows.len() > 1 {
+            self.cycle_focus();
+            return EventResult::Consumed(None);
+        }
+
+        let result = match self.windows.last_mut() {
+            Some(window) => window.on_event(event),
+            None => return EventResult::Ignored,
+        };
+        self.windows.retain(|window| !window.is_closed());
+        result.resolve_on(self)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        !self.windows.is_empty()
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        for window in &mut self.windows {
+            window.call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        let i = self.windows
+            .iter_mut()
+            .position(|window| window.focus_view(selector).is_ok())
+            .ok_or(())?;
+        self.focus_window(i);
+        Ok(())
+    }
+
+    fn describe(&self) -> Option<String> {
+        self.windows.last().and_then(|window| window.describe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WindowManager, WindowView};
+    use event::{Event, Key};
+    use vec::Vec2;
+    use view::View;
+    use views::TextView;
+
+    #[test]
+    fn test_windows() {
+        let mut windows = WindowManager::new()
+            .window(WindowView::new("a", TextView::new("aaa")))
+            .window(WindowView::new("b", TextView::new("bbb")).size((10, 4)));
+        windows.layout(Vec2::new(40, 10));
+        assert_eq!(windows.windows[1].get_offset(), Vec2::new(2, 1));
+        assert_eq!(windows.windows[1].get_size(), Vec2::new(10, 4));
+
+        // Move b to the left, then make it wider.
+        windows.on_event(Event::Alt(Key::F7));
+        windows.on_event(Event::Key(Key::Left));
+        windows.on_event(Event::Key(Key::Enter));
+        windows.on_event(Event::Alt(Key::F8));
+        windows.on_event(Event::Key(Key::Right));
+        windows.on_event(Event::Key(Key::Esc));
+        windows.layout(Vec2::new(40, 10));
+        assert_eq!(windows.windows[1].get_offset(), Vec2::new(1, 1));
+        assert_eq!(windows.windows[1].get_size(), Vec2::new(11, 4));
+
+        windows.on_event(Event::Ctrl(Key::F6));
+        assert_eq!(windows.titles(), ["b", "a"]);
+
+        windows.on_event(Event::Alt(Key::F4));
+        assert_eq!(windows.titles(), ["b"]);
+    }
+}